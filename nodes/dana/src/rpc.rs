@@ -58,10 +58,12 @@ pub fn create_full<C, P, B>(
 where
     C: ProvideRuntimeApi<Block>,
     C: HeaderBackend<Block> + HeaderMetadata<Block, Error = BlockChainError> + 'static,
-    C: Send + Sync + 'static,
+    C: sc_client_api::ProofProvider<Block> + Send + Sync + 'static,
     C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
     C::Api: pallet_mmr_rpc::MmrRuntimeApi<Block, <Block as sp_runtime::traits::Block>::Hash>,
     C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+    C::Api: parami_credential_rpc::CredentialRuntimeApi<Block, Hash, DecentralizedId>,
+    C::Api: parami_did_rpc::DidRuntimeApi<Block, DecentralizedId, AccountId>,
     C::Api: parami_swap_rpc::SwapRuntimeApi<Block, AssetId, Balance>,
     C::Api: parami_nft_rpc::NftRuntimeApi<Block, NftId, DecentralizedId, Balance>,
     C::Api: BlockBuilder<Block>,
@@ -71,18 +73,21 @@ where
 {
     use pallet_mmr_rpc::{Mmr, MmrApiServer};
     use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
-    use parami_did_rpc::{DidApiServer, DidRpcHandler};
+    use parami_credential_rpc::{CredentialApiServer, CredentialRpcHandler};
+    use parami_did_rpc::{
+        DidApiServer, DidResolverApiServer, DidResolverRpcHandler, DidRpcHandler,
+    };
     use parami_nft_rpc::{NftApiServer, NftRpcHandler};
     use parami_swap_rpc::{SwapApiServer, SwapsRpcHandler};
     use substrate_frame_rpc_system::{System, SystemApiServer};
 
     let mut io = RpcModule::new(());
     let FullDeps {
-        backend,
         client,
         pool,
         deny_unsafe,
         grandpa,
+        ..
     } = deps;
 
     let GrandpaDeps {
@@ -111,12 +116,9 @@ where
         .into_rpc(),
     )?;
 
-    if let Some(did_rpc) = backend
-        .offchain_storage()
-        .map(|storage| DidRpcHandler::<_, DecentralizedId>::new(storage).into_rpc())
-    {
-        io.merge(did_rpc)?;
-    }
+    io.merge(CredentialRpcHandler::new(client.clone()).into_rpc())?;
+    io.merge(DidRpcHandler::new(client.clone()).into_rpc())?;
+    io.merge(DidResolverRpcHandler::new(client.clone()).into_rpc())?;
     io.merge(SwapsRpcHandler::new(client.clone()).into_rpc())?;
     io.merge(NftRpcHandler::new(client.clone()).into_rpc())?;
 
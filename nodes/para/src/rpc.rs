@@ -13,7 +13,7 @@ use parami_para_runtime::{
     NftId,
 };
 
-use sc_client_api::AuxStore;
+use sc_client_api::{AuxStore, ProofProvider};
 pub use sc_rpc::{DenyUnsafe, SubscriptionTaskExecutor};
 use sc_transaction_pool_api::TransactionPool;
 use sp_api::ProvideRuntimeApi;
@@ -40,6 +40,7 @@ where
     C: ProvideRuntimeApi<Block>
         + HeaderBackend<Block>
         + AuxStore
+        + ProofProvider<Block>
         + HeaderMetadata<Block, Error = BlockChainError>
         + Sync
         + Send
@@ -48,6 +49,8 @@ where
     C::Api: pallet_contracts_rpc::ContractsRuntimeApi<Block, AccountId, Balance, BlockNumber, Hash>,
     C::Api: pallet_mmr_rpc::MmrRuntimeApi<Block, <Block as sp_runtime::traits::Block>::Hash>,
     C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+    C::Api: parami_credential_rpc::CredentialRuntimeApi<Block, Hash, DecentralizedId>,
+    C::Api: parami_did_rpc::DidRuntimeApi<Block, DecentralizedId, AccountId>,
     C::Api: parami_swap_rpc::SwapRuntimeApi<Block, AssetId, Balance>,
     C::Api: parami_nft_rpc::NftRuntimeApi<Block, NftId, DecentralizedId, Balance>,
     C::Api: BlockBuilder<Block>,
@@ -58,17 +61,20 @@ where
     use pallet_contracts_rpc::{Contracts, ContractsApiServer};
     use pallet_mmr_rpc::{Mmr, MmrApiServer};
     use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
-    use parami_did_rpc::{DidApiServer, DidRpcHandler};
+    use parami_credential_rpc::{CredentialApiServer, CredentialRpcHandler};
+    use parami_did_rpc::{
+        DidApiServer, DidResolverApiServer, DidResolverRpcHandler, DidRpcHandler,
+    };
     use parami_nft_rpc::{NftApiServer, NftRpcHandler};
     use parami_swap_rpc::{SwapApiServer, SwapsRpcHandler};
     use substrate_frame_rpc_system::{System, SystemApiServer};
 
     let mut io = RpcModule::new(());
     let FullDeps {
-        backend,
         client,
         pool,
         deny_unsafe,
+        ..
     } = deps;
 
     io.merge(System::new(client.clone(), pool, deny_unsafe).into_rpc())?;
@@ -79,12 +85,9 @@ where
     io.merge(Mmr::new(client.clone()).into_rpc())?;
     io.merge(TransactionPayment::new(client.clone()).into_rpc())?;
 
-    if let Some(did_rpc) = backend
-        .offchain_storage()
-        .map(|storage| DidRpcHandler::<_, DecentralizedId>::new(storage).into_rpc())
-    {
-        io.merge(did_rpc)?;
-    }
+    io.merge(CredentialRpcHandler::new(client.clone()).into_rpc())?;
+    io.merge(DidRpcHandler::new(client.clone()).into_rpc())?;
+    io.merge(DidResolverRpcHandler::new(client.clone()).into_rpc())?;
     io.merge(SwapsRpcHandler::new(client.clone()).into_rpc())?;
     io.merge(NftRpcHandler::new(client.clone()).into_rpc())?;
 
@@ -234,6 +234,8 @@ where
         + pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>
         + substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>
         + pallet_mmr_rpc::MmrRuntimeApi<Block, <Block as sp_runtime::traits::Block>::Hash>
+        + parami_credential_rpc::CredentialRuntimeApi<Block, Hash, DecentralizedId>
+        + parami_did_rpc::DidRuntimeApi<Block, DecentralizedId, AccountId>
         + parami_swap_rpc::SwapRuntimeApi<Block, AssetId, Balance>
         + parami_nft_rpc::NftRuntimeApi<Block, NftId, DecentralizedId, Balance>
         + pallet_contracts_rpc::ContractsRuntimeApi<Block, AccountId, Balance, BlockNumber, Hash>,
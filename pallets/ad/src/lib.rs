@@ -26,6 +26,7 @@ use frame_support::{
         tokens::fungibles::{Inspect as FungInspect, Transfer as FungTransfer},
         Currency, StorageVersion,
     },
+    transactional,
     weights::{Pays, Weight},
     Blake2_256, PalletId, StorageHasher,
 };
@@ -33,10 +34,10 @@ use frame_support::{
 use crate::types::{AdAsset, CurrencyOrAsset, RewardInfo};
 use frame_system::pallet_prelude::BlockNumberFor;
 use frame_system::pallet_prelude::*;
-use parami_did::EnsureDid;
 use parami_did::Pallet as Did;
+use parami_did::{AdClaimScope, EnsureDid, Milestone};
 use parami_nft::Pallet as Nft;
-use parami_traits::{Swaps, Tags};
+use parami_traits::{OnDidRevoked, Swaps, Tags};
 use sp_core::crypto::AccountId32;
 use sp_core::crypto::ByteArray;
 use sp_core::U512;
@@ -66,7 +67,14 @@ type SlotMetaOf<T> = types::Slot<
 >;
 type TagOf = <Blake2_256 as StorageHasher>::Output;
 
-const STORAGE_VERSION: StorageVersion = StorageVersion::new(4);
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(6);
+
+/// The maximum number of advertisements cleaned up when a DID is revoked
+const MAX_REVOKED_ADS: u32 = 100;
+
+/// The maximum number of slots ended when a DID is revoked,
+/// the slots left end at their deadlines
+const MAX_REVOKED_SLOTS: u32 = 100;
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -149,6 +157,17 @@ pub mod pallet {
     #[pallet::getter(fn slot_of)]
     pub(super) type SlotOf<T: Config> = StorageMap<_, Twox64Concat, NftOf<T>, SlotMetaOf<T>>;
 
+    /// Slots an advertisement is running in
+    #[pallet::storage]
+    pub(super) type SlotsOf<T: Config> =
+        StorageDoubleMap<_, Identity, HashOf<T>, Twox64Concat, NftOf<T>, ()>;
+
+    /// Minimum bid for the slot of a NFT, set by the holders of the NFT
+    #[pallet::storage]
+    #[pallet::getter(fn reserve_price_of)]
+    pub(super) type ReservePriceOf<T: Config> =
+        StorageMap<_, Twox64Concat, NftOf<T>, BalanceOf<T>, ValueQuery>;
+
     #[pallet::storage]
     pub(super) type Ad2DelegateAccount<T: Config> = StorageMap<_, Identity, HashOf<T>, DidOf<T>>;
 
@@ -192,6 +211,8 @@ pub mod pallet {
         ),
         /// Swap Triggered \[id, kol, remain\]
         SwapTriggered(HashOf<T>, NftOf<T>, BalanceOf<T>),
+        /// Reserve price of a slot updated \[nft, value\]
+        ReservePriceUpdated(NftOf<T>, BalanceOf<T>),
     }
 
     #[pallet::hooks]
@@ -362,6 +383,21 @@ pub mod pallet {
             Ok(())
         }
 
+        /// Set the minimum bid for the slot of a NFT,
+        /// called by the holders of the NFT through governance.
+        #[pallet::weight(<T as Config>::WeightInfo::update_reward_rate())]
+        pub fn set_reserve_price(origin: OriginFor<T>, price: BalanceOf<T>) -> DispatchResult {
+            let nft_id = <T as parami_nft::Config>::HoldersOrigin::ensure_origin(origin)?;
+
+            Nft::<T>::meta(nft_id).ok_or(Error::<T>::NotExists)?;
+
+            <ReservePriceOf<T>>::insert(nft_id, price);
+
+            Self::deposit_event(Event::ReservePriceUpdated(nft_id, price));
+
+            Ok(())
+        }
+
         #[pallet::weight((0, Pays::No))]
         pub fn bid_with_currency(
             origin: OriginFor<T>,
@@ -473,7 +509,7 @@ pub mod pallet {
             signature: MultiSignature,
             signer: AccountId32, // advertiser or delegator
         ) -> DispatchResult {
-            let (_, _) = EnsureDid::<T>::ensure_origin(origin)?;
+            let (_, _) = EnsureDid::<T, AdClaimScope>::ensure_origin(origin)?;
 
             let msg = Self::construct_claim_sig_msg(&ad_id, nft_id, &visitor, &scores, &referrer);
 
@@ -505,7 +541,7 @@ pub mod pallet {
             _scores: Vec<(Vec<u8>, i8)>,
             referrer: Option<DidOf<T>>,
         ) -> DispatchResult {
-            let (origin_did, _) = EnsureDid::<T>::ensure_origin(origin)?;
+            let (origin_did, _) = EnsureDid::<T, AdClaimScope>::ensure_origin(origin)?;
 
             let tag_hashes: Vec<TagOf> = T::Tags::tags_of(&ad_id).into_keys().collect();
 
@@ -699,11 +735,14 @@ impl<T: Config> Pallet<T> {
         Ok(T::DbWeight::get().reads_writes(read as Weight, write as Weight))
     }
 
+    #[transactional]
     fn drawback(slot: &SlotMetaOf<T>) -> Result<(), DispatchError> {
         let meta = <Metadata<T>>::get(slot.ad_id).ok_or(Error::<T>::NotExists)?;
 
-        let owner_account =
-            Did::<T>::lookup_did(meta.creator).ok_or(Error::<T>::DrawbackFailedForDidNotExists)?;
+        // budgets are returned to the account of the creator, even if the DID was revoked
+        let owner_account = Did::<T>::meta(&meta.creator)
+            .map(|meta| meta.account)
+            .ok_or(Error::<T>::DrawbackFailedForDidNotExists)?;
 
         if let Some(fungible_id) = slot.fungible_id {
             let locking_fungibles = T::Assets::balance(fungible_id, &slot.budget_pot);
@@ -727,6 +766,7 @@ impl<T: Config> Pallet<T> {
         )?;
 
         <SlotOf<T>>::remove(slot.nft_id);
+        <SlotsOf<T>>::remove(slot.ad_id, slot.nft_id);
 
         <DeadlineOf<T>>::remove(slot.nft_id, slot.ad_id);
 
@@ -861,10 +901,11 @@ impl<T: Config> Pallet<T> {
         let account = Did::<T>::lookup_did(*visitor).ok_or(parami_did::Error::<T>::DidNotExists)?;
         AdAsset::<T>::transfer(&slot.ad_asset, &slot.budget_pot, &account, reward, false)?;
 
+        Did::<T>::reach_milestone(visitor, Milestone::AdClaimed);
+
         // 4.2 pay nft fractions to referrer
-        if let Some(referrer) = referrer {
-            let referrer_account =
-                Did::<T>::lookup_did(*referrer).ok_or(parami_did::Error::<T>::DidNotExists)?;
+        // revoked referrers are skipped
+        if let Some(referrer_account) = referrer.and_then(Did::<T>::lookup_did) {
             AdAsset::<T>::transfer(
                 &slot.ad_asset,
                 &slot.budget_pot,
@@ -1024,6 +1065,11 @@ impl<T: Config> Pallet<T> {
 
         let ad_meta = Self::ensure_owned_or_delegated_by_ad_id(did, ad_id)?;
 
+        ensure!(
+            bid_amount >= <ReservePriceOf<T>>::get(nft_id),
+            Error::<T>::Underbid
+        );
+
         let created = <frame_system::Pallet<T>>::block_number();
 
         // check account has enough balance
@@ -1081,6 +1127,7 @@ impl<T: Config> Pallet<T> {
         };
 
         <SlotOf<T>>::insert(nft_id, &slot);
+        <SlotsOf<T>>::insert(&ad_id, nft_id, ());
         <DeadlineOf<T>>::insert(nft_id, &ad_id, deadline);
         <Metadata<T>>::insert(&ad_id, &ad_meta);
 
@@ -1089,3 +1136,51 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 }
+
+impl<T: Config> OnDidRevoked<DidOf<T>, AccountOf<T>> for Pallet<T> {
+    fn on_did_revoked(did: &DidOf<T>, _account: &AccountOf<T>) -> Weight {
+        let mut read = 1;
+        let mut write = 0;
+
+        let ads = <AdsOf<T>>::get(did).unwrap_or_default();
+
+        let mut ended = 0;
+        for ad in ads.iter().take(MAX_REVOKED_ADS as usize) {
+            write += 1;
+            <Ad2DelegateAccount<T>>::remove(ad);
+
+            // end running slots of the ads, budgets are returned to the advertiser
+            read += 1;
+            let slots: Vec<_> = <SlotsOf<T>>::iter_key_prefix(ad)
+                .take(MAX_REVOKED_SLOTS.saturating_sub(ended) as usize)
+                .collect();
+            for nft_id in slots {
+                ended += 1;
+
+                read += 1;
+                let slot = match <SlotOf<T>>::get(nft_id) {
+                    Some(slot) => slot,
+                    None => continue,
+                };
+
+                // a failed drawback is rolled back, the slot ends at its deadline
+                read += 2;
+                write += 5;
+                if let Err(e) = Self::drawback(&slot) {
+                    sp_runtime::print(e);
+                }
+            }
+        }
+
+        // delegations to the DID are left, a revoked DID cannot sign anymore
+
+        T::DbWeight::get().reads_writes(read, write)
+    }
+
+    fn max_weight() -> Weight {
+        let ads = MAX_REVOKED_ADS as Weight;
+        let slots = MAX_REVOKED_SLOTS as Weight;
+
+        T::DbWeight::get().reads_writes(1 + ads + 3 * slots, ads + 5 * slots)
+    }
+}
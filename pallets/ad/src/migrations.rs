@@ -100,3 +100,31 @@ pub mod v5 {
         }
     }
 }
+
+pub mod v6 {
+    use frame_support::traits::{Get, OnRuntimeUpgrade};
+    use frame_support::weights::Weight;
+
+    use crate::{Config, Pallet, SlotOf, SlotsOf, StorageVersion};
+
+    pub struct SlotsIndex<T>(sp_std::marker::PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for SlotsIndex<T> {
+        fn on_runtime_upgrade() -> Weight {
+            // slots are only readable once migrated to v5
+            let version = StorageVersion::get::<Pallet<T>>();
+            if version != 5 {
+                return T::DbWeight::get().reads(1);
+            }
+
+            let mut count = 0u64;
+            for (nft_id, slot) in <SlotOf<T>>::iter() {
+                count += 1;
+                <SlotsOf<T>>::insert(slot.ad_id, nft_id, ());
+            }
+
+            StorageVersion::new(6).put::<Pallet<T>>();
+            T::DbWeight::get().reads_writes(count + 1, count + 1)
+        }
+    }
+}
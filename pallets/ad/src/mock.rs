@@ -172,13 +172,27 @@ impl pallet_uniques::Config for Test {
     type Locker = ();
 }
 
+parameter_types! {
+    pub const DidPalletId: PalletId = PalletId(*b"prm/did ");
+}
+
 impl parami_did::Config for Test {
     type Event = Event;
     type Currency = Balances;
     type DecentralizedId = H160;
     type Hashing = Keccak256;
+    type HandleFee = ();
+    type HandleFeeDestination = ();
+    type HandlePeriod = ();
+    type RecoveryLifetime = ();
+    type Links = ();
+    type MetadataDepositBase = MetadataDepositBase;
+    type MetadataDepositPerByte = MetadataDepositPerByte;
+    type PalletId = DidPalletId;
     type WeightInfo = ();
     type Transferables = (Nft,);
+    type OnDidRevoked = Ad;
+    type OnReferralMilestone = ();
 }
 
 parameter_types! {
@@ -208,6 +222,9 @@ impl parami_nft::Config for Test {
     type Swaps = Swap;
     type WeightInfo = ();
     type UnsignedPriority = ();
+    type AnchorOrigin = EnsureRoot<Self::AccountId>;
+    type Bridge = ();
+    type HoldersOrigin = frame_system::EnsureNever<AssetId>;
     type AssetIdManager = AssetIdManager;
     type NftId = u32;
 }
@@ -1,6 +1,6 @@
 use crate::{
     mock::*, AdAsset, AdsOf, Config, CurrencyOrAsset, DeadlineOf, EndtimeOf, Error, Metadata,
-    SlotOf,
+    ReservePriceOf, SlotOf,
 };
 use frame_support::{assert_noop, assert_ok, traits::Hooks};
 use parami_primitives::constants::DOLLARS;
@@ -211,6 +211,43 @@ fn should_generate_unique_slot_pot() {
     });
 }
 
+#[test]
+fn should_fail_to_bid_under_reserve_price() {
+    new_test_ext().execute_with(|| {
+        let nft = Nft::preferred(DID_ALICE).unwrap();
+
+        assert_ok!(Ad::create(
+            Origin::signed(BOB),
+            vec![],
+            [0u8; 64].into(),
+            1,
+            43200,
+            1u128,
+            0,
+            10u128,
+            None
+        ));
+
+        let ad = <Metadata<Test>>::iter_keys().next().unwrap();
+
+        <ReservePriceOf<Test>>::insert(nft, 450);
+
+        assert_noop!(
+            Ad::bid_with_fraction(Origin::signed(BOB), ad, nft, 400, None, None),
+            Error::<Test>::Underbid
+        );
+
+        assert_ok!(Ad::bid_with_fraction(
+            Origin::signed(BOB),
+            ad,
+            nft,
+            450,
+            None,
+            None
+        ));
+    });
+}
+
 #[test]
 fn should_bid_with_fraction() {
     new_test_ext().execute_with(|| {
@@ -1598,3 +1635,47 @@ fn should_fail_bid_with_fraction_if_not_minted() {
         );
     });
 }
+
+#[test]
+fn should_cleanup_when_did_revoked() {
+    use crate::{Ad2DelegateAccount, SlotsOf};
+
+    new_test_ext().execute_with(|| {
+        let nft = Nft::preferred(DID_ALICE).unwrap();
+        let meta = Nft::meta(nft).unwrap();
+
+        assert_ok!(Ad::create(
+            Origin::signed(BOB),
+            vec![],
+            [0u8; 64].into(),
+            1,
+            43200 * 2,
+            1u128,
+            0,
+            10u128,
+            Some(DID_CHARLIE)
+        ));
+
+        let ad = <Metadata<Test>>::iter_keys().next().unwrap();
+
+        assert_ok!(Ad::bid_with_fraction(
+            Origin::signed(BOB),
+            ad,
+            nft,
+            400,
+            None,
+            None
+        ));
+        assert_eq!(Assets::balance(meta.token_asset_id, BOB), 101);
+        assert_eq!(<SlotsOf<Test>>::get(ad, nft), Some(()));
+
+        assert_ok!(Did::revoke(Origin::signed(BOB)));
+
+        assert_eq!(<SlotsOf<Test>>::iter_prefix(ad).count(), 0);
+
+        assert_eq!(<SlotOf<Test>>::get(nft), None);
+        assert_eq!(<DeadlineOf<Test>>::get(nft, ad), None);
+        assert_eq!(Ad2DelegateAccount::<Test>::get(ad), None);
+        assert_eq!(Assets::balance(meta.token_asset_id, BOB), 501);
+    });
+}
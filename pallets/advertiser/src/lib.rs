@@ -17,11 +17,16 @@ mod benchmarking;
 use frame_support::{
     dispatch::DispatchResult,
     ensure,
-    traits::{Currency, EnsureOrigin, NamedReservableCurrency, OnUnbalanced},
+    traits::{
+        Currency, EnsureOrigin, ExistenceRequirement::AllowDeath, Get, NamedReservableCurrency,
+        OnUnbalanced,
+    },
+    weights::Weight,
     PalletId,
 };
 use parami_did::{EnsureDid, Pallet as Did};
-use sp_runtime::traits::Saturating;
+use parami_traits::{transferable::Transferable, OnDidRevoked};
+use sp_runtime::traits::{Saturating, Zero};
 
 use weights::WeightInfo;
 
@@ -173,6 +178,37 @@ pub mod pallet {
     }
 }
 
+impl<T: Config> OnDidRevoked<DidOf<T>, AccountOf<T>> for Pallet<T> {
+    fn on_did_revoked(_did: &DidOf<T>, account: &AccountOf<T>) -> Weight {
+        let id = <T as Config>::PalletId::get();
+
+        T::Currency::unreserve_all_named(&id.0, account);
+
+        Self::max_weight()
+    }
+
+    fn max_weight() -> Weight {
+        T::DbWeight::get().reads_writes(1, 1)
+    }
+}
+
+impl<T: Config> Transferable<AccountOf<T>> for Pallet<T> {
+    fn transfer_all(src: &AccountOf<T>, dest: &AccountOf<T>) -> DispatchResult {
+        let id = <T as Config>::PalletId::get();
+
+        let reserved = T::Currency::reserved_balance_named(&id.0, src);
+        if reserved.is_zero() {
+            return Ok(());
+        }
+
+        T::Currency::unreserve_named(&id.0, src, reserved);
+        T::Currency::transfer(src, dest, reserved, AllowDeath)?;
+        T::Currency::reserve_named(&id.0, dest, reserved)?;
+
+        Ok(())
+    }
+}
+
 pub struct EnsureAdvertiser<T>(sp_std::marker::PhantomData<T>);
 impl<T: pallet::Config> EnsureOrigin<T::Origin> for EnsureAdvertiser<T> {
     type Success = (DidOf<T>, AccountOf<T>);
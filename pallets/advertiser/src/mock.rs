@@ -78,13 +78,29 @@ impl pallet_balances::Config for Test {
     type ReserveIdentifier = [u8; 8];
 }
 
+parameter_types! {
+    pub const DidPalletId: PalletId = PalletId(*b"prm/did ");
+    pub const MetadataDepositBase: Balance = 0;
+    pub const MetadataDepositPerByte: Balance = 0;
+}
+
 impl parami_did::Config for Test {
     type Event = Event;
     type Currency = Balances;
     type DecentralizedId = sp_core::H160;
     type Hashing = Keccak256;
+    type HandleFee = ();
+    type HandleFeeDestination = ();
+    type HandlePeriod = ();
+    type RecoveryLifetime = ();
+    type Links = ();
+    type MetadataDepositBase = MetadataDepositBase;
+    type MetadataDepositPerByte = MetadataDepositPerByte;
+    type PalletId = DidPalletId;
     type WeightInfo = ();
-    type Transferables = ();
+    type Transferables = Advertiser;
+    type OnDidRevoked = Advertiser;
+    type OnReferralMilestone = ();
 }
 
 parameter_types! {
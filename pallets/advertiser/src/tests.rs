@@ -88,3 +88,45 @@ fn should_ensure() {
         assert_eq!(ensure.unwrap(), (did, alice));
     });
 }
+
+#[test]
+fn should_unreserve_when_did_revoked() {
+    new_test_ext().execute_with(|| {
+        let alice = sr25519::Public([1; 32]);
+
+        assert_ok!(Advertiser::deposit(Origin::signed(alice), 10));
+
+        assert_eq!(Balances::reserved_balance(alice), 10);
+
+        assert_ok!(Did::revoke(Origin::signed(alice)));
+
+        assert_eq!(Balances::free_balance(&alice), 100);
+        assert_eq!(Balances::reserved_balance(alice), 0);
+    });
+}
+
+#[test]
+fn should_move_deposit_with_did() {
+    new_test_ext().execute_with(|| {
+        use frame_support::traits::EnsureOrigin;
+
+        let alice = sr25519::Public([1; 32]);
+        let charlie = sr25519::Public([3; 32]);
+        let did = DID::from_slice(&[0xff; 20]);
+
+        assert_ok!(Advertiser::deposit(Origin::signed(alice), 10));
+
+        assert_ok!(Did::force_transfer_with_assets(
+            Origin::root(),
+            did,
+            charlie
+        ));
+
+        assert_eq!(Balances::total_balance(&alice), 0);
+        assert_eq!(Balances::free_balance(&charlie), 90);
+        assert_eq!(Balances::reserved_balance(charlie), 10);
+
+        let ensure = EnsureAdvertiser::<Test>::try_origin(Origin::signed(charlie));
+        assert_eq!(ensure.unwrap(), (did, charlie));
+    });
+}
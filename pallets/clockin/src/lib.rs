@@ -64,6 +64,7 @@ pub mod pallet {
 
     /// Lottery Metadata
     #[pallet::storage]
+    #[pallet::getter(fn lottery_metadata)]
     pub(super) type LotteryMetadataStore<T: Config> =
         StorageMap<_, Twox64Concat, NftOf<T>, LotteryMetaOf<T>>;
 
@@ -117,6 +118,7 @@ pub mod pallet {
             let (did, who) = parami_did::EnsureDid::<T>::ensure_origin(origin)?;
             let nft_meta = parami_nft::Pallet::<T>::meta(nft_id).ok_or(Error::<T>::NftNotExists)?;
             ensure!(nft_meta.owner == did, Error::<T>::NotNftOwner);
+            parami_nft::Pallet::<T>::ensure_not_bridged(nft_id)?;
             ensure!(nft_meta.minted, Error::<T>::NftNotMinted);
             ensure!(
                 T::Assets::balance(nft_meta.token_asset_id, &who) >= total_reward_token,
@@ -175,6 +177,7 @@ pub mod pallet {
             let (did, who) = parami_did::EnsureDid::<T>::ensure_origin(origin)?;
             let nft_meta = parami_nft::Pallet::<T>::meta(nft_id).ok_or(Error::<T>::NftNotExists)?;
             ensure!(nft_meta.owner == did, Error::<T>::NotNftOwner);
+            parami_nft::Pallet::<T>::ensure_not_bridged(nft_id)?;
             ensure!(nft_meta.minted, Error::<T>::NftNotMinted);
             let meta =
                 LotteryMetadataStore::<T>::get(nft_id).ok_or(Error::<T>::ClockInNotExists)?;
@@ -196,26 +199,36 @@ pub mod pallet {
             let (did, _who) = parami_did::EnsureDid::<T>::ensure_origin(origin)?;
             let nft_meta = parami_nft::Pallet::<T>::meta(nft_id).ok_or(Error::<T>::NftNotExists)?;
             ensure!(nft_meta.owner == did, Error::<T>::NotNftOwner);
-            ensure!(nft_meta.minted, Error::<T>::NftNotMinted);
-            ensure!(
-                level_upper_bounds.len() == level_probability.len(),
-                Error::<T>::MetaParamInvalid
-            );
-            let meta =
-                <LotteryMetadataStore<T>>::get(nft_id).ok_or(Error::<T>::ClockInNotExists)?;
+            parami_nft::Pallet::<T>::ensure_not_bridged(nft_id)?;
 
-            <LotteryMetadataStore<T>>::insert(
+            Self::update_clock_in_inner(
                 nft_id,
-                LotteryMetaOf::<T> {
-                    level_probability,
-                    level_upper_bounds,
-                    shares_per_bucket,
-                    award_per_share,
-                    ..meta
-                },
-            );
+                level_probability,
+                level_upper_bounds,
+                shares_per_bucket,
+                award_per_share,
+            )
+        }
 
-            Ok(())
+        /// Update the clock-in parameters of a NFT,
+        /// called by the holders of the NFT through governance.
+        #[pallet::weight(0)]
+        pub fn update_clock_in_by_holders(
+            origin: OriginFor<T>,
+            level_probability: Vec<u32>,
+            level_upper_bounds: Vec<BalanceOf<T>>,
+            shares_per_bucket: u32,
+            award_per_share: BalanceOf<T>,
+        ) -> DispatchResult {
+            let nft_id = <T as parami_nft::Config>::HoldersOrigin::ensure_origin(origin)?;
+
+            Self::update_clock_in_inner(
+                nft_id,
+                level_probability,
+                level_upper_bounds,
+                shares_per_bucket,
+                award_per_share,
+            )
         }
 
         #[pallet::weight(0)]
@@ -223,6 +236,7 @@ pub mod pallet {
             let (did, who) = parami_did::EnsureDid::<T>::ensure_origin(origin)?;
             let nft_meta = parami_nft::Pallet::<T>::meta(nft_id).ok_or(Error::<T>::NftNotExists)?;
             ensure!(nft_meta.owner == did, Error::<T>::NotNftOwner);
+            parami_nft::Pallet::<T>::ensure_not_bridged(nft_id)?;
             let metadata =
                 <LotteryMetadataStore<T>>::get(nft_id).ok_or(Error::<T>::ClockInNotExists)?;
 
@@ -237,7 +251,8 @@ pub mod pallet {
 
         #[pallet::weight(0)]
         pub fn clock_in(origin: OriginFor<T>, nft_id: NftOf<T>) -> DispatchResult {
-            let (did, who) = parami_did::EnsureDid::<T>::ensure_origin(origin)?;
+            let (did, who) =
+                parami_did::EnsureDid::<T, parami_did::ClockInScope>::ensure_origin(origin)?;
             let meta =
                 <LotteryMetadataStore<T>>::get(nft_id).ok_or(Error::<T>::ClockInNotExists)?;
 
@@ -268,6 +283,36 @@ pub mod pallet {
     }
 
     impl<T: Config> Pallet<T> {
+        fn update_clock_in_inner(
+            nft_id: NftOf<T>,
+            level_probability: Vec<u32>,
+            level_upper_bounds: Vec<BalanceOf<T>>,
+            shares_per_bucket: u32,
+            award_per_share: BalanceOf<T>,
+        ) -> DispatchResult {
+            let nft_meta = parami_nft::Pallet::<T>::meta(nft_id).ok_or(Error::<T>::NftNotExists)?;
+            ensure!(nft_meta.minted, Error::<T>::NftNotMinted);
+            ensure!(
+                level_upper_bounds.len() == level_probability.len(),
+                Error::<T>::MetaParamInvalid
+            );
+            let meta =
+                <LotteryMetadataStore<T>>::get(nft_id).ok_or(Error::<T>::ClockInNotExists)?;
+
+            <LotteryMetadataStore<T>>::insert(
+                nft_id,
+                LotteryMetaOf::<T> {
+                    level_probability,
+                    level_upper_bounds,
+                    shares_per_bucket,
+                    award_per_share,
+                    ..meta
+                },
+            );
+
+            Ok(())
+        }
+
         pub fn generate_reward_pot(nft_id: &NftOf<T>) -> AccountOf<T> {
             return <T as crate::Config>::PalletId::get().into_sub_account_truncating(&nft_id);
         }
@@ -5,6 +5,7 @@ use crate::{Error, LotteryMetadataStore};
 use frame_support::traits::fungibles::{Create, Mutate};
 use frame_support::{assert_noop, assert_ok};
 use parami_primitives::constants::DOLLARS;
+use parami_traits::BridgeNfts;
 use sp_core::sr25519;
 use sp_runtime::traits::One;
 use sp_std::prelude::*;
@@ -548,3 +549,31 @@ fn create_parent_hash_with(last_u8: u8) -> [u8; 32] {
 fn public_keys() -> Vec<sr25519::Public> {
     return vec![ALICE, BOB, CHARLIE, DAVE, EVA];
 }
+
+#[test]
+fn should_not_enable_clockin_when_bridged() {
+    new_test_ext().execute_with(|| {
+        let nft_id = 1;
+
+        assert_ok!(<Nft as BridgeNfts<_>>::lock(&ALICE, nft_id));
+
+        assert_noop!(
+            ClockIn::enable_clock_in(
+                Origin::signed(ALICE),
+                nft_id,
+                vec![10, 20, 30, 40, 50],
+                vec![
+                    1_000 * DOLLARS,
+                    10_000 * DOLLARS,
+                    100_000 * DOLLARS,
+                    1_000_000 * DOLLARS,
+                    10_000_000 * DOLLARS,
+                ],
+                5,
+                100 * DOLLARS,
+                5_000 * DOLLARS,
+            ),
+            parami_nft::Error::<Test>::Bridged
+        );
+    });
+}
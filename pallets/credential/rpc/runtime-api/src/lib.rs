@@ -0,0 +1,22 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_runtime::DispatchError;
+
+pub type ApiResult<T> = Result<T, DispatchError>;
+
+sp_api::decl_runtime_apis! {
+    pub trait CredentialRuntimeApi<Hash, DecentralizedId>
+    where
+        Hash: Codec,
+        DecentralizedId: Codec,
+    {
+        // verify a presented credential against the chain
+        fn verify(
+            credential: Hash,
+            issuer: DecentralizedId,
+            subject: DecentralizedId,
+            schema: Hash,
+        ) -> ApiResult<()>;
+    }
+}
@@ -0,0 +1,90 @@
+use codec::Codec;
+use jsonrpsee::{
+    core::{Error, RpcResult},
+    proc_macros::rpc,
+    types::error::{CallError, ErrorObject, INTERNAL_ERROR_CODE},
+};
+pub use parami_credential_rpc_runtime_api::{ApiResult, CredentialRuntimeApi};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use std::sync::Arc;
+
+#[rpc(client, server)]
+pub trait CredentialApi<BlockHash, Hash, DecentralizedId> {
+    /// Verify a presented credential against the chain
+    ///
+    /// # Arguments
+    ///
+    /// * `credential` - The hash of the credential document
+    /// * `issuer` - The issuer claimed by the credential
+    /// * `subject` - The subject claimed by the credential
+    /// * `schema` - The schema claimed by the credential
+    ///
+    /// # Results
+    ///
+    /// true if the credential is anchored, not revoked and not expired
+    #[method(name = "credential_verify")]
+    fn verify(
+        &self,
+        credential: Hash,
+        issuer: DecentralizedId,
+        subject: DecentralizedId,
+        schema: Hash,
+        at: Option<BlockHash>,
+    ) -> RpcResult<bool>;
+}
+
+pub struct CredentialRpcHandler<C, Block, Hash, DecentralizedId> {
+    client: Arc<C>,
+    _marker: std::marker::PhantomData<(Block, Hash, DecentralizedId)>,
+}
+
+impl<C, Block, Hash, DecentralizedId> CredentialRpcHandler<C, Block, Hash, DecentralizedId> {
+    pub fn new(client: Arc<C>) -> Self {
+        Self {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<C, Block, Hash, DecentralizedId>
+    CredentialApiServer<<Block as BlockT>::Hash, Hash, DecentralizedId>
+    for CredentialRpcHandler<C, Block, Hash, DecentralizedId>
+where
+    Block: BlockT,
+    C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: CredentialRuntimeApi<Block, Hash, DecentralizedId>,
+    Hash: Codec + Send + Sync + 'static,
+    DecentralizedId: Codec + Send + Sync + 'static,
+{
+    fn verify(
+        &self,
+        credential: Hash,
+        issuer: DecentralizedId,
+        subject: DecentralizedId,
+        schema: Hash,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<bool> {
+        let api = self.client.runtime_api();
+        let at: BlockId<Block> = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let res = api
+            .verify(&at, credential, issuer, subject, schema)
+            .map_err(|e| {
+                Error::Call(CallError::Custom(ErrorObject::owned(
+                    INTERNAL_ERROR_CODE,
+                    "Unable to verify credential.",
+                    Some(format!("{:?}", e)),
+                )))
+            })?;
+
+        res.map(|_| true).map_err(|e| {
+            Error::Call(CallError::Custom(ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                "Unable to verify credential.",
+                Some(format!("{:?}", e)),
+            )))
+        })
+    }
+}
@@ -0,0 +1,92 @@
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as Credential;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::traits::{Currency, NamedReservableCurrency};
+use frame_system::RawOrigin;
+use sp_runtime::traits::Bounded;
+
+fn register<T: Config>(who: &T::AccountId) -> Result<DidOf<T>, &'static str> {
+    T::Currency::make_free_balance_be(who, BalanceOf::<T>::max_value());
+
+    Did::<T>::register(RawOrigin::Signed(who.clone()).into(), None)?;
+
+    Ok(Did::<T>::did_of(who).unwrap())
+}
+
+fn create_schema<T: Config>(who: &T::AccountId, body: Vec<u8>) -> Result<HashOf<T>, &'static str> {
+    let issuer = Did::<T>::did_of(who).unwrap();
+
+    Credential::<T>::create_schema(RawOrigin::Signed(who.clone()).into(), body.clone())?;
+
+    Ok(<T as frame_system::Config>::Hashing::hash_of(&(
+        &issuer, &body,
+    )))
+}
+
+benchmarks! {
+    create_schema {
+        let n in 1 .. 2048;
+
+        let body = vec![0u8; n as usize];
+
+        let caller: T::AccountId = whitelisted_caller();
+        let issuer = register::<T>(&caller)?;
+
+        let hash = <T as frame_system::Config>::Hashing::hash_of(&(&issuer, &body));
+    }: _(RawOrigin::Signed(caller), body)
+    verify {
+        assert!(<Schemas<T>>::contains_key(&hash));
+    }
+
+    remove_schema {
+        let caller: T::AccountId = whitelisted_caller();
+        register::<T>(&caller)?;
+
+        let schema = create_schema::<T>(&caller, vec![0u8; 2048])?;
+    }: _(RawOrigin::Signed(caller.clone()), schema)
+    verify {
+        let id = <T as Config>::PalletId::get();
+        assert!(!<Schemas<T>>::contains_key(&schema));
+        assert_eq!(T::Currency::reserved_balance_named(&id.0, &caller), Zero::zero());
+    }
+
+    issue {
+        let caller: T::AccountId = whitelisted_caller();
+        let subject: T::AccountId = account("subject", 1, 1);
+
+        register::<T>(&caller)?;
+        let subject = register::<T>(&subject)?;
+
+        let schema = create_schema::<T>(&caller, vec![0u8; 2048])?;
+        let credential = <T as frame_system::Config>::Hashing::hash_of(&subject);
+    }: _(RawOrigin::Signed(caller), schema, subject, credential, None)
+    verify {
+        assert!(<Credentials<T>>::contains_key(&credential));
+    }
+
+    revoke {
+        let caller: T::AccountId = whitelisted_caller();
+        let subject: T::AccountId = account("subject", 1, 1);
+
+        let issuer = register::<T>(&caller)?;
+        let subject = register::<T>(&subject)?;
+
+        let schema = create_schema::<T>(&caller, vec![0u8; 2048])?;
+        let credential = <T as frame_system::Config>::Hashing::hash_of(&subject);
+
+        Credential::<T>::issue(
+            RawOrigin::Signed(caller.clone()).into(),
+            schema,
+            subject,
+            credential,
+            None,
+        )?;
+    }: _(RawOrigin::Signed(caller), credential)
+    verify {
+        assert!(Credential::<T>::is_revoked(&issuer, 0));
+    }
+}
+
+impl_benchmark_test_suite!(Credential, crate::mock::new_test_ext(), crate::mock::Test);
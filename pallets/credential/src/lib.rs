@@ -0,0 +1,374 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[rustfmt::skip]
+pub mod weights;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+mod types;
+
+use frame_support::{
+    dispatch::DispatchResult,
+    ensure,
+    traits::{ConstU32, Currency, ExistenceRequirement::AllowDeath, Get, NamedReservableCurrency},
+    weights::Weight,
+    BoundedVec, PalletId,
+};
+use parami_did::{EnsureDid, Pallet as Did};
+use parami_traits::{transferable::Transferable, OnDidRevoked};
+use sp_runtime::traits::{Hash, Saturating, Zero};
+use sp_std::prelude::*;
+
+use weights::WeightInfo;
+
+type AccountOf<T> = <T as frame_system::Config>::AccountId;
+type BalanceOf<T> = <<T as parami_did::Config>::Currency as Currency<AccountOf<T>>>::Balance;
+type DidOf<T> = <T as parami_did::Config>::DecentralizedId;
+type HashOf<T> = <T as frame_system::Config>::Hash;
+type HeightOf<T> = <T as frame_system::Config>::BlockNumber;
+type SchemaBodyOf = BoundedVec<u8, ConstU32<2048>>;
+type SchemaMetaOf<T> = types::Schema<DidOf<T>, SchemaBodyOf, BalanceOf<T>, HeightOf<T>>;
+type CredentialMetaOf<T> = types::Credential<DidOf<T>, HashOf<T>, BalanceOf<T>, HeightOf<T>>;
+
+/// Bits of a word in the revocation bitmap
+const WORD_BITS: u32 = u64::BITS;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use frame_support::pallet_prelude::*;
+    use frame_system::pallet_prelude::*;
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config + parami_did::Config {
+        /// The overarching event type
+        type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+        /// The deposit for anchoring a credential
+        #[pallet::constant]
+        type CredentialDeposit: Get<BalanceOf<Self>>;
+
+        /// The base deposit for publishing a schema
+        #[pallet::constant]
+        type SchemaDepositBase: Get<BalanceOf<Self>>;
+
+        /// The deposit per byte of a schema document
+        #[pallet::constant]
+        type SchemaDepositPerByte: Get<BalanceOf<Self>>;
+
+        /// The pallet id, used for reserving deposits
+        #[pallet::constant]
+        type PalletId: Get<PalletId>;
+
+        /// Weight information for extrinsics in this pallet.
+        type WeightInfo: WeightInfo;
+    }
+
+    #[pallet::pallet]
+    #[pallet::generate_store(pub(super) trait Store)]
+    pub struct Pallet<T>(_);
+
+    /// Credential schemas, keyed by the hash of issuer and schema document
+    #[pallet::storage]
+    #[pallet::getter(fn schema)]
+    pub(super) type Schemas<T: Config> = StorageMap<_, Identity, HashOf<T>, SchemaMetaOf<T>>;
+
+    /// Anchored credentials, keyed by the hash of the credential document
+    #[pallet::storage]
+    #[pallet::getter(fn credential)]
+    pub(super) type Credentials<T: Config> =
+        StorageMap<_, Identity, HashOf<T>, CredentialMetaOf<T>>;
+
+    /// The index of the next credential of an issuer
+    #[pallet::storage]
+    #[pallet::getter(fn next_index)]
+    pub(super) type NextIndexOf<T: Config> = StorageMap<_, Identity, DidOf<T>, u32, ValueQuery>;
+
+    /// The revocation bitmap of an issuer, in words of 64 bits
+    #[pallet::storage]
+    pub(super) type RevocationBitmapOf<T: Config> =
+        StorageDoubleMap<_, Identity, DidOf<T>, Twox64Concat, u32, u64, ValueQuery>;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// Schema created \[schema, issuer\]
+        SchemaCreated(HashOf<T>, DidOf<T>),
+        /// Schema removed \[schema, issuer\]
+        SchemaRemoved(HashOf<T>, DidOf<T>),
+        /// Credential issued \[credential, issuer, subject, index\]
+        Issued(HashOf<T>, DidOf<T>, DidOf<T>, u32),
+        /// Credential revoked \[credential, issuer, index\]
+        Revoked(HashOf<T>, DidOf<T>, u32),
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+    #[pallet::error]
+    pub enum Error<T> {
+        BadSchema,
+        CredentialExists,
+        CredentialNotExists,
+        DidNotExists,
+        Expired,
+        IssuerMismatch,
+        NotIssuer,
+        Revoked,
+        SchemaExists,
+        SchemaMismatch,
+        SchemaNotExists,
+        SubjectMismatch,
+        TooManyCredentials,
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Publish a credential schema.
+        ///
+        /// A deposit proportional to the size of the schema document is reserved
+        /// from the account of the issuer.
+        ///
+        /// # Arguments
+        ///
+        /// * `body` - The schema document, e.g. a JSON schema or its URI
+        #[pallet::weight(T::WeightInfo::create_schema(body.len() as u32))]
+        pub fn create_schema(origin: OriginFor<T>, body: Vec<u8>) -> DispatchResult {
+            let (issuer, who) = EnsureDid::<T>::ensure_origin(origin)?;
+
+            ensure!(!body.is_empty(), Error::<T>::BadSchema);
+
+            let hash = <T as frame_system::Config>::Hashing::hash_of(&(&issuer, &body));
+
+            ensure!(!<Schemas<T>>::contains_key(&hash), Error::<T>::SchemaExists);
+
+            let body: SchemaBodyOf = body.try_into().map_err(|_| Error::<T>::BadSchema)?;
+
+            let deposit = Self::schema_deposit(&body);
+
+            let id = <T as Config>::PalletId::get();
+            T::Currency::reserve_named(&id.0, &who, deposit)?;
+
+            let created = <frame_system::Pallet<T>>::block_number();
+
+            <Schemas<T>>::insert(
+                &hash,
+                types::Schema {
+                    issuer,
+                    body,
+                    deposit,
+                    created,
+                },
+            );
+
+            Self::deposit_event(Event::<T>::SchemaCreated(hash, issuer));
+
+            Ok(())
+        }
+
+        /// Remove a credential schema, and release its deposit.
+        ///
+        /// Credentials already issued with the schema stay valid.
+        #[pallet::weight(T::WeightInfo::remove_schema())]
+        pub fn remove_schema(origin: OriginFor<T>, schema: HashOf<T>) -> DispatchResult {
+            let (did, who) = EnsureDid::<T>::ensure_origin(origin)?;
+
+            let meta = <Schemas<T>>::get(&schema).ok_or(Error::<T>::SchemaNotExists)?;
+
+            ensure!(meta.issuer == did, Error::<T>::NotIssuer);
+
+            <Schemas<T>>::remove(&schema);
+
+            let id = <T as Config>::PalletId::get();
+            T::Currency::unreserve_named(&id.0, &who, meta.deposit);
+
+            Self::deposit_event(Event::<T>::SchemaRemoved(schema, did));
+
+            Ok(())
+        }
+
+        /// Anchor the hash of a credential issued to a DID.
+        ///
+        /// A deposit is reserved from the account of the issuer until the credential is revoked.
+        ///
+        /// # Arguments
+        ///
+        /// * `schema` - The schema of the credential
+        /// * `subject` - The DID the credential is issued to
+        /// * `credential` - The hash of the credential document
+        /// * `expires_at` - The height since which the credential is invalid
+        #[pallet::weight(T::WeightInfo::issue())]
+        pub fn issue(
+            origin: OriginFor<T>,
+            schema: HashOf<T>,
+            subject: DidOf<T>,
+            credential: HashOf<T>,
+            expires_at: Option<HeightOf<T>>,
+        ) -> DispatchResult {
+            let (issuer, who) = EnsureDid::<T>::ensure_origin(origin)?;
+
+            ensure!(
+                <Schemas<T>>::contains_key(&schema),
+                Error::<T>::SchemaNotExists
+            );
+
+            ensure!(
+                !<Credentials<T>>::contains_key(&credential),
+                Error::<T>::CredentialExists
+            );
+
+            let meta = Did::<T>::meta(&subject).ok_or(Error::<T>::DidNotExists)?;
+            ensure!(!meta.revoked, Error::<T>::DidNotExists);
+
+            let issued = <frame_system::Pallet<T>>::block_number();
+
+            if let Some(expires_at) = expires_at {
+                ensure!(expires_at > issued, Error::<T>::Expired);
+            }
+
+            let index = <NextIndexOf<T>>::get(&issuer);
+            let next = index.checked_add(1).ok_or(Error::<T>::TooManyCredentials)?;
+
+            let deposit = T::CredentialDeposit::get();
+
+            let id = <T as Config>::PalletId::get();
+            T::Currency::reserve_named(&id.0, &who, deposit)?;
+
+            <NextIndexOf<T>>::insert(&issuer, next);
+
+            <Credentials<T>>::insert(
+                &credential,
+                types::Credential {
+                    issuer,
+                    subject,
+                    schema,
+                    index,
+                    deposit,
+                    issued,
+                    expires_at,
+                },
+            );
+
+            Self::deposit_event(Event::<T>::Issued(credential, issuer, subject, index));
+
+            Ok(())
+        }
+
+        /// Revoke a credential by setting its bit in the revocation bitmap of the issuer,
+        /// the anchored credential is removed and its deposit released.
+        #[pallet::weight(T::WeightInfo::revoke())]
+        pub fn revoke(origin: OriginFor<T>, credential: HashOf<T>) -> DispatchResult {
+            let (did, who) = EnsureDid::<T>::ensure_origin(origin)?;
+
+            let meta = <Credentials<T>>::get(&credential).ok_or(Error::<T>::CredentialNotExists)?;
+
+            ensure!(meta.issuer == did, Error::<T>::NotIssuer);
+
+            ensure!(
+                !Self::is_revoked(&meta.issuer, meta.index),
+                Error::<T>::Revoked
+            );
+
+            <RevocationBitmapOf<T>>::mutate(&meta.issuer, meta.index / WORD_BITS, |word| {
+                *word |= 1u64 << (meta.index % WORD_BITS)
+            });
+
+            <Credentials<T>>::remove(&credential);
+
+            let id = <T as Config>::PalletId::get();
+            T::Currency::unreserve_named(&id.0, &who, meta.deposit);
+
+            Self::deposit_event(Event::<T>::Revoked(credential, meta.issuer, meta.index));
+
+            Ok(())
+        }
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// The deposit required to publish a schema
+    pub fn schema_deposit(body: &[u8]) -> BalanceOf<T> {
+        T::SchemaDepositPerByte::get()
+            .saturating_mul((body.len() as u32).into())
+            .saturating_add(T::SchemaDepositBase::get())
+    }
+
+    /// Check if the credential at `index` of an issuer is revoked
+    pub fn is_revoked(issuer: &DidOf<T>, index: u32) -> bool {
+        let word = <RevocationBitmapOf<T>>::get(issuer, index / WORD_BITS);
+
+        word & (1u64 << (index % WORD_BITS)) != 0
+    }
+
+    /// Verify a presented credential against the chain
+    ///
+    /// # Arguments
+    ///
+    /// * `credential` - The hash of the presented credential document
+    /// * `issuer` - The issuer claimed by the credential
+    /// * `subject` - The subject claimed by the credential
+    /// * `schema` - The schema claimed by the credential
+    pub fn verify(
+        credential: HashOf<T>,
+        issuer: DidOf<T>,
+        subject: DidOf<T>,
+        schema: HashOf<T>,
+    ) -> DispatchResult {
+        let meta = <Credentials<T>>::get(&credential).ok_or(Error::<T>::CredentialNotExists)?;
+
+        ensure!(meta.issuer == issuer, Error::<T>::IssuerMismatch);
+        ensure!(meta.subject == subject, Error::<T>::SubjectMismatch);
+        ensure!(meta.schema == schema, Error::<T>::SchemaMismatch);
+
+        ensure!(
+            !Self::is_revoked(&meta.issuer, meta.index),
+            Error::<T>::Revoked
+        );
+
+        if let Some(expires_at) = meta.expires_at {
+            let height = <frame_system::Pallet<T>>::block_number();
+            ensure!(height < expires_at, Error::<T>::Expired);
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: Config> OnDidRevoked<DidOf<T>, AccountOf<T>> for Pallet<T> {
+    fn on_did_revoked(_did: &DidOf<T>, account: &AccountOf<T>) -> Weight {
+        let id = <T as Config>::PalletId::get();
+
+        T::Currency::unreserve_all_named(&id.0, account);
+
+        Self::max_weight()
+    }
+
+    fn max_weight() -> Weight {
+        T::DbWeight::get().reads_writes(1, 1)
+    }
+}
+
+impl<T: Config> Transferable<AccountOf<T>> for Pallet<T> {
+    fn transfer_all(src: &AccountOf<T>, dest: &AccountOf<T>) -> DispatchResult {
+        let id = <T as Config>::PalletId::get();
+
+        let reserved = T::Currency::reserved_balance_named(&id.0, src);
+        if reserved.is_zero() {
+            return Ok(());
+        }
+
+        T::Currency::unreserve_named(&id.0, src, reserved);
+        T::Currency::transfer(src, dest, reserved, AllowDeath)?;
+        T::Currency::reserve_named(&id.0, dest, reserved)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,230 @@
+use crate::{mock::*, Credentials, Error, RevocationBitmapOf, Schemas};
+use frame_support::{assert_noop, assert_ok, traits::Currency};
+use sp_core::H256;
+use sp_runtime::traits::{BlakeTwo256, Hash};
+
+const SCHEMA: &[u8] = br#"{"type":"object","properties":{"twitter":{"type":"string"}}}"#;
+
+fn create_schema() -> H256 {
+    assert_ok!(Credential::create_schema(
+        Origin::signed(ALICE),
+        SCHEMA.to_vec()
+    ));
+
+    BlakeTwo256::hash_of(&(&DID_ALICE, &SCHEMA.to_vec()))
+}
+
+#[test]
+fn should_create_schema() {
+    new_test_ext().execute_with(|| {
+        let schema = create_schema();
+
+        let meta = <Schemas<Test>>::get(&schema).unwrap();
+        assert_eq!(meta.issuer, DID_ALICE);
+        assert_eq!(meta.body, SCHEMA.to_vec());
+        assert_eq!(meta.deposit, 10);
+        assert_eq!(Balances::reserved_balance(&ALICE), 10);
+
+        assert_noop!(
+            Credential::create_schema(Origin::signed(ALICE), SCHEMA.to_vec()),
+            Error::<Test>::SchemaExists
+        );
+
+        assert_noop!(
+            Credential::create_schema(Origin::signed(ALICE), vec![]),
+            Error::<Test>::BadSchema
+        );
+
+        assert_noop!(
+            Credential::create_schema(Origin::signed(ALICE), vec![0u8; 2049]),
+            Error::<Test>::BadSchema
+        );
+    });
+}
+
+#[test]
+fn should_remove_schema() {
+    new_test_ext().execute_with(|| {
+        let schema = create_schema();
+
+        assert_noop!(
+            Credential::remove_schema(Origin::signed(BOB), schema),
+            Error::<Test>::NotIssuer
+        );
+
+        assert_ok!(Credential::remove_schema(Origin::signed(ALICE), schema));
+
+        assert!(!<Schemas<Test>>::contains_key(&schema));
+        assert_eq!(Balances::reserved_balance(&ALICE), 0);
+
+        assert_noop!(
+            Credential::remove_schema(Origin::signed(ALICE), schema),
+            Error::<Test>::SchemaNotExists
+        );
+    });
+}
+
+#[test]
+fn should_fail_without_deposit() {
+    new_test_ext().execute_with(|| {
+        let schema = create_schema();
+
+        Balances::make_free_balance_be(&ALICE, 3);
+
+        assert_noop!(
+            Credential::issue(
+                Origin::signed(ALICE),
+                schema,
+                DID_BOB,
+                H256::repeat_byte(1),
+                None
+            ),
+            pallet_balances::Error::<Test>::InsufficientBalance
+        );
+
+        assert_noop!(
+            Credential::create_schema(Origin::signed(ALICE), b"{}".to_vec()),
+            pallet_balances::Error::<Test>::InsufficientBalance
+        );
+    });
+}
+
+#[test]
+fn should_issue_and_verify() {
+    new_test_ext().execute_with(|| {
+        let schema = create_schema();
+        let credential = H256::repeat_byte(1);
+
+        assert_ok!(Credential::issue(
+            Origin::signed(ALICE),
+            schema,
+            DID_BOB,
+            credential,
+            Some(10)
+        ));
+
+        let meta = <Credentials<Test>>::get(&credential).unwrap();
+        assert_eq!(meta.issuer, DID_ALICE);
+        assert_eq!(meta.subject, DID_BOB);
+        assert_eq!(meta.index, 0);
+        assert_eq!(meta.deposit, 5);
+        assert_eq!(Credential::next_index(&DID_ALICE), 1);
+        assert_eq!(Balances::reserved_balance(&ALICE), 15);
+
+        assert_ok!(Credential::verify(credential, DID_ALICE, DID_BOB, schema));
+
+        assert_noop!(
+            Credential::verify(credential, DID_BOB, DID_BOB, schema),
+            Error::<Test>::IssuerMismatch
+        );
+        assert_noop!(
+            Credential::verify(credential, DID_ALICE, DID_ALICE, schema),
+            Error::<Test>::SubjectMismatch
+        );
+        assert_noop!(
+            Credential::verify(credential, DID_ALICE, DID_BOB, H256::zero()),
+            Error::<Test>::SchemaMismatch
+        );
+        assert_noop!(
+            Credential::verify(H256::zero(), DID_ALICE, DID_BOB, schema),
+            Error::<Test>::CredentialNotExists
+        );
+
+        System::set_block_number(10);
+
+        assert_noop!(
+            Credential::verify(credential, DID_ALICE, DID_BOB, schema),
+            Error::<Test>::Expired
+        );
+    });
+}
+
+#[test]
+fn should_fail_to_issue() {
+    new_test_ext().execute_with(|| {
+        let schema = create_schema();
+        let credential = H256::repeat_byte(1);
+
+        assert_noop!(
+            Credential::issue(
+                Origin::signed(ALICE),
+                H256::zero(),
+                DID_BOB,
+                credential,
+                None
+            ),
+            Error::<Test>::SchemaNotExists
+        );
+
+        assert_noop!(
+            Credential::issue(Origin::signed(ALICE), schema, DID_CHARLIE, credential, None),
+            Error::<Test>::DidNotExists
+        );
+
+        assert_ok!(Credential::issue(
+            Origin::signed(ALICE),
+            schema,
+            DID_BOB,
+            credential,
+            None
+        ));
+
+        assert_noop!(
+            Credential::issue(Origin::signed(BOB), schema, DID_BOB, credential, None),
+            Error::<Test>::CredentialExists
+        );
+    });
+}
+
+#[test]
+fn should_revoke() {
+    new_test_ext().execute_with(|| {
+        let schema = create_schema();
+
+        for i in 0..66u8 {
+            assert_ok!(Credential::issue(
+                Origin::signed(ALICE),
+                schema,
+                DID_BOB,
+                H256::repeat_byte(i),
+                None
+            ));
+        }
+
+        let credential = H256::repeat_byte(65);
+
+        assert_noop!(
+            Credential::revoke(Origin::signed(BOB), credential),
+            Error::<Test>::NotIssuer
+        );
+
+        assert_eq!(Balances::reserved_balance(&ALICE), 10 + 66 * 5);
+
+        assert_ok!(Credential::revoke(Origin::signed(ALICE), credential));
+
+        assert!(!<Credentials<Test>>::contains_key(&credential));
+        assert_eq!(Balances::reserved_balance(&ALICE), 10 + 65 * 5);
+
+        assert_eq!(<RevocationBitmapOf<Test>>::get(&DID_ALICE, 0), 0);
+        assert_eq!(<RevocationBitmapOf<Test>>::get(&DID_ALICE, 1), 0b10);
+
+        assert!(Credential::is_revoked(&DID_ALICE, 65));
+        assert!(!Credential::is_revoked(&DID_ALICE, 64));
+
+        assert_noop!(
+            Credential::verify(credential, DID_ALICE, DID_BOB, schema),
+            Error::<Test>::CredentialNotExists
+        );
+        assert_ok!(Credential::verify(
+            H256::repeat_byte(64),
+            DID_ALICE,
+            DID_BOB,
+            schema
+        ));
+
+        assert_noop!(
+            Credential::revoke(Origin::signed(ALICE), credential),
+            Error::<Test>::CredentialNotExists
+        );
+    });
+}
@@ -0,0 +1,26 @@
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+
+#[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Schema<D, Body, B, N> {
+    pub issuer: D,
+    /// The schema document, e.g. a JSON schema or its URI
+    pub body: Body,
+    /// The deposit reserved from the issuer
+    pub deposit: B,
+    pub created: N,
+}
+
+#[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Credential<D, H, B, N> {
+    pub issuer: D,
+    pub subject: D,
+    pub schema: H,
+    /// Position in the revocation bitmap of the issuer
+    pub index: u32,
+    /// The deposit reserved from the issuer
+    pub deposit: B,
+    pub issued: N,
+    pub expires_at: Option<N>,
+}
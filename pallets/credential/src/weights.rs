@@ -0,0 +1,134 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Weights for parami_credential
+//!
+//! The base weights are estimated from the benchmarked extrinsics of parami_did
+//! and parami_advertiser with the same storage access, the storage access is exact.
+//! Regenerate this file with the command below on the reference hardware.
+
+// Executed Command:
+// ./target/release/parami
+// benchmark
+// pallet
+// --chain=dev
+// --execution=wasm
+// --wasm-execution=compiled
+// --pallet=parami_credential
+// --extrinsic=*
+// --steps=50
+// --repeat=20
+// --template=./.maintain/frame-weight-template.hbs
+// --output=./pallets/credential/src/weights.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for parami_credential.
+pub trait WeightInfo {
+    fn create_schema(n: u32, ) -> Weight;
+    fn remove_schema() -> Weight;
+    fn issue() -> Weight;
+    fn revoke() -> Weight;
+}
+
+/// Weights for parami_credential using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Credential Schemas (r:1 w:1)
+    // Storage: Balances Reserves (r:1 w:1)
+    fn create_schema(n: u32, ) -> Weight {
+        (31_000_000 as Weight)
+            .saturating_add((4_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Credential Schemas (r:1 w:1)
+    // Storage: Balances Reserves (r:1 w:1)
+    fn remove_schema() -> Weight {
+        (27_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Credential Schemas (r:1 w:0)
+    // Storage: Credential Credentials (r:1 w:1)
+    // Storage: Did Metadata (r:1 w:0)
+    // Storage: Credential NextIndexOf (r:1 w:1)
+    // Storage: Balances Reserves (r:1 w:1)
+    fn issue() -> Weight {
+        (35_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Credential Credentials (r:1 w:1)
+    // Storage: Credential RevocationBitmapOf (r:1 w:1)
+    // Storage: Balances Reserves (r:1 w:1)
+    fn revoke() -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Credential Schemas (r:1 w:1)
+    // Storage: Balances Reserves (r:1 w:1)
+    fn create_schema(n: u32, ) -> Weight {
+        (31_000_000 as Weight)
+            .saturating_add((4_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Credential Schemas (r:1 w:1)
+    // Storage: Balances Reserves (r:1 w:1)
+    fn remove_schema() -> Weight {
+        (27_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Credential Schemas (r:1 w:0)
+    // Storage: Credential Credentials (r:1 w:1)
+    // Storage: Did Metadata (r:1 w:0)
+    // Storage: Credential NextIndexOf (r:1 w:1)
+    // Storage: Balances Reserves (r:1 w:1)
+    fn issue() -> Weight {
+        (35_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Credential Credentials (r:1 w:1)
+    // Storage: Credential RevocationBitmapOf (r:1 w:1)
+    // Storage: Balances Reserves (r:1 w:1)
+    fn revoke() -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+}
@@ -0,0 +1,40 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use parami_primitives::did::DidDocument;
+use sp_runtime::DispatchError;
+use sp_std::vec::Vec;
+
+pub type ApiResult<T> = Result<T, DispatchError>;
+
+sp_api::decl_runtime_apis! {
+    pub trait DidRuntimeApi<DecentralizedId, AccountId>
+    where
+        DecentralizedId: Codec,
+        AccountId: Codec,
+    {
+        // resolve the DID document of the did
+        fn resolve(did: DecentralizedId) -> ApiResult<DidDocument<DecentralizedId>>;
+
+        // preview the DID the account will get when registering
+        fn did_preview(account: AccountId) -> ApiResult<DecentralizedId>;
+
+        // get the on-chain metadata of the did
+        fn get_metadata(did: DecentralizedId, key: Vec<u8>) -> Option<Vec<u8>>;
+
+        // get the raw storage key of the metadata, used to build storage proofs
+        fn metadata_storage_key(did: DecentralizedId, key: Vec<u8>) -> Option<Vec<u8>>;
+
+        // resolve a handle, e.g. alice.ad3, to its did
+        fn lookup_handle(handle: Vec<u8>) -> Option<DecentralizedId>;
+
+        // resolve the handle of the did
+        fn reverse_lookup_handle(did: DecentralizedId) -> Option<Vec<u8>>;
+
+        // get the invitees of the did, paginated
+        fn invitees(did: DecentralizedId, start: u32, limit: u32) -> Vec<DecentralizedId>;
+
+        // get the number of invitees in the downline of the did, by level
+        fn referral_counts(did: DecentralizedId) -> Vec<u32>;
+    }
+}
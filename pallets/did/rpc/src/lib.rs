@@ -1,15 +1,23 @@
+use base58::ToBase58;
 use codec::Codec;
 use jsonrpsee::{
-    core::{async_trait, RpcResult},
+    core::{async_trait, Error, RpcResult},
     proc_macros::rpc,
+    types::error::{CallError, ErrorObject, INTERNAL_ERROR_CODE},
 };
-use parami_did_utils::derive_storage_key;
-use parking_lot::RwLock;
-use sp_core::offchain::OffchainStorage;
+pub use parami_did_rpc_runtime_api::{ApiResult, DidRuntimeApi};
+use parami_primitives::did::{to_did_uri, DidDocument, PublicKey, VerificationRelationship};
+use sc_client_api::ProofProvider;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
 use std::sync::Arc;
 
 #[rpc(client, server)]
-pub trait DidApi<DecentralizedId> {
+pub trait DidApi<BlockHash, DecentralizedId> {
     /// Get metadata of a DID
     ///
     /// # Arguments
@@ -21,7 +29,12 @@ pub trait DidApi<DecentralizedId> {
     ///
     /// the requested metadata
     #[method(name = "did_getMetadata")]
-    fn get_metadata(&self, did: DecentralizedId, key: String) -> RpcResult<String>;
+    fn get_metadata(
+        &self,
+        did: DecentralizedId,
+        key: String,
+        at: Option<BlockHash>,
+    ) -> RpcResult<String>;
 
     /// Batch get metadata of a DID
     ///
@@ -34,71 +47,500 @@ pub trait DidApi<DecentralizedId> {
     ///
     /// the requested metadata
     #[method(name = "did_batchGetMetadata")]
-    fn batch_get_metadata(&self, did: DecentralizedId, keys: Vec<String>)
-        -> RpcResult<Vec<String>>;
+    fn batch_get_metadata(
+        &self,
+        did: DecentralizedId,
+        keys: Vec<String>,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<String>>;
+
+    /// Get metadata of a DID with a storage proof
+    ///
+    /// # Arguments
+    ///
+    /// * `did` - The DID
+    /// * `key` - The metadata key
+    ///
+    /// # Results
+    ///
+    /// the requested metadata, its storage key and the proof against the state root of `at`
+    #[method(name = "did_getMetadataProof")]
+    fn get_metadata_proof(
+        &self,
+        did: DecentralizedId,
+        key: String,
+        at: Option<BlockHash>,
+    ) -> RpcResult<MetadataProof<BlockHash>>;
 }
 
-pub struct DidRpcHandler<T: OffchainStorage, DecentralizedId> {
-    storage: Arc<RwLock<T>>,
-    _marker: std::marker::PhantomData<DecentralizedId>,
+/// Metadata of a DID with a storage proof
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MetadataProof<Hash> {
+    /// Block hash used to generate the proof
+    pub at: Hash,
+    /// Raw storage key of the metadata entry
+    pub key: Bytes,
+    /// The metadata, empty if not exists
+    pub value: String,
+    /// Trie nodes proving the storage key against the state root
+    pub proof: Vec<Bytes>,
 }
 
-impl<T, DecentralizedId> DidRpcHandler<T, DecentralizedId>
-where
-    T: OffchainStorage,
-    DecentralizedId: Codec,
-{
-    pub fn new(storage: T) -> Self {
+pub struct DidRpcHandler<C, Block, DecentralizedId, AccountId> {
+    client: Arc<C>,
+    _marker: std::marker::PhantomData<(Block, DecentralizedId, AccountId)>,
+}
+
+impl<C, Block, DecentralizedId, AccountId> DidRpcHandler<C, Block, DecentralizedId, AccountId> {
+    pub fn new(client: Arc<C>) -> Self {
         Self {
-            storage: Arc::new(RwLock::new(storage)),
+            client,
             _marker: Default::default(),
         }
     }
 }
 
-#[async_trait]
-impl<T, DecentralizedId> DidApiServer<DecentralizedId> for DidRpcHandler<T, DecentralizedId>
+impl<C, Block, DecentralizedId, AccountId> DidRpcHandler<C, Block, DecentralizedId, AccountId>
 where
-    T: OffchainStorage + 'static,
-    DecentralizedId: Codec + Send + Sync + 'static,
+    Block: BlockT,
+    C: ProvideRuntimeApi<Block>,
+    C::Api: DidRuntimeApi<Block, DecentralizedId, AccountId>,
+    DecentralizedId: Codec,
+    AccountId: Codec,
 {
-    fn get_metadata(&self, did: DecentralizedId, key: String) -> RpcResult<String> {
-        let metadata = self
-            .storage
-            .read()
-            .get(
-                sp_offchain::STORAGE_PREFIX,
-                &*derive_storage_key(key.as_bytes(), &did),
-            )
-            .map(from_utf8)
-            .unwrap_or_default();
+    fn metadata(
+        &self,
+        at: &BlockId<Block>,
+        did: DecentralizedId,
+        key: String,
+    ) -> RpcResult<String> {
+        let api = self.client.runtime_api();
+        let res = api.get_metadata(at, did, key.into_bytes()).map_err(|e| {
+            Error::Call(CallError::Custom(ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                "Unable to get metadata.",
+                Some(format!("{:?}", e)),
+            )))
+        })?;
 
-        Ok(metadata)
+        Ok(res.map(from_utf8).unwrap_or_default())
+    }
+}
+
+impl<C, Block, DecentralizedId, AccountId> DidApiServer<<Block as BlockT>::Hash, DecentralizedId>
+    for DidRpcHandler<C, Block, DecentralizedId, AccountId>
+where
+    Block: BlockT,
+    C: Send
+        + Sync
+        + 'static
+        + ProvideRuntimeApi<Block>
+        + HeaderBackend<Block>
+        + ProofProvider<Block>,
+    C::Api: DidRuntimeApi<Block, DecentralizedId, AccountId>,
+    DecentralizedId: Codec + Clone + Send + Sync + 'static,
+    AccountId: Codec + Send + Sync + 'static,
+{
+    fn get_metadata(
+        &self,
+        did: DecentralizedId,
+        key: String,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<String> {
+        let at: BlockId<Block> = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        self.metadata(&at, did, key)
     }
 
     fn batch_get_metadata(
         &self,
         did: DecentralizedId,
         keys: Vec<String>,
+        at: Option<<Block as BlockT>::Hash>,
     ) -> RpcResult<Vec<String>> {
+        let at: BlockId<Block> = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
         let mut result = Vec::new();
 
         for key in keys {
-            let metadata = self
-                .storage
-                .read()
-                .get(
-                    sp_offchain::STORAGE_PREFIX,
-                    &*derive_storage_key(key.as_bytes(), &did),
-                )
-                .map(from_utf8)
-                .unwrap_or_default();
-
-            result.push(metadata);
+            result.push(self.metadata(&at, did.clone(), key)?);
         }
 
         Ok(result)
     }
+
+    fn get_metadata_proof(
+        &self,
+        did: DecentralizedId,
+        key: String,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<MetadataProof<<Block as BlockT>::Hash>> {
+        let hash = at.unwrap_or_else(|| self.client.info().best_hash);
+        let at: BlockId<Block> = BlockId::hash(hash);
+
+        let api = self.client.runtime_api();
+        let storage_key = api
+            .metadata_storage_key(&at, did.clone(), key.clone().into_bytes())
+            .map_err(|e| {
+                Error::Call(CallError::Custom(ErrorObject::owned(
+                    INTERNAL_ERROR_CODE,
+                    "Unable to get metadata proof.",
+                    Some(format!("{:?}", e)),
+                )))
+            })?
+            .ok_or_else(|| {
+                Error::Call(CallError::Custom(ErrorObject::owned(
+                    INTERNAL_ERROR_CODE,
+                    "Unable to get metadata proof.",
+                    Some("metadata key too long"),
+                )))
+            })?;
+
+        let proof = self
+            .client
+            .read_proof(&at, &mut std::iter::once(storage_key.as_slice()))
+            .map_err(|e| {
+                Error::Call(CallError::Custom(ErrorObject::owned(
+                    INTERNAL_ERROR_CODE,
+                    "Unable to get metadata proof.",
+                    Some(format!("{:?}", e)),
+                )))
+            })?;
+
+        let value = self.metadata(&at, did, key)?;
+
+        Ok(MetadataProof {
+            at: hash,
+            key: storage_key.into(),
+            value,
+            proof: proof.iter_nodes().map(Into::into).collect(),
+        })
+    }
+}
+
+#[rpc(client, server)]
+pub trait DidResolverApi<BlockHash, DecentralizedId, AccountId> {
+    /// Resolve the DID document of a DID
+    ///
+    /// # Arguments
+    ///
+    /// * `did` - The DID
+    ///
+    /// # Results
+    ///
+    /// the W3C DID document in JSON-LD
+    #[method(name = "did_resolve")]
+    fn resolve(&self, did: DecentralizedId, at: Option<BlockHash>) -> RpcResult<Value>;
+
+    /// Preview the DID an account will get when registering
+    ///
+    /// # Arguments
+    ///
+    /// * `account` - The account
+    ///
+    /// # Results
+    ///
+    /// the DID derived from the account and its registration nonce
+    #[method(name = "did_preview")]
+    fn did_preview(&self, account: AccountId, at: Option<BlockHash>) -> RpcResult<DecentralizedId>;
+
+    /// Resolve a handle to its DID
+    ///
+    /// # Arguments
+    ///
+    /// * `handle` - The handle, e.g. `alice.ad3`
+    ///
+    /// # Results
+    ///
+    /// the DID owning the handle, if registered and not expired
+    #[method(name = "did_lookupHandle")]
+    fn lookup_handle(
+        &self,
+        handle: String,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<DecentralizedId>>;
+
+    /// Resolve the handle of a DID
+    ///
+    /// # Arguments
+    ///
+    /// * `did` - The DID
+    ///
+    /// # Results
+    ///
+    /// the handle of the DID, if registered and not expired
+    #[method(name = "did_reverseLookupHandle")]
+    fn reverse_lookup_handle(
+        &self,
+        did: DecentralizedId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<String>>;
+
+    /// Get the invitees of a DID
+    ///
+    /// # Arguments
+    ///
+    /// * `did` - The inviter's DID
+    /// * `start` - The number of invitees to skip
+    /// * `limit` - The maximum number of invitees to return, at most 100
+    ///
+    /// # Results
+    ///
+    /// a page of DIDs invited by the DID
+    #[method(name = "did_getInvitees")]
+    fn get_invitees(
+        &self,
+        did: DecentralizedId,
+        start: u32,
+        limit: u32,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<DecentralizedId>>;
+
+    /// Get the referral counts of a DID
+    ///
+    /// # Arguments
+    ///
+    /// * `did` - The inviter's DID
+    ///
+    /// # Results
+    ///
+    /// the number of invitees in the downline of the DID, by level
+    #[method(name = "did_getReferralCounts")]
+    fn get_referral_counts(
+        &self,
+        did: DecentralizedId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<u32>>;
+}
+
+pub struct DidResolverRpcHandler<C, Block, DecentralizedId, AccountId> {
+    client: Arc<C>,
+    _marker: std::marker::PhantomData<(Block, DecentralizedId, AccountId)>,
+}
+
+impl<C, Block, DecentralizedId, AccountId>
+    DidResolverRpcHandler<C, Block, DecentralizedId, AccountId>
+{
+    pub fn new(client: Arc<C>) -> Self {
+        Self {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<C, Block, DecentralizedId, AccountId>
+    DidResolverApiServer<<Block as BlockT>::Hash, DecentralizedId, AccountId>
+    for DidResolverRpcHandler<C, Block, DecentralizedId, AccountId>
+where
+    Block: BlockT,
+    C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: DidRuntimeApi<Block, DecentralizedId, AccountId>,
+    DecentralizedId: Codec + AsRef<[u8]> + Send + Sync + 'static,
+    AccountId: Codec + Send + Sync + 'static,
+{
+    fn resolve(
+        &self,
+        did: DecentralizedId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Value> {
+        let api = self.client.runtime_api();
+        let at: BlockId<Block> = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let res = api.resolve(&at, did).map_err(|e| {
+            Error::Call(CallError::Custom(ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                "Unable to resolve did.",
+                Some(format!("{:?}", e)),
+            )))
+        })?;
+
+        res.map(to_json_ld).map_err(|e| {
+            Error::Call(CallError::Custom(ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                "Unable to resolve did.",
+                Some(format!("{:?}", e)),
+            )))
+        })
+    }
+
+    fn did_preview(
+        &self,
+        account: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<DecentralizedId> {
+        let api = self.client.runtime_api();
+        let at: BlockId<Block> = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let res = api.did_preview(&at, account).map_err(|e| {
+            Error::Call(CallError::Custom(ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                "Unable to preview did.",
+                Some(format!("{:?}", e)),
+            )))
+        })?;
+
+        res.map_err(|e| {
+            Error::Call(CallError::Custom(ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                "Unable to preview did.",
+                Some(format!("{:?}", e)),
+            )))
+        })
+    }
+
+    fn lookup_handle(
+        &self,
+        handle: String,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<DecentralizedId>> {
+        let api = self.client.runtime_api();
+        let at: BlockId<Block> = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.lookup_handle(&at, handle.into_bytes()).map_err(|e| {
+            Error::Call(CallError::Custom(ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                "Unable to lookup handle.",
+                Some(format!("{:?}", e)),
+            )))
+        })
+    }
+
+    fn reverse_lookup_handle(
+        &self,
+        did: DecentralizedId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<String>> {
+        let api = self.client.runtime_api();
+        let at: BlockId<Block> = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let res = api.reverse_lookup_handle(&at, did).map_err(|e| {
+            Error::Call(CallError::Custom(ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                "Unable to lookup handle.",
+                Some(format!("{:?}", e)),
+            )))
+        })?;
+
+        Ok(res.map(|handle| String::from_utf8_lossy(&handle).into_owned()))
+    }
+
+    fn get_invitees(
+        &self,
+        did: DecentralizedId,
+        start: u32,
+        limit: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<DecentralizedId>> {
+        let api = self.client.runtime_api();
+        let at: BlockId<Block> = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.invitees(&at, did, start, limit).map_err(|e| {
+            Error::Call(CallError::Custom(ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                "Unable to get invitees.",
+                Some(format!("{:?}", e)),
+            )))
+        })
+    }
+
+    fn get_referral_counts(
+        &self,
+        did: DecentralizedId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<u32>> {
+        let api = self.client.runtime_api();
+        let at: BlockId<Block> = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.referral_counts(&at, did).map_err(|e| {
+            Error::Call(CallError::Custom(ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                "Unable to get referral counts.",
+                Some(format!("{:?}", e)),
+            )))
+        })
+    }
+}
+
+/// Render a DID document as W3C DID Core JSON-LD
+pub fn to_json_ld<DecentralizedId: AsRef<[u8]>>(document: DidDocument<DecentralizedId>) -> Value {
+    let id = from_utf8(to_did_uri(&document.id));
+
+    let mut result = Map::new();
+    result.insert("@context".into(), json!(["https://www.w3.org/ns/did/v1"]));
+    result.insert("id".into(), json!(id));
+
+    if document.deactivated {
+        result.insert("deactivated".into(), json!(true));
+        return Value::Object(result);
+    }
+
+    if !document.controllers.is_empty() {
+        let controllers: Vec<String> = document
+            .controllers
+            .iter()
+            .map(|controller| from_utf8(to_did_uri(controller)))
+            .collect();
+        result.insert("controller".into(), json!(controllers));
+    }
+
+    let relationships = [
+        (VerificationRelationship::Authentication, "authentication"),
+        (VerificationRelationship::AssertionMethod, "assertionMethod"),
+        (VerificationRelationship::KeyAgreement, "keyAgreement"),
+        (
+            VerificationRelationship::CapabilityInvocation,
+            "capabilityInvocation",
+        ),
+        (
+            VerificationRelationship::CapabilityDelegation,
+            "capabilityDelegation",
+        ),
+    ];
+
+    let mut methods = Vec::new();
+    for method in &document.verification_methods {
+        let key_type = match method.public_key {
+            PublicKey::Sr25519(_) => "Sr25519VerificationKey2020",
+            PublicKey::Ed25519(_) => "Ed25519VerificationKey2018",
+            PublicKey::Secp256k1(_) => "EcdsaSecp256k1VerificationKey2019",
+        };
+
+        methods.push(json!({
+            "id": format!("{}#{}", id, from_utf8(&method.id)),
+            "type": key_type,
+            "controller": id,
+            "publicKeyBase58": method.public_key.as_bytes().to_base58(),
+        }));
+    }
+    if !methods.is_empty() {
+        result.insert("verificationMethod".into(), json!(methods));
+    }
+
+    for (relationship, name) in relationships {
+        let refs: Vec<String> = document
+            .verification_methods
+            .iter()
+            .filter(|method| method.purposes.contains(&relationship))
+            .map(|method| format!("{}#{}", id, from_utf8(&method.id)))
+            .collect();
+
+        if !refs.is_empty() {
+            result.insert(name.into(), json!(refs));
+        }
+    }
+
+    let services: Vec<Value> = document
+        .services
+        .iter()
+        .map(|service| {
+            json!({
+                "id": format!("{}#{}", id, from_utf8(&service.id)),
+                "type": from_utf8(&service.service_type),
+                "serviceEndpoint": from_utf8(&service.endpoint),
+            })
+        })
+        .collect();
+    if !services.is_empty() {
+        result.insert("service".into(), json!(services));
+    }
+
+    Value::Object(result)
 }
 
 fn from_utf8<S: AsRef<[u8]>>(s: S) -> String {
@@ -4,6 +4,99 @@ use super::*;
 use crate::Pallet as Did;
 use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
 use frame_system::RawOrigin;
+use sp_runtime::traits::Bounded;
+
+/// The largest fragment of a document item
+fn fragment(i: u32) -> Vec<u8> {
+    vec![b'a' + i as u8; 32]
+}
+
+/// Fill the DID document of `caller` with the largest items
+fn fill_document<T: Config>(
+    caller: &T::AccountId,
+    methods: u32,
+    services: u32,
+    controllers: u32,
+) -> Result<T::DecentralizedId, &'static str> {
+    use VerificationRelationship::*;
+
+    let did = <DidOf<T>>::get(caller).unwrap();
+
+    for i in 0..methods {
+        Did::<T>::add_verification_method(
+            RawOrigin::Signed(caller.clone()).into(),
+            did,
+            fragment(i),
+            PublicKey::Secp256k1([0u8; 33]),
+            vec![
+                Authentication,
+                AssertionMethod,
+                KeyAgreement,
+                CapabilityInvocation,
+                CapabilityDelegation,
+            ],
+        )?;
+    }
+
+    for i in 0..services {
+        Did::<T>::add_service(
+            RawOrigin::Signed(caller.clone()).into(),
+            did,
+            fragment(i),
+            vec![b't'; 64],
+            vec![b'e'; 256],
+        )?;
+    }
+
+    for i in 0..controllers {
+        let account: T::AccountId = account("controller", i, i);
+        Did::<T>::register(RawOrigin::Signed(account.clone()).into(), None)?;
+
+        let controller = <DidOf<T>>::get(&account).unwrap();
+        Did::<T>::add_controller(RawOrigin::Signed(caller.clone()).into(), did, controller)?;
+    }
+
+    Ok(did)
+}
+
+/// Register `count` guardians and nominate them for the DID of `caller`
+fn set_guardians<T: Config>(
+    caller: &T::AccountId,
+    count: u32,
+) -> Result<Vec<T::AccountId>, &'static str> {
+    let mut accounts = Vec::new();
+    let mut guardians = Vec::new();
+
+    for i in 0..count {
+        let account: T::AccountId = account("guardian", i, i);
+        Did::<T>::register(RawOrigin::Signed(account.clone()).into(), None)?;
+
+        guardians.push(<DidOf<T>>::get(&account).unwrap());
+        accounts.push(account);
+    }
+
+    Did::<T>::set_recovery(
+        RawOrigin::Signed(caller.clone()).into(),
+        guardians,
+        count,
+        1u32.into(),
+    )?;
+
+    Ok(accounts)
+}
+
+/// Let each guardian vouch for a different candidate account
+fn vouch_all<T: Config>(
+    did: T::DecentralizedId,
+    guardians: &[T::AccountId],
+) -> Result<(), &'static str> {
+    for (i, guardian) in guardians.iter().enumerate() {
+        let candidate: T::AccountId = account("candidate", i as u32, i as u32);
+        Did::<T>::vouch_recovery(RawOrigin::Signed(guardian.clone()).into(), did, candidate)?;
+    }
+
+    Ok(())
+}
 
 benchmarks! {
     register {
@@ -41,18 +134,312 @@ benchmarks! {
     }
 
     set_metadata {
-        let k in 0 .. 100;
-        let v in 0 .. 1000;
+        let k in 1 .. 64;
+        let v in 0 .. 1024;
 
         let key = vec![0u8; k as usize];
         let value = vec![0u8; v as usize];
 
         let caller: T::AccountId = whitelisted_caller();
 
+        let deposit = Did::<T>::metadata_deposit(&key, &value);
+        T::Currency::make_free_balance_be(&caller, deposit.saturating_mul(2u32.into()));
+
+        Did::<T>::register(RawOrigin::Signed(caller.clone()).into(), None)?;
+    }: _(RawOrigin::Signed(caller.clone()), key.clone(), value.clone())
+    verify {
+        let did = <DidOf<T>>::get(&caller).unwrap();
+        assert_eq!(Did::<T>::metadata(&did, &key), Some(value));
+    }
+
+    add_session_key {
+        let caller: T::AccountId = whitelisted_caller();
+
+        Did::<T>::register(RawOrigin::Signed(caller.clone()).into(), None)?;
+
+        for i in 1..8 {
+            let account: T::AccountId = account("session", i, i);
+            Did::<T>::add_session_key(
+                RawOrigin::Signed(caller.clone()).into(),
+                account,
+                SessionScope::AdClaim,
+                HeightOf::<T>::max_value(),
+            )?;
+        }
+
+        let session: T::AccountId = account("session", 0, 0);
+    }: _(RawOrigin::Signed(caller), session.clone(), SessionScope::AdClaim, HeightOf::<T>::max_value())
+    verify {
+        assert_ne!(<SessionKeyOf<T>>::get(&session), None);
+    }
+
+    remove_session_key {
+        let caller: T::AccountId = whitelisted_caller();
+
+        Did::<T>::register(RawOrigin::Signed(caller.clone()).into(), None)?;
+
+        for i in 0..8 {
+            let account: T::AccountId = account("session", i, i);
+            Did::<T>::add_session_key(
+                RawOrigin::Signed(caller.clone()).into(),
+                account,
+                SessionScope::AdClaim,
+                HeightOf::<T>::max_value(),
+            )?;
+        }
+
+        let session: T::AccountId = account("session", 0, 0);
+    }: _(RawOrigin::Signed(caller), session.clone())
+    verify {
+        assert_eq!(<SessionKeyOf<T>>::get(&session), None);
+    }
+
+    register_handle {
+        let caller: T::AccountId = whitelisted_caller();
+        let holder: T::AccountId = account("holder", 1, 1);
+
+        T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+        T::Currency::make_free_balance_be(&holder, BalanceOf::<T>::max_value());
+
+        Did::<T>::register(RawOrigin::Signed(caller.clone()).into(), None)?;
+        Did::<T>::register(RawOrigin::Signed(holder.clone()).into(), None)?;
+
+        // take over an expired handle
+        Did::<T>::register_handle(RawOrigin::Signed(holder).into(), b"alice".to_vec())?;
+
+        let height = <frame_system::Pallet<T>>::block_number();
+        <frame_system::Pallet<T>>::set_block_number(height.saturating_add(T::HandlePeriod::get()));
+    }: _(RawOrigin::Signed(caller.clone()), b"alice".to_vec())
+    verify {
+        let did = <DidOf<T>>::get(&caller).unwrap();
+        assert_ne!(<HandleOf<T>>::get(&did), None);
+    }
+
+    renew_handle {
+        let caller: T::AccountId = whitelisted_caller();
+
+        T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+
+        Did::<T>::register(RawOrigin::Signed(caller.clone()).into(), None)?;
+        Did::<T>::register_handle(RawOrigin::Signed(caller.clone()).into(), b"alice".to_vec())?;
+    }: _(RawOrigin::Signed(caller))
+    verify {
+        let handle = Did::<T>::handle_name(b"alice").unwrap();
+        let height = <frame_system::Pallet<T>>::block_number();
+        let expires_at = height.saturating_add(T::HandlePeriod::get()).saturating_add(T::HandlePeriod::get());
+        assert_eq!(<Handles<T>>::get(&handle).unwrap().expires_at, expires_at);
+    }
+
+    release_handle {
+        let caller: T::AccountId = whitelisted_caller();
+
+        T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+
+        Did::<T>::register(RawOrigin::Signed(caller.clone()).into(), None)?;
+        Did::<T>::register_handle(RawOrigin::Signed(caller.clone()).into(), b"alice".to_vec())?;
+    }: _(RawOrigin::Signed(caller))
+    verify {
+        let handle = Did::<T>::handle_name(b"alice").unwrap();
+        assert_eq!(<Handles<T>>::get(&handle), None);
+    }
+
+    force_reserve_handle {
+        let profile = vec![b'a'; 256];
+    }: _(RawOrigin::Root, b"alice".to_vec(), Network::Twitter, profile)
+    verify {
+        let handle = Did::<T>::handle_name(b"alice").unwrap();
+        assert_ne!(<ReservedHandles<T>>::get(&handle), None);
+    }
+
+    force_unreserve_handle {
+        let profile = vec![b'a'; 256];
+
+        Did::<T>::force_reserve_handle(
+            RawOrigin::Root.into(),
+            b"alice".to_vec(),
+            Network::Twitter,
+            profile,
+        )?;
+    }: _(RawOrigin::Root, b"alice".to_vec())
+    verify {
+        let handle = Did::<T>::handle_name(b"alice").unwrap();
+        assert_eq!(<ReservedHandles<T>>::get(&handle), None);
+    }
+
+    add_verification_method {
+        let caller: T::AccountId = whitelisted_caller();
+
+        Did::<T>::register(RawOrigin::Signed(caller.clone()).into(), None)?;
+
+        let did = fill_document::<T>(&caller, 15, 16, 8)?;
+
+        let purposes = vec![
+            VerificationRelationship::Authentication,
+            VerificationRelationship::AssertionMethod,
+            VerificationRelationship::KeyAgreement,
+            VerificationRelationship::CapabilityInvocation,
+            VerificationRelationship::CapabilityDelegation,
+        ];
+    }: _(RawOrigin::Signed(caller), did, fragment(15), PublicKey::Secp256k1([0u8; 33]), purposes)
+    verify {
+        assert_eq!(<DocumentOf<T>>::get(&did).verification_methods.len(), 16);
+    }
+
+    remove_verification_method {
+        let caller: T::AccountId = whitelisted_caller();
+
         Did::<T>::register(RawOrigin::Signed(caller.clone()).into(), None)?;
-    }: _(RawOrigin::Signed(caller), key.clone(), value.clone())
+
+        let did = fill_document::<T>(&caller, 16, 16, 8)?;
+    }: _(RawOrigin::Signed(caller), did, fragment(15))
+    verify {
+        assert_eq!(<DocumentOf<T>>::get(&did).verification_methods.len(), 15);
+    }
+
+    add_service {
+        let caller: T::AccountId = whitelisted_caller();
+
+        Did::<T>::register(RawOrigin::Signed(caller.clone()).into(), None)?;
+
+        let did = fill_document::<T>(&caller, 16, 15, 8)?;
+    }: _(RawOrigin::Signed(caller), did, fragment(15), vec![b't'; 64], vec![b'e'; 256])
+    verify {
+        assert_eq!(<DocumentOf<T>>::get(&did).services.len(), 16);
+    }
+
+    remove_service {
+        let caller: T::AccountId = whitelisted_caller();
+
+        Did::<T>::register(RawOrigin::Signed(caller.clone()).into(), None)?;
+
+        let did = fill_document::<T>(&caller, 16, 16, 8)?;
+    }: _(RawOrigin::Signed(caller), did, fragment(15))
     verify {
-        // TODO: verify metadata
+        assert_eq!(<DocumentOf<T>>::get(&did).services.len(), 15);
+    }
+
+    add_controller {
+        let caller: T::AccountId = whitelisted_caller();
+        let account: T::AccountId = account("controller", 7, 7);
+
+        Did::<T>::register(RawOrigin::Signed(caller.clone()).into(), None)?;
+        Did::<T>::register(RawOrigin::Signed(account.clone()).into(), None)?;
+
+        let did = fill_document::<T>(&caller, 16, 16, 7)?;
+        let controller = <DidOf<T>>::get(&account).unwrap();
+    }: _(RawOrigin::Signed(caller), did, controller)
+    verify {
+        assert_eq!(<DocumentOf<T>>::get(&did).controllers.len(), 8);
+    }
+
+    remove_controller {
+        let caller: T::AccountId = whitelisted_caller();
+
+        Did::<T>::register(RawOrigin::Signed(caller.clone()).into(), None)?;
+
+        let did = fill_document::<T>(&caller, 16, 16, 8)?;
+
+        let account: T::AccountId = account("controller", 7, 7);
+        let controller = <DidOf<T>>::get(&account).unwrap();
+    }: _(RawOrigin::Signed(caller), did, controller)
+    verify {
+        assert_eq!(<DocumentOf<T>>::get(&did).controllers.len(), 7);
+    }
+
+    set_recovery {
+        let g in 1 .. 8;
+
+        let caller: T::AccountId = whitelisted_caller();
+
+        Did::<T>::register(RawOrigin::Signed(caller.clone()).into(), None)?;
+
+        let mut guardians = Vec::new();
+        for i in 0..g {
+            let account: T::AccountId = account("guardian", i, i);
+            Did::<T>::register(RawOrigin::Signed(account.clone()).into(), None)?;
+
+            guardians.push(<DidOf<T>>::get(&account).unwrap());
+        }
+    }: _(RawOrigin::Signed(caller.clone()), guardians, g, 1u32.into())
+    verify {
+        let did = <DidOf<T>>::get(&caller).unwrap();
+        assert_ne!(<RecoveryConfigOf<T>>::get(&did), None);
+    }
+
+    remove_recovery {
+        let caller: T::AccountId = whitelisted_caller();
+
+        Did::<T>::register(RawOrigin::Signed(caller.clone()).into(), None)?;
+
+        let did = <DidOf<T>>::get(&caller).unwrap();
+        let guardians = set_guardians::<T>(&caller, 8)?;
+        vouch_all::<T>(did, &guardians)?;
+    }: _(RawOrigin::Signed(caller))
+    verify {
+        assert_eq!(<RecoveryConfigOf<T>>::get(&did), None);
+        assert_eq!(<ActiveRecoveryOf<T>>::iter_prefix(&did).count(), 0);
+    }
+
+    vouch_recovery {
+        let caller: T::AccountId = whitelisted_caller();
+
+        Did::<T>::register(RawOrigin::Signed(caller.clone()).into(), None)?;
+
+        let did = <DidOf<T>>::get(&caller).unwrap();
+        let guardians = set_guardians::<T>(&caller, 8)?;
+        vouch_all::<T>(did, &guardians)?;
+
+        // the vouch of the last guardian is moved
+        let candidate: T::AccountId = account("candidate", 8, 8);
+    }: _(RawOrigin::Signed(guardians[7].clone()), did, candidate.clone())
+    verify {
+        assert_ne!(<ActiveRecoveryOf<T>>::get(&did, &candidate), None);
+    }
+
+    cancel_recovery {
+        let caller: T::AccountId = whitelisted_caller();
+
+        Did::<T>::register(RawOrigin::Signed(caller.clone()).into(), None)?;
+
+        let did = <DidOf<T>>::get(&caller).unwrap();
+        let guardians = set_guardians::<T>(&caller, 8)?;
+        vouch_all::<T>(did, &guardians)?;
+    }: _(RawOrigin::Signed(caller))
+    verify {
+        assert_eq!(<ActiveRecoveryOf<T>>::iter_prefix(&did).count(), 0);
+    }
+
+    claim_recovery {
+        let caller: T::AccountId = whitelisted_caller();
+        let candidate: T::AccountId = account("candidate", 0, 0);
+
+        T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+
+        Did::<T>::register(RawOrigin::Signed(caller.clone()).into(), None)?;
+
+        let did = <DidOf<T>>::get(&caller).unwrap();
+
+        for i in 0..8 {
+            let account: T::AccountId = account("session", i, i);
+            Did::<T>::add_session_key(
+                RawOrigin::Signed(caller.clone()).into(),
+                account,
+                SessionScope::AdClaim,
+                HeightOf::<T>::max_value(),
+            )?;
+        }
+
+        let guardians = set_guardians::<T>(&caller, 8)?;
+        for guardian in guardians {
+            Did::<T>::vouch_recovery(RawOrigin::Signed(guardian).into(), did, candidate.clone())?;
+        }
+
+        let height = <frame_system::Pallet<T>>::block_number();
+        <frame_system::Pallet<T>>::set_block_number(height.saturating_add(1u32.into()));
+    }: _(RawOrigin::Signed(caller.clone()), did, candidate.clone())
+    verify {
+        assert_eq!(<DidOf<T>>::get(&caller), None);
+        assert_eq!(<DidOf<T>>::get(&candidate), Some(did));
     }
 }
 
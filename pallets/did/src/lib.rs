@@ -17,19 +17,24 @@ mod benchmarking;
 pub mod migrations;
 mod types;
 
+pub use types::{Milestone, SessionScope};
+
 use frame_support::{
-    dispatch::DispatchResult,
+    dispatch::{DispatchResult, DispatchResultWithPostInfo},
     ensure,
     traits::{
-        Currency, EnsureOrigin, ExistenceRequirement::AllowDeath, NamedReservableCurrency,
-        StorageVersion,
+        ConstU32, Currency, EnsureOrigin,
+        ExistenceRequirement::{AllowDeath, KeepAlive},
+        Get, NamedReservableCurrency, OnUnbalanced, StorageVersion, WithdrawReasons,
     },
+    transactional, BoundedVec, PalletId,
 };
-use parami_did_utils::derive_storage_key;
+use parami_primitives::did::{DidDocument, PublicKey, VerificationRelationship};
+use parami_traits::{types::Network, Links, OnDidRevoked};
 use sp_runtime::{
     traits::{
-        Hash, LookupError, MaybeDisplay, MaybeMallocSizeOf, MaybeSerializeDeserialize, Member,
-        SimpleBitOps, StaticLookup,
+        AccountIdConversion, Hash, LookupError, MaybeDisplay, MaybeMallocSizeOf,
+        MaybeSerializeDeserialize, Member, Saturating, SimpleBitOps, StaticLookup, Zero,
     },
     DispatchError, MultiAddress,
 };
@@ -38,10 +43,49 @@ use sp_std::prelude::*;
 use weights::WeightInfo;
 
 type AccountOf<T> = <T as frame_system::Config>::AccountId;
+type BalanceOf<T> = <<T as Config>::Currency as Currency<AccountOf<T>>>::Balance;
+type NegativeImbOf<T> = <<T as Config>::Currency as Currency<AccountOf<T>>>::NegativeImbalance;
 type HeightOf<T> = <T as frame_system::Config>::BlockNumber;
 type MetaOf<T> = types::Metadata<AccountOf<T>, HeightOf<T>>;
-
-const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+type FragmentOf = BoundedVec<u8, ConstU32<32>>;
+type PurposesOf = BoundedVec<VerificationRelationship, ConstU32<5>>;
+type ServiceTypeOf = BoundedVec<u8, ConstU32<64>>;
+type ServiceEndpointOf = BoundedVec<u8, ConstU32<256>>;
+type MethodOf = types::VerificationMethod<FragmentOf, PurposesOf>;
+type ServiceOf = types::Service<FragmentOf, ServiceTypeOf, ServiceEndpointOf>;
+type DocOf<T> = types::Document<
+    BoundedVec<<T as Config>::DecentralizedId, ConstU32<8>>,
+    BoundedVec<MethodOf, ConstU32<16>>,
+    BoundedVec<ServiceOf, ConstU32<16>>,
+>;
+type GuardiansOf<T> = BoundedVec<<T as Config>::DecentralizedId, ConstU32<8>>;
+type RecoveryMetaOf<T> = types::RecoveryConfig<GuardiansOf<T>, HeightOf<T>>;
+type RecoveryOf<T> = types::ActiveRecovery<GuardiansOf<T>, HeightOf<T>>;
+type MetadataKeyOf = BoundedVec<u8, ConstU32<64>>;
+type MetadataValueOf = BoundedVec<u8, ConstU32<1024>>;
+type MetadataEntryOf<T> = types::MetadataEntry<MetadataValueOf, BalanceOf<T>>;
+type SessionOf<T> = types::Session<<T as Config>::DecentralizedId, HeightOf<T>>;
+type SessionAccountsOf<T> = BoundedVec<AccountOf<T>, ConstU32<8>>;
+type HandleNameOf = BoundedVec<u8, ConstU32<32>>;
+type HandleMetaOf<T> = types::Handle<<T as Config>::DecentralizedId, HeightOf<T>>;
+type ReservedHandleOf = types::ReservedHandle<BoundedVec<u8, ConstU32<256>>>;
+
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
+
+/// Nonces tried before giving up deriving a DID
+const MAX_DERIVATION_ATTEMPTS: u32 = 8;
+
+/// The suffix of handles, e.g. `alice.ad3`
+const HANDLE_SUFFIX: &[u8] = b".ad3";
+
+/// The minimum length of a handle, without the suffix
+const MIN_HANDLE_LENGTH: usize = 3;
+
+/// Levels of the downline counted for a referrer
+pub const REFERRAL_LEVELS: usize = 3;
+
+/// The maximum number of invitees returned in a page
+pub const MAX_INVITEES_PAGE: u32 = 100;
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -81,10 +125,47 @@ pub mod pallet {
         /// The hashing algorithm being used to create DID
         type Hashing: Hash + TypeInfo;
 
+        /// The fee to register or renew a handle for a period
+        #[pallet::constant]
+        type HandleFee: Get<BalanceOf<Self>>;
+
+        /// Handler for the fees of handles
+        type HandleFeeDestination: OnUnbalanced<NegativeImbOf<Self>>;
+
+        /// The period of a handle registration or renewal
+        #[pallet::constant]
+        type HandlePeriod: Get<HeightOf<Self>>;
+
+        /// The links of DIDs, used to verify the owners of reserved handles
+        type Links: Links<Self::DecentralizedId>;
+
+        /// The base deposit for storing a metadata entry
+        #[pallet::constant]
+        type MetadataDepositBase: Get<BalanceOf<Self>>;
+
+        /// The deposit per byte of metadata key and value
+        #[pallet::constant]
+        type MetadataDepositPerByte: Get<BalanceOf<Self>>;
+
+        /// The pallet id, used for reserving metadata deposits
+        #[pallet::constant]
+        type PalletId: Get<PalletId>;
+
+        /// The lifetime of an ongoing recovery, it should be longer than the delays of recoveries
+        #[pallet::constant]
+        type RecoveryLifetime: Get<HeightOf<Self>>;
+
         /// Weight information for extrinsics in this pallet.
         type WeightInfo: WeightInfo;
 
+        /// Assets moved along with a DID, including named reserves held by other pallets
         type Transferables: Transferable<AccountOf<Self>>;
+
+        /// Handler for the revocation of a DID, cleans up states of the DID in other pallets
+        type OnDidRevoked: OnDidRevoked<Self::DecentralizedId, AccountOf<Self>>;
+
+        /// Handler for milestones reached by invitees, e.g. paying referral bonuses
+        type OnReferralMilestone: OnReferralMilestone<Self>;
     }
 
     #[pallet::pallet]
@@ -102,6 +183,11 @@ pub mod pallet {
     #[pallet::getter(fn did_of)]
     pub(super) type DidOf<T: Config> = StorageMap<_, Blake2_256, AccountOf<T>, T::DecentralizedId>;
 
+    /// The registration nonce of an account id, used to derive its next DID.
+    #[pallet::storage]
+    #[pallet::getter(fn nonce_of)]
+    pub(super) type NonceOf<T: Config> = StorageMap<_, Blake2_256, AccountOf<T>, u32, ValueQuery>;
+
     /// The inviter's DID of a DID.
     #[pallet::storage]
     #[pallet::getter(fn referrer_of)]
@@ -112,6 +198,91 @@ pub mod pallet {
         T::DecentralizedId, // inviter's DID
     >;
 
+    /// The invitees of a DID.
+    #[pallet::storage]
+    pub(super) type InviteesOf<T: Config> = StorageDoubleMap<
+        _,
+        Identity,
+        T::DecentralizedId, // inviter's DID
+        Identity,
+        T::DecentralizedId,
+        (),
+    >;
+
+    /// The number of invitees of a DID, by level of the downline.
+    #[pallet::storage]
+    #[pallet::getter(fn referral_counts_of)]
+    pub(super) type ReferralCountsOf<T: Config> =
+        StorageMap<_, Identity, T::DecentralizedId, [u32; REFERRAL_LEVELS], ValueQuery>;
+
+    /// The milestones reached by a DID.
+    #[pallet::storage]
+    pub(super) type MilestonesOf<T: Config> =
+        StorageDoubleMap<_, Identity, T::DecentralizedId, Twox64Concat, Milestone, ()>;
+
+    /// The DID document of a DID.
+    #[pallet::storage]
+    #[pallet::getter(fn document_of)]
+    pub(super) type DocumentOf<T: Config> =
+        StorageMap<_, Identity, T::DecentralizedId, DocOf<T>, ValueQuery>;
+
+    /// The social recovery config of a DID.
+    #[pallet::storage]
+    #[pallet::getter(fn recovery_config_of)]
+    pub(super) type RecoveryConfigOf<T: Config> =
+        StorageMap<_, Identity, T::DecentralizedId, RecoveryMetaOf<T>>;
+
+    /// The ongoing recoveries of a DID, keyed by the candidate account.
+    #[pallet::storage]
+    #[pallet::getter(fn active_recovery_of)]
+    pub(super) type ActiveRecoveryOf<T: Config> = StorageDoubleMap<
+        _,
+        Identity,
+        T::DecentralizedId,
+        Blake2_128Concat,
+        AccountOf<T>,
+        RecoveryOf<T>,
+    >;
+
+    /// The metadata of a DID, keyed by the metadata key.
+    #[pallet::storage]
+    pub(super) type MetadataOf<T: Config> = StorageDoubleMap<
+        _,
+        Identity,
+        T::DecentralizedId,
+        Blake2_128Concat,
+        MetadataKeyOf,
+        MetadataEntryOf<T>,
+    >;
+
+    /// The session of a session key account.
+    #[pallet::storage]
+    #[pallet::getter(fn session_of)]
+    pub(super) type SessionKeyOf<T: Config> = StorageMap<_, Blake2_256, AccountOf<T>, SessionOf<T>>;
+
+    /// The session key accounts of a DID.
+    #[pallet::storage]
+    #[pallet::getter(fn session_keys_of)]
+    pub(super) type SessionKeysOf<T: Config> =
+        StorageMap<_, Identity, T::DecentralizedId, SessionAccountsOf<T>, ValueQuery>;
+
+    /// The registration of a handle, keyed by the handle without suffix.
+    #[pallet::storage]
+    #[pallet::getter(fn handle)]
+    pub(super) type Handles<T: Config> =
+        StorageMap<_, Blake2_128Concat, HandleNameOf, HandleMetaOf<T>>;
+
+    /// The handle of a DID.
+    #[pallet::storage]
+    #[pallet::getter(fn handle_of)]
+    pub(super) type HandleOf<T: Config> = StorageMap<_, Identity, T::DecentralizedId, HandleNameOf>;
+
+    /// Handles reserved for verified KOLs.
+    #[pallet::storage]
+    #[pallet::getter(fn reserved_handle)]
+    pub(super) type ReservedHandles<T: Config> =
+        StorageMap<_, Blake2_128Concat, HandleNameOf, ReservedHandleOf>;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
@@ -121,6 +292,64 @@ pub mod pallet {
         Revoked(T::DecentralizedId),
         /// DID transferred \[did, from, to\]
         Transferred(T::DecentralizedId, AccountOf<T>, AccountOf<T>),
+        /// Verification method added \[did, id\]
+        VerificationMethodAdded(T::DecentralizedId, Vec<u8>),
+        /// Verification method removed \[did, id\]
+        VerificationMethodRemoved(T::DecentralizedId, Vec<u8>),
+        /// Service endpoint added \[did, id\]
+        ServiceAdded(T::DecentralizedId, Vec<u8>),
+        /// Service endpoint removed \[did, id\]
+        ServiceRemoved(T::DecentralizedId, Vec<u8>),
+        /// Controller added \[did, controller\]
+        ControllerAdded(T::DecentralizedId, T::DecentralizedId),
+        /// Controller removed \[did, controller\]
+        ControllerRemoved(T::DecentralizedId, T::DecentralizedId),
+        /// Social recovery configured \[did, guardians, threshold, delay\]
+        RecoveryConfigured(
+            T::DecentralizedId,
+            Vec<T::DecentralizedId>,
+            u32,
+            HeightOf<T>,
+        ),
+        /// Social recovery removed \[did\]
+        RecoveryRemoved(T::DecentralizedId),
+        /// Guardian vouched for a new account \[did, guardian, account\]
+        RecoveryVouched(T::DecentralizedId, T::DecentralizedId, AccountOf<T>),
+        /// Recovery threshold reached \[did, account, executable_at\]
+        RecoveryApproved(T::DecentralizedId, AccountOf<T>, HeightOf<T>),
+        /// Recovery cancelled by the owner \[did\]
+        RecoveryCancelled(T::DecentralizedId),
+        /// Guardian withdrew the vouch for an account \[did, guardian, account\]
+        RecoveryUnvouched(T::DecentralizedId, T::DecentralizedId, AccountOf<T>),
+        /// DID recovered \[did, from, to\]
+        Recovered(T::DecentralizedId, AccountOf<T>, AccountOf<T>),
+        /// Metadata set \[did, key\]
+        MetadataSet(T::DecentralizedId, Vec<u8>),
+        /// Metadata removed \[did, key\]
+        MetadataRemoved(T::DecentralizedId, Vec<u8>),
+        /// Session key added \[did, account, scope, expires_at\]
+        SessionKeyAdded(T::DecentralizedId, AccountOf<T>, SessionScope, HeightOf<T>),
+        /// Session key removed \[did, account\]
+        SessionKeyRemoved(T::DecentralizedId, AccountOf<T>),
+        /// Handle registered \[did, handle, expires_at\]
+        HandleRegistered(T::DecentralizedId, Vec<u8>, HeightOf<T>),
+        /// Handle renewed \[did, handle, expires_at\]
+        HandleRenewed(T::DecentralizedId, Vec<u8>, HeightOf<T>),
+        /// Handle released \[did, handle\]
+        HandleReleased(T::DecentralizedId, Vec<u8>),
+        /// Handle reserved \[handle, network, profile\]
+        HandleReserved(Vec<u8>, Network, Vec<u8>),
+        /// Handle unreserved \[handle\]
+        HandleUnreserved(Vec<u8>),
+        /// Milestone reached \[did, milestone\]
+        MilestoneReached(T::DecentralizedId, Milestone),
+        /// Referral bonus paid \[referrer, invitee, milestone, value\]
+        ReferralBonusPaid(
+            T::DecentralizedId,
+            T::DecentralizedId,
+            Milestone,
+            BalanceOf<T>,
+        ),
     }
 
     #[pallet::hooks]
@@ -128,9 +357,31 @@ pub mod pallet {
 
     #[pallet::error]
     pub enum Error<T> {
+        AlreadyVouched,
+        BadDocument,
+        BadRecoveryConfig,
         DidExists,
         DidNotExists,
+        DocumentItemExists,
+        DocumentItemNotExists,
+        HandleExists,
+        HandleNotExists,
+        HandleReserved,
+        HandleTaken,
+        InvalidHandle,
+        MetadataNotExists,
+        MetadataTooLong,
+        NotController,
+        NotGuardian,
+        RecoveryExpired,
+        RecoveryNotExists,
+        RecoveryNotReady,
         ReferrerNotExists,
+        SessionKeyExists,
+        SessionKeyExpired,
+        SessionKeyNotExists,
+        TooManyDocumentItems,
+        TooManySessionKeys,
     }
 
     #[pallet::call]
@@ -168,29 +419,17 @@ pub mod pallet {
         ) -> DispatchResult {
             let _who = ensure_root(origin)?;
 
-            ensure!(!<DidOf<T>>::contains_key(&dest), Error::<T>::DidExists);
-            let mut meta = <Metadata<T>>::get(did).ok_or(Error::<T>::DidNotExists)?;
-
-            let source = meta.account.clone();
-            meta.account = dest.clone();
-            meta.created = <frame_system::Pallet<T>>::block_number();
-
-            let ad3_balance = T::Currency::total_balance(&source);
-            T::Currency::transfer(&source, &dest, ad3_balance, AllowDeath)?;
-            T::Transferables::transfer_all(&source, &dest)?;
-
-            <Metadata<T>>::insert(did, meta);
-            <DidOf<T>>::remove(&source);
-            <DidOf<T>>::insert(dest.clone(), did);
-
-            Self::deposit_event(Event::<T>::Transferred(did.clone(), source, dest.clone()));
+            Self::transfer_with_assets(did, dest)?;
 
             Ok(())
         }
 
         /// Revoke a new DID.
-        #[pallet::weight(T::WeightInfo::revoke())]
-        pub fn revoke(origin: OriginFor<T>) -> DispatchResult {
+        ///
+        /// The states of the DID in other pallets are cleaned up by `OnDidRevoked`,
+        /// charged up to its maximum weight and refunded by the actual weight.
+        #[pallet::weight(T::WeightInfo::revoke().saturating_add(T::OnDidRevoked::max_weight()))]
+        pub fn revoke(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
 
             let did = <DidOf<T>>::get(&who).ok_or(Error::<T>::DidNotExists)?;
@@ -206,20 +445,724 @@ pub mod pallet {
                 },
             );
 
+            let deposit = <MetadataOf<T>>::drain_prefix(&did)
+                .fold(Zero::zero(), |acc: BalanceOf<T>, (_, entry)| {
+                    acc.saturating_add(entry.deposit)
+                });
+            let id = <T as Config>::PalletId::get();
+            T::Currency::unreserve_named(&id.0, &who, deposit);
+
+            Self::clear_session_keys(&did);
+
+            if let Some(handle) = <HandleOf<T>>::take(&did) {
+                <Handles<T>>::remove(&handle);
+            }
+
             <DidOf<T>>::remove(&who);
+            <DocumentOf<T>>::remove(&did);
+            <RecoveryConfigOf<T>>::remove(&did);
+            <ActiveRecoveryOf<T>>::remove_prefix(&did, None);
+
+            let weight = T::OnDidRevoked::on_did_revoked(&did, &who);
 
             Self::deposit_event(Event::<T>::Revoked(did));
 
-            Ok(())
+            Ok(Some(T::WeightInfo::revoke().saturating_add(weight)).into())
         }
 
         /// Set metadata of a DID.
-        #[pallet::weight((0, Pays::No))]
+        ///
+        /// A deposit proportional to the size of key and value is reserved
+        /// from the account of the DID.
+        #[pallet::weight(T::WeightInfo::set_metadata(key.len() as u32, value.len() as u32))]
         pub fn set_metadata(origin: OriginFor<T>, key: Vec<u8>, value: Vec<u8>) -> DispatchResult {
+            let (did, who) = EnsureDid::<T>::ensure_origin(origin)?;
+
+            let deposit = Self::metadata_deposit(&key, &value);
+
+            Self::insert_metadata(&did, key, value, Some((&who, deposit)))?;
+
+            Ok(())
+        }
+
+        /// Remove metadata of a DID, and release its deposit.
+        #[pallet::weight(T::WeightInfo::set_metadata(key.len() as u32, 0))]
+        pub fn remove_metadata(origin: OriginFor<T>, key: Vec<u8>) -> DispatchResult {
+            let (did, who) = EnsureDid::<T>::ensure_origin(origin)?;
+
+            let bounded: MetadataKeyOf = key
+                .clone()
+                .try_into()
+                .map_err(|_| Error::<T>::MetadataTooLong)?;
+
+            let entry =
+                <MetadataOf<T>>::take(&did, &bounded).ok_or(Error::<T>::MetadataNotExists)?;
+
+            let id = <T as Config>::PalletId::get();
+            T::Currency::unreserve_named(&id.0, &who, entry.deposit);
+
+            Self::deposit_event(Event::<T>::MetadataRemoved(did, key));
+
+            Ok(())
+        }
+
+        /// Set metadata of a DID without deposit.
+        ///
+        /// Used to replay metadata written to the offchain index before
+        /// it was moved to on-chain storage.
+        #[pallet::weight(T::WeightInfo::set_metadata(key.len() as u32, value.len() as u32))]
+        pub fn force_set_metadata(
+            origin: OriginFor<T>,
+            did: T::DecentralizedId,
+            key: Vec<u8>,
+            value: Vec<u8>,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+
+            ensure!(<Metadata<T>>::contains_key(&did), Error::<T>::DidNotExists);
+
+            Self::insert_metadata(&did, key, value, None)?;
+
+            Ok(())
+        }
+
+        /// Grant an account to sign calls of `scope` on behalf of the DID.
+        ///
+        /// # Arguments
+        ///
+        /// * `account` - The session key account, should not have a DID
+        /// * `scope` - The calls the session key is allowed to sign
+        /// * `expires_at` - The height since which the session key is invalid
+        #[pallet::weight(T::WeightInfo::add_session_key())]
+        pub fn add_session_key(
+            origin: OriginFor<T>,
+            account: AccountOf<T>,
+            scope: SessionScope,
+            expires_at: HeightOf<T>,
+        ) -> DispatchResult {
+            let (did, _) = EnsureDid::<T>::ensure_origin(origin)?;
+
+            let height = <frame_system::Pallet<T>>::block_number();
+            ensure!(expires_at > height, Error::<T>::SessionKeyExpired);
+
+            ensure!(!<DidOf<T>>::contains_key(&account), Error::<T>::DidExists);
+            ensure!(
+                !<SessionKeyOf<T>>::contains_key(&account),
+                Error::<T>::SessionKeyExists
+            );
+
+            <SessionKeysOf<T>>::try_mutate(&did, |accounts| {
+                accounts
+                    .try_push(account.clone())
+                    .map_err(|_| Error::<T>::TooManySessionKeys)
+            })?;
+
+            <SessionKeyOf<T>>::insert(
+                &account,
+                types::Session {
+                    did,
+                    scope,
+                    expires_at,
+                },
+            );
+
+            Self::deposit_event(Event::<T>::SessionKeyAdded(did, account, scope, expires_at));
+
+            Ok(())
+        }
+
+        /// Remove a session key of the DID.
+        #[pallet::weight(T::WeightInfo::remove_session_key())]
+        pub fn remove_session_key(origin: OriginFor<T>, account: AccountOf<T>) -> DispatchResult {
+            let (did, _) = EnsureDid::<T>::ensure_origin(origin)?;
+
+            let session =
+                <SessionKeyOf<T>>::get(&account).ok_or(Error::<T>::SessionKeyNotExists)?;
+            ensure!(session.did == did, Error::<T>::SessionKeyNotExists);
+
+            <SessionKeyOf<T>>::remove(&account);
+            <SessionKeysOf<T>>::mutate(&did, |accounts| accounts.retain(|a| a != &account));
+
+            Self::deposit_event(Event::<T>::SessionKeyRemoved(did, account));
+
+            Ok(())
+        }
+
+        /// Register a handle, e.g. `alice.ad3`, for the DID of the origin.
+        ///
+        /// The handle fee is charged for a period,
+        /// an expired handle can be registered by anyone.
+        /// Reserved handles can only be registered by the DID
+        /// which has linked the reserved profile.
+        ///
+        /// # Arguments
+        ///
+        /// * `handle` - The handle, with or without the `.ad3` suffix
+        #[pallet::weight(T::WeightInfo::register_handle())]
+        pub fn register_handle(origin: OriginFor<T>, handle: Vec<u8>) -> DispatchResult {
+            let (did, who) = EnsureDid::<T>::ensure_origin(origin)?;
+
+            let handle = Self::handle_name(&handle)?;
+
+            ensure!(!<HandleOf<T>>::contains_key(&did), Error::<T>::HandleExists);
+
+            let height = <frame_system::Pallet<T>>::block_number();
+
+            let existing = <Handles<T>>::get(&handle);
+            if let Some(ref existing) = existing {
+                ensure!(height >= existing.expires_at, Error::<T>::HandleTaken);
+            }
+
+            if let Some(reserved) = <ReservedHandles<T>>::get(&handle) {
+                let links = T::Links::links(&did, reserved.network);
+                ensure!(
+                    links.contains(&reserved.profile.into_inner()),
+                    Error::<T>::HandleReserved
+                );
+            }
+
+            Self::charge_handle_fee(&who)?;
+
+            if let Some(existing) = existing {
+                <HandleOf<T>>::remove(&existing.did);
+            }
+
+            let expires_at = height.saturating_add(T::HandlePeriod::get());
+
+            <Handles<T>>::insert(&handle, types::Handle { did, expires_at });
+            <HandleOf<T>>::insert(&did, &handle);
+
+            Self::deposit_event(Event::<T>::HandleRegistered(
+                did,
+                handle.into_inner(),
+                expires_at,
+            ));
+
+            Ok(())
+        }
+
+        /// Renew the handle of the DID of the origin for another period.
+        #[pallet::weight(T::WeightInfo::renew_handle())]
+        pub fn renew_handle(origin: OriginFor<T>) -> DispatchResult {
+            let (did, who) = EnsureDid::<T>::ensure_origin(origin)?;
+
+            let handle = <HandleOf<T>>::get(&did).ok_or(Error::<T>::HandleNotExists)?;
+
+            let mut meta = <Handles<T>>::get(&handle).ok_or(Error::<T>::HandleNotExists)?;
+
+            Self::charge_handle_fee(&who)?;
+
+            let height = <frame_system::Pallet<T>>::block_number();
+
+            meta.expires_at = meta
+                .expires_at
+                .max(height)
+                .saturating_add(T::HandlePeriod::get());
+
+            <Handles<T>>::insert(&handle, &meta);
+
+            Self::deposit_event(Event::<T>::HandleRenewed(
+                did,
+                handle.into_inner(),
+                meta.expires_at,
+            ));
+
+            Ok(())
+        }
+
+        /// Release the handle of the DID of the origin.
+        #[pallet::weight(T::WeightInfo::release_handle())]
+        pub fn release_handle(origin: OriginFor<T>) -> DispatchResult {
+            let (did, _) = EnsureDid::<T>::ensure_origin(origin)?;
+
+            let handle = <HandleOf<T>>::take(&did).ok_or(Error::<T>::HandleNotExists)?;
+
+            <Handles<T>>::remove(&handle);
+
+            Self::deposit_event(Event::<T>::HandleReleased(did, handle.into_inner()));
+
+            Ok(())
+        }
+
+        /// Reserve a handle for the owner of a sociality, e.g. a verified KOL.
+        ///
+        /// # Arguments
+        ///
+        /// * `handle` - The handle, with or without the `.ad3` suffix
+        /// * `network` - The network of the sociality
+        /// * `profile` - The profile URL of the sociality, as linked in the linker
+        #[pallet::weight(T::WeightInfo::force_reserve_handle())]
+        pub fn force_reserve_handle(
+            origin: OriginFor<T>,
+            handle: Vec<u8>,
+            network: Network,
+            profile: Vec<u8>,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+
+            let handle = Self::handle_name(&handle)?;
+
+            let bounded = profile
+                .clone()
+                .try_into()
+                .map_err(|_| Error::<T>::InvalidHandle)?;
+
+            <ReservedHandles<T>>::insert(
+                &handle,
+                types::ReservedHandle {
+                    network,
+                    profile: bounded,
+                },
+            );
+
+            Self::deposit_event(Event::<T>::HandleReserved(
+                handle.into_inner(),
+                network,
+                profile,
+            ));
+
+            Ok(())
+        }
+
+        /// Remove the reservation of a handle.
+        #[pallet::weight(T::WeightInfo::force_unreserve_handle())]
+        pub fn force_unreserve_handle(origin: OriginFor<T>, handle: Vec<u8>) -> DispatchResult {
+            ensure_root(origin)?;
+
+            let handle = Self::handle_name(&handle)?;
+
+            ensure!(
+                <ReservedHandles<T>>::contains_key(&handle),
+                Error::<T>::HandleNotExists
+            );
+
+            <ReservedHandles<T>>::remove(&handle);
+
+            Self::deposit_event(Event::<T>::HandleUnreserved(handle.into_inner()));
+
+            Ok(())
+        }
+
+        /// Add a verification method to the DID document.
+        ///
+        /// # Arguments
+        ///
+        /// * `did` - The DID, the origin should be the DID or one of its controllers
+        /// * `id` - The fragment of the method id, e.g. `key-1`
+        /// * `public_key` - The public key
+        /// * `purposes` - The verification relationships of the method
+        #[pallet::weight(T::WeightInfo::add_verification_method())]
+        pub fn add_verification_method(
+            origin: OriginFor<T>,
+            did: T::DecentralizedId,
+            id: Vec<u8>,
+            public_key: PublicKey,
+            mut purposes: Vec<VerificationRelationship>,
+        ) -> DispatchResult {
+            let (controller, _) = EnsureDid::<T>::ensure_origin(origin)?;
+            Self::ensure_controller(&did, &controller)?;
+
+            purposes.sort();
+            purposes.dedup();
+            ensure!(!purposes.is_empty(), Error::<T>::BadDocument);
+            let purposes: PurposesOf = purposes.try_into().map_err(|_| Error::<T>::BadDocument)?;
+
+            let fragment = Self::fragment(&id)?;
+
+            <DocumentOf<T>>::try_mutate(&did, |document| -> DispatchResult {
+                ensure!(
+                    !document
+                        .verification_methods
+                        .iter()
+                        .any(|method| method.id == fragment),
+                    Error::<T>::DocumentItemExists
+                );
+
+                document
+                    .verification_methods
+                    .try_push(types::VerificationMethod {
+                        id: fragment,
+                        public_key,
+                        purposes,
+                    })
+                    .map_err(|_| Error::<T>::TooManyDocumentItems)?;
+
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::<T>::VerificationMethodAdded(did, id));
+
+            Ok(())
+        }
+
+        /// Remove a verification method from the DID document.
+        #[pallet::weight(T::WeightInfo::remove_verification_method())]
+        pub fn remove_verification_method(
+            origin: OriginFor<T>,
+            did: T::DecentralizedId,
+            id: Vec<u8>,
+        ) -> DispatchResult {
+            let (controller, _) = EnsureDid::<T>::ensure_origin(origin)?;
+            Self::ensure_controller(&did, &controller)?;
+
+            <DocumentOf<T>>::try_mutate(&did, |document| -> DispatchResult {
+                let index = document
+                    .verification_methods
+                    .iter()
+                    .position(|method| method.id[..] == id[..])
+                    .ok_or(Error::<T>::DocumentItemNotExists)?;
+
+                document.verification_methods.remove(index);
+
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::<T>::VerificationMethodRemoved(did, id));
+
+            Ok(())
+        }
+
+        /// Add a service endpoint to the DID document.
+        ///
+        /// # Arguments
+        ///
+        /// * `did` - The DID, the origin should be the DID or one of its controllers
+        /// * `id` - The fragment of the service id, e.g. `linked-domain`
+        /// * `service_type` - The service type, e.g. `LinkedDomains`
+        /// * `endpoint` - The service endpoint URI
+        #[pallet::weight(T::WeightInfo::add_service())]
+        pub fn add_service(
+            origin: OriginFor<T>,
+            did: T::DecentralizedId,
+            id: Vec<u8>,
+            service_type: Vec<u8>,
+            endpoint: Vec<u8>,
+        ) -> DispatchResult {
+            let (controller, _) = EnsureDid::<T>::ensure_origin(origin)?;
+            Self::ensure_controller(&did, &controller)?;
+
+            let fragment = Self::fragment(&id)?;
+
+            ensure!(!service_type.is_empty(), Error::<T>::BadDocument);
+            let service_type: ServiceTypeOf = service_type
+                .try_into()
+                .map_err(|_| Error::<T>::BadDocument)?;
+
+            ensure!(!endpoint.is_empty(), Error::<T>::BadDocument);
+            let endpoint: ServiceEndpointOf =
+                endpoint.try_into().map_err(|_| Error::<T>::BadDocument)?;
+
+            <DocumentOf<T>>::try_mutate(&did, |document| -> DispatchResult {
+                ensure!(
+                    !document
+                        .services
+                        .iter()
+                        .any(|service| service.id == fragment),
+                    Error::<T>::DocumentItemExists
+                );
+
+                document
+                    .services
+                    .try_push(types::Service {
+                        id: fragment,
+                        service_type,
+                        endpoint,
+                    })
+                    .map_err(|_| Error::<T>::TooManyDocumentItems)?;
+
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::<T>::ServiceAdded(did, id));
+
+            Ok(())
+        }
+
+        /// Remove a service endpoint from the DID document.
+        #[pallet::weight(T::WeightInfo::remove_service())]
+        pub fn remove_service(
+            origin: OriginFor<T>,
+            did: T::DecentralizedId,
+            id: Vec<u8>,
+        ) -> DispatchResult {
+            let (controller, _) = EnsureDid::<T>::ensure_origin(origin)?;
+            Self::ensure_controller(&did, &controller)?;
+
+            <DocumentOf<T>>::try_mutate(&did, |document| -> DispatchResult {
+                let index = document
+                    .services
+                    .iter()
+                    .position(|service| service.id[..] == id[..])
+                    .ok_or(Error::<T>::DocumentItemNotExists)?;
+
+                document.services.remove(index);
+
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::<T>::ServiceRemoved(did, id));
+
+            Ok(())
+        }
+
+        /// Add a controller to the DID document.
+        #[pallet::weight(T::WeightInfo::add_controller())]
+        pub fn add_controller(
+            origin: OriginFor<T>,
+            did: T::DecentralizedId,
+            controller: T::DecentralizedId,
+        ) -> DispatchResult {
+            let (who, _) = EnsureDid::<T>::ensure_origin(origin)?;
+            Self::ensure_controller(&did, &who)?;
+
+            ensure!(controller != did, Error::<T>::BadDocument);
+            ensure!(
+                <Metadata<T>>::contains_key(&controller),
+                Error::<T>::DidNotExists
+            );
+
+            <DocumentOf<T>>::try_mutate(&did, |document| -> DispatchResult {
+                ensure!(
+                    !document.controllers.contains(&controller),
+                    Error::<T>::DocumentItemExists
+                );
+
+                document
+                    .controllers
+                    .try_push(controller)
+                    .map_err(|_| Error::<T>::TooManyDocumentItems)?;
+
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::<T>::ControllerAdded(did, controller));
+
+            Ok(())
+        }
+
+        /// Remove a controller from the DID document.
+        #[pallet::weight(T::WeightInfo::remove_controller())]
+        pub fn remove_controller(
+            origin: OriginFor<T>,
+            did: T::DecentralizedId,
+            controller: T::DecentralizedId,
+        ) -> DispatchResult {
+            let (who, _) = EnsureDid::<T>::ensure_origin(origin)?;
+            Self::ensure_controller(&did, &who)?;
+
+            <DocumentOf<T>>::try_mutate(&did, |document| -> DispatchResult {
+                let index = document
+                    .controllers
+                    .iter()
+                    .position(|c| *c == controller)
+                    .ok_or(Error::<T>::DocumentItemNotExists)?;
+
+                document.controllers.remove(index);
+
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::<T>::ControllerRemoved(did, controller));
+
+            Ok(())
+        }
+
+        /// Nominate guardian DIDs which are able to recover the DID to a new account.
+        ///
+        /// # Arguments
+        ///
+        /// * `guardians` - The guardian DIDs
+        /// * `threshold` - The number of guardians required to vouch for a new account
+        /// * `delay` - The blocks to wait after the threshold is reached
+        #[pallet::weight(T::WeightInfo::set_recovery(guardians.len() as u32))]
+        pub fn set_recovery(
+            origin: OriginFor<T>,
+            mut guardians: Vec<T::DecentralizedId>,
+            threshold: u32,
+            delay: HeightOf<T>,
+        ) -> DispatchResult {
+            let (did, _) = EnsureDid::<T>::ensure_origin(origin)?;
+
+            ensure!(
+                <ActiveRecoveryOf<T>>::iter_key_prefix(&did)
+                    .next()
+                    .is_none(),
+                Error::<T>::BadRecoveryConfig
+            );
+
+            guardians.sort();
+            guardians.dedup();
+
+            ensure!(
+                0 < threshold && threshold as usize <= guardians.len(),
+                Error::<T>::BadRecoveryConfig
+            );
+            ensure!(!delay.is_zero(), Error::<T>::BadRecoveryConfig);
+            ensure!(!guardians.contains(&did), Error::<T>::BadRecoveryConfig);
+            for guardian in &guardians {
+                ensure!(
+                    <Metadata<T>>::contains_key(guardian),
+                    Error::<T>::DidNotExists
+                );
+            }
+
+            let bounded: GuardiansOf<T> = guardians
+                .clone()
+                .try_into()
+                .map_err(|_| Error::<T>::BadRecoveryConfig)?;
+
+            <RecoveryConfigOf<T>>::insert(
+                &did,
+                types::RecoveryConfig {
+                    guardians: bounded,
+                    threshold,
+                    delay,
+                },
+            );
+
+            Self::deposit_event(Event::<T>::RecoveryConfigured(
+                did, guardians, threshold, delay,
+            ));
+
+            Ok(())
+        }
+
+        /// Remove the social recovery config of the DID.
+        #[pallet::weight(T::WeightInfo::remove_recovery())]
+        pub fn remove_recovery(origin: OriginFor<T>) -> DispatchResult {
+            let (did, _) = EnsureDid::<T>::ensure_origin(origin)?;
+
+            ensure!(
+                <RecoveryConfigOf<T>>::contains_key(&did),
+                Error::<T>::RecoveryNotExists
+            );
+
+            <RecoveryConfigOf<T>>::remove(&did);
+            <ActiveRecoveryOf<T>>::remove_prefix(&did, None);
+
+            Self::deposit_event(Event::<T>::RecoveryRemoved(did));
+
+            Ok(())
+        }
+
+        /// Vouch for recovering a DID to a new account, called by a guardian.
+        ///
+        /// Vouches are counted per account, a guardian vouching for another account
+        /// moves its vouch, so that no single guardian can block the recovery.
+        ///
+        /// # Arguments
+        ///
+        /// * `did` - The DID to recover
+        /// * `account` - The new account
+        #[pallet::weight(T::WeightInfo::vouch_recovery())]
+        pub fn vouch_recovery(
+            origin: OriginFor<T>,
+            did: T::DecentralizedId,
+            account: AccountOf<T>,
+        ) -> DispatchResult {
+            let (guardian, _) = EnsureDid::<T>::ensure_origin(origin)?;
+
+            let config = <RecoveryConfigOf<T>>::get(&did).ok_or(Error::<T>::RecoveryNotExists)?;
+            ensure!(
+                config.guardians.contains(&guardian),
+                Error::<T>::NotGuardian
+            );
+
+            ensure!(!<DidOf<T>>::contains_key(&account), Error::<T>::DidExists);
+            ensure!(
+                !<SessionKeyOf<T>>::contains_key(&account),
+                Error::<T>::SessionKeyExists
+            );
+
+            let height = <frame_system::Pallet<T>>::block_number();
+
+            let mut recovery = match <ActiveRecoveryOf<T>>::get(&did, &account) {
+                Some(recovery) if height < recovery.expires_at => {
+                    ensure!(
+                        !recovery.vouchers.contains(&guardian),
+                        Error::<T>::AlreadyVouched
+                    );
+                    recovery
+                }
+                _ => types::ActiveRecovery {
+                    vouchers: Default::default(),
+                    executable_at: None,
+                    expires_at: height.saturating_add(T::RecoveryLifetime::get()),
+                },
+            };
+
+            Self::unvouch(&did, &guardian, config.threshold);
+
+            recovery
+                .vouchers
+                .try_push(guardian)
+                .map_err(|_| Error::<T>::AlreadyVouched)?;
+
+            Self::deposit_event(Event::<T>::RecoveryVouched(did, guardian, account.clone()));
+
+            if recovery.executable_at.is_none()
+                && recovery.vouchers.len() as u32 >= config.threshold
+            {
+                let executable_at = height.saturating_add(config.delay);
+
+                recovery.executable_at = Some(executable_at);
+
+                Self::deposit_event(Event::<T>::RecoveryApproved(
+                    did,
+                    account.clone(),
+                    executable_at,
+                ));
+            }
+
+            <ActiveRecoveryOf<T>>::insert(&did, &account, recovery);
+
+            Ok(())
+        }
+
+        /// Cancel all ongoing recoveries of the DID, called by the owner.
+        #[pallet::weight(T::WeightInfo::cancel_recovery())]
+        pub fn cancel_recovery(origin: OriginFor<T>) -> DispatchResult {
             let (did, _) = EnsureDid::<T>::ensure_origin(origin)?;
 
-            let key = derive_storage_key(&key, &did);
-            sp_io::offchain_index::set(&key, &value);
+            ensure!(
+                <ActiveRecoveryOf<T>>::iter_key_prefix(&did)
+                    .next()
+                    .is_some(),
+                Error::<T>::RecoveryNotExists
+            );
+
+            <ActiveRecoveryOf<T>>::remove_prefix(&did, None);
+
+            Self::deposit_event(Event::<T>::RecoveryCancelled(did));
+
+            Ok(())
+        }
+
+        /// Finish the recovery after the delay and before it expires,
+        /// the DID and its assets are moved to the new account.
+        #[pallet::weight(T::WeightInfo::claim_recovery())]
+        pub fn claim_recovery(
+            origin: OriginFor<T>,
+            did: T::DecentralizedId,
+            account: AccountOf<T>,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            let recovery =
+                <ActiveRecoveryOf<T>>::get(&did, &account).ok_or(Error::<T>::RecoveryNotExists)?;
+
+            let height = <frame_system::Pallet<T>>::block_number();
+            ensure!(height < recovery.expires_at, Error::<T>::RecoveryExpired);
+            match recovery.executable_at {
+                Some(executable_at) if executable_at <= height => {}
+                _ => Err(Error::<T>::RecoveryNotReady)?,
+            }
+
+            let source = Self::transfer_with_assets(did, account.clone())?;
+
+            <ActiveRecoveryOf<T>>::remove_prefix(&did, None);
+
+            Self::deposit_event(Event::<T>::Recovered(did, source, account));
 
             Ok(())
         }
@@ -263,10 +1206,6 @@ impl<T: Config> Pallet<T> {
         account: AccountOf<T>,
         referrer: Option<T::DecentralizedId>,
     ) -> Result<T::DecentralizedId, DispatchError> {
-        use codec::Encode;
-
-        ensure!(!<DidOf<T>>::contains_key(&account), Error::<T>::DidExists);
-
         if let Some(r) = referrer.as_ref() {
             ensure!(
                 <Metadata<T>>::contains_key(r),
@@ -276,15 +1215,9 @@ impl<T: Config> Pallet<T> {
 
         // 1. generate DID
 
-        let created = <frame_system::Pallet<T>>::block_number();
-
-        // TODO: use a HMAC-based algorithm.
-        let mut raw = <AccountOf<T>>::encode(&account);
-        let mut ord = T::BlockNumber::encode(&created);
-        raw.append(&mut ord);
+        let (did, nonce) = Self::preview(&account)?;
 
-        let did = <T as Config>::Hashing::hash(&raw);
-        let did: T::DecentralizedId = Self::truncate(&did);
+        let created = <frame_system::Pallet<T>>::block_number();
 
         // 2. store metadata
 
@@ -297,8 +1230,11 @@ impl<T: Config> Pallet<T> {
             },
         );
         <DidOf<T>>::insert(&account, did);
+        <NonceOf<T>>::insert(&account, nonce.saturating_add(1));
         if let Some(referrer) = referrer {
             <ReferrerOf<T>>::insert(&did, referrer);
+
+            Self::index_invitee(&did, &referrer);
         }
 
         Self::deposit_event(Event::<T>::Assigned(did.clone(), account, referrer));
@@ -306,8 +1242,116 @@ impl<T: Config> Pallet<T> {
         Ok(did)
     }
 
+    /// Withdraw the vouch of a guardian from the ongoing recoveries of a DID,
+    /// a recovery falling below the threshold is no longer approved
+    fn unvouch(did: &T::DecentralizedId, guardian: &T::DecentralizedId, threshold: u32) {
+        let recoveries: Vec<_> = <ActiveRecoveryOf<T>>::iter_prefix(did)
+            .filter(|(_, recovery)| recovery.vouchers.contains(guardian))
+            .collect();
+
+        for (account, mut recovery) in recoveries {
+            recovery.vouchers.retain(|voucher| voucher != guardian);
+
+            if (recovery.vouchers.len() as u32) < threshold {
+                recovery.executable_at = None;
+            }
+
+            if recovery.vouchers.is_empty() {
+                <ActiveRecoveryOf<T>>::remove(did, &account);
+            } else {
+                <ActiveRecoveryOf<T>>::insert(did, &account, recovery);
+            }
+
+            Self::deposit_event(Event::<T>::RecoveryUnvouched(*did, *guardian, account));
+        }
+    }
+
+    /// Index an invitee and count it in the downline of its referrers
+    pub(crate) fn index_invitee(did: &T::DecentralizedId, referrer: &T::DecentralizedId) {
+        <InviteesOf<T>>::insert(referrer, did, ());
+
+        let mut ancestor = Some(*referrer);
+        for level in 0..REFERRAL_LEVELS {
+            let current = match ancestor {
+                Some(current) => current,
+                None => break,
+            };
+
+            <ReferralCountsOf<T>>::mutate(&current, |counts| {
+                counts[level] = counts[level].saturating_add(1)
+            });
+
+            ancestor = <ReferrerOf<T>>::get(&current);
+        }
+    }
+
+    /// The invitees of a DID, paginated
+    ///
+    /// # Arguments
+    ///
+    /// * `did` - The inviter's DID
+    /// * `start` - The number of invitees to skip
+    /// * `limit` - The maximum number of invitees to return, capped by `MAX_INVITEES_PAGE`
+    pub fn invitees(did: &T::DecentralizedId, start: u32, limit: u32) -> Vec<T::DecentralizedId> {
+        <InviteesOf<T>>::iter_key_prefix(did)
+            .skip(start as usize)
+            .take(limit.min(MAX_INVITEES_PAGE) as usize)
+            .collect()
+    }
+
+    /// Record a milestone reached by a DID,
+    /// the referrer of the DID is rewarded when it is reached for the first time
+    pub fn reach_milestone(did: &T::DecentralizedId, milestone: Milestone) {
+        if <MilestonesOf<T>>::contains_key(did, milestone) {
+            return;
+        }
+
+        <MilestonesOf<T>>::insert(did, milestone, ());
+
+        Self::deposit_event(Event::<T>::MilestoneReached(*did, milestone));
+
+        if let Some(referrer) = <ReferrerOf<T>>::get(did) {
+            T::OnReferralMilestone::on_referral_milestone(&referrer, did, milestone);
+        }
+    }
+
+    /// Preview the DID an account will get when registering, returns (did, nonce)
+    ///
+    /// The DID is the last 20 bytes of `Hashing(SCALE(account) ++ SCALE(nonce as u32))`,
+    /// `nonce` starts from the number of DIDs the account has registered,
+    /// and is increased while the derived DID is already taken.
+    pub fn preview(account: &AccountOf<T>) -> Result<(T::DecentralizedId, u32), DispatchError> {
+        use codec::Encode;
+
+        ensure!(!<DidOf<T>>::contains_key(account), Error::<T>::DidExists);
+        ensure!(
+            !<SessionKeyOf<T>>::contains_key(account),
+            Error::<T>::SessionKeyExists
+        );
+
+        let start = <NonceOf<T>>::get(account);
+
+        for nonce in start..start.saturating_add(MAX_DERIVATION_ATTEMPTS) {
+            let mut raw = <AccountOf<T>>::encode(account);
+            raw.append(&mut nonce.encode());
+
+            let did = <T as Config>::Hashing::hash(&raw);
+            let did: T::DecentralizedId = Self::truncate(&did);
+
+            if !<Metadata<T>>::contains_key(&did) {
+                return Ok((did, nonce));
+            }
+        }
+
+        Err(Error::<T>::DidExists.into())
+    }
+
     pub fn assign(did: &T::DecentralizedId, dest: &AccountOf<T>) -> DispatchResult {
         ensure!(!<DidOf<T>>::contains_key(dest), Error::<T>::DidExists);
+        ensure!(
+            !<SessionKeyOf<T>>::contains_key(dest),
+            Error::<T>::SessionKeyExists
+        );
 
         let mut meta = <Metadata<T>>::get(did).ok_or(Error::<T>::DidNotExists)?;
 
@@ -316,6 +1360,10 @@ impl<T: Config> Pallet<T> {
         meta.account = dest.clone();
         meta.created = <frame_system::Pallet<T>>::block_number();
 
+        Self::move_metadata_deposit(&source, dest)?;
+
+        Self::clear_session_keys(did);
+
         <Metadata<T>>::insert(did, meta);
 
         <DidOf<T>>::remove(&source);
@@ -326,6 +1374,152 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
+    /// Move a DID and all assets of its account to a new account,
+    /// returns the previous account
+    #[transactional]
+    pub fn transfer_with_assets(
+        did: T::DecentralizedId,
+        dest: AccountOf<T>,
+    ) -> Result<AccountOf<T>, DispatchError> {
+        ensure!(!<DidOf<T>>::contains_key(&dest), Error::<T>::DidExists);
+        ensure!(
+            !<SessionKeyOf<T>>::contains_key(&dest),
+            Error::<T>::SessionKeyExists
+        );
+        let mut meta = <Metadata<T>>::get(did).ok_or(Error::<T>::DidNotExists)?;
+
+        let source = meta.account.clone();
+        meta.account = dest.clone();
+        meta.created = <frame_system::Pallet<T>>::block_number();
+
+        Self::move_metadata_deposit(&source, &dest)?;
+
+        // named reserves are moved by their pallets in `Transferables`,
+        // other reserves are left to the previous account,
+        // so is the free balance when a lock keeps it from being moved
+        let ad3_balance = T::Currency::free_balance(&source);
+        if let Err(e) = T::Currency::transfer(&source, &dest, ad3_balance, AllowDeath) {
+            sp_runtime::print(e);
+        }
+        T::Transferables::transfer_all(&source, &dest)?;
+
+        Self::clear_session_keys(&did);
+
+        <Metadata<T>>::insert(did, meta);
+        <DidOf<T>>::remove(&source);
+        <DidOf<T>>::insert(dest.clone(), did);
+
+        Self::deposit_event(Event::<T>::Transferred(did, source.clone(), dest));
+
+        Ok(source)
+    }
+
+    /// Get the on-chain metadata of a DID
+    pub fn metadata(did: &T::DecentralizedId, key: &[u8]) -> Option<Vec<u8>> {
+        let key: MetadataKeyOf = key.to_vec().try_into().ok()?;
+
+        <MetadataOf<T>>::get(did, &key).map(|entry| entry.value.into_inner())
+    }
+
+    /// Get the raw storage key of a metadata entry, used to build storage proofs
+    pub fn metadata_storage_key(did: &T::DecentralizedId, key: &[u8]) -> Option<Vec<u8>> {
+        let key: MetadataKeyOf = key.to_vec().try_into().ok()?;
+
+        Some(<MetadataOf<T>>::hashed_key_for(did, &key))
+    }
+
+    /// The deposit required to store a metadata entry
+    pub fn metadata_deposit(key: &[u8], value: &[u8]) -> BalanceOf<T> {
+        let bytes = key.len().saturating_add(value.len()) as u32;
+
+        T::MetadataDepositPerByte::get()
+            .saturating_mul(bytes.into())
+            .saturating_add(T::MetadataDepositBase::get())
+    }
+
+    /// Store a metadata entry, adjusting the reserved deposit of `depositor`,
+    /// or keeping the existing deposit if `depositor` is `None`
+    fn insert_metadata(
+        did: &T::DecentralizedId,
+        key: Vec<u8>,
+        value: Vec<u8>,
+        depositor: Option<(&AccountOf<T>, BalanceOf<T>)>,
+    ) -> DispatchResult {
+        let bounded: MetadataKeyOf = key
+            .clone()
+            .try_into()
+            .map_err(|_| Error::<T>::MetadataTooLong)?;
+        let value: MetadataValueOf = value.try_into().map_err(|_| Error::<T>::MetadataTooLong)?;
+
+        let old = <MetadataOf<T>>::get(did, &bounded)
+            .map(|entry| entry.deposit)
+            .unwrap_or_default();
+
+        let deposit = match depositor {
+            Some((who, deposit)) => {
+                let id = <T as Config>::PalletId::get();
+
+                if deposit > old {
+                    T::Currency::reserve_named(&id.0, who, deposit - old)?;
+                } else {
+                    T::Currency::unreserve_named(&id.0, who, old - deposit);
+                }
+
+                deposit
+            }
+            None => old,
+        };
+
+        <MetadataOf<T>>::insert(did, &bounded, types::MetadataEntry { value, deposit });
+
+        Self::deposit_event(Event::<T>::MetadataSet(did.clone(), key));
+
+        Ok(())
+    }
+
+    /// Move the reserved metadata deposits to the new account of a DID
+    fn move_metadata_deposit(source: &AccountOf<T>, dest: &AccountOf<T>) -> DispatchResult {
+        let id = <T as Config>::PalletId::get();
+
+        let reserved = T::Currency::reserved_balance_named(&id.0, source);
+        if reserved.is_zero() {
+            return Ok(());
+        }
+
+        T::Currency::unreserve_named(&id.0, source, reserved);
+        T::Currency::transfer(source, dest, reserved, AllowDeath)?;
+        T::Currency::reserve_named(&id.0, dest, reserved)?;
+
+        Ok(())
+    }
+
+    /// Remove all session keys of a DID, called when its account changes or it is revoked
+    fn clear_session_keys(did: &T::DecentralizedId) {
+        for account in <SessionKeysOf<T>>::take(did) {
+            <SessionKeyOf<T>>::remove(&account);
+        }
+    }
+
+    /// Resolve a session key granted with `scope` to its DID and the account of the DID
+    pub fn lookup_session(
+        account: &AccountOf<T>,
+        scope: SessionScope,
+    ) -> Option<(T::DecentralizedId, AccountOf<T>)> {
+        let session = <SessionKeyOf<T>>::get(account)?;
+
+        let height = <frame_system::Pallet<T>>::block_number();
+        if session.scope != scope || height >= session.expires_at {
+            return None;
+        }
+
+        let meta = <Metadata<T>>::get(&session.did)?;
+        if meta.revoked {
+            return None;
+        }
+
+        Some((session.did, meta.account))
+    }
+
     pub fn lookup_did_by_account_id(a: AccountOf<T>) -> Option<T::DecentralizedId> {
         <DidOf<T>>::get(a)
     }
@@ -335,15 +1529,143 @@ impl<T: Config> Pallet<T> {
             MultiAddress::Id(i) => Some(i),
             MultiAddress::Address20(a) => Self::lookup_did(a.into()),
             MultiAddress::Raw(r) => match r.len() {
-                20 => Self::lookup_did(Self::truncate(&r)),
-                _ => None,
+                20 => Self::lookup_did(Self::truncate(&r))
+                    .or_else(|| Self::lookup_handle(&r).and_then(Self::lookup_did)),
+                _ => Self::lookup_handle(&r).and_then(Self::lookup_did),
             },
             _ => None,
         }
     }
 
+    /// Resolve a handle, with or without the `.ad3` suffix, to its DID
+    pub fn lookup_handle(handle: &[u8]) -> Option<T::DecentralizedId> {
+        let handle = Self::handle_name(handle).ok()?;
+
+        let meta = <Handles<T>>::get(&handle)?;
+
+        let height = <frame_system::Pallet<T>>::block_number();
+        if height >= meta.expires_at {
+            return None;
+        }
+
+        Some(meta.did)
+    }
+
+    /// Resolve a DID to its handle, with the `.ad3` suffix
+    pub fn reverse_lookup_handle(did: &T::DecentralizedId) -> Option<Vec<u8>> {
+        let handle = <HandleOf<T>>::get(did)?;
+
+        let meta = <Handles<T>>::get(&handle)?;
+
+        let height = <frame_system::Pallet<T>>::block_number();
+        if meta.did != *did || height >= meta.expires_at {
+            return None;
+        }
+
+        let mut handle = handle.into_inner();
+        handle.extend_from_slice(HANDLE_SUFFIX);
+
+        Some(handle)
+    }
+
+    fn handle_name(handle: &[u8]) -> Result<HandleNameOf, DispatchError> {
+        let handle = handle.strip_suffix(HANDLE_SUFFIX).unwrap_or(handle);
+
+        let is_valid_char = |c: &u8| c.is_ascii_lowercase() || c.is_ascii_digit() || *c == b'-';
+
+        ensure!(
+            handle.len() >= MIN_HANDLE_LENGTH
+                && handle.iter().all(is_valid_char)
+                && !handle.starts_with(b"-")
+                && !handle.ends_with(b"-"),
+            Error::<T>::InvalidHandle
+        );
+
+        handle
+            .to_vec()
+            .try_into()
+            .map_err(|_| Error::<T>::InvalidHandle.into())
+    }
+
+    fn charge_handle_fee(who: &AccountOf<T>) -> DispatchResult {
+        let fee = T::HandleFee::get();
+        if fee.is_zero() {
+            return Ok(());
+        }
+
+        let imbalance = T::Currency::withdraw(who, fee, WithdrawReasons::FEE, KeepAlive)?;
+        T::HandleFeeDestination::on_unbalanced(imbalance);
+
+        Ok(())
+    }
+
     pub fn lookup_did(did: T::DecentralizedId) -> Option<AccountOf<T>> {
-        <Metadata<T>>::get(&did).map(|x| x.account)
+        <Metadata<T>>::get(&did)
+            .filter(|x| !x.revoked)
+            .map(|x| x.account)
+    }
+
+    /// Resolve the DID document of a DID
+    pub fn resolve(
+        did: T::DecentralizedId,
+    ) -> Result<DidDocument<T::DecentralizedId>, DispatchError> {
+        use parami_primitives::did::{Service, VerificationMethod};
+
+        let meta = <Metadata<T>>::get(&did).ok_or(Error::<T>::DidNotExists)?;
+
+        let document = <DocumentOf<T>>::get(&did);
+
+        Ok(DidDocument {
+            id: did,
+            controllers: document.controllers.into_inner(),
+            verification_methods: document
+                .verification_methods
+                .into_iter()
+                .map(|method| VerificationMethod {
+                    id: method.id.into_inner(),
+                    public_key: method.public_key,
+                    purposes: method.purposes.into_inner(),
+                })
+                .collect(),
+            services: document
+                .services
+                .into_iter()
+                .map(|service| Service {
+                    id: service.id.into_inner(),
+                    service_type: service.service_type.into_inner(),
+                    endpoint: service.endpoint.into_inner(),
+                })
+                .collect(),
+            deactivated: meta.revoked,
+        })
+    }
+
+    fn ensure_controller(
+        did: &T::DecentralizedId,
+        controller: &T::DecentralizedId,
+    ) -> DispatchResult {
+        let meta = <Metadata<T>>::get(did).ok_or(Error::<T>::DidNotExists)?;
+        ensure!(!meta.revoked, Error::<T>::DidNotExists);
+
+        ensure!(
+            did == controller || <DocumentOf<T>>::get(did).controllers.contains(controller),
+            Error::<T>::NotController
+        );
+
+        Ok(())
+    }
+
+    fn fragment(id: &[u8]) -> Result<FragmentOf, DispatchError> {
+        let is_valid_char = |c: &u8| c.is_ascii_alphanumeric() || *c == b'-' || *c == b'_';
+
+        ensure!(
+            !id.is_empty() && id.iter().all(is_valid_char),
+            Error::<T>::BadDocument
+        );
+
+        id.to_vec()
+            .try_into()
+            .map_err(|_| Error::<T>::BadDocument.into())
     }
 
     pub fn set_meta(did: &T::DecentralizedId, meta: MetaOf<T>) {
@@ -373,8 +1695,85 @@ impl<T: Config> StaticLookup for Pallet<T> {
     }
 }
 
-pub struct EnsureDid<T>(sp_std::marker::PhantomData<T>);
-impl<T: pallet::Config> EnsureOrigin<T::Origin> for EnsureDid<T> {
+/// Handler for milestones reached by invitees
+pub trait OnReferralMilestone<T: Config> {
+    fn on_referral_milestone(
+        referrer: &T::DecentralizedId,
+        invitee: &T::DecentralizedId,
+        milestone: Milestone,
+    );
+}
+
+impl<T: Config> OnReferralMilestone<T> for () {
+    fn on_referral_milestone(
+        _referrer: &T::DecentralizedId,
+        _invitee: &T::DecentralizedId,
+        _milestone: Milestone,
+    ) {
+    }
+}
+
+/// Pay a bonus of `Bonus` to the referrer from the account of `Pot`, e.g. the treasury,
+/// nothing is paid if the pot runs out or the referrer was revoked
+///
+/// Invitees younger than `MinAge` blocks earn no bonus,
+/// so that it can not be farmed by registering and revoking DIDs in a loop.
+pub struct PayReferralBonus<T, Pot, Bonus, MinAge>(
+    sp_std::marker::PhantomData<(T, Pot, Bonus, MinAge)>,
+);
+impl<T, Pot, Bonus, MinAge> OnReferralMilestone<T> for PayReferralBonus<T, Pot, Bonus, MinAge>
+where
+    T: Config,
+    Pot: Get<PalletId>,
+    Bonus: Get<BalanceOf<T>>,
+    MinAge: Get<HeightOf<T>>,
+{
+    fn on_referral_milestone(
+        referrer: &T::DecentralizedId,
+        invitee: &T::DecentralizedId,
+        milestone: Milestone,
+    ) {
+        let bonus = Bonus::get();
+        if bonus.is_zero() {
+            return;
+        }
+
+        let created = match <Metadata<T>>::get(invitee) {
+            Some(meta) if !meta.revoked => meta.created,
+            _ => return,
+        };
+
+        let height = <frame_system::Pallet<T>>::block_number();
+        if height < created.saturating_add(MinAge::get()) {
+            return;
+        }
+
+        let account = match Pallet::<T>::lookup_did(*referrer) {
+            Some(account) => account,
+            None => return,
+        };
+
+        let pot: AccountOf<T> = Pot::get().into_account_truncating();
+
+        if T::Currency::transfer(&pot, &account, bonus, KeepAlive).is_ok() {
+            Pallet::<T>::deposit_event(Event::<T>::ReferralBonusPaid(
+                *referrer, *invitee, milestone, bonus,
+            ));
+        }
+    }
+}
+
+frame_support::parameter_types! {
+    pub const AdClaimScope: Option<SessionScope> = Some(SessionScope::AdClaim);
+    pub const ClockInScope: Option<SessionScope> = Some(SessionScope::ClockIn);
+    pub const LinkerScope: Option<SessionScope> = Some(SessionScope::Linker);
+}
+
+/// Ensure the origin is signed by the account of a DID,
+/// or by a session key of the DID granted with scope `S`,
+/// succeeds with the DID and the account of the DID
+pub struct EnsureDid<T, S = ()>(sp_std::marker::PhantomData<(T, S)>);
+impl<T: pallet::Config, S: Get<Option<SessionScope>>> EnsureOrigin<T::Origin> for EnsureDid<T, S> {
     type Success = (T::DecentralizedId, AccountOf<T>);
 
     fn try_origin(o: T::Origin) -> Result<Self::Success, T::Origin> {
@@ -383,9 +1782,13 @@ impl<T: pallet::Config> EnsureOrigin<T::Origin> for EnsureDid<T> {
 
         o.into().and_then(|o| match o {
             RawOrigin::Signed(who) => {
-                let did = <DidOf<T>>::get(&who).ok_or(T::Origin::none())?;
+                if let Some(did) = <DidOf<T>>::get(&who) {
+                    return Ok((did, who));
+                }
+
+                let scope = S::get().ok_or(T::Origin::none())?;
 
-                Ok((did, who))
+                Pallet::<T>::lookup_session(&who, scope).ok_or(T::Origin::none())
             }
             r => Err(T::Origin::from(r)),
         })
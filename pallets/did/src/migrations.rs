@@ -1 +1,28 @@
+pub mod v3 {
+    use frame_support::traits::{Get, OnRuntimeUpgrade};
+    use frame_support::weights::Weight;
 
+    use crate::{Config, Pallet, ReferrerOf, StorageVersion, REFERRAL_LEVELS};
+
+    pub struct InviteesIndex<T>(sp_std::marker::PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for InviteesIndex<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let version = StorageVersion::get::<Pallet<T>>();
+            if version > 2 {
+                return 0;
+            }
+
+            let mut count = 0u64;
+            for (did, referrer) in <ReferrerOf<T>>::iter() {
+                count += 1;
+                <Pallet<T>>::index_invitee(&did, &referrer);
+            }
+
+            StorageVersion::new(3).put::<Pallet<T>>();
+
+            let levels = REFERRAL_LEVELS as u64;
+            T::DbWeight::get().reads_writes(count * (levels + 1) + 1, count * (levels + 1) + 1)
+        }
+    }
+}
@@ -1,12 +1,15 @@
 use crate as parami_did;
+use crate::PayReferralBonus;
 use frame_support::traits::ConstU32;
-use frame_support::{parameter_types, traits::GenesisBuild};
+use frame_support::{parameter_types, traits::GenesisBuild, PalletId};
 use frame_system as system;
+use parami_traits::{types::Network, Links};
 use sp_core::{sr25519, H160, H256};
 use sp_runtime::{
     testing::Header,
-    traits::{BlakeTwo256, Keccak256},
+    traits::{AccountIdConversion, BlakeTwo256, Keccak256},
 };
+use std::collections::BTreeMap;
 
 type UncheckedExtrinsic = system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = system::mocking::MockBlock<Test>;
@@ -82,13 +85,54 @@ impl pallet_balances::Config for Test {
     type ReserveIdentifier = [u8; 8];
 }
 
+parameter_types! {
+    pub const DidPalletId: PalletId = PalletId(*b"prm/did ");
+    pub const MetadataDepositBase: Balance = 1;
+    pub const MetadataDepositPerByte: Balance = 1;
+    pub const HandleFee: Balance = 10;
+    pub const HandlePeriod: u64 = 100;
+    pub const RecoveryLifetime: u64 = 50;
+    pub const ReferralBonus: Balance = 5;
+    pub const ReferralMinAge: u64 = 10;
+    pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
+}
+
+pub const PROFILE_ALICE: &[u8] = b"https://twitter.com/alice";
+
+pub struct MockLinks;
+impl Links<H160> for MockLinks {
+    fn all_links(did: &H160) -> BTreeMap<Network, Vec<Vec<u8>>> {
+        let mut links = BTreeMap::new();
+        links.insert(Network::Twitter, Self::links(did, Network::Twitter));
+        links
+    }
+
+    fn links(did: &H160, network: Network) -> Vec<Vec<u8>> {
+        match (*did, network) {
+            (DID_ALICE, Network::Twitter) => vec![PROFILE_ALICE.to_vec()],
+            _ => vec![],
+        }
+    }
+}
+
 impl parami_did::Config for Test {
     type Event = Event;
     type Currency = Balances;
     type DecentralizedId = sp_core::H160;
     type Hashing = Keccak256;
+    type HandleFee = HandleFee;
+    type HandleFeeDestination = ();
+    type HandlePeriod = HandlePeriod;
+    type RecoveryLifetime = RecoveryLifetime;
+    type Links = MockLinks;
+    type MetadataDepositBase = MetadataDepositBase;
+    type MetadataDepositPerByte = MetadataDepositPerByte;
+    type PalletId = DidPalletId;
     type WeightInfo = ();
     type Transferables = ();
+    type OnDidRevoked = ();
+    type OnReferralMilestone =
+        PayReferralBonus<Test, TreasuryPalletId, ReferralBonus, ReferralMinAge>;
 }
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
@@ -97,7 +141,11 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
         .unwrap();
 
     pallet_balances::GenesisConfig::<Test> {
-        balances: vec![(ALICE, 100), (BOB, 100)],
+        balances: vec![
+            (ALICE, 100),
+            (BOB, 100),
+            (TreasuryPalletId::get().into_account_truncating(), 100),
+        ],
     }
     .assimilate_storage(&mut t)
     .unwrap();
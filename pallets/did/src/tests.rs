@@ -1,11 +1,15 @@
-use crate::{mock::*, DidOf, EnsureDid, Error, Metadata, ReferrerOf};
-use frame_support::{assert_noop, assert_ok, traits::Currency};
-use parami_did_utils::derive_storage_key;
-use sp_core::offchain::{
-    testing::{TestOffchainExt, TestPersistentOffchainDB},
-    OffchainDbExt,
+use crate::{
+    mock::*, types, ActiveRecoveryOf, DidOf, DocumentOf, EnsureDid, Error, Metadata, MetadataOf,
+    NonceOf, RecoveryConfigOf, ReferrerOf,
 };
-use sp_runtime::DispatchError;
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::{ConstU32, Currency, LockableCurrency, ReservableCurrency, WithdrawReasons},
+    BoundedVec,
+};
+use parami_primitives::did::{PublicKey, VerificationRelationship};
+use sp_core::{sr25519, H160};
+use sp_runtime::{traits::AccountIdConversion, DispatchError};
 
 #[test]
 fn should_register() {
@@ -68,6 +72,8 @@ fn should_revoke() {
         let meta = <Metadata<Test>>::get(&DID_ALICE).unwrap();
 
         assert_eq!(meta.revoked, true);
+
+        assert_eq!(Did::lookup_did(DID_ALICE), None);
     });
 }
 
@@ -151,24 +157,116 @@ fn should_set_metadata() {
     const KEY: &[u8] = b"avatar";
     const VALUE: &[u8] = b"ipfs://QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG";
 
-    let db = TestPersistentOffchainDB::new();
-    let (offchain, _state) = TestOffchainExt::with_offchain_db(db);
-    let mut t = new_test_ext();
-    t.register_extension(OffchainDbExt::new(offchain.clone()));
-
-    t.execute_with(|| {
+    new_test_ext().execute_with(|| {
         assert_ok!(Did::set_metadata(
             Origin::signed(ALICE),
             KEY.to_vec(),
             VALUE.to_vec()
         ));
+
+        assert_eq!(Did::metadata(&DID_ALICE, KEY), Some(VALUE.to_vec()));
+
+        let deposit = Did::metadata_deposit(KEY, VALUE);
+        assert_eq!(deposit, 1 + (KEY.len() + VALUE.len()) as u128);
+        assert_eq!(Balances::reserved_balance(&ALICE), deposit);
+
+        // shrinking the value releases part of the deposit
+
+        assert_ok!(Did::set_metadata(
+            Origin::signed(ALICE),
+            KEY.to_vec(),
+            b"ipfs://".to_vec()
+        ));
+
+        assert_eq!(Did::metadata(&DID_ALICE, KEY), Some(b"ipfs://".to_vec()));
+        assert_eq!(
+            Balances::reserved_balance(&ALICE),
+            Did::metadata_deposit(KEY, b"ipfs://")
+        );
+
+        assert_ok!(Did::remove_metadata(Origin::signed(ALICE), KEY.to_vec()));
+
+        assert_eq!(Did::metadata(&DID_ALICE, KEY), None);
+        assert_eq!(Balances::reserved_balance(&ALICE), 0);
+
+        assert_noop!(
+            Did::remove_metadata(Origin::signed(ALICE), KEY.to_vec()),
+            Error::<Test>::MetadataNotExists
+        );
+    });
+}
+
+#[test]
+fn should_fail_to_set_metadata() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Did::set_metadata(Origin::signed(ALICE), vec![0u8; 65], b"value".to_vec()),
+            Error::<Test>::MetadataTooLong
+        );
+
+        assert_noop!(
+            Did::set_metadata(Origin::signed(ALICE), b"key".to_vec(), vec![0u8; 1025]),
+            Error::<Test>::MetadataTooLong
+        );
+
+        // insufficient balance for the deposit
+
+        assert_noop!(
+            Did::set_metadata(Origin::signed(ALICE), b"key".to_vec(), vec![0u8; 1000]),
+            pallet_balances::Error::<Test>::InsufficientBalance
+        );
+    });
+}
+
+#[test]
+fn should_force_set_metadata() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Did::force_set_metadata(
+            Origin::root(),
+            DID_ALICE,
+            b"name".to_vec(),
+            b"alice".to_vec()
+        ));
+
+        assert_eq!(Did::metadata(&DID_ALICE, b"name"), Some(b"alice".to_vec()));
+        assert_eq!(Balances::reserved_balance(&ALICE), 0);
+
+        let key: BoundedVec<u8, ConstU32<64>> = b"name".to_vec().try_into().unwrap();
+        assert_eq!(
+            <MetadataOf<Test>>::get(&DID_ALICE, &key).unwrap().deposit,
+            0
+        );
+
+        assert_noop!(
+            Did::force_set_metadata(Origin::root(), DID_BOB, b"name".to_vec(), b"bob".to_vec()),
+            Error::<Test>::DidNotExists
+        );
     });
+}
+
+#[test]
+fn should_move_metadata_deposit() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Did::set_metadata(
+            Origin::signed(ALICE),
+            b"name".to_vec(),
+            b"alice".to_vec()
+        ));
+
+        let deposit = Did::metadata_deposit(b"name", b"alice");
+
+        assert_ok!(Did::transfer(Origin::signed(ALICE), BOB));
 
-    t.persist_offchain_overlay();
+        assert_eq!(Balances::reserved_balance(&ALICE), 0);
+        assert_eq!(Balances::free_balance(&ALICE), 100 - deposit);
+        assert_eq!(Balances::reserved_balance(&BOB), deposit);
 
-    let db = t.offchain_db();
-    let avatar = db.get(&derive_storage_key(KEY, &DID_ALICE));
-    assert_eq!(avatar, Some(VALUE.to_vec()));
+        assert_ok!(Did::revoke(Origin::signed(BOB)));
+
+        assert_eq!(Did::metadata(&DID_ALICE, b"name"), None);
+        assert_eq!(Balances::reserved_balance(&BOB), 0);
+        assert_eq!(Balances::free_balance(&BOB), 100 + deposit);
+    });
 }
 
 #[test]
@@ -213,6 +311,33 @@ fn should_force_transfer_did() {
     });
 }
 
+#[test]
+fn should_force_transfer_did_with_reserves() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Did::set_metadata(
+            Origin::signed(ALICE),
+            b"key".to_vec(),
+            b"value".to_vec()
+        ));
+        let deposit = Did::metadata_deposit(b"key", b"value");
+
+        assert_ok!(Balances::reserve(&ALICE, 10));
+
+        assert_ok!(Did::force_transfer_with_assets(
+            Origin::root(),
+            DID_ALICE,
+            DAVE
+        ));
+
+        // the metadata deposit moves with the DID, other reserves stay
+        assert_eq!(Balances::free_balance(&ALICE), 0);
+        assert_eq!(Balances::reserved_balance(&ALICE), 10);
+        assert_eq!(Balances::free_balance(&DAVE), 90 - deposit);
+        assert_eq!(Balances::reserved_balance(&DAVE), deposit);
+        assert_eq!(Did::did_of(DAVE), Some(DID_ALICE));
+    });
+}
+
 #[test]
 fn should_fail_force_transfer_did_if_not_root_user() {
     new_test_ext().execute_with(|| {
@@ -243,3 +368,997 @@ fn should_fail_force_transfer_did_if_dest_bind_did() {
         );
     });
 }
+
+#[test]
+fn should_add_verification_method() {
+    use VerificationRelationship::*;
+
+    new_test_ext().execute_with(|| {
+        assert_ok!(Did::add_verification_method(
+            Origin::signed(ALICE),
+            DID_ALICE,
+            b"key-1".to_vec(),
+            PublicKey::Sr25519([1; 32]),
+            vec![AssertionMethod, Authentication, Authentication]
+        ));
+
+        let document = Did::resolve(DID_ALICE).unwrap();
+        assert!(!document.deactivated);
+        assert_eq!(document.verification_methods.len(), 1);
+        assert_eq!(document.verification_methods[0].id, b"key-1".to_vec());
+        assert_eq!(
+            document.verification_methods[0].public_key,
+            PublicKey::Sr25519([1; 32])
+        );
+        assert_eq!(
+            document.verification_methods[0].purposes,
+            vec![Authentication, AssertionMethod]
+        );
+
+        assert_noop!(
+            Did::add_verification_method(
+                Origin::signed(ALICE),
+                DID_ALICE,
+                b"key-1".to_vec(),
+                PublicKey::Ed25519([2; 32]),
+                vec![Authentication]
+            ),
+            Error::<Test>::DocumentItemExists
+        );
+
+        assert_noop!(
+            Did::add_verification_method(
+                Origin::signed(ALICE),
+                DID_ALICE,
+                b"key 2".to_vec(),
+                PublicKey::Ed25519([2; 32]),
+                vec![Authentication]
+            ),
+            Error::<Test>::BadDocument
+        );
+
+        assert_noop!(
+            Did::add_verification_method(
+                Origin::signed(ALICE),
+                DID_ALICE,
+                b"key-2".to_vec(),
+                PublicKey::Secp256k1([3; 33]),
+                vec![]
+            ),
+            Error::<Test>::BadDocument
+        );
+
+        assert_ok!(Did::remove_verification_method(
+            Origin::signed(ALICE),
+            DID_ALICE,
+            b"key-1".to_vec()
+        ));
+        assert_eq!(
+            Did::resolve(DID_ALICE).unwrap().verification_methods,
+            vec![]
+        );
+
+        assert_noop!(
+            Did::remove_verification_method(Origin::signed(ALICE), DID_ALICE, b"key-1".to_vec()),
+            Error::<Test>::DocumentItemNotExists
+        );
+    });
+}
+
+#[test]
+fn should_fail_to_add_too_many_verification_methods() {
+    new_test_ext().execute_with(|| {
+        for i in 0..16 {
+            assert_ok!(Did::add_verification_method(
+                Origin::signed(ALICE),
+                DID_ALICE,
+                format!("key-{}", i).into_bytes(),
+                PublicKey::Ed25519([i; 32]),
+                vec![VerificationRelationship::Authentication]
+            ));
+        }
+
+        assert_noop!(
+            Did::add_verification_method(
+                Origin::signed(ALICE),
+                DID_ALICE,
+                b"key-16".to_vec(),
+                PublicKey::Ed25519([16; 32]),
+                vec![VerificationRelationship::Authentication]
+            ),
+            Error::<Test>::TooManyDocumentItems
+        );
+    });
+}
+
+#[test]
+fn should_manage_document_by_controller() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Did::register(Origin::signed(BOB), None));
+        let did_bob = Did::did_of(BOB).unwrap();
+
+        assert_noop!(
+            Did::add_service(
+                Origin::signed(BOB),
+                DID_ALICE,
+                b"linked-domain".to_vec(),
+                b"LinkedDomains".to_vec(),
+                b"https://parami.io".to_vec()
+            ),
+            Error::<Test>::NotController
+        );
+
+        assert_noop!(
+            Did::add_controller(Origin::signed(ALICE), DID_ALICE, DID_BOB),
+            Error::<Test>::DidNotExists
+        );
+
+        assert_ok!(Did::add_controller(
+            Origin::signed(ALICE),
+            DID_ALICE,
+            did_bob
+        ));
+
+        assert_ok!(Did::add_service(
+            Origin::signed(BOB),
+            DID_ALICE,
+            b"linked-domain".to_vec(),
+            b"LinkedDomains".to_vec(),
+            b"https://parami.io".to_vec()
+        ));
+
+        let document = Did::resolve(DID_ALICE).unwrap();
+        assert_eq!(document.controllers, vec![did_bob]);
+        assert_eq!(document.services.len(), 1);
+        assert_eq!(document.services[0].id, b"linked-domain".to_vec());
+        assert_eq!(document.services[0].service_type, b"LinkedDomains".to_vec());
+        assert_eq!(document.services[0].endpoint, b"https://parami.io".to_vec());
+
+        assert_ok!(Did::remove_controller(
+            Origin::signed(ALICE),
+            DID_ALICE,
+            did_bob
+        ));
+
+        assert_noop!(
+            Did::remove_service(Origin::signed(BOB), DID_ALICE, b"linked-domain".to_vec()),
+            Error::<Test>::NotController
+        );
+
+        assert_ok!(Did::remove_service(
+            Origin::signed(ALICE),
+            DID_ALICE,
+            b"linked-domain".to_vec()
+        ));
+        assert_eq!(<DocumentOf<Test>>::get(DID_ALICE).services.len(), 0);
+    });
+}
+
+#[test]
+fn should_resolve_revoked_did() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Did::add_verification_method(
+            Origin::signed(ALICE),
+            DID_ALICE,
+            b"key-1".to_vec(),
+            PublicKey::Sr25519([1; 32]),
+            vec![VerificationRelationship::Authentication]
+        ));
+
+        assert_ok!(Did::revoke(Origin::signed(ALICE)));
+
+        let document = Did::resolve(DID_ALICE).unwrap();
+        assert!(document.deactivated);
+        assert_eq!(document.verification_methods, vec![]);
+
+        assert_noop!(Did::resolve(DID_BOB), Error::<Test>::DidNotExists);
+    });
+}
+
+const CHARLIE: sr25519::Public = sr25519::Public([3; 32]);
+const DAVE: sr25519::Public = sr25519::Public([4; 32]);
+
+fn setup_guardians() -> (H160, H160) {
+    assert_ok!(Did::register(Origin::signed(BOB), None));
+    assert_ok!(Did::register(Origin::signed(CHARLIE), None));
+
+    (Did::did_of(BOB).unwrap(), Did::did_of(CHARLIE).unwrap())
+}
+
+#[test]
+fn should_set_recovery() {
+    new_test_ext().execute_with(|| {
+        let (did_bob, did_charlie) = setup_guardians();
+
+        for (guardians, threshold, delay) in [
+            (vec![did_bob, did_charlie], 0, 5),
+            (vec![did_bob, did_bob], 2, 5),
+            (vec![did_bob, did_charlie], 2, 0),
+            (vec![did_bob, DID_ALICE], 1, 5),
+        ] {
+            assert_noop!(
+                Did::set_recovery(Origin::signed(ALICE), guardians, threshold, delay),
+                Error::<Test>::BadRecoveryConfig
+            );
+        }
+
+        assert_noop!(
+            Did::set_recovery(Origin::signed(ALICE), vec![did_bob, DID_BOB], 1, 5),
+            Error::<Test>::DidNotExists
+        );
+
+        assert_ok!(Did::set_recovery(
+            Origin::signed(ALICE),
+            vec![did_charlie, did_bob, did_bob],
+            2,
+            5
+        ));
+
+        let config = <RecoveryConfigOf<Test>>::get(DID_ALICE).unwrap();
+        assert_eq!(config.guardians.len(), 2);
+        assert_eq!(config.threshold, 2);
+        assert_eq!(config.delay, 5);
+
+        assert_ok!(Did::remove_recovery(Origin::signed(ALICE)));
+        assert_eq!(<RecoveryConfigOf<Test>>::get(DID_ALICE), None);
+    });
+}
+
+#[test]
+fn should_recover_did() {
+    new_test_ext().execute_with(|| {
+        let (did_bob, did_charlie) = setup_guardians();
+
+        assert_ok!(Did::set_recovery(
+            Origin::signed(ALICE),
+            vec![did_bob, did_charlie],
+            2,
+            5
+        ));
+
+        assert_noop!(
+            Did::vouch_recovery(Origin::signed(ALICE), DID_ALICE, DAVE),
+            Error::<Test>::NotGuardian
+        );
+
+        assert_ok!(Did::vouch_recovery(Origin::signed(BOB), DID_ALICE, DAVE));
+
+        assert_noop!(
+            Did::vouch_recovery(Origin::signed(BOB), DID_ALICE, DAVE),
+            Error::<Test>::AlreadyVouched
+        );
+        assert_noop!(
+            Did::vouch_recovery(Origin::signed(CHARLIE), DID_ALICE, BOB),
+            Error::<Test>::DidExists
+        );
+        assert_noop!(
+            Did::claim_recovery(Origin::signed(DAVE), DID_ALICE, DAVE),
+            Error::<Test>::RecoveryNotReady
+        );
+
+        assert_ok!(Did::vouch_recovery(
+            Origin::signed(CHARLIE),
+            DID_ALICE,
+            DAVE
+        ));
+
+        let recovery = <ActiveRecoveryOf<Test>>::get(DID_ALICE, DAVE).unwrap();
+        assert_eq!(recovery.executable_at, Some(5));
+        assert_eq!(recovery.expires_at, 50);
+
+        System::set_block_number(4);
+
+        assert_noop!(
+            Did::claim_recovery(Origin::signed(DAVE), DID_ALICE, DAVE),
+            Error::<Test>::RecoveryNotReady
+        );
+
+        System::set_block_number(5);
+
+        assert_ok!(Did::claim_recovery(Origin::signed(DAVE), DID_ALICE, DAVE));
+
+        assert_eq!(Did::did_of(DAVE), Some(DID_ALICE));
+        assert_eq!(Did::did_of(ALICE), None);
+        assert_eq!(<Metadata<Test>>::get(DID_ALICE).unwrap().account, DAVE);
+        assert_eq!(Balances::total_balance(&DAVE), 100);
+        assert_eq!(<ActiveRecoveryOf<Test>>::iter_prefix(DID_ALICE).count(), 0);
+    });
+}
+
+#[test]
+fn should_recover_did_with_locked_balance() {
+    new_test_ext().execute_with(|| {
+        let (did_bob, did_charlie) = setup_guardians();
+
+        assert_ok!(Did::set_recovery(
+            Origin::signed(ALICE),
+            vec![did_bob, did_charlie],
+            2,
+            5
+        ));
+
+        Balances::set_lock(*b"vesting ", &ALICE, 50, WithdrawReasons::all());
+
+        assert_ok!(Did::vouch_recovery(Origin::signed(BOB), DID_ALICE, DAVE));
+        assert_ok!(Did::vouch_recovery(
+            Origin::signed(CHARLIE),
+            DID_ALICE,
+            DAVE
+        ));
+
+        System::set_block_number(5);
+
+        assert_ok!(Did::claim_recovery(Origin::signed(DAVE), DID_ALICE, DAVE));
+
+        assert_eq!(Did::did_of(DAVE), Some(DID_ALICE));
+        assert_eq!(Did::did_of(ALICE), None);
+        assert_eq!(<Metadata<Test>>::get(DID_ALICE).unwrap().account, DAVE);
+        // the locked balance is left to the previous account
+        assert!(Balances::free_balance(&ALICE) >= 50);
+    });
+}
+
+#[test]
+fn should_not_block_recovery_by_one_guardian() {
+    new_test_ext().execute_with(|| {
+        let (did_bob, did_charlie) = setup_guardians();
+
+        assert_ok!(Did::register(Origin::signed(DAVE), None));
+        let did_dave = Did::did_of(DAVE).unwrap();
+
+        let eve = sr25519::Public([5; 32]);
+        let ferdie = sr25519::Public([6; 32]);
+
+        assert_ok!(Did::set_recovery(
+            Origin::signed(ALICE),
+            vec![did_bob, did_charlie, did_dave],
+            2,
+            5
+        ));
+
+        // a malicious guardian vouches first
+        assert_ok!(Did::vouch_recovery(Origin::signed(BOB), DID_ALICE, ferdie));
+
+        assert_ok!(Did::vouch_recovery(Origin::signed(CHARLIE), DID_ALICE, eve));
+        assert_ok!(Did::vouch_recovery(Origin::signed(DAVE), DID_ALICE, eve));
+
+        assert_eq!(
+            <ActiveRecoveryOf<Test>>::get(DID_ALICE, eve)
+                .unwrap()
+                .executable_at,
+            Some(5)
+        );
+
+        // a guardian changing its mind moves its vouch
+        assert_ok!(Did::vouch_recovery(Origin::signed(DAVE), DID_ALICE, ferdie));
+
+        assert_eq!(
+            <ActiveRecoveryOf<Test>>::get(DID_ALICE, eve)
+                .unwrap()
+                .executable_at,
+            None
+        );
+        assert_eq!(
+            <ActiveRecoveryOf<Test>>::get(DID_ALICE, ferdie)
+                .unwrap()
+                .executable_at,
+            Some(5)
+        );
+
+        assert_ok!(Did::vouch_recovery(Origin::signed(BOB), DID_ALICE, eve));
+        assert_eq!(
+            <ActiveRecoveryOf<Test>>::get(DID_ALICE, ferdie)
+                .unwrap()
+                .vouchers
+                .len(),
+            1
+        );
+
+        System::set_block_number(5);
+
+        assert_noop!(
+            Did::claim_recovery(Origin::signed(eve), DID_ALICE, ferdie),
+            Error::<Test>::RecoveryNotReady
+        );
+
+        assert_ok!(Did::claim_recovery(Origin::signed(eve), DID_ALICE, eve));
+
+        assert_eq!(Did::did_of(eve), Some(DID_ALICE));
+        assert_eq!(<ActiveRecoveryOf<Test>>::iter_prefix(DID_ALICE).count(), 0);
+    });
+}
+
+#[test]
+fn should_expire_recovery() {
+    new_test_ext().execute_with(|| {
+        let (did_bob, did_charlie) = setup_guardians();
+
+        assert_ok!(Did::set_recovery(
+            Origin::signed(ALICE),
+            vec![did_bob, did_charlie],
+            2,
+            5
+        ));
+
+        assert_ok!(Did::vouch_recovery(Origin::signed(BOB), DID_ALICE, DAVE));
+        assert_ok!(Did::vouch_recovery(
+            Origin::signed(CHARLIE),
+            DID_ALICE,
+            DAVE
+        ));
+
+        System::set_block_number(50);
+
+        assert_noop!(
+            Did::claim_recovery(Origin::signed(DAVE), DID_ALICE, DAVE),
+            Error::<Test>::RecoveryExpired
+        );
+
+        // vouching again starts a new recovery
+        assert_ok!(Did::vouch_recovery(Origin::signed(BOB), DID_ALICE, DAVE));
+
+        let recovery = <ActiveRecoveryOf<Test>>::get(DID_ALICE, DAVE).unwrap();
+        assert_eq!(recovery.vouchers.len(), 1);
+        assert_eq!(recovery.executable_at, None);
+        assert_eq!(recovery.expires_at, 100);
+    });
+}
+
+#[test]
+fn should_cancel_recovery() {
+    new_test_ext().execute_with(|| {
+        let (did_bob, did_charlie) = setup_guardians();
+
+        assert_ok!(Did::set_recovery(
+            Origin::signed(ALICE),
+            vec![did_bob, did_charlie],
+            1,
+            5
+        ));
+
+        assert_ok!(Did::vouch_recovery(Origin::signed(BOB), DID_ALICE, DAVE));
+
+        assert_ok!(Did::cancel_recovery(Origin::signed(ALICE)));
+        assert_eq!(<ActiveRecoveryOf<Test>>::get(DID_ALICE, DAVE), None);
+
+        System::set_block_number(5);
+
+        assert_noop!(
+            Did::claim_recovery(Origin::signed(DAVE), DID_ALICE, DAVE),
+            Error::<Test>::RecoveryNotExists
+        );
+        assert_noop!(
+            Did::cancel_recovery(Origin::signed(ALICE)),
+            Error::<Test>::RecoveryNotExists
+        );
+    });
+}
+
+#[test]
+fn should_preview_did() {
+    new_test_ext().execute_with(|| {
+        let (did, nonce) = Did::preview(&BOB).unwrap();
+        assert_eq!(nonce, 0);
+
+        System::set_block_number(5);
+
+        assert_ok!(Did::register(Origin::signed(BOB), None));
+        assert_eq!(Did::did_of(BOB), Some(did));
+        assert_eq!(<NonceOf<Test>>::get(BOB), 1);
+
+        assert_noop!(Did::preview(&BOB), Error::<Test>::DidExists);
+        assert_noop!(Did::preview(&ALICE), Error::<Test>::DidExists);
+    });
+}
+
+#[test]
+fn should_derive_new_did_after_revoke() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Did::register(Origin::signed(BOB), None));
+        let first = Did::did_of(BOB).unwrap();
+
+        assert_ok!(Did::revoke(Origin::signed(BOB)));
+
+        let (did, nonce) = Did::preview(&BOB).unwrap();
+        assert_ne!(did, first);
+        assert_eq!(nonce, 1);
+
+        assert_ok!(Did::register(Origin::signed(BOB), None));
+        assert_eq!(Did::did_of(BOB), Some(did));
+        assert_eq!(<NonceOf<Test>>::get(BOB), 2);
+    });
+}
+
+#[test]
+fn should_skip_taken_did() {
+    new_test_ext().execute_with(|| {
+        let (taken, _) = Did::preview(&BOB).unwrap();
+
+        Did::set_meta(
+            &taken,
+            types::Metadata {
+                account: ALICE,
+                revoked: true,
+                created: 0,
+            },
+        );
+
+        let (did, nonce) = Did::preview(&BOB).unwrap();
+        assert_ne!(did, taken);
+        assert_eq!(nonce, 1);
+
+        assert_ok!(Did::register(Origin::signed(BOB), None));
+        assert_eq!(Did::did_of(BOB), Some(did));
+        assert_eq!(<NonceOf<Test>>::get(BOB), 2);
+    });
+}
+
+#[test]
+fn should_ensure_session_key() {
+    new_test_ext().execute_with(|| {
+        use crate::{AdClaimScope, ClockInScope, SessionScope};
+        use frame_support::traits::EnsureOrigin;
+
+        System::set_block_number(1);
+
+        assert_ok!(Did::add_session_key(
+            Origin::signed(ALICE),
+            BOB,
+            SessionScope::AdClaim,
+            10
+        ));
+
+        assert_eq!(Did::session_keys_of(&DID_ALICE), vec![BOB]);
+
+        // resolves to the DID and its account within the scope
+
+        let ensure = EnsureDid::<Test, AdClaimScope>::try_origin(Origin::signed(BOB));
+        assert_eq!(ensure.unwrap(), (DID_ALICE, ALICE));
+
+        assert!(EnsureDid::<Test>::try_origin(Origin::signed(BOB)).is_err());
+        assert!(EnsureDid::<Test, ClockInScope>::try_origin(Origin::signed(BOB)).is_err());
+
+        // session keys can not sign unscoped calls
+
+        assert_noop!(
+            Did::set_metadata(Origin::signed(BOB), b"name".to_vec(), b"bob".to_vec()),
+            DispatchError::BadOrigin
+        );
+
+        // expired
+
+        System::set_block_number(10);
+
+        assert!(EnsureDid::<Test, AdClaimScope>::try_origin(Origin::signed(BOB)).is_err());
+    });
+}
+
+#[test]
+fn should_fail_to_add_session_key() {
+    new_test_ext().execute_with(|| {
+        use crate::SessionScope;
+
+        System::set_block_number(5);
+
+        assert_noop!(
+            Did::add_session_key(Origin::signed(ALICE), BOB, SessionScope::ClockIn, 5),
+            Error::<Test>::SessionKeyExpired
+        );
+
+        assert_noop!(
+            Did::add_session_key(Origin::signed(ALICE), ALICE, SessionScope::ClockIn, 10),
+            Error::<Test>::DidExists
+        );
+
+        assert_ok!(Did::add_session_key(
+            Origin::signed(ALICE),
+            BOB,
+            SessionScope::ClockIn,
+            10
+        ));
+
+        assert_noop!(
+            Did::add_session_key(Origin::signed(ALICE), BOB, SessionScope::Linker, 10),
+            Error::<Test>::SessionKeyExists
+        );
+
+        // a session key can not register a DID
+
+        assert_noop!(
+            Did::register(Origin::signed(BOB), None),
+            Error::<Test>::SessionKeyExists
+        );
+
+        for i in 0..7u8 {
+            assert_ok!(Did::add_session_key(
+                Origin::signed(ALICE),
+                sr25519::Public([0x10 + i; 32]),
+                SessionScope::ClockIn,
+                10
+            ));
+        }
+
+        assert_noop!(
+            Did::add_session_key(Origin::signed(ALICE), CHARLIE, SessionScope::ClockIn, 10),
+            Error::<Test>::TooManySessionKeys
+        );
+    });
+}
+
+#[test]
+fn should_remove_session_key() {
+    new_test_ext().execute_with(|| {
+        use crate::{SessionKeyOf, SessionScope};
+
+        assert_ok!(Did::add_session_key(
+            Origin::signed(ALICE),
+            BOB,
+            SessionScope::Linker,
+            10
+        ));
+        assert_ok!(Did::add_session_key(
+            Origin::signed(ALICE),
+            CHARLIE,
+            SessionScope::Linker,
+            10
+        ));
+
+        assert_ok!(Did::remove_session_key(Origin::signed(ALICE), BOB));
+
+        assert_eq!(<SessionKeyOf<Test>>::get(&BOB), None);
+        assert_eq!(Did::session_keys_of(&DID_ALICE), vec![CHARLIE]);
+
+        assert_noop!(
+            Did::remove_session_key(Origin::signed(ALICE), BOB),
+            Error::<Test>::SessionKeyNotExists
+        );
+
+        // revoking the DID removes all its session keys
+
+        assert_ok!(Did::revoke(Origin::signed(ALICE)));
+
+        assert_eq!(<SessionKeyOf<Test>>::get(&CHARLIE), None);
+        assert!(Did::session_keys_of(&DID_ALICE).is_empty());
+    });
+}
+
+#[test]
+fn should_clear_session_keys_when_transferred() {
+    new_test_ext().execute_with(|| {
+        use crate::{SessionKeyOf, SessionScope};
+
+        assert_ok!(Did::add_session_key(
+            Origin::signed(ALICE),
+            CHARLIE,
+            SessionScope::Linker,
+            10
+        ));
+
+        assert_ok!(Did::transfer(Origin::signed(ALICE), DAVE));
+
+        assert_eq!(<SessionKeyOf<Test>>::get(&CHARLIE), None);
+        assert!(Did::session_keys_of(&DID_ALICE).is_empty());
+        assert_eq!(Did::lookup_session(&CHARLIE, SessionScope::Linker), None);
+
+        assert_ok!(Did::add_session_key(
+            Origin::signed(DAVE),
+            CHARLIE,
+            SessionScope::Linker,
+            10
+        ));
+
+        assert_ok!(Did::force_transfer_with_assets(
+            Origin::root(),
+            DID_ALICE,
+            ALICE
+        ));
+
+        assert_eq!(<SessionKeyOf<Test>>::get(&CHARLIE), None);
+        assert!(Did::session_keys_of(&DID_ALICE).is_empty());
+    });
+}
+
+#[test]
+fn should_clear_session_keys_when_recovered() {
+    new_test_ext().execute_with(|| {
+        use crate::{SessionKeyOf, SessionScope};
+
+        let (did_bob, _) = setup_guardians();
+
+        assert_ok!(Did::set_recovery(
+            Origin::signed(ALICE),
+            vec![did_bob],
+            1,
+            5
+        ));
+
+        let eve = sr25519::Public([5; 32]);
+        assert_ok!(Did::add_session_key(
+            Origin::signed(ALICE),
+            eve,
+            SessionScope::Linker,
+            10
+        ));
+
+        assert_ok!(Did::vouch_recovery(Origin::signed(BOB), DID_ALICE, DAVE));
+
+        System::set_block_number(5);
+
+        assert_ok!(Did::claim_recovery(Origin::signed(DAVE), DID_ALICE, DAVE));
+
+        assert_eq!(<SessionKeyOf<Test>>::get(&eve), None);
+        assert!(Did::session_keys_of(&DID_ALICE).is_empty());
+    });
+}
+
+#[test]
+fn should_fail_to_transfer_to_session_key() {
+    new_test_ext().execute_with(|| {
+        use crate::SessionScope;
+
+        assert_ok!(Did::add_session_key(
+            Origin::signed(ALICE),
+            DAVE,
+            SessionScope::Linker,
+            10
+        ));
+
+        assert_noop!(
+            Did::transfer(Origin::signed(ALICE), DAVE),
+            Error::<Test>::SessionKeyExists
+        );
+        assert_noop!(
+            Did::force_transfer_with_assets(Origin::root(), DID_ALICE, DAVE),
+            Error::<Test>::SessionKeyExists
+        );
+    });
+}
+
+#[test]
+fn should_register_handle() {
+    new_test_ext().execute_with(|| {
+        use crate::{HandleOf, Handles};
+        use sp_runtime::{traits::StaticLookup, MultiAddress};
+
+        assert_ok!(Did::register_handle(
+            Origin::signed(ALICE),
+            b"alice.ad3".to_vec()
+        ));
+
+        assert_eq!(Balances::free_balance(&ALICE), 100 - 10);
+
+        let handle = <Handles<Test>>::get(&BoundedVec::try_from(b"alice".to_vec()).unwrap());
+        assert_eq!(
+            handle,
+            Some(types::Handle {
+                did: DID_ALICE,
+                expires_at: 100
+            })
+        );
+        assert_eq!(
+            <HandleOf<Test>>::get(&DID_ALICE).unwrap().into_inner(),
+            b"alice"
+        );
+
+        assert_eq!(Did::lookup_handle(b"alice"), Some(DID_ALICE));
+        assert_eq!(Did::lookup_handle(b"alice.ad3"), Some(DID_ALICE));
+        assert_eq!(
+            Did::reverse_lookup_handle(&DID_ALICE),
+            Some(b"alice.ad3".to_vec())
+        );
+        assert_eq!(
+            Did::lookup(MultiAddress::Raw(b"alice.ad3".to_vec())),
+            Ok(ALICE)
+        );
+
+        // one handle per DID
+
+        assert_noop!(
+            Did::register_handle(Origin::signed(ALICE), b"alice2".to_vec()),
+            Error::<Test>::HandleExists
+        );
+
+        // handles are unique
+
+        assert_ok!(Did::register(Origin::signed(BOB), None));
+
+        assert_noop!(
+            Did::register_handle(Origin::signed(BOB), b"alice".to_vec()),
+            Error::<Test>::HandleTaken
+        );
+
+        for handle in [&b"al"[..], b"Alice", b"-alice", b"alice.eth", &[b'a'; 33]] {
+            assert_noop!(
+                Did::register_handle(Origin::signed(BOB), handle.to_vec()),
+                Error::<Test>::InvalidHandle
+            );
+        }
+
+        assert_ok!(Did::release_handle(Origin::signed(ALICE)));
+
+        assert_eq!(Did::lookup_handle(b"alice"), None);
+        assert_eq!(Did::reverse_lookup_handle(&DID_ALICE), None);
+
+        assert_ok!(Did::register_handle(Origin::signed(BOB), b"alice".to_vec()));
+    });
+}
+
+#[test]
+fn should_renew_handle() {
+    new_test_ext().execute_with(|| {
+        use crate::HandleOf;
+
+        assert_ok!(Did::register(Origin::signed(BOB), None));
+        let did_bob = <DidOf<Test>>::get(&BOB).unwrap();
+
+        System::set_block_number(50);
+
+        assert_ok!(Did::register_handle(
+            Origin::signed(ALICE),
+            b"alice".to_vec()
+        ));
+
+        assert_ok!(Did::renew_handle(Origin::signed(ALICE)));
+
+        assert_eq!(Balances::free_balance(&ALICE), 100 - 10 * 2);
+        assert_eq!(
+            Did::handle(&Did::handle_of(&DID_ALICE).unwrap())
+                .unwrap()
+                .expires_at,
+            250
+        );
+
+        // expired handles are not resolved, and can be registered by others
+
+        System::set_block_number(250);
+
+        assert_eq!(Did::lookup_handle(b"alice"), None);
+
+        assert_ok!(Did::register_handle(Origin::signed(BOB), b"alice".to_vec()));
+
+        assert_eq!(Did::lookup_handle(b"alice"), Some(did_bob));
+        assert_eq!(<HandleOf<Test>>::get(&DID_ALICE), None);
+
+        assert_noop!(
+            Did::renew_handle(Origin::signed(ALICE)),
+            Error::<Test>::HandleNotExists
+        );
+    });
+}
+
+#[test]
+fn should_reserve_handle() {
+    new_test_ext().execute_with(|| {
+        use parami_traits::types::Network;
+
+        assert_ok!(Did::register(Origin::signed(BOB), None));
+
+        assert_noop!(
+            Did::force_reserve_handle(
+                Origin::signed(ALICE),
+                b"alice".to_vec(),
+                Network::Twitter,
+                PROFILE_ALICE.to_vec()
+            ),
+            DispatchError::BadOrigin
+        );
+
+        assert_ok!(Did::force_reserve_handle(
+            Origin::root(),
+            b"alice".to_vec(),
+            Network::Twitter,
+            PROFILE_ALICE.to_vec()
+        ));
+
+        assert_noop!(
+            Did::register_handle(Origin::signed(BOB), b"alice".to_vec()),
+            Error::<Test>::HandleReserved
+        );
+
+        assert_ok!(Did::register_handle(
+            Origin::signed(ALICE),
+            b"alice".to_vec()
+        ));
+
+        assert_ok!(Did::force_unreserve_handle(
+            Origin::root(),
+            b"alice".to_vec()
+        ));
+
+        assert_eq!(
+            Did::reserved_handle(&BoundedVec::try_from(b"alice".to_vec()).unwrap()),
+            None
+        );
+
+        assert_noop!(
+            Did::force_unreserve_handle(Origin::root(), b"alice".to_vec()),
+            Error::<Test>::HandleNotExists
+        );
+    });
+}
+
+#[test]
+fn should_index_invitees() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Did::register(Origin::signed(BOB), Some(DID_ALICE)));
+        let did_bob = Did::did_of(BOB).unwrap();
+
+        assert_ok!(Did::register(Origin::signed(CHARLIE), Some(did_bob)));
+        assert_ok!(Did::register(Origin::signed(DAVE), Some(did_bob)));
+        let did_charlie = Did::did_of(CHARLIE).unwrap();
+        let did_dave = Did::did_of(DAVE).unwrap();
+
+        assert_eq!(Did::referral_counts_of(&DID_ALICE), [1, 2, 0]);
+        assert_eq!(Did::referral_counts_of(&did_bob), [2, 0, 0]);
+        assert_eq!(Did::referral_counts_of(&did_charlie), [0, 0, 0]);
+
+        assert_eq!(Did::invitees(&DID_ALICE, 0, 10), vec![did_bob]);
+
+        let mut invitees = Did::invitees(&did_bob, 0, 10);
+        invitees.sort();
+        let mut expected = vec![did_charlie, did_dave];
+        expected.sort();
+        assert_eq!(invitees, expected);
+
+        assert_eq!(Did::invitees(&did_bob, 1, 10).len(), 1);
+        assert_eq!(Did::invitees(&did_bob, 0, 1).len(), 1);
+        assert_eq!(Did::invitees(&did_bob, 2, 10), vec![]);
+    });
+}
+
+#[test]
+fn should_pay_referral_bonus() {
+    new_test_ext().execute_with(|| {
+        use crate::Milestone;
+
+        assert_ok!(Did::register(Origin::signed(BOB), Some(DID_ALICE)));
+        let did_bob = Did::did_of(BOB).unwrap();
+
+        System::set_block_number(10);
+
+        Did::reach_milestone(&did_bob, Milestone::AdClaimed);
+
+        assert_eq!(Balances::free_balance(&ALICE), 100 + 5);
+
+        // bonus is paid only once per milestone
+
+        Did::reach_milestone(&did_bob, Milestone::AdClaimed);
+
+        assert_eq!(Balances::free_balance(&ALICE), 100 + 5);
+
+        Did::reach_milestone(&did_bob, Milestone::Linked);
+
+        assert_eq!(Balances::free_balance(&ALICE), 100 + 5 * 2);
+
+        // no referrer, no bonus
+
+        Did::reach_milestone(&DID_ALICE, Milestone::Linked);
+
+        assert_eq!(Balances::free_balance(&BOB), 100);
+    });
+}
+
+#[test]
+fn should_not_pay_referral_bonus_for_young_invitees() {
+    new_test_ext().execute_with(|| {
+        use crate::Milestone;
+
+        // register, reach a milestone and revoke in a loop
+
+        for _ in 0..3 {
+            assert_ok!(Did::register(Origin::signed(BOB), Some(DID_ALICE)));
+            let did_bob = Did::did_of(BOB).unwrap();
+
+            Did::reach_milestone(&did_bob, Milestone::Linked);
+            Did::reach_milestone(&did_bob, Milestone::AdClaimed);
+
+            assert_ok!(Did::revoke(Origin::signed(BOB)));
+        }
+
+        assert_eq!(Balances::free_balance(&ALICE), 100);
+        assert_eq!(
+            Balances::free_balance(&TreasuryPalletId::get().into_account_truncating()),
+            100
+        );
+    });
+}
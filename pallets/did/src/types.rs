@@ -1,4 +1,6 @@
 use codec::{Decode, Encode, MaxEncodedLen};
+use parami_primitives::did::PublicKey;
+use parami_traits::types::Network;
 use scale_info::TypeInfo;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
@@ -12,3 +14,92 @@ pub struct Metadata<A, N> {
     pub revoked: bool,
     pub created: N,
 }
+
+#[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct VerificationMethod<Fragment, Purposes> {
+    pub id: Fragment,
+    pub public_key: PublicKey,
+    pub purposes: Purposes,
+}
+
+#[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Service<Fragment, Type, Endpoint> {
+    pub id: Fragment,
+    pub service_type: Type,
+    pub endpoint: Endpoint,
+}
+
+#[derive(Clone, Decode, Default, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Document<Controllers, Methods, Services> {
+    pub controllers: Controllers,
+    pub verification_methods: Methods,
+    pub services: Services,
+}
+
+#[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct RecoveryConfig<Guardians, N> {
+    pub guardians: Guardians,
+    /// Vouches required to recover
+    pub threshold: u32,
+    /// Blocks to wait after the threshold is reached, the owner can cancel during this period
+    pub delay: N,
+}
+
+#[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ActiveRecovery<Guardians, N> {
+    pub vouchers: Guardians,
+    pub executable_at: Option<N>,
+    /// The recovery can not be vouched or claimed since this height
+    pub expires_at: N,
+}
+
+#[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct MetadataEntry<Value, Balance> {
+    pub value: Value,
+    pub deposit: Balance,
+}
+
+/// Calls a session key of a DID is allowed to sign
+#[derive(Clone, Copy, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum SessionScope {
+    /// Ad claims
+    AdClaim,
+    /// Clock-in of NFTs
+    ClockIn,
+    /// Linking accounts and socialities
+    Linker,
+}
+
+#[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Session<D, N> {
+    pub did: D,
+    pub scope: SessionScope,
+    /// The session key can not be used since this height
+    pub expires_at: N,
+}
+
+#[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Handle<D, N> {
+    pub did: D,
+    /// The handle can be registered by others since this height
+    pub expires_at: N,
+}
+
+/// A handle reserved for the owner of a linked sociality
+#[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ReservedHandle<Profile> {
+    pub network: Network,
+    pub profile: Profile,
+}
+
+/// Milestones of an invitee, referrers may be rewarded when they are reached
+#[derive(Clone, Copy, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum Milestone {
+    /// The first claim of an advertisement
+    AdClaimed,
+    /// The first link of an account or sociality verified by a registrar or the offchain worker,
+    /// links proven by the DID itself do not count
+    Linked,
+}
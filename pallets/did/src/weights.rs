@@ -20,6 +20,9 @@
 //! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
 //! DATE: 2022-08-15, STEPS: `2`, REPEAT: 50, LOW RANGE: `[]`, HIGH RANGE: `[]`
 //! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
+//!
+//! The weights following `set_metadata` are estimated from the benchmarked weights
+//! with similar storage access until this file is regenerated.
 
 // Executed Command:
 // ./target/release/parami
@@ -48,6 +51,24 @@ pub trait WeightInfo {
     fn transfer() -> Weight;
     fn revoke() -> Weight;
     fn set_metadata(k: u32, v: u32, ) -> Weight;
+    fn add_session_key() -> Weight;
+    fn remove_session_key() -> Weight;
+    fn register_handle() -> Weight;
+    fn renew_handle() -> Weight;
+    fn release_handle() -> Weight;
+    fn force_reserve_handle() -> Weight;
+    fn force_unreserve_handle() -> Weight;
+    fn add_verification_method() -> Weight;
+    fn remove_verification_method() -> Weight;
+    fn add_service() -> Weight;
+    fn remove_service() -> Weight;
+    fn add_controller() -> Weight;
+    fn remove_controller() -> Weight;
+    fn set_recovery(g: u32, ) -> Weight;
+    fn remove_recovery() -> Weight;
+    fn vouch_recovery() -> Weight;
+    fn cancel_recovery() -> Weight;
+    fn claim_recovery() -> Weight;
 }
 
 /// Weights for parami_did using the Substrate node and recommended hardware.
@@ -84,6 +105,157 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add((1_000 as Weight).saturating_mul(v as Weight))
             .saturating_add(T::DbWeight::get().reads(1 as Weight))
     }
+    // Storage: Did DidOf (r:2 w:0)
+    // Storage: Did SessionKeyOf (r:1 w:1)
+    // Storage: Did SessionKeysOf (r:1 w:1)
+    fn add_session_key() -> Weight {
+        (19_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Did SessionKeyOf (r:1 w:1)
+    // Storage: Did SessionKeysOf (r:1 w:1)
+    fn remove_session_key() -> Weight {
+        (17_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Did HandleOf (r:1 w:2)
+    // Storage: Did Handles (r:1 w:1)
+    // Storage: Did ReservedHandles (r:1 w:0)
+    // Storage: Linker LinksOf (r:1 w:0)
+    // Storage: System Account (r:1 w:1)
+    fn register_handle() -> Weight {
+        (35_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Did HandleOf (r:1 w:0)
+    // Storage: Did Handles (r:1 w:1)
+    // Storage: System Account (r:1 w:1)
+    fn renew_handle() -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Did HandleOf (r:1 w:1)
+    // Storage: Did Handles (r:0 w:1)
+    fn release_handle() -> Weight {
+        (15_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    // Storage: Did ReservedHandles (r:0 w:1)
+    fn force_reserve_handle() -> Weight {
+        (10_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Did ReservedHandles (r:1 w:1)
+    fn force_unreserve_handle() -> Weight {
+        (12_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Did Metadata (r:1 w:0)
+    // Storage: Did DocumentOf (r:1 w:1)
+    fn add_verification_method() -> Weight {
+        (25_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Did Metadata (r:1 w:0)
+    // Storage: Did DocumentOf (r:1 w:1)
+    fn remove_verification_method() -> Weight {
+        (24_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Did Metadata (r:1 w:0)
+    // Storage: Did DocumentOf (r:1 w:1)
+    fn add_service() -> Weight {
+        (26_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Did Metadata (r:1 w:0)
+    // Storage: Did DocumentOf (r:1 w:1)
+    fn remove_service() -> Weight {
+        (24_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Did Metadata (r:2 w:0)
+    // Storage: Did DocumentOf (r:1 w:1)
+    fn add_controller() -> Weight {
+        (25_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Did Metadata (r:1 w:0)
+    // Storage: Did DocumentOf (r:1 w:1)
+    fn remove_controller() -> Weight {
+        (24_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Did ActiveRecoveryOf (r:1 w:0)
+    // Storage: Did Metadata (r:1 w:0)
+    // Storage: Did RecoveryConfigOf (r:0 w:1)
+    fn set_recovery(g: u32, ) -> Weight {
+        (20_000_000 as Weight)
+            // Standard Error: 0
+            .saturating_add((500_000 as Weight).saturating_mul(g as Weight))
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(g as Weight)))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Did RecoveryConfigOf (r:1 w:1)
+    // Storage: Did ActiveRecoveryOf (r:0 w:8)
+    fn remove_recovery() -> Weight {
+        (22_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(9 as Weight))
+    }
+    // Storage: Did DidOf (r:2 w:0)
+    // Storage: Did SessionKeyOf (r:1 w:0)
+    // Storage: Did RecoveryConfigOf (r:1 w:0)
+    // Storage: Did ActiveRecoveryOf (r:9 w:9)
+    fn vouch_recovery() -> Weight {
+        (45_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(13 as Weight))
+            .saturating_add(T::DbWeight::get().writes(9 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Did ActiveRecoveryOf (r:1 w:8)
+    fn cancel_recovery() -> Weight {
+        (21_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(8 as Weight))
+    }
+    // Storage: Did ActiveRecoveryOf (r:1 w:8)
+    // Storage: Did DidOf (r:1 w:2)
+    // Storage: Did SessionKeyOf (r:1 w:8)
+    // Storage: Did Metadata (r:1 w:1)
+    // Storage: Did SessionKeysOf (r:1 w:1)
+    // Storage: Balances Reserves (r:2 w:2)
+    // Storage: System Account (r:2 w:2)
+    fn claim_recovery() -> Weight {
+        (60_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(9 as Weight))
+            .saturating_add(T::DbWeight::get().writes(24 as Weight))
+    }
 }
 
 // For backwards compatibility and tests
@@ -119,4 +291,155 @@ impl WeightInfo for () {
             .saturating_add((1_000 as Weight).saturating_mul(v as Weight))
             .saturating_add(RocksDbWeight::get().reads(1 as Weight))
     }
+    // Storage: Did DidOf (r:2 w:0)
+    // Storage: Did SessionKeyOf (r:1 w:1)
+    // Storage: Did SessionKeysOf (r:1 w:1)
+    fn add_session_key() -> Weight {
+        (19_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Did SessionKeyOf (r:1 w:1)
+    // Storage: Did SessionKeysOf (r:1 w:1)
+    fn remove_session_key() -> Weight {
+        (17_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Did HandleOf (r:1 w:2)
+    // Storage: Did Handles (r:1 w:1)
+    // Storage: Did ReservedHandles (r:1 w:0)
+    // Storage: Linker LinksOf (r:1 w:0)
+    // Storage: System Account (r:1 w:1)
+    fn register_handle() -> Weight {
+        (35_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Did HandleOf (r:1 w:0)
+    // Storage: Did Handles (r:1 w:1)
+    // Storage: System Account (r:1 w:1)
+    fn renew_handle() -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Did HandleOf (r:1 w:1)
+    // Storage: Did Handles (r:0 w:1)
+    fn release_handle() -> Weight {
+        (15_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    // Storage: Did ReservedHandles (r:0 w:1)
+    fn force_reserve_handle() -> Weight {
+        (10_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Did ReservedHandles (r:1 w:1)
+    fn force_unreserve_handle() -> Weight {
+        (12_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Did Metadata (r:1 w:0)
+    // Storage: Did DocumentOf (r:1 w:1)
+    fn add_verification_method() -> Weight {
+        (25_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Did Metadata (r:1 w:0)
+    // Storage: Did DocumentOf (r:1 w:1)
+    fn remove_verification_method() -> Weight {
+        (24_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Did Metadata (r:1 w:0)
+    // Storage: Did DocumentOf (r:1 w:1)
+    fn add_service() -> Weight {
+        (26_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Did Metadata (r:1 w:0)
+    // Storage: Did DocumentOf (r:1 w:1)
+    fn remove_service() -> Weight {
+        (24_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Did Metadata (r:2 w:0)
+    // Storage: Did DocumentOf (r:1 w:1)
+    fn add_controller() -> Weight {
+        (25_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Did Metadata (r:1 w:0)
+    // Storage: Did DocumentOf (r:1 w:1)
+    fn remove_controller() -> Weight {
+        (24_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Did ActiveRecoveryOf (r:1 w:0)
+    // Storage: Did Metadata (r:1 w:0)
+    // Storage: Did RecoveryConfigOf (r:0 w:1)
+    fn set_recovery(g: u32, ) -> Weight {
+        (20_000_000 as Weight)
+            // Standard Error: 0
+            .saturating_add((500_000 as Weight).saturating_mul(g as Weight))
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(g as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Did RecoveryConfigOf (r:1 w:1)
+    // Storage: Did ActiveRecoveryOf (r:0 w:8)
+    fn remove_recovery() -> Weight {
+        (22_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(9 as Weight))
+    }
+    // Storage: Did DidOf (r:2 w:0)
+    // Storage: Did SessionKeyOf (r:1 w:0)
+    // Storage: Did RecoveryConfigOf (r:1 w:0)
+    // Storage: Did ActiveRecoveryOf (r:9 w:9)
+    fn vouch_recovery() -> Weight {
+        (45_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(13 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(9 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Did ActiveRecoveryOf (r:1 w:8)
+    fn cancel_recovery() -> Weight {
+        (21_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(8 as Weight))
+    }
+    // Storage: Did ActiveRecoveryOf (r:1 w:8)
+    // Storage: Did DidOf (r:1 w:2)
+    // Storage: Did SessionKeyOf (r:1 w:8)
+    // Storage: Did Metadata (r:1 w:1)
+    // Storage: Did SessionKeysOf (r:1 w:1)
+    // Storage: Balances Reserves (r:2 w:2)
+    // Storage: System Account (r:2 w:2)
+    fn claim_recovery() -> Weight {
+        (60_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(9 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(24 as Weight))
+    }
 }
@@ -0,0 +1,158 @@
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as Governance;
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::traits::Hooks;
+use frame_system::RawOrigin as SystemOrigin;
+use parami_did::Pallet as Did;
+use parami_nft::Pallet as Nft;
+use sp_runtime::traits::Bounded;
+
+/// Registers a DID for `owner`, kicks and mints its NFT, returns the NFT and the fractions minted
+fn minted_nft<T: Config>(owner: &T::AccountId) -> (NftOf<T>, BalanceOf<T>) {
+    T::Currency::make_free_balance_be(owner, BalanceOf::<T>::max_value());
+
+    Did::<T>::register(SystemOrigin::Signed(owner.clone()).into(), None).unwrap();
+
+    Nft::<T>::kick(SystemOrigin::Signed(owner.clone()).into()).unwrap();
+
+    let did = Did::<T>::did_of(owner).unwrap();
+    let nft = Nft::<T>::preferred(&did).unwrap();
+
+    Nft::<T>::mint_nft_power(
+        SystemOrigin::Signed(owner.clone()).into(),
+        nft,
+        b"Test Token".to_vec(),
+        b"XTT".to_vec(),
+        1_000_000_000u32.into(),
+    )
+    .unwrap();
+
+    let meta = Nft::<T>::meta(nft).unwrap();
+
+    (nft, T::Assets::balance(meta.token_asset_id, owner))
+}
+
+fn remark<T: Config>() -> Box<<T as Config>::Proposal> {
+    Box::new(frame_system::Call::<T>::remark { remark: vec![] }.into())
+}
+
+fn did_of<T: Config>(i: u32) -> DidOf<T> {
+    let mut did = DidOf::<T>::default();
+    did.as_mut()[..4].copy_from_slice(&i.to_le_bytes());
+    did
+}
+
+/// Leave `MAX_CLEANUP` votes and snapshots of other holders on a proposal
+fn fill_votes<T: Config>(index: ProposalIndex) {
+    for i in 0..MAX_CLEANUP {
+        let did = did_of::<T>(i + 1);
+        <Votes<T>>::insert(index, &did, true);
+        <Snapshots<T>>::insert(index, &did, BalanceOf::<T>::max_value());
+    }
+}
+
+benchmarks! {
+    lock {
+        let n in 0 .. T::MaxProposals::get();
+
+        let caller: T::AccountId = whitelisted_caller();
+
+        let (nft, fractions) = minted_nft::<T>(&caller);
+        let value = fractions / 2u32.into();
+
+        Governance::<T>::lock(SystemOrigin::Signed(caller.clone()).into(), nft, value)?;
+        for _ in 0..n {
+            Governance::<T>::propose(SystemOrigin::Signed(caller.clone()).into(), nft, remark::<T>())?;
+        }
+
+        let did = Did::<T>::did_of(&caller).unwrap();
+    }: _(SystemOrigin::Signed(caller), nft, value)
+    verify {
+        assert_eq!(<LockedOf<T>>::get(nft, &did), value + value);
+    }
+
+    unlock {
+        let n in 0 .. T::MaxProposals::get();
+
+        let caller: T::AccountId = whitelisted_caller();
+
+        let (nft, fractions) = minted_nft::<T>(&caller);
+        let value = fractions / 2u32.into();
+
+        Governance::<T>::lock(SystemOrigin::Signed(caller.clone()).into(), nft, value + value)?;
+        for _ in 0..n {
+            Governance::<T>::propose(SystemOrigin::Signed(caller.clone()).into(), nft, remark::<T>())?;
+        }
+
+        let did = Did::<T>::did_of(&caller).unwrap();
+    }: _(SystemOrigin::Signed(caller), nft, value)
+    verify {
+        assert_eq!(<LockedOf<T>>::get(nft, &did), value);
+    }
+
+    propose {
+        let caller: T::AccountId = whitelisted_caller();
+
+        let (nft, fractions) = minted_nft::<T>(&caller);
+
+        Governance::<T>::lock(SystemOrigin::Signed(caller.clone()).into(), nft, fractions)?;
+        for _ in 1..T::MaxProposals::get() {
+            Governance::<T>::propose(SystemOrigin::Signed(caller.clone()).into(), nft, remark::<T>())?;
+        }
+
+        let index = <ProposalCount<T>>::get();
+    }: _(SystemOrigin::Signed(caller), nft, remark::<T>())
+    verify {
+        assert!(<Proposals<T>>::contains_key(index));
+    }
+
+    vote {
+        let caller: T::AccountId = whitelisted_caller();
+
+        let (nft, fractions) = minted_nft::<T>(&caller);
+
+        Governance::<T>::lock(SystemOrigin::Signed(caller.clone()).into(), nft, fractions)?;
+        Governance::<T>::propose(SystemOrigin::Signed(caller.clone()).into(), nft, remark::<T>())?;
+
+        let did = Did::<T>::did_of(&caller).unwrap();
+    }: _(SystemOrigin::Signed(caller), 0, true)
+    verify {
+        assert_eq!(<Votes<T>>::get(0, &did), Some(true));
+    }
+
+    close {
+        let caller: T::AccountId = whitelisted_caller();
+
+        let (nft, fractions) = minted_nft::<T>(&caller);
+
+        Governance::<T>::lock(SystemOrigin::Signed(caller.clone()).into(), nft, fractions)?;
+        Governance::<T>::propose(SystemOrigin::Signed(caller.clone()).into(), nft, remark::<T>())?;
+        Governance::<T>::vote(SystemOrigin::Signed(caller.clone()).into(), 0, true)?;
+
+        fill_votes::<T>(0);
+
+        let proposal = <Proposals<T>>::get(0).unwrap();
+        let weight = proposal.call.get_dispatch_info().weight;
+
+        frame_system::Pallet::<T>::set_block_number(proposal.end);
+    }: _(SystemOrigin::Signed(caller), 0, weight)
+    verify {
+        assert!(!<Proposals<T>>::contains_key(0));
+    }
+
+    clear_leftovers {
+        fill_votes::<T>(0);
+
+        <Leftovers<T>>::insert(0, ());
+    }: {
+        Governance::<T>::on_idle(Zero::zero(), Weight::max_value());
+    }
+    verify {
+        assert_eq!(<Votes<T>>::iter_prefix(0).count(), 0);
+        assert_eq!(<Snapshots<T>>::iter_prefix(0).count(), 0);
+    }
+}
+
+impl_benchmark_test_suite!(Governance, crate::mock::new_test_ext(), crate::mock::Test);
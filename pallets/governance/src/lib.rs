@@ -0,0 +1,493 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[rustfmt::skip]
+pub mod weights;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+mod types;
+
+use codec::{Decode, Encode};
+use frame_support::{
+    dispatch::{DispatchResult, DispatchResultWithPostInfo, Dispatchable, PostDispatchInfo},
+    ensure,
+    traits::{
+        tokens::fungibles::{Inspect, Transfer},
+        Currency, EnsureOrigin, Get, NamedReservableCurrency,
+    },
+    transactional,
+    weights::{GetDispatchInfo, Weight},
+    PalletId,
+};
+use parami_did::EnsureDid;
+use scale_info::TypeInfo;
+use sp_runtime::{
+    traits::{AccountIdConversion, Saturating, Zero},
+    Perbill, RuntimeDebug,
+};
+use sp_std::{marker::PhantomData, prelude::*};
+
+use weights::WeightInfo;
+
+type AccountOf<T> = <T as frame_system::Config>::AccountId;
+type BalanceOf<T> = <<T as parami_did::Config>::Currency as Currency<AccountOf<T>>>::Balance;
+type DidOf<T> = <T as parami_did::Config>::DecentralizedId;
+type HeightOf<T> = <T as frame_system::Config>::BlockNumber;
+type NftOf<T> = <T as parami_nft::Config>::AssetId;
+type ProposalOf<T> = types::Proposal<
+    <T as Config>::Proposal,
+    DidOf<T>,
+    AccountOf<T>,
+    BalanceOf<T>,
+    HeightOf<T>,
+    NftOf<T>,
+>;
+
+/// Index of a proposal
+pub type ProposalIndex = u32;
+
+/// The maximum number of votes and of snapshots removed at once
+const MAX_CLEANUP: u32 = 256;
+
+/// Origin of a call approved by the holders of a NFT
+#[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub enum RawOrigin<NftId> {
+    /// The holders of the NFT's fractions
+    Holders(NftId),
+}
+
+/// Ensure the origin is the holders of a NFT, returns the NFT ID
+pub struct EnsureNftHolders<NftId>(PhantomData<NftId>);
+
+impl<O, NftId> EnsureOrigin<O> for EnsureNftHolders<NftId>
+where
+    O: Into<Result<RawOrigin<NftId>, O>> + From<RawOrigin<NftId>>,
+    NftId: Default,
+{
+    type Success = NftId;
+
+    fn try_origin(o: O) -> Result<Self::Success, O> {
+        o.into().and_then(|o| match o {
+            RawOrigin::Holders(nft) => Ok(nft),
+        })
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn successful_origin() -> O {
+        O::from(RawOrigin::Holders(Default::default()))
+    }
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use frame_support::pallet_prelude::*;
+    use frame_system::pallet_prelude::*;
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config + parami_did::Config + parami_nft::Config {
+        /// The overarching event type
+        type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+        /// The outer origin type
+        type Origin: From<RawOrigin<NftOf<Self>>>;
+
+        /// The outer call dispatch type
+        type Proposal: Parameter
+            + Dispatchable<Origin = <Self as Config>::Origin, PostInfo = PostDispatchInfo>
+            + From<frame_system::Call<Self>>
+            + GetDispatchInfo;
+
+        /// The maximum number of active proposals of a NFT
+        #[pallet::constant]
+        type MaxProposals: Get<u32>;
+
+        /// The deposit reserved from the proposer, returned when the proposal is closed
+        #[pallet::constant]
+        type ProposalDeposit: Get<BalanceOf<Self>>;
+
+        /// The pallet id, used for deriving the account holding locked fractions
+        /// and identifying proposal deposits
+        #[pallet::constant]
+        type PalletId: Get<PalletId>;
+
+        /// The minimum approval, in proportion of the fractions issued when a proposal is created
+        #[pallet::constant]
+        type Quorum: Get<Perbill>;
+
+        /// How long a proposal is open for voting
+        #[pallet::constant]
+        type VotingPeriod: Get<HeightOf<Self>>;
+
+        /// Weight information for extrinsics in this pallet
+        type WeightInfo: WeightInfo;
+    }
+
+    #[pallet::pallet]
+    #[pallet::generate_store(pub(super) trait Store)]
+    #[pallet::without_storage_info]
+    pub struct Pallet<T>(_);
+
+    #[pallet::origin]
+    pub type Origin<T> = RawOrigin<NftOf<T>>;
+
+    /// Fractions locked by a holder for voting
+    #[pallet::storage]
+    #[pallet::getter(fn locked_of)]
+    pub(super) type LockedOf<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        NftOf<T>,
+        Identity,
+        DidOf<T>,
+        BalanceOf<T>,
+        ValueQuery,
+    >;
+
+    /// Next proposal index
+    #[pallet::storage]
+    pub(super) type ProposalCount<T: Config> = StorageValue<_, ProposalIndex, ValueQuery>;
+
+    /// Proposals
+    #[pallet::storage]
+    #[pallet::getter(fn proposal)]
+    pub(super) type Proposals<T: Config> =
+        StorageMap<_, Twox64Concat, ProposalIndex, ProposalOf<T>>;
+
+    /// Proposals open for voting of a NFT
+    #[pallet::storage]
+    #[pallet::getter(fn active_proposals)]
+    pub(super) type ActiveProposals<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        NftOf<T>,
+        BoundedVec<ProposalIndex, T::MaxProposals>,
+        ValueQuery,
+    >;
+
+    /// Locked fractions of a holder when a proposal was created,
+    /// only recorded once the holder changes the locked fractions.
+    #[pallet::storage]
+    pub(super) type Snapshots<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, ProposalIndex, Identity, DidOf<T>, BalanceOf<T>>;
+
+    /// Votes of a proposal
+    #[pallet::storage]
+    #[pallet::getter(fn vote_of)]
+    pub(super) type Votes<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, ProposalIndex, Identity, DidOf<T>, bool>;
+
+    /// Closed proposals with votes or snapshots left to remove
+    #[pallet::storage]
+    pub(super) type Leftovers<T: Config> = StorageMap<_, Twox64Concat, ProposalIndex, ()>;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// Fractions Locked \[nft, did, value\]
+        Locked(NftOf<T>, DidOf<T>, BalanceOf<T>),
+        /// Fractions Unlocked \[nft, did, value\]
+        Unlocked(NftOf<T>, DidOf<T>, BalanceOf<T>),
+        /// New proposal \[nft, index, proposer\]
+        Proposed(NftOf<T>, ProposalIndex, DidOf<T>),
+        /// Voted on a proposal \[index, voter, approve, value\]
+        Voted(ProposalIndex, DidOf<T>, bool, BalanceOf<T>),
+        /// Proposal approved and executed \[index, result\]
+        Executed(ProposalIndex, DispatchResult),
+        /// Proposal rejected \[index\]
+        Rejected(ProposalIndex),
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            Self::clear_leftovers(remaining_weight)
+        }
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        DuplicateVote,
+        InsufficientBalance,
+        InsufficientLocked,
+        NoVotingPower,
+        NotExists,
+        NotMinted,
+        TooManyProposals,
+        VotingClosed,
+        VotingOpen,
+        WrongProposalWeight,
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Lock fractions of a NFT to vote on its proposals
+        #[pallet::weight(<T as Config>::WeightInfo::lock(T::MaxProposals::get()))]
+        pub fn lock(
+            origin: OriginFor<T>,
+            nft: NftOf<T>,
+            #[pallet::compact] value: BalanceOf<T>,
+        ) -> DispatchResult {
+            let (did, who) = EnsureDid::<T>::ensure_origin(origin)?;
+
+            let meta = parami_nft::Pallet::<T>::meta(nft).ok_or(Error::<T>::NotExists)?;
+            ensure!(meta.minted, Error::<T>::NotMinted);
+
+            ensure!(
+                value <= T::Assets::balance(meta.token_asset_id, &who),
+                Error::<T>::InsufficientBalance
+            );
+
+            Self::snapshot(nft, &did);
+
+            T::Assets::transfer(
+                meta.token_asset_id,
+                &who,
+                &Self::account_id(),
+                value,
+                false,
+            )?;
+
+            <LockedOf<T>>::mutate(nft, &did, |locked| locked.saturating_accrue(value));
+
+            Self::deposit_event(Event::Locked(nft, did, value));
+
+            Ok(())
+        }
+
+        /// Unlock fractions of a NFT, votes already cast are kept
+        #[pallet::weight(<T as Config>::WeightInfo::unlock(T::MaxProposals::get()))]
+        pub fn unlock(
+            origin: OriginFor<T>,
+            nft: NftOf<T>,
+            #[pallet::compact] value: BalanceOf<T>,
+        ) -> DispatchResult {
+            let (did, who) = EnsureDid::<T>::ensure_origin(origin)?;
+
+            let meta = parami_nft::Pallet::<T>::meta(nft).ok_or(Error::<T>::NotExists)?;
+
+            let locked = <LockedOf<T>>::get(nft, &did);
+            ensure!(value <= locked, Error::<T>::InsufficientLocked);
+
+            Self::snapshot(nft, &did);
+
+            T::Assets::transfer(
+                meta.token_asset_id,
+                &Self::account_id(),
+                &who,
+                value,
+                false,
+            )?;
+
+            let locked = locked.saturating_sub(value);
+            if locked.is_zero() {
+                <LockedOf<T>>::remove(nft, &did);
+            } else {
+                <LockedOf<T>>::insert(nft, &did, locked);
+            }
+
+            Self::deposit_event(Event::Unlocked(nft, did, value));
+
+            Ok(())
+        }
+
+        /// Propose a call to be dispatched as the holders of a NFT
+        #[pallet::weight(<T as Config>::WeightInfo::propose())]
+        #[transactional]
+        pub fn propose(
+            origin: OriginFor<T>,
+            nft: NftOf<T>,
+            call: Box<<T as Config>::Proposal>,
+        ) -> DispatchResult {
+            let (did, who) = EnsureDid::<T>::ensure_origin(origin)?;
+
+            let meta = parami_nft::Pallet::<T>::meta(nft).ok_or(Error::<T>::NotExists)?;
+            ensure!(meta.minted, Error::<T>::NotMinted);
+
+            ensure!(
+                !<LockedOf<T>>::get(nft, &did).is_zero(),
+                Error::<T>::NoVotingPower
+            );
+
+            let index = <ProposalCount<T>>::get();
+
+            <ActiveProposals<T>>::try_mutate(nft, |active| active.try_push(index))
+                .map_err(|_| Error::<T>::TooManyProposals)?;
+
+            let id = <T as Config>::PalletId::get();
+            let deposit = T::ProposalDeposit::get();
+
+            T::Currency::reserve_named(&id.0, &who, deposit)?;
+
+            let height = <frame_system::Pallet<T>>::block_number();
+
+            <Proposals<T>>::insert(
+                index,
+                types::Proposal {
+                    nft,
+                    proposer: did,
+                    depositor: who,
+                    deposit,
+                    call: *call,
+                    electorate: T::Assets::total_issuance(meta.token_asset_id),
+                    ayes: Zero::zero(),
+                    nays: Zero::zero(),
+                    end: height.saturating_add(T::VotingPeriod::get()),
+                },
+            );
+
+            <ProposalCount<T>>::put(index + 1);
+
+            Self::deposit_event(Event::Proposed(nft, index, did));
+
+            Ok(())
+        }
+
+        /// Vote on a proposal with the fractions locked when it was created
+        #[pallet::weight(<T as Config>::WeightInfo::vote())]
+        pub fn vote(origin: OriginFor<T>, index: ProposalIndex, approve: bool) -> DispatchResult {
+            let (did, _) = EnsureDid::<T>::ensure_origin(origin)?;
+
+            let mut proposal = <Proposals<T>>::get(index).ok_or(Error::<T>::NotExists)?;
+
+            let height = <frame_system::Pallet<T>>::block_number();
+            ensure!(height < proposal.end, Error::<T>::VotingClosed);
+
+            ensure!(
+                !<Votes<T>>::contains_key(index, &did),
+                Error::<T>::DuplicateVote
+            );
+
+            let power = Self::voting_power(index, proposal.nft, &did);
+            ensure!(!power.is_zero(), Error::<T>::NoVotingPower);
+
+            if approve {
+                proposal.ayes.saturating_accrue(power);
+            } else {
+                proposal.nays.saturating_accrue(power);
+            }
+
+            <Proposals<T>>::insert(index, proposal);
+            <Votes<T>>::insert(index, &did, approve);
+
+            Self::deposit_event(Event::Voted(index, did, approve, power));
+
+            Ok(())
+        }
+
+        /// Close a proposal once voting ended and return its deposit,
+        /// an approved proposal is dispatched as the holders of the NFT.
+        ///
+        /// Votes and snapshots left over the cleanup limit are removed when blocks are idle.
+        #[pallet::weight(<T as Config>::WeightInfo::close().saturating_add(*proposal_weight_bound))]
+        pub fn close(
+            origin: OriginFor<T>,
+            index: ProposalIndex,
+            #[pallet::compact] proposal_weight_bound: Weight,
+        ) -> DispatchResultWithPostInfo {
+            ensure_signed(origin)?;
+
+            let proposal = <Proposals<T>>::get(index).ok_or(Error::<T>::NotExists)?;
+
+            let height = <frame_system::Pallet<T>>::block_number();
+            ensure!(height >= proposal.end, Error::<T>::VotingOpen);
+
+            let proposal_weight = proposal.call.get_dispatch_info().weight;
+            ensure!(
+                proposal_weight <= proposal_weight_bound,
+                Error::<T>::WrongProposalWeight
+            );
+
+            <Proposals<T>>::remove(index);
+            <ActiveProposals<T>>::mutate(proposal.nft, |active| active.retain(|i| *i != index));
+            if !Self::clear_votes(index) {
+                <Leftovers<T>>::insert(index, ());
+            }
+
+            let id = <T as Config>::PalletId::get();
+            T::Currency::unreserve_named(&id.0, &proposal.depositor, proposal.deposit);
+
+            let approved = proposal.ayes > proposal.nays
+                && proposal.ayes >= T::Quorum::get() * proposal.electorate;
+
+            if !approved {
+                Self::deposit_event(Event::Rejected(index));
+
+                return Ok(Some(<T as Config>::WeightInfo::close()).into());
+            }
+
+            let result = proposal
+                .call
+                .dispatch(RawOrigin::Holders(proposal.nft).into());
+
+            Self::deposit_event(Event::Executed(
+                index,
+                result.map(|_| ()).map_err(|e| e.error),
+            ));
+
+            Ok(().into())
+        }
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    pub fn account_id() -> AccountOf<T> {
+        <T as Config>::PalletId::get().into_account_truncating()
+    }
+
+    /// Fractions a holder may vote with on a proposal
+    pub fn voting_power(index: ProposalIndex, nft: NftOf<T>, did: &DidOf<T>) -> BalanceOf<T> {
+        <Snapshots<T>>::get(index, did).unwrap_or_else(|| <LockedOf<T>>::get(nft, did))
+    }
+
+    /// Remove at most `MAX_CLEANUP` votes and snapshots of a closed proposal,
+    /// returns true once none are left.
+    fn clear_votes(index: ProposalIndex) -> bool {
+        use sp_io::KillStorageResult::*;
+
+        let votes = <Votes<T>>::remove_prefix(index, Some(MAX_CLEANUP));
+        let snapshots = <Snapshots<T>>::remove_prefix(index, Some(MAX_CLEANUP));
+
+        matches!((votes, snapshots), (AllRemoved(_), AllRemoved(_)))
+    }
+
+    /// Continue removing the votes and snapshots of a closed proposal within `limit`,
+    /// one proposal per block as a limited removal makes no progress on keys
+    /// already removed in the same block.
+    fn clear_leftovers(limit: Weight) -> Weight {
+        let weight = <T as Config>::WeightInfo::clear_leftovers();
+        if weight > limit {
+            return 0;
+        }
+
+        let index = match <Leftovers<T>>::iter_keys().next() {
+            Some(index) => index,
+            None => return T::DbWeight::get().reads(1),
+        };
+
+        if Self::clear_votes(index) {
+            <Leftovers<T>>::remove(index);
+        }
+
+        weight
+    }
+
+    /// Record the locked fractions of a holder for active proposals,
+    /// before they are changed.
+    fn snapshot(nft: NftOf<T>, did: &DidOf<T>) {
+        let locked = <LockedOf<T>>::get(nft, did);
+        for index in <ActiveProposals<T>>::get(nft) {
+            if !<Snapshots<T>>::contains_key(index, did) {
+                <Snapshots<T>>::insert(index, did, locked);
+            }
+        }
+    }
+}
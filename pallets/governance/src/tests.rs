@@ -0,0 +1,301 @@
+use crate::{
+    mock::*, ActiveProposals, Error, Leftovers, LockedOf, Proposals, RawOrigin, Snapshots, Votes,
+};
+use frame_support::{
+    assert_noop, assert_ok,
+    dispatch::DispatchError,
+    traits::{Currency, Hooks},
+    weights::Weight,
+};
+use sp_std::prelude::*;
+
+fn update_clock_in() -> Box<Call> {
+    Box::new(Call::ClockIn(
+        parami_clockin::Call::update_clock_in_by_holders {
+            level_probability: vec![50, 50],
+            level_upper_bounds: vec![100, 1000],
+            shares_per_bucket: 3,
+            award_per_share: 20,
+        },
+    ))
+}
+
+fn set_reserve_price(price: u128) -> Box<Call> {
+    Box::new(Call::Ad(parami_ad::Call::set_reserve_price { price }))
+}
+
+#[test]
+fn should_lock_and_unlock() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Governance::lock(Origin::signed(BOB), 0, 1000),
+            Error::<Test>::NotMinted
+        );
+        assert_noop!(
+            Governance::lock(Origin::signed(BOB), 1, 3001),
+            Error::<Test>::InsufficientBalance
+        );
+
+        assert_ok!(Governance::lock(Origin::signed(BOB), 1, 1000));
+
+        assert_eq!(<LockedOf<Test>>::get(1, DID_BOB), 1000);
+        assert_eq!(Assets::balance(1, &BOB), 2000);
+        assert_eq!(Assets::balance(1, &Governance::account_id()), 1000);
+
+        assert_noop!(
+            Governance::unlock(Origin::signed(BOB), 1, 1001),
+            Error::<Test>::InsufficientLocked
+        );
+
+        assert_ok!(Governance::unlock(Origin::signed(BOB), 1, 400));
+        assert_eq!(<LockedOf<Test>>::get(1, DID_BOB), 600);
+        assert_eq!(Assets::balance(1, &BOB), 2400);
+
+        assert_ok!(Governance::unlock(Origin::signed(BOB), 1, 600));
+        assert!(!<LockedOf<Test>>::contains_key(1, DID_BOB));
+    });
+}
+
+#[test]
+fn should_execute_approved_proposal() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(ClockIn::enable_clock_in(
+            Origin::signed(ALICE),
+            1,
+            vec![10, 20],
+            vec![100, 1000],
+            5,
+            10,
+            1000,
+        ));
+
+        assert_ok!(Governance::lock(Origin::signed(ALICE), 1, 2000));
+        assert_ok!(Governance::lock(Origin::signed(BOB), 1, 1000));
+
+        assert_noop!(
+            Governance::propose(Origin::signed(CHARLIE), 1, update_clock_in()),
+            Error::<Test>::NoVotingPower
+        );
+
+        assert_ok!(Governance::propose(
+            Origin::signed(BOB),
+            1,
+            update_clock_in()
+        ));
+
+        let proposal = <Proposals<Test>>::get(0).unwrap();
+        assert_eq!(proposal.proposer, DID_BOB);
+        assert_eq!(proposal.depositor, BOB);
+        assert_eq!(proposal.deposit, 10);
+        assert_eq!(proposal.electorate, 10000);
+        assert_eq!(proposal.end, 11);
+        assert_eq!(<ActiveProposals<Test>>::get(1).into_inner(), vec![0]);
+        assert_eq!(Balances::reserved_balance(&BOB), 10);
+
+        assert_ok!(Governance::vote(Origin::signed(ALICE), 0, true));
+        assert_ok!(Governance::vote(Origin::signed(BOB), 0, false));
+
+        assert_noop!(
+            Governance::vote(Origin::signed(BOB), 0, true),
+            Error::<Test>::DuplicateVote
+        );
+        assert_noop!(
+            Governance::close(Origin::signed(CHARLIE), 0, Weight::MAX),
+            Error::<Test>::VotingOpen
+        );
+
+        System::set_block_number(11);
+
+        assert_noop!(
+            Governance::vote(Origin::signed(CHARLIE), 0, true),
+            Error::<Test>::VotingClosed
+        );
+
+        assert_ok!(Governance::close(Origin::signed(CHARLIE), 0, Weight::MAX));
+
+        System::assert_last_event(Event::Governance(crate::Event::Executed(0, Ok(()))));
+
+        let meta = ClockIn::lottery_metadata(1).unwrap();
+        assert_eq!(meta.level_probability, vec![50, 50]);
+        assert_eq!(meta.shares_per_bucket, 3);
+        assert_eq!(meta.award_per_share, 20);
+
+        assert_eq!(<Proposals<Test>>::get(0), None);
+        assert!(<ActiveProposals<Test>>::get(1).is_empty());
+        assert_eq!(<Votes<Test>>::iter_prefix(0).count(), 0);
+        assert_eq!(Balances::reserved_balance(&BOB), 0);
+    });
+}
+
+#[test]
+fn should_reject_proposal_without_quorum() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Governance::lock(Origin::signed(CHARLIE), 1, 1000));
+
+        assert_ok!(Governance::propose(
+            Origin::signed(CHARLIE),
+            1,
+            set_reserve_price(100)
+        ));
+        assert_ok!(Governance::vote(Origin::signed(CHARLIE), 0, true));
+
+        System::set_block_number(11);
+
+        assert_ok!(Governance::close(Origin::signed(CHARLIE), 0, Weight::MAX));
+
+        System::assert_last_event(Event::Governance(crate::Event::Rejected(0)));
+
+        assert_eq!(Ad::reserve_price_of(1), 0);
+        assert_eq!(Balances::reserved_balance(&CHARLIE), 0);
+        assert_eq!(Balances::free_balance(&CHARLIE), 100);
+    });
+}
+
+#[test]
+fn should_vote_with_snapshot() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Governance::lock(Origin::signed(ALICE), 1, 1000));
+        assert_ok!(Governance::lock(Origin::signed(BOB), 1, 3000));
+
+        assert_ok!(Governance::propose(
+            Origin::signed(ALICE),
+            1,
+            set_reserve_price(100)
+        ));
+
+        // changes after the proposal are not counted
+        assert_ok!(Governance::unlock(Origin::signed(BOB), 1, 3000));
+        assert_ok!(Governance::lock(Origin::signed(CHARLIE), 1, 1000));
+        assert_ok!(Governance::lock(Origin::signed(ALICE), 1, 2000));
+
+        assert_eq!(<Snapshots<Test>>::get(0, DID_BOB), Some(3000));
+        assert_eq!(<Snapshots<Test>>::get(0, DID_CHARLIE), Some(0));
+        assert_eq!(<Snapshots<Test>>::get(0, DID_ALICE), Some(1000));
+
+        assert_ok!(Governance::vote(Origin::signed(BOB), 0, true));
+        assert_ok!(Governance::vote(Origin::signed(ALICE), 0, false));
+        assert_noop!(
+            Governance::vote(Origin::signed(CHARLIE), 0, true),
+            Error::<Test>::NoVotingPower
+        );
+
+        let proposal = <Proposals<Test>>::get(0).unwrap();
+        assert_eq!(proposal.ayes, 3000);
+        assert_eq!(proposal.nays, 1000);
+
+        System::set_block_number(11);
+
+        assert_ok!(Governance::close(Origin::signed(CHARLIE), 0, Weight::MAX));
+
+        System::assert_last_event(Event::Governance(crate::Event::Executed(0, Ok(()))));
+
+        assert_eq!(Ad::reserve_price_of(1), 100);
+        assert_eq!(<Snapshots<Test>>::iter_prefix(0).count(), 0);
+    });
+}
+
+#[test]
+fn should_release_influence_mining_funds() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Nft::start_dao_influenceming_activity(
+            Origin::signed(ALICE),
+            1,
+            1000
+        ));
+
+        assert_ok!(Governance::lock(Origin::signed(BOB), 1, 3000));
+
+        assert_ok!(Governance::propose(
+            Origin::signed(BOB),
+            1,
+            Box::new(Call::Nft(
+                parami_nft::Call::release_influence_mining_funds {
+                    to: CHARLIE,
+                    amount: 400,
+                }
+            ))
+        ));
+        assert_ok!(Governance::vote(Origin::signed(BOB), 0, true));
+
+        System::set_block_number(11);
+
+        assert_ok!(Governance::close(Origin::signed(BOB), 0, Weight::MAX));
+
+        System::assert_last_event(Event::Governance(crate::Event::Executed(0, Ok(()))));
+
+        assert_eq!(Assets::balance(1, &CHARLIE), 1400);
+    });
+}
+
+#[test]
+fn should_clear_leftovers_when_idle() {
+    new_test_ext().execute_with(|| {
+        <Votes<Test>>::insert(0, DID_ALICE, true);
+        <Votes<Test>>::insert(0, DID_BOB, false);
+        <Snapshots<Test>>::insert(0, DID_BOB, 3000);
+        <Leftovers<Test>>::insert(0, ());
+
+        assert_eq!(Governance::on_idle(1, 0), 0);
+        assert!(<Leftovers<Test>>::contains_key(0));
+
+        assert!(Governance::on_idle(1, Weight::MAX) > 0);
+
+        assert!(!<Leftovers<Test>>::contains_key(0));
+        assert_eq!(<Votes<Test>>::iter_prefix(0).count(), 0);
+        assert_eq!(<Snapshots<Test>>::iter_prefix(0).count(), 0);
+    });
+}
+
+#[test]
+fn should_fail_without_deposit() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Governance::lock(Origin::signed(CHARLIE), 1, 1000));
+
+        Balances::make_free_balance_be(&CHARLIE, 5);
+
+        assert_noop!(
+            Governance::propose(Origin::signed(CHARLIE), 1, set_reserve_price(1)),
+            pallet_balances::Error::<Test>::InsufficientBalance
+        );
+    });
+}
+
+#[test]
+fn should_fail_when_too_many_proposals() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Governance::lock(Origin::signed(BOB), 1, 3000));
+
+        assert_ok!(Governance::propose(
+            Origin::signed(BOB),
+            1,
+            set_reserve_price(1)
+        ));
+        assert_ok!(Governance::propose(
+            Origin::signed(BOB),
+            1,
+            set_reserve_price(2)
+        ));
+
+        assert_noop!(
+            Governance::propose(Origin::signed(BOB), 1, set_reserve_price(3)),
+            Error::<Test>::TooManyProposals
+        );
+    });
+}
+
+#[test]
+fn should_ensure_nft_holders() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Ad::set_reserve_price(Origin::signed(ALICE), 100),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Ad::set_reserve_price(Origin::root(), 100),
+            DispatchError::BadOrigin
+        );
+
+        assert_ok!(Ad::set_reserve_price(RawOrigin::Holders(1).into(), 100));
+        assert_eq!(Ad::reserve_price_of(1), 100);
+    });
+}
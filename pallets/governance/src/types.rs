@@ -0,0 +1,16 @@
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+
+#[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct Proposal<Call, Did, Account, Balance, Height, NftId> {
+    pub nft: NftId,
+    pub proposer: Did,
+    pub depositor: Account,
+    pub deposit: Balance,
+    pub call: Call,
+    pub electorate: Balance,
+    pub ayes: Balance,
+    pub nays: Balance,
+    pub end: Height,
+}
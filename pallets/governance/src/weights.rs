@@ -0,0 +1,213 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Weights for parami_governance
+//!
+//! The weights are estimated from the benchmarked weights of pallets with similar
+//! storage access until this file is generated with the command below.
+
+// Command:
+// ./target/release/parami
+// benchmark
+// pallet
+// --chain=dev
+// --execution=wasm
+// --wasm-execution=compiled
+// --pallet=parami_governance
+// --extrinsic=*
+// --steps=50
+// --repeat=20
+// --template=./.maintain/frame-weight-template.hbs
+// --output=./pallets/governance/src/weights.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for parami_governance.
+pub trait WeightInfo {
+    fn lock(n: u32, ) -> Weight;
+    fn unlock(n: u32, ) -> Weight;
+    fn propose() -> Weight;
+    fn vote() -> Weight;
+    fn close() -> Weight;
+    fn clear_leftovers() -> Weight;
+}
+
+/// Weights for parami_governance using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Nft Metadata (r:1 w:0)
+    // Storage: Assets Asset (r:1 w:1)
+    // Storage: Assets Account (r:2 w:2)
+    // Storage: Governance LockedOf (r:1 w:1)
+    // Storage: Governance ActiveProposals (r:1 w:0)
+    // Storage: Governance Snapshots (r:1 w:1)
+    fn lock(n: u32, ) -> Weight {
+        (52_000_000 as Weight)
+            // Standard Error: 0
+            .saturating_add((4_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(7 as Weight))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Nft Metadata (r:1 w:0)
+    // Storage: Governance LockedOf (r:1 w:1)
+    // Storage: Governance ActiveProposals (r:1 w:0)
+    // Storage: Governance Snapshots (r:1 w:1)
+    // Storage: Assets Asset (r:1 w:1)
+    // Storage: Assets Account (r:2 w:2)
+    fn unlock(n: u32, ) -> Weight {
+        (50_000_000 as Weight)
+            // Standard Error: 0
+            .saturating_add((4_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(7 as Weight))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Nft Metadata (r:1 w:0)
+    // Storage: Governance LockedOf (r:1 w:0)
+    // Storage: Governance ProposalCount (r:1 w:1)
+    // Storage: Governance ActiveProposals (r:1 w:1)
+    // Storage: Assets Asset (r:1 w:0)
+    // Storage: Balances Reserves (r:1 w:1)
+    // Storage: System Account (r:1 w:1)
+    // Storage: Governance Proposals (r:0 w:1)
+    fn propose() -> Weight {
+        (46_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(8 as Weight))
+            .saturating_add(T::DbWeight::get().writes(5 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Governance Proposals (r:1 w:1)
+    // Storage: Governance Votes (r:1 w:1)
+    // Storage: Governance Snapshots (r:1 w:0)
+    // Storage: Governance LockedOf (r:1 w:0)
+    fn vote() -> Weight {
+        (31_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    // Storage: Governance Proposals (r:1 w:1)
+    // Storage: Governance ActiveProposals (r:1 w:1)
+    // Storage: Governance Votes (r:0 w:256)
+    // Storage: Governance Snapshots (r:0 w:256)
+    // Storage: Governance Leftovers (r:0 w:1)
+    // Storage: Balances Reserves (r:1 w:1)
+    // Storage: System Account (r:1 w:1)
+    fn close() -> Weight {
+        (1_462_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(517 as Weight))
+    }
+    // Storage: Governance Leftovers (r:1 w:1)
+    // Storage: Governance Votes (r:0 w:256)
+    // Storage: Governance Snapshots (r:0 w:256)
+    fn clear_leftovers() -> Weight {
+        (1_396_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(513 as Weight))
+    }
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Nft Metadata (r:1 w:0)
+    // Storage: Assets Asset (r:1 w:1)
+    // Storage: Assets Account (r:2 w:2)
+    // Storage: Governance LockedOf (r:1 w:1)
+    // Storage: Governance ActiveProposals (r:1 w:0)
+    // Storage: Governance Snapshots (r:1 w:1)
+    fn lock(n: u32, ) -> Weight {
+        (52_000_000 as Weight)
+            // Standard Error: 0
+            .saturating_add((4_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(7 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Nft Metadata (r:1 w:0)
+    // Storage: Governance LockedOf (r:1 w:1)
+    // Storage: Governance ActiveProposals (r:1 w:0)
+    // Storage: Governance Snapshots (r:1 w:1)
+    // Storage: Assets Asset (r:1 w:1)
+    // Storage: Assets Account (r:2 w:2)
+    fn unlock(n: u32, ) -> Weight {
+        (50_000_000 as Weight)
+            // Standard Error: 0
+            .saturating_add((4_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(7 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Nft Metadata (r:1 w:0)
+    // Storage: Governance LockedOf (r:1 w:0)
+    // Storage: Governance ProposalCount (r:1 w:1)
+    // Storage: Governance ActiveProposals (r:1 w:1)
+    // Storage: Assets Asset (r:1 w:0)
+    // Storage: Balances Reserves (r:1 w:1)
+    // Storage: System Account (r:1 w:1)
+    // Storage: Governance Proposals (r:0 w:1)
+    fn propose() -> Weight {
+        (46_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(8 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(5 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Governance Proposals (r:1 w:1)
+    // Storage: Governance Votes (r:1 w:1)
+    // Storage: Governance Snapshots (r:1 w:0)
+    // Storage: Governance LockedOf (r:1 w:0)
+    fn vote() -> Weight {
+        (31_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    // Storage: Governance Proposals (r:1 w:1)
+    // Storage: Governance ActiveProposals (r:1 w:1)
+    // Storage: Governance Votes (r:0 w:256)
+    // Storage: Governance Snapshots (r:0 w:256)
+    // Storage: Governance Leftovers (r:0 w:1)
+    // Storage: Balances Reserves (r:1 w:1)
+    // Storage: System Account (r:1 w:1)
+    fn close() -> Weight {
+        (1_462_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(517 as Weight))
+    }
+    // Storage: Governance Leftovers (r:1 w:1)
+    // Storage: Governance Votes (r:0 w:256)
+    // Storage: Governance Snapshots (r:0 w:256)
+    fn clear_leftovers() -> Weight {
+        (1_396_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(513 as Weight))
+    }
+}
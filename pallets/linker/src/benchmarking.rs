@@ -9,6 +9,29 @@ use parami_did::Pallet as Did;
 use parami_traits::types::Network;
 use sp_runtime::traits::{Bounded, Saturating};
 
+/// Link the maximum of accounts of the largest address, returns the addresses
+fn fill_links<T: Config>(caller: &T::AccountId) -> Result<Vec<Vec<u8>>, &'static str> {
+    let signature = [0u8; 65];
+
+    let mut addresses = Vec::new();
+
+    for i in 0..ProfilesOf::bound() {
+        let address = vec![i as u8; 256];
+
+        Linker::<T>::link_crypto(
+            RawOrigin::Signed(caller.clone()).into(),
+            Network::Unknown,
+            address.clone(),
+            signature,
+            SignatureScheme::Plain,
+        )?;
+
+        addresses.push(address);
+    }
+
+    Ok(addresses)
+}
+
 benchmarks! {
     where_clause {
         where
@@ -24,7 +47,7 @@ benchmarks! {
         let did = Did::<T>::did_of(&caller).unwrap();
 
         let profile = vec![0u8; n as usize];
-    }: _(RawOrigin::Signed(caller), Network::Mastodon, profile)
+    }: _(RawOrigin::Signed(caller), Network::Mastodon, profile, Proof::Avatar)
     verify {
         assert_ne!(<PendingOf<T>>::get(&Network::Mastodon, &did), None);
     }
@@ -37,9 +60,9 @@ benchmarks! {
 
         let address = vec![0u8; 256];
         let signature = [0u8; 65];
-    }: _(RawOrigin::Signed(caller), Network::Unknown, address.clone(), signature)
+    }: _(RawOrigin::Signed(caller), Network::Unknown, address.clone(), signature, SignatureScheme::Plain)
     verify {
-        assert_eq!(<LinksOf<T>>::get(&did, &Network::Unknown), Some(address));
+        assert_eq!(<LinksOf<T>>::get(&did, &Network::Unknown), Some(vec![LinkOf::new(address)].try_into().unwrap()));
     }
 
     deposit {
@@ -101,15 +124,41 @@ benchmarks! {
         Did::<T>::register(RawOrigin::Signed(caller.clone()).into(), None)?;
         let did = Did::<T>::did_of(&caller).unwrap();
 
-        let address = vec![0u8; 20];
-        let signature = [0u8; 65];
-
-        Linker::<T>::link_crypto(RawOrigin::Signed(caller.clone()).into(), Network::Unknown, address.clone(), signature.clone())?;
+        fill_links::<T>(&caller)?;
     }: _(RawOrigin::Root, did.clone(), Network::Unknown)
     verify {
         assert_eq!(<LinksOf<T>>::get(&did, &Network::Unknown), None);
     }
 
+    unlink {
+        let caller: T::AccountId = whitelisted_caller();
+
+        Did::<T>::register(RawOrigin::Signed(caller.clone()).into(), None)?;
+        let did = Did::<T>::did_of(&caller).unwrap();
+
+        // the last link is found after all the others
+        let address = fill_links::<T>(&caller)?.pop().unwrap();
+    }: _(RawOrigin::Signed(caller), Network::Unknown, address.clone())
+    verify {
+        assert!(!<Linked<T>>::get(&Network::Unknown, &address));
+        assert_eq!(<LinksOf<T>>::decode_len(&did, &Network::Unknown), Some(ProfilesOf::bound() - 1));
+    }
+
+    set_label {
+        let caller: T::AccountId = whitelisted_caller();
+
+        Did::<T>::register(RawOrigin::Signed(caller.clone()).into(), None)?;
+        let did = Did::<T>::did_of(&caller).unwrap();
+
+        // the last link is found after all the others
+        let address = fill_links::<T>(&caller)?.pop().unwrap();
+        let label = vec![0u8; LabelOf::bound()];
+    }: _(RawOrigin::Signed(caller), Network::Unknown, address, label.clone())
+    verify {
+        let last = ProfilesOf::bound() - 1;
+        assert_eq!(<LinksOf<T>>::get(&did, &Network::Unknown).unwrap()[last].label.to_vec(), label);
+    }
+
     submit_link {
         let n in 0 .. 1000;
 
@@ -132,10 +181,10 @@ benchmarks! {
 
         let profile = vec![0u8; n as usize];
 
-        Linker::<T>::link_sociality(RawOrigin::Signed(applicant.clone()).into(), Network::Mastodon, profile.clone())?;
+        Linker::<T>::link_sociality(RawOrigin::Signed(applicant.clone()).into(), Network::Mastodon, profile.clone(), Proof::Avatar)?;
     }: _(RawOrigin::Signed(caller), did.clone(), Network::Mastodon, profile.clone(), true)
     verify {
-        assert_eq!(<LinksOf<T>>::get(&did, &Network::Mastodon), Some(profile));
+        assert_eq!(<LinksOf<T>>::get(&did, &Network::Mastodon), Some(vec![LinkOf::new(profile)].try_into().unwrap()));
     }
 
     submit_score {
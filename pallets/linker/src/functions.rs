@@ -1,10 +1,18 @@
-use crate::{Config, DidOf, Error, Event, Linked, LinksOf, Pallet, PendingOf};
+use crate::{
+    types, Config, DidOf, Error, Event, LinkOf, Linked, LinksOf, Pallet, PendingOf, ProfilesOf,
+    ProofOf,
+};
 
 use frame_support::ensure;
+use parami_did::{Milestone, Pallet as Did};
+use parami_primitives::signature::generate_text_proof;
 use parami_traits::types::{Network, Task};
-use sp_runtime::DispatchResult;
+use sp_runtime::{traits::Hash, DispatchResult};
 use sp_std::prelude::*;
 
+/// Maximum length of the URL of a text proof
+const MAX_POST_LENGTH: usize = 256;
+
 macro_rules! is_task {
     ($profile:expr, $prefix:expr) => {
         $profile.starts_with($prefix) && $profile.len() > $prefix.len()
@@ -15,15 +23,17 @@ impl<T: Config> Pallet<T> {
     fn ensure_profile(did: &DidOf<T>, site: Network, profile: &[u8]) -> DispatchResult {
         use Network::*;
 
-        ensure!(!<LinksOf<T>>::contains_key(did, site), Error::<T>::Exists);
         ensure!(
             !<Linked<T>>::contains_key(site, profile),
             Error::<T>::Exists
         );
 
+        let count = <LinksOf<T>>::decode_len(did, site).unwrap_or_default();
+        ensure!(count < ProfilesOf::bound(), Error::<T>::TooManyLinks);
+
         match site {
-            Binance | Bitcoin | Eosio | Ethereum | Kusama | Polkadot | Solana | Tron | Near
-            | Unknown => {}
+            Binance | Bitcoin | Cosmos | Eosio | Ethereum | Kusama | Polkadot | Solana | Tron
+            | Near | Unknown => {}
 
             Discord if is_task!(profile, b"https://discordapp.com/users/") => {}
             Facebook if is_task!(profile, b"https://www.facebook.com/") => {}
@@ -42,6 +52,7 @@ impl<T: Config> Pallet<T> {
 
     pub fn veto_pending(did: DidOf<T>, site: Network, profile: Vec<u8>) -> DispatchResult {
         <PendingOf<T>>::remove(site, &did);
+        <ProofOf<T>>::remove(site, &did);
 
         Self::deposit_event(Event::<T>::ValidationFailed(did, site, profile));
 
@@ -57,17 +68,63 @@ impl<T: Config> Pallet<T> {
         Self::ensure_profile(&did, site, &profile)?;
 
         <PendingOf<T>>::remove(site, &did);
+        <ProofOf<T>>::remove(site, &did);
 
         <Linked<T>>::insert(site, &profile, true);
 
-        <LinksOf<T>>::insert(&did, site, profile.clone());
+        <LinksOf<T>>::try_mutate(&did, site, |maybe_links| {
+            let links = maybe_links.get_or_insert_with(Default::default);
+            links
+                .try_push(LinkOf::new(profile.clone()))
+                .map_err(|_| Error::<T>::TooManyLinks)
+        })?;
+
+        // links proven by the DID itself can be self-issued at no cost
+        if registrar != did {
+            Did::<T>::reach_milestone(&did, Milestone::Linked);
+        }
 
         Self::deposit_event(Event::<T>::AccountLinked(did, site, profile, registrar));
 
         Ok(())
     }
 
-    pub fn insert_pending(did: DidOf<T>, site: Network, profile: Vec<u8>) -> DispatchResult {
+    pub fn remove_link(
+        did: DidOf<T>,
+        site: Network,
+        profile: Vec<u8>,
+        registrar: DidOf<T>,
+    ) -> DispatchResult {
+        <LinksOf<T>>::try_mutate_exists(&did, site, |maybe_links| -> DispatchResult {
+            let links = maybe_links.as_mut().ok_or(Error::<T>::NotExists)?;
+
+            let index = links
+                .iter()
+                .position(|link| link.profile == profile)
+                .ok_or(Error::<T>::NotExists)?;
+
+            links.remove(index);
+
+            if links.is_empty() {
+                *maybe_links = None;
+            }
+
+            Ok(())
+        })?;
+
+        <Linked<T>>::remove(site, &profile);
+
+        Self::deposit_event(Event::<T>::AccountUnlinked(did, site, profile, registrar));
+
+        Ok(())
+    }
+
+    pub fn insert_pending(
+        did: DidOf<T>,
+        site: Network,
+        profile: Vec<u8>,
+        proof: types::Proof,
+    ) -> DispatchResult {
         use frame_support::traits::Get;
         use sp_runtime::traits::Saturating;
 
@@ -78,6 +135,15 @@ impl<T: Config> Pallet<T> {
             Error::<T>::Exists
         );
 
+        if let types::Proof::Text(ref post) = proof {
+            let prefix = Self::post_prefix(site, &profile).ok_or(Error::<T>::InvalidProof)?;
+
+            ensure!(
+                post.len() <= MAX_POST_LENGTH && is_task!(post, &prefix[..]),
+                Error::<T>::InvalidProof
+            );
+        }
+
         let created = <frame_system::Pallet<T>>::block_number();
         let lifetime = T::PendingLifetime::get();
         let deadline = created.saturating_add(lifetime);
@@ -92,6 +158,31 @@ impl<T: Config> Pallet<T> {
             },
         );
 
+        <ProofOf<T>>::insert(site, &did, proof);
+
         Ok(())
     }
+
+    /// The URL prefix of the posts authored by a profile, `None` if text proofs are unsupported
+    pub(crate) fn post_prefix(site: Network, profile: &[u8]) -> Option<Vec<u8>> {
+        let profile = profile.strip_suffix(b"/").unwrap_or(profile);
+
+        match site {
+            Network::Telegram => Some([profile, b"/"].concat()),
+            Network::Twitter => Some([profile, b"/status/"].concat()),
+            _ => None,
+        }
+    }
+
+    /// The message to sign for linking a sociality account with a text proof
+    ///
+    /// The nonce is the hash of the DID, the site and the profile,
+    /// so that a post cannot be replayed for another DID or profile.
+    /// The post contains `Link: did:ad3:{base58} {base58 signature}`,
+    /// signed with an authentication key of the DID document, or the sr25519 account of the DID.
+    pub fn text_proof(did: &DidOf<T>, site: Network, profile: &[u8]) -> Vec<u8> {
+        let nonce = <T as frame_system::Config>::Hashing::hash_of(&(did, site, profile));
+
+        generate_text_proof(did, nonce)
+    }
 }
@@ -0,0 +1,75 @@
+use crate::{
+    AccountOf, Config, DidOf, Event, Linked, LinksOf, Pallet, PendingOf, ProfilesOf, ProofOf,
+    Registrar,
+};
+
+use frame_support::{
+    traits::{Get, NamedReservableCurrency},
+    weights::Weight,
+};
+use parami_traits::{types::Network, OnDidRevoked};
+use sp_std::prelude::*;
+
+/// All networks, a DID has at most `ProfilesOf` links and one pending link on each
+const NETWORKS: [Network; 19] = {
+    use Network::*;
+
+    [
+        Unknown, Binance, Bitcoin, Eosio, Ethereum, Kusama, Polkadot, Solana, Tron, Near, Cosmos,
+        Discord, Facebook, Github, HackerNews, Mastodon, Reddit, Telegram, Twitter,
+    ]
+};
+
+impl<T: Config> OnDidRevoked<DidOf<T>, AccountOf<T>> for Pallet<T> {
+    fn on_did_revoked(did: &DidOf<T>, account: &AccountOf<T>) -> Weight {
+        let mut read = 0;
+        let mut write = 0;
+
+        for site in NETWORKS {
+            read += 2;
+
+            if let Some(links) = <LinksOf<T>>::take(did, site) {
+                write += 1;
+
+                for link in links {
+                    write += 1;
+                    <Linked<T>>::remove(site, &link.profile);
+
+                    Self::deposit_event(Event::<T>::AccountUnlinked(
+                        *did,
+                        site,
+                        link.profile,
+                        DidOf::<T>::default(),
+                    ));
+                }
+            }
+
+            if <PendingOf<T>>::take(site, did).is_some() {
+                write += 2;
+                <ProofOf<T>>::remove(site, did);
+            }
+        }
+
+        // blocked registrars stay blocked
+        read += 1;
+        if <Registrar<T>>::get(did) == Some(true) {
+            write += 1;
+            <Registrar<T>>::remove(did);
+        }
+
+        let id = <T as Config>::PalletId::get();
+
+        read += 1;
+        write += 1;
+        T::Currency::unreserve_all_named(&id.0, account);
+
+        T::DbWeight::get().reads_writes(read, write)
+    }
+
+    fn max_weight() -> Weight {
+        let sites = NETWORKS.len() as Weight;
+        let links = ProfilesOf::bound() as Weight;
+
+        T::DbWeight::get().reads_writes(2 * sites + 2, sites * (links + 3) + 2)
+    }
+}
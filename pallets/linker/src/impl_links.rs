@@ -7,8 +7,11 @@ impl<T: Config> Links<DidOf<T>> for Pallet<T> {
     fn all_links(did: &DidOf<T>) -> BTreeMap<Network, Vec<Vec<u8>>> {
         let mut links = BTreeMap::<Network, Vec<Vec<u8>>>::new();
 
-        for (network, link) in <LinksOf<T>>::iter_prefix(did) {
-            links.entry(network).or_default().push(link);
+        for (network, profiles) in <LinksOf<T>>::iter_prefix(did) {
+            links
+                .entry(network)
+                .or_default()
+                .extend(profiles.into_iter().map(|link| link.profile));
         }
 
         links
@@ -16,7 +19,7 @@ impl<T: Config> Links<DidOf<T>> for Pallet<T> {
 
     fn links(did: &DidOf<T>, network: Network) -> Vec<Vec<u8>> {
         <LinksOf<T>>::get(did, network)
-            .map(|link| vec![link])
+            .map(|links| links.into_iter().map(|link| link.profile).collect())
             .unwrap_or_default()
     }
 }
@@ -0,0 +1,25 @@
+use crate::{AccountOf, Config, Pallet};
+
+use frame_support::{
+    dispatch::DispatchResult,
+    traits::{Currency, ExistenceRequirement::AllowDeath, Get, NamedReservableCurrency},
+};
+use parami_traits::transferable::Transferable;
+use sp_runtime::traits::Zero;
+
+impl<T: Config> Transferable<AccountOf<T>> for Pallet<T> {
+    fn transfer_all(src: &AccountOf<T>, dest: &AccountOf<T>) -> DispatchResult {
+        let id = <T as Config>::PalletId::get();
+
+        let reserved = T::Currency::reserved_balance_named(&id.0, src);
+        if reserved.is_zero() {
+            return Ok(());
+        }
+
+        T::Currency::unreserve_named(&id.0, src, reserved);
+        T::Currency::transfer(src, dest, reserved, AllowDeath)?;
+        T::Currency::reserve_named(&id.0, dest, reserved)?;
+
+        Ok(())
+    }
+}
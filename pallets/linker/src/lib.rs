@@ -18,27 +18,33 @@ mod benchmarking;
 mod btc;
 mod did;
 mod functions;
+mod impl_did_revoked;
 mod impl_links;
-mod migrations;
+mod impl_transferable;
+pub mod migrations;
 mod ocw;
 mod types;
 
 pub use btc::hashing;
+pub use types::{Link, Proof};
 
+use codec::Encode;
 use frame_support::{
     dispatch::{DispatchResult, DispatchResultWithPostInfo},
     ensure,
-    traits::{Currency, NamedReservableCurrency, OnUnbalanced, StorageVersion},
-    Blake2_256, PalletId, StorageHasher,
+    traits::{ConstU32, Currency, NamedReservableCurrency, OnUnbalanced, StorageVersion},
+    Blake2_256, BoundedVec, PalletId, StorageHasher,
 };
 use frame_system::offchain::SendTransactionTypes;
-use parami_did::{EnsureDid, Pallet as Did};
-use parami_primitives::signature::recover_address;
+use parami_did::{EnsureDid, LinkerScope, Pallet as Did};
+use parami_primitives::signature::{
+    parse_near_named_account, recover_address_with, Action, SignatureScheme,
+};
 use parami_traits::{
     types::{Network, Task},
     Tags,
 };
-use sp_runtime::traits::Hash;
+use sp_runtime::traits::{Hash, SaturatedConversion, Zero};
 use sp_std::prelude::*;
 
 use weights::WeightInfo;
@@ -49,11 +55,14 @@ type BalanceOf<T> = <CurrencyOf<T> as Currency<AccountOf<T>>>::Balance;
 type CurrencyOf<T> = <T as parami_did::Config>::Currency;
 type DidOf<T> = <T as parami_did::Config>::DecentralizedId;
 type HeightOf<T> = <T as frame_system::Config>::BlockNumber;
+type LabelOf = BoundedVec<u8, ConstU32<32>>;
+type LinkOf = types::Link<LabelOf>;
 type NegativeImbOf<T> = <CurrencyOf<T> as Currency<AccountOf<T>>>::NegativeImbalance;
+type ProfilesOf = BoundedVec<LinkOf, ConstU32<8>>;
 type TagOf = <Blake2_256 as StorageHasher>::Output;
 type TaskOf<T> = Task<Vec<u8>, HeightOf<T>>;
 
-const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -106,7 +115,7 @@ pub mod pallet {
     #[pallet::without_storage_info]
     pub struct Pallet<T>(_);
 
-    /// Linked accounts of a DID
+    /// Linked accounts of a DID, up to 8 of each network
     #[pallet::storage]
     #[pallet::getter(fn links_of)]
     pub(super) type LinksOf<T: Config> = StorageDoubleMap<
@@ -115,7 +124,7 @@ pub mod pallet {
         DidOf<T>,
         Twox64Concat,
         Network,
-        Vec<u8>, //
+        ProfilesOf, //
     >;
 
     /// Accounts pending to be checked with the offchain worker
@@ -130,6 +139,12 @@ pub mod pallet {
         TaskOf<T>, //
     >;
 
+    /// Proofs of the pending accounts, avatar by default
+    #[pallet::storage]
+    #[pallet::getter(fn proof_of)]
+    pub(super) type ProofOf<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, Network, Identity, DidOf<T>, types::Proof, ValueQuery>;
+
     /// Linked accounts
     #[pallet::storage]
     #[pallet::getter(fn linked)]
@@ -155,10 +170,12 @@ pub mod pallet {
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
+        /// Account labelled \[did, type, account, label\]
+        AccountLabelled(DidOf<T>, Network, Vec<u8>, Vec<u8>),
         /// Account linked \[did, type, account, by\]
         AccountLinked(DidOf<T>, Network, Vec<u8>, DidOf<T>),
-        /// Account unlinked \[did, type, by\]
-        AccountUnlinked(DidOf<T>, Network, DidOf<T>),
+        /// Account unlinked \[did, type, account, by\]
+        AccountUnlinked(DidOf<T>, Network, Vec<u8>, DidOf<T>),
         /// Registrar was blocked \[id\]
         Blocked(DidOf<T>),
         /// Registrar deposited \[id, value\]
@@ -187,9 +204,13 @@ pub mod pallet {
         Deadline,
         ExistentialDeposit,
         Exists,
+        Expired,
         InvalidAddress,
+        InvalidProof,
         InvalidSignature,
         NotExists,
+        TooLong,
+        TooManyLinks,
         UnexpectedAddress,
         UnsupportedSite,
         NotAuthroized,
@@ -217,15 +238,25 @@ pub mod pallet {
         ///
         /// * `site` - Account type
         /// * `profile` - Profile URL
-        #[pallet::weight(<T as Config>::WeightInfo::link_sociality(profile.len() as u32))]
+        /// * `proof` - Proof type
+        ///   * `Avatar` - the DID is encoded in the avatar of the profile
+        ///   * `Text(post)` - the post of the profile contains the signed `text_proof`,
+        ///     for Telegram and Twitter only
+        #[pallet::weight(<T as Config>::WeightInfo::link_sociality((profile.len() + proof.encoded_size()) as u32))]
         pub fn link_sociality(
             origin: OriginFor<T>,
             site: Network,
             profile: Vec<u8>,
+            proof: types::Proof,
         ) -> DispatchResult {
-            let (did, _) = EnsureDid::<T>::ensure_origin(origin)?;
+            let (did, _) = EnsureDid::<T, LinkerScope>::ensure_origin(origin)?;
+
+            ensure!(
+                !matches!(proof, types::Proof::NearAccessKey(_)),
+                Error::<T>::InvalidProof
+            );
 
-            Self::insert_pending(did, site, profile)
+            Self::insert_pending(did, site, profile, proof)
         }
 
         /// Link a cryptographic account to a DID
@@ -235,31 +266,118 @@ pub mod pallet {
         ///
         /// * `crypto` - Account type
         /// * `address` - Account address
-        ///   * When dealing with BTC, DOT, SOL, TRX, the address should in the format of base58
+        ///   * When dealing with BTC, DOT, KSM, SOL, TRX, the address should in the format of base58
         ///   * When dealing with ETH, the address should in the format of binary or hex
+        ///   * When dealing with EOS, the address should be the public key, legacy or `PUB_K1_`
+        ///   * When dealing with NEAR, the address should be an implicit account,
+        ///     or a named account in the format of `{account_id}:ed25519:{base58}`,
+        ///     the link of a named account will become pending until the key is confirmed
+        ///     as a full access key of the account, with the offchain worker or a registrar
+        ///   * When dealing with ATOM, the address should in the format of bech32
         /// * `signature` - Account signature
-        ///   * When dealing with DOT, SOL, the signature should have a prefix of `0x00`
+        ///   * When dealing with DOT, KSM, NEAR, SOL, the signature should have a prefix of `0x00`
+        ///   * When dealing with ATOM, the signature should have a suffix of `0x00`
+        ///   * When dealing with BTC Taproot, the signature should be the BIP-322 Schnorr signature,
+        ///     followed by the sighash type
+        /// * `scheme` - Signature scheme
+        ///   * `Plain` - the signed message is `Link: did:ad3:{base58}`
+        ///   * `Eip712` - the signed message is the EIP-712 typed data, for BSC and ETH only
         #[pallet::weight(<T as Config>::WeightInfo::link_crypto())]
         pub fn link_crypto(
             origin: OriginFor<T>,
             crypto: Network,
             address: Vec<u8>,
             signature: parami_primitives::signature::Signature,
+            scheme: SignatureScheme,
         ) -> DispatchResult {
-            let (did, _) = EnsureDid::<T>::ensure_origin(origin)?;
+            let (did, _) = EnsureDid::<T, LinkerScope>::ensure_origin(origin)?;
 
             ensure!(address.len() >= 2, Error::<T>::InvalidAddress);
 
-            let bytes = parami_primitives::signature::generate_message(&did);
+            if let SignatureScheme::Eip712 { expiry } = scheme {
+                let height = <frame_system::Pallet<T>>::block_number();
+                ensure!(
+                    height.saturated_into::<u64>() <= expiry,
+                    Error::<T>::Expired
+                );
+            }
+
+            let genesis = <frame_system::Pallet<T>>::block_hash(HeightOf::<T>::zero());
 
-            let recovered = recover_address(crypto, address.clone(), signature, bytes)
-                .map_err(|e| Into::<Error<T>>::into(e))?;
+            let recovered = recover_address_with(
+                scheme,
+                crypto,
+                address.clone(),
+                signature,
+                genesis.as_ref(),
+                &did,
+                Action::Link,
+            )
+            .map_err(|e| Into::<Error<T>>::into(e))?;
 
             ensure!(recovered == address, Error::<T>::UnexpectedAddress);
 
+            if crypto == Network::Near {
+                if let Some((account, key)) = parse_near_named_account(&address) {
+                    return Self::insert_pending(
+                        did,
+                        crypto,
+                        account.to_vec(),
+                        types::Proof::NearAccessKey(key.to_vec()),
+                    );
+                }
+            }
+
             Self::insert_link(did, crypto, address, did)
         }
 
+        /// Unlink an account from a DID
+        ///
+        /// # Arguments
+        ///
+        /// * `site` - Account type
+        /// * `profile` - Profile URL or account address
+        #[pallet::weight(<T as Config>::WeightInfo::unlink())]
+        pub fn unlink(origin: OriginFor<T>, site: Network, profile: Vec<u8>) -> DispatchResult {
+            let (did, _) = EnsureDid::<T, LinkerScope>::ensure_origin(origin)?;
+
+            Self::remove_link(did, site, profile, did)
+        }
+
+        /// Label a linked account, e.g. `Cold Wallet`
+        ///
+        /// # Arguments
+        ///
+        /// * `site` - Account type
+        /// * `profile` - Profile URL or account address
+        /// * `label` - Label, up to 32 bytes, empty to clear
+        #[pallet::weight(<T as Config>::WeightInfo::set_label())]
+        pub fn set_label(
+            origin: OriginFor<T>,
+            site: Network,
+            profile: Vec<u8>,
+            label: Vec<u8>,
+        ) -> DispatchResult {
+            let (did, _) = EnsureDid::<T, LinkerScope>::ensure_origin(origin)?;
+
+            let bounded = LabelOf::try_from(label.clone()).map_err(|_| Error::<T>::TooLong)?;
+
+            <LinksOf<T>>::try_mutate(&did, site, |maybe_links| -> DispatchResult {
+                let link = maybe_links
+                    .as_mut()
+                    .and_then(|links| links.iter_mut().find(|link| link.profile == profile))
+                    .ok_or(Error::<T>::NotExists)?;
+
+                link.label = bounded;
+
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::<T>::AccountLabelled(did, site, profile, label));
+
+            Ok(())
+        }
+
         #[pallet::weight(1_000_000)]
         pub fn submit_register(
             origin: OriginFor<T>,
@@ -419,16 +537,18 @@ pub mod pallet {
         pub fn force_unlink(origin: OriginFor<T>, did: DidOf<T>, site: Network) -> DispatchResult {
             T::ForceOrigin::ensure_origin(origin)?;
 
-            let link = <LinksOf<T>>::get(&did, site).ok_or(Error::<T>::NotExists)?;
+            let links = <LinksOf<T>>::take(&did, site).ok_or(Error::<T>::NotExists)?;
 
-            <LinksOf<T>>::remove(&did, site);
-            <Linked<T>>::remove(site, &link);
+            for link in links {
+                <Linked<T>>::remove(site, &link.profile);
 
-            Self::deposit_event(Event::<T>::AccountUnlinked(
-                did,
-                site,
-                DidOf::<T>::default(),
-            ));
+                Self::deposit_event(Event::<T>::AccountUnlinked(
+                    did,
+                    site,
+                    link.profile,
+                    DidOf::<T>::default(),
+                ));
+            }
 
             Ok(())
         }
@@ -494,7 +614,12 @@ pub mod pallet {
     impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
         fn build(&self) {
             for (did, typ, dat) in &self.links {
-                <LinksOf<T>>::insert(did, typ, dat);
+                <LinksOf<T>>::mutate(did, typ, |maybe_links| {
+                    let links = maybe_links.get_or_insert_with(Default::default);
+                    links
+                        .try_push(LinkOf::new(dat.clone()))
+                        .expect("too many links");
+                });
                 <Linked<T>>::insert(typ, dat, true);
             }
 
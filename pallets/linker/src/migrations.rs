@@ -1 +1,27 @@
+pub mod v3 {
+    use frame_support::traits::{Get, OnRuntimeUpgrade};
+    use frame_support::weights::Weight;
+    use sp_std::prelude::*;
 
+    use crate::{Config, LinkOf, LinksOf, Pallet, ProfilesOf, StorageVersion};
+
+    pub struct MultipleLinks<T>(sp_std::marker::PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MultipleLinks<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let version = StorageVersion::get::<Pallet<T>>();
+            if version > 2 {
+                return 0;
+            }
+
+            let mut count = 0u64;
+            <LinksOf<T>>::translate_values::<Vec<u8>, _>(|profile| {
+                count += 1;
+                ProfilesOf::try_from(sp_std::vec![LinkOf::new(profile)]).ok()
+            });
+
+            StorageVersion::new(3).put::<Pallet<T>>();
+            T::DbWeight::get().reads_writes(count + 1, count + 1)
+        }
+    }
+}
@@ -1,11 +1,25 @@
-use crate::{did, types, Call, Config, Error, HeightOf, Pallet, PendingOf};
+use crate::{did, types, Call, Config, Error, HeightOf, Pallet, PendingOf, ProofOf};
 use codec::Encode;
-use frame_support::dispatch::DispatchResult;
+use frame_support::{dispatch::DispatchResult, ensure};
 use frame_system::offchain::{SendTransactionTypes, SubmitTransaction};
-use parami_ocw::{submit_unsigned, Pallet as Ocw};
+use parami_did::Pallet as Did;
+use parami_ocw::{submit_unsigned, JsonValue, Pallet as Ocw, Response};
+use parami_primitives::{
+    did::{to_did_uri, PublicKey, VerificationRelationship},
+    signature::parse_text_proof,
+};
+use parami_traits::types::Network;
+use sp_runtime::{offchain::storage::StorageValueRef, traits::Saturating, DispatchError};
 use sp_runtime_interface::runtime_interface;
 use sp_std::prelude::*;
 
+/// JSON-RPC endpoint of the NEAR mainnet
+const NEAR_RPC: &str = "https://rpc.mainnet.near.org";
+
+/// Attempts on unavailable sites before a link is left to expire,
+/// the n-th retry waits 2^n blocks
+const MAX_RETRIES: u32 = 5;
+
 #[runtime_interface]
 pub trait Images {
     fn decode_jpeg(data: &[u8]) -> Option<types::RawImage> {
@@ -42,37 +56,77 @@ impl<T: Config + SendTransactionTypes<Call<T>>> Pallet<T> {
     pub fn ocw_begin_block(block_number: HeightOf<T>) -> DispatchResult {
         use parami_traits::types::Network::*;
 
-        for site in [Telegram, Twitter] {
+        // Discord and Facebook have no public profiles, they are left to registrars
+        for site in [
+            Telegram, Twitter, Github, Reddit, HackerNews, Mastodon, Near,
+        ] {
             let pending = <PendingOf<T>>::iter_prefix(site);
 
             for (did, task) in pending {
+                let key = Self::ocw_retry_key(site, &did, task.created);
+                let mut retry = StorageValueRef::persistent(&key);
+
                 if task.deadline <= block_number {
                     // call to remove
                     Self::ocw_submit_link(did, site, task.task, false);
 
-                    return Err(Error::<T>::Deadline)?;
+                    retry.clear();
+
+                    continue;
                 }
 
-                if task.created < block_number {
-                    // only start once (at created + 1)
+                let (attempts, next_attempt) = retry
+                    .get::<(u32, HeightOf<T>)>()
+                    .ok()
+                    .flatten()
+                    .unwrap_or((0, task.created));
+
+                if block_number < next_attempt {
                     continue;
                 }
 
                 let profile = sp_std::str::from_utf8(&task.task).unwrap_or_default();
 
-                let result = match site {
-                    Telegram => Self::ocw_verify_telegram(did, profile),
-                    Twitter => Self::ocw_verify_twitter(did, profile),
+                let result = match (site, <ProofOf<T>>::get(site, &did)) {
+                    (_, types::Proof::Text(post)) => {
+                        let post = sp_std::str::from_utf8(&post).unwrap_or_default();
+
+                        Self::ocw_verify_text(did, site, profile, post)
+                    }
+                    (Telegram, _) => Self::ocw_verify_telegram(did, profile),
+                    (Twitter, _) => Self::ocw_verify_twitter(did, profile),
+                    (Github, _) => Self::ocw_verify_github(did, profile),
+                    (Reddit, _) => Self::ocw_verify_reddit(did, profile),
+                    (HackerNews, _) => Self::ocw_verify_hackernews(did, profile),
+                    (Mastodon, _) => Self::ocw_verify_mastodon(did, profile),
+                    (Near, types::Proof::NearAccessKey(key)) => {
+                        let key = sp_std::str::from_utf8(&key).unwrap_or_default();
+
+                        Self::ocw_verify_near(profile, key)
+                    }
+                    (Near, _) => Err(Error::<T>::InvalidProof.into()),
                     _ => {
                         // drop unsupported sites
                         Self::ocw_submit_link(did, site, task.task, false);
 
+                        retry.clear();
+
                         continue;
                     }
                 };
 
-                if let Ok(()) = result {
-                    Self::ocw_submit_link(did, site, task.task, true);
+                match result {
+                    Ok(()) => {
+                        Self::ocw_submit_link(did, site, task.task, true);
+
+                        retry.clear();
+                    }
+                    Err(e) if Self::ocw_unavailable(&e) && attempts + 1 < MAX_RETRIES => {
+                        let backoff: HeightOf<T> = (1u32 << attempts).into();
+                        retry.set(&(attempts + 1, block_number.saturating_add(backoff)));
+                    }
+                    // not attempted again, left to expire
+                    Err(_) => retry.set(&(attempts + 1, task.deadline)),
                 }
             }
         }
@@ -80,6 +134,23 @@ impl<T: Config + SendTransactionTypes<Call<T>>> Pallet<T> {
         Ok(())
     }
 
+    fn ocw_retry_key(site: Network, did: &T::DecentralizedId, created: HeightOf<T>) -> Vec<u8> {
+        let mut key = b"parami_linker::link_retry".to_vec();
+        key.append(&mut (site, did, created).encode());
+        key
+    }
+
+    /// Whether the site could not be reached, rather than the profile failed the verification
+    fn ocw_unavailable(e: &DispatchError) -> bool {
+        [
+            parami_ocw::Error::<T>::RequestError,
+            parami_ocw::Error::<T>::ResponseError,
+            parami_ocw::Error::<T>::HttpError,
+        ]
+        .into_iter()
+        .any(|error| *e == DispatchError::from(error))
+    }
+
     pub(super) fn ocw_submit_link(
         did: T::DecentralizedId,
         site: parami_traits::types::Network,
@@ -142,6 +213,213 @@ impl<T: Config + SendTransactionTypes<Call<T>>> Pallet<T> {
         Self::ocw_check_avatar(avatar, did)
     }
 
+    /// Verify a post authored by the profile containing the signed text proof
+    ///
+    /// The author is taken from the canonical URL of the post,
+    /// and only the text of the post is checked, so replies and comments are ignored.
+    pub(super) fn ocw_verify_text<U: AsRef<str>>(
+        did: T::DecentralizedId,
+        site: parami_traits::types::Network,
+        profile: U,
+        post: U,
+    ) -> DispatchResult {
+        let profile = profile.as_ref().as_bytes();
+
+        let prefix = Self::post_prefix(site, profile).ok_or(Error::<T>::InvalidProof)?;
+
+        let res = Ocw::<T>::ocw_get(post)?;
+
+        let res = res.text();
+
+        let canonical = html_meta(&res, "property=\"og:url\"")
+            .or_else(|| html_link(&res, "rel=\"canonical\""))
+            .ok_or(Error::<T>::InvalidProof)?;
+        let canonical = canonical.as_bytes();
+
+        ensure!(
+            canonical.len() > prefix.len()
+                && canonical[..prefix.len()].eq_ignore_ascii_case(&prefix)
+                && canonical[prefix.len()..].iter().all(u8::is_ascii_digit),
+            Error::<T>::InvalidProof
+        );
+
+        let text =
+            html_meta(&res, "property=\"og:description\"").ok_or(Error::<T>::InvalidProof)?;
+
+        let signature = parse_text_proof(text.as_bytes(), did).ok_or(Error::<T>::InvalidProof)?;
+
+        let message = Self::text_proof(&did, site, profile);
+
+        let mut keys: Vec<PublicKey> = Did::<T>::resolve(did)?
+            .verification_methods
+            .into_iter()
+            .filter(|method| {
+                method
+                    .purposes
+                    .contains(&VerificationRelationship::Authentication)
+            })
+            .map(|method| method.public_key)
+            .collect();
+
+        if let Some(account) = Did::<T>::lookup_did(did) {
+            if let Ok(account) = <[u8; 32]>::try_from(&account.encode()[..]) {
+                keys.push(PublicKey::Sr25519(account));
+            }
+        }
+
+        ensure!(
+            keys.iter().any(|key| key.verify(&signature, &message)),
+            Error::<T>::InvalidSignature
+        );
+
+        Ok(())
+    }
+
+    /// Verify the public key, e.g. `ed25519:{base58}`, is a full access key of a NEAR account
+    pub(super) fn ocw_verify_near<U: AsRef<str>>(account: U, key: U) -> DispatchResult {
+        let body = [
+            r#"{"jsonrpc":"2.0","id":"parami","method":"query","params":{"#,
+            r#""request_type":"view_access_key","finality":"final","account_id":""#,
+            account.as_ref(),
+            r#"","public_key":""#,
+            key.as_ref(),
+            r#""}}"#,
+        ]
+        .concat();
+
+        let res = Ocw::<T>::ocw_post(NEAR_RPC, body.into_bytes())?;
+        let res = json_response::<T>(&res)?;
+
+        // function call access keys are limited to contracts, only full access keys are accepted
+        match json_get(&res, &["result", "permission"]) {
+            Some(JsonValue::String(permission))
+                if permission.iter().copied().eq("FullAccess".chars()) =>
+            {
+                Ok(())
+            }
+            _ => Err(Error::<T>::InvalidSignature)?,
+        }
+    }
+
+    /// Verify a GitHub profile with the DID in its bio, or in the description of a public gist
+    pub(super) fn ocw_verify_github<U: AsRef<str>>(
+        did: T::DecentralizedId,
+        profile: U,
+    ) -> DispatchResult {
+        let username = Self::ocw_username(profile.as_ref(), "https://github.com/")?;
+
+        let res = Ocw::<T>::ocw_get(["https://api.github.com/users/", username].concat())?;
+        let res = json_response::<T>(&res)?;
+
+        if json_contains_did(&res, &["bio"], did) {
+            return Ok(());
+        }
+
+        let res =
+            Ocw::<T>::ocw_get(["https://api.github.com/users/", username, "/gists"].concat())?;
+
+        match json_response::<T>(&res)? {
+            JsonValue::Array(gists)
+                if gists
+                    .iter()
+                    .any(|gist| json_contains_did(gist, &["description"], did)) =>
+            {
+                Ok(())
+            }
+            _ => Err(Error::<T>::InvalidSignature)?,
+        }
+    }
+
+    /// Verify a Reddit profile with the DID in its about
+    pub(super) fn ocw_verify_reddit<U: AsRef<str>>(
+        did: T::DecentralizedId,
+        profile: U,
+    ) -> DispatchResult {
+        let username = Self::ocw_username(profile.as_ref(), "https://www.reddit.com/user/")?;
+
+        let res =
+            Ocw::<T>::ocw_get(["https://www.reddit.com/user/", username, "/about.json"].concat())?;
+        let res = json_response::<T>(&res)?;
+
+        ensure_json_contains_did::<T>(&res, &["data", "subreddit", "public_description"], did)
+    }
+
+    /// Verify a HackerNews profile with the DID in its about
+    pub(super) fn ocw_verify_hackernews<U: AsRef<str>>(
+        did: T::DecentralizedId,
+        profile: U,
+    ) -> DispatchResult {
+        let username =
+            Self::ocw_username(profile.as_ref(), "https://news.ycombinator.com/user?id=")?;
+
+        let res = Ocw::<T>::ocw_get(
+            [
+                "https://hacker-news.firebaseio.com/v0/user/",
+                username,
+                ".json",
+            ]
+            .concat(),
+        )?;
+        let res = json_response::<T>(&res)?;
+
+        ensure_json_contains_did::<T>(&res, &["about"], did)
+    }
+
+    /// Verify a Mastodon profile with the DID in its bio, or in a `rel="me"` link
+    pub(super) fn ocw_verify_mastodon<U: AsRef<str>>(
+        did: T::DecentralizedId,
+        profile: U,
+    ) -> DispatchResult {
+        let profile = profile.as_ref();
+
+        ensure!(
+            profile.starts_with("https://") && profile.contains("/@"),
+            Error::<T>::UnsupportedSite
+        );
+
+        let res = Ocw::<T>::ocw_get(profile)?;
+
+        let res = res.text();
+
+        let uri = to_did_uri(did);
+        let uri = sp_std::str::from_utf8(&uri).unwrap_or_default();
+
+        let in_bio = res
+            .split("<meta ")
+            .skip(1)
+            .filter_map(|meta| meta.split('>').next())
+            .filter(|meta| meta.contains("property=\"og:description\""))
+            .any(|meta| meta.contains(uri));
+
+        let in_links = res
+            .split("<a ")
+            .skip(1)
+            .filter_map(|tag| tag.split('>').next())
+            .filter(|tag| tag.contains("rel=\"me"))
+            .any(|tag| tag.contains(uri));
+
+        ensure!(in_bio || in_links, Error::<T>::InvalidSignature);
+
+        Ok(())
+    }
+
+    fn ocw_username<'a>(profile: &'a str, prefix: &str) -> Result<&'a str, DispatchError> {
+        let username = profile
+            .strip_prefix(prefix)
+            .ok_or(Error::<T>::UnsupportedSite)?
+            .trim_end_matches('/');
+
+        ensure!(
+            !username.is_empty()
+                && username
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'),
+            Error::<T>::UnsupportedSite
+        );
+
+        Ok(username)
+    }
+
     pub(self) fn ocw_check_avatar<U: AsRef<str>>(
         avatar: U,
         did: T::DecentralizedId,
@@ -158,3 +436,67 @@ impl<T: Config + SendTransactionTypes<Call<T>>> Pallet<T> {
         }
     }
 }
+
+/// Get the content of the first `<meta>` tag with `attr`, e.g. `property="og:url"`
+fn html_meta<'a>(html: &'a str, attr: &str) -> Option<&'a str> {
+    html_attr(html, "<meta ", attr, "content=\"")
+}
+
+/// Get the href of the first `<link>` tag with `attr`, e.g. `rel="canonical"`
+fn html_link<'a>(html: &'a str, attr: &str) -> Option<&'a str> {
+    html_attr(html, "<link ", attr, "href=\"")
+}
+
+fn html_attr<'a>(html: &'a str, tag: &str, attr: &str, value: &str) -> Option<&'a str> {
+    html.split(tag)
+        .skip(1)
+        .filter_map(|tag| tag.split('>').next())
+        .find(|tag| tag.contains(attr))
+        .and_then(|tag| tag.split(value).nth(1))
+        .and_then(|rest| rest.split('"').next())
+}
+
+/// Parse the response of a JSON API,
+/// anything else, e.g. an error page of a gateway or a rate limiter, is unavailable
+fn json_response<T: Config>(res: &Response) -> Result<JsonValue, DispatchError> {
+    match res.json() {
+        json @ (JsonValue::Object(_) | JsonValue::Array(_)) => Ok(json),
+        _ => Err(parami_ocw::Error::<T>::ResponseError)?,
+    }
+}
+
+/// Get the field at `path` of a JSON object
+fn json_get<'a>(value: &'a JsonValue, path: &[&str]) -> Option<&'a JsonValue> {
+    path.iter().try_fold(value, |value, key| match value {
+        JsonValue::Object(fields) => fields
+            .iter()
+            .find(|(name, _)| name.iter().copied().eq(key.chars()))
+            .map(|(_, value)| value),
+        _ => None,
+    })
+}
+
+/// Check if the string field at `path` of a JSON object contains the DID URI
+fn json_contains_did<D: AsRef<[u8]>>(value: &JsonValue, path: &[&str], did: D) -> bool {
+    let text = match json_get(value, path) {
+        Some(JsonValue::String(text)) => text,
+        _ => return false,
+    };
+
+    let uri: Vec<char> = to_did_uri(did).into_iter().map(char::from).collect();
+
+    text.windows(uri.len()).any(|window| window == &uri[..])
+}
+
+fn ensure_json_contains_did<T: Config>(
+    value: &JsonValue,
+    path: &[&str],
+    did: T::DecentralizedId,
+) -> DispatchResult {
+    ensure!(
+        json_contains_did(value, path, did),
+        Error::<T>::InvalidSignature
+    );
+
+    Ok(())
+}
@@ -1,8 +1,10 @@
-use crate::{mock::*, Config, Error, Linked, LinksOf, PendingOf, Registrar};
+use crate::{mock::*, Config, Error, Linked, LinksOf, PendingOf, Proof, ProofOf, Registrar};
 use codec::Decode;
 use frame_support::{assert_noop, assert_ok, traits::Hooks};
 use parami_ocw::USER_AGENT;
-use parami_traits::types::Network;
+use parami_primitives::did::{PublicKey, VerificationRelationship};
+use parami_primitives::signature::SignatureScheme;
+use parami_traits::{types::Network, Links};
 use sp_core::offchain::{testing, OffchainWorkerExt, TransactionPoolExt};
 
 macro_rules! assert_tx {
@@ -23,6 +25,7 @@ fn should_link() {
             DID_ALICE,
             Network::Telegram,
             profile.clone(),
+            Proof::Avatar,
         ));
 
         let maybe_pending = <PendingOf<Test>>::get(Network::Telegram, &DID_ALICE);
@@ -43,10 +46,7 @@ fn should_link() {
 
         assert!(<Linked<Test>>::get(Network::Telegram, &profile));
 
-        assert_eq!(
-            <LinksOf<Test>>::get(&DID_ALICE, Network::Telegram),
-            Some(profile)
-        );
+        assert_eq!(Linker::links(&DID_ALICE, Network::Telegram), vec![profile]);
     })
 }
 
@@ -54,12 +54,12 @@ fn should_link() {
 fn should_fail_when_exists() {
     new_test_ext().execute_with(|| {
         assert_noop!(
-            Linker::insert_pending(DID_ALICE, Network::Polkadot, POLKA.to_vec()),
+            Linker::insert_pending(DID_ALICE, Network::Polkadot, POLKA.to_vec(), Proof::Avatar),
             Error::<Test>::Exists
         );
 
         assert_noop!(
-            Linker::insert_pending(DID_BOB, Network::Polkadot, POLKA.to_vec()),
+            Linker::insert_pending(DID_BOB, Network::Polkadot, POLKA.to_vec(), Proof::Avatar),
             Error::<Test>::Exists
         );
     })
@@ -115,10 +115,7 @@ fn should_submit() {
 
         assert!(<Linked<Test>>::get(Network::Telegram, &profile));
 
-        assert_eq!(
-            <LinksOf<Test>>::get(&DID_ALICE, Network::Telegram),
-            Some(profile)
-        );
+        assert_eq!(Linker::links(&DID_ALICE, Network::Telegram), vec![profile]);
     })
 }
 
@@ -131,6 +128,7 @@ fn should_submit_when_pending() {
             Origin::signed(ALICE),
             Network::Telegram,
             profile.clone(),
+            Proof::Avatar,
         ));
 
         assert_ne!(<PendingOf<Test>>::get(Network::Telegram, &DID_ALICE), None);
@@ -177,6 +175,7 @@ fn should_link_sociality() {
             Origin::signed(ALICE),
             Network::Telegram,
             profile.clone(),
+            Proof::Avatar,
         ));
 
         Linker::offchain_worker(0);
@@ -197,6 +196,148 @@ fn should_link_sociality() {
     });
 }
 
+#[test]
+fn should_retry_link_when_site_unavailable() {
+    const USER: &[u8] = include_bytes!("../artifacts/github_user.json");
+    const GISTS: &[u8] = include_bytes!("../artifacts/github_gists.json");
+
+    let (offchain, state) = testing::TestOffchainExt::new();
+    let (pool, tx) = testing::TestTransactionPoolExt::new();
+
+    {
+        let mut state = state.write();
+        expect_get(
+            &mut state,
+            "https://api.github.com/users/AmeliaParami",
+            b"<html><body>502 Bad Gateway</body></html>",
+        );
+        expect_get(
+            &mut state,
+            "https://api.github.com/users/AmeliaParami",
+            USER,
+        );
+        expect_get(
+            &mut state,
+            "https://api.github.com/users/AmeliaParami/gists",
+            GISTS,
+        );
+    }
+
+    let profile = b"https://github.com/AmeliaParami".to_vec();
+
+    let mut t = new_test_ext();
+    t.register_extension(OffchainWorkerExt::new(offchain));
+    t.register_extension(TransactionPoolExt::new(pool));
+
+    t.execute_with(|| {
+        assert_ok!(Linker::link_sociality(
+            Origin::signed(ALICE),
+            Network::Github,
+            profile.clone(),
+            Proof::Avatar,
+        ));
+
+        Linker::offchain_worker(0);
+        assert!(tx.read().transactions.is_empty());
+
+        // backing off, no request is sent
+        Linker::offchain_worker(0);
+        assert!(tx.read().transactions.is_empty());
+
+        Linker::offchain_worker(1);
+
+        let tx = tx.write().transactions.pop().unwrap();
+
+        assert_tx!(
+            tx,
+            Call::Linker(crate::Call::submit_link {
+                did: DID_ALICE,
+                site: Network::Github,
+                profile: profile,
+                validated: true,
+            })
+        );
+    });
+}
+
+#[test]
+fn should_continue_after_expired_link() {
+    const USER: &[u8] = include_bytes!("../artifacts/github_user.json");
+    const GISTS: &[u8] = include_bytes!("../artifacts/github_gists.json");
+
+    let (offchain, state) = testing::TestOffchainExt::new();
+    let (pool, tx) = testing::TestTransactionPoolExt::new();
+
+    {
+        let mut state = state.write();
+        expect_get(
+            &mut state,
+            "https://api.github.com/users/AmeliaParami",
+            USER,
+        );
+        expect_get(
+            &mut state,
+            "https://api.github.com/users/AmeliaParami/gists",
+            GISTS,
+        );
+    }
+
+    let telegram = b"https://t.me/AmeliaParami".to_vec();
+    let github = b"https://github.com/AmeliaParami".to_vec();
+
+    let mut t = new_test_ext();
+    t.register_extension(OffchainWorkerExt::new(offchain));
+    t.register_extension(TransactionPoolExt::new(pool));
+
+    t.execute_with(|| {
+        assert_ok!(Linker::link_sociality(
+            Origin::signed(ALICE),
+            Network::Telegram,
+            telegram.clone(),
+            Proof::Avatar,
+        ));
+
+        let deadline = <PendingOf<Test>>::get(Network::Telegram, &DID_ALICE)
+            .unwrap()
+            .deadline;
+
+        System::set_block_number(deadline);
+
+        assert_ok!(Linker::link_sociality(
+            Origin::signed(ALICE),
+            Network::Github,
+            github.clone(),
+            Proof::Avatar,
+        ));
+
+        Linker::offchain_worker(deadline);
+
+        let mut tx = tx.write();
+
+        let linked = tx.transactions.pop().unwrap();
+        assert_tx!(
+            linked,
+            Call::Linker(crate::Call::submit_link {
+                did: DID_ALICE,
+                site: Network::Github,
+                profile: github,
+                validated: true,
+            })
+        );
+
+        let expired = tx.transactions.pop().unwrap();
+        assert_tx!(
+            expired,
+            Call::Linker(crate::Call::submit_link {
+                did: DID_ALICE,
+                site: Network::Telegram,
+                profile: telegram,
+                validated: false,
+            })
+        );
+    });
+}
+
 #[test]
 fn should_verify_telegram() {
     const HTM: &[u8] = include_bytes!("../artifacts/telegram.html");
@@ -279,6 +420,314 @@ fn should_verify_twitter() {
     });
 }
 
+fn expect_get(state: &mut testing::OffchainState, uri: &str, response: &[u8]) {
+    state.expect_request(testing::PendingRequest {
+        method: "GET".into(),
+        uri: uri.into(),
+        headers: vec![("User-Agent".into(), USER_AGENT.into())],
+        response: Some(response.to_vec()),
+        sent: true,
+        ..Default::default()
+    });
+}
+
+#[test]
+fn should_verify_text() {
+    const HTM: &[u8] = include_bytes!("../artifacts/telegram_post.html");
+
+    let profile = b"https://t.me/AmeliaParami".to_vec();
+    let post = b"https://t.me/AmeliaParami/42".to_vec();
+
+    // the proof is only in a reply, not in the text of the post
+    let reply = sp_std::str::from_utf8(HTM)
+        .unwrap()
+        .replace(
+            "<meta property=\"og:description\" content=\"Link:",
+            "<meta property=\"og:description\" content=\"Reply:",
+        )
+        .into_bytes();
+
+    let (offchain, state) = testing::TestOffchainExt::new();
+    let (pool, tx) = testing::TestTransactionPoolExt::new();
+
+    {
+        let mut state = state.write();
+        expect_get(&mut state, "https://t.me/AmeliaParami/42", HTM);
+        expect_get(&mut state, "https://t.me/Victim/42", HTM);
+        expect_get(&mut state, "https://t.me/AmeliaParami/42", HTM);
+        expect_get(&mut state, "https://t.me/AmeliaParami/43", &reply);
+        expect_get(&mut state, "https://t.me/AmeliaParami/42", HTM);
+    }
+
+    let mut t = new_test_ext();
+    t.register_extension(OffchainWorkerExt::new(offchain));
+    t.register_extension(TransactionPoolExt::new(pool));
+
+    t.execute_with(|| {
+        assert_eq!(
+            Linker::text_proof(&DID_ALICE, Network::Telegram, &profile),
+            b"Link: did:ad3:hwtGPq42GojPtyx5ngtSRSpJfjN 5QT96d212bT7DKHFNbrdFKePSRB5ZcdPxkkseFgoC1cH"
+                .to_vec()
+        );
+
+        assert_noop!(
+            Linker::link_sociality(
+                Origin::signed(ALICE),
+                Network::Telegram,
+                profile.clone(),
+                Proof::Text(b"https://t.me/AmeliaParamiFake/42".to_vec()),
+            ),
+            Error::<Test>::InvalidProof
+        );
+
+        assert_noop!(
+            Linker::link_sociality(
+                Origin::signed(ALICE),
+                Network::Twitter,
+                b"https://twitter.com/AmeliaParami".to_vec(),
+                Proof::Text(b"https://twitter.com/AmeliaParami/likes".to_vec()),
+            ),
+            Error::<Test>::InvalidProof
+        );
+
+        assert_noop!(
+            Linker::link_sociality(
+                Origin::signed(ALICE),
+                Network::Github,
+                b"https://github.com/AmeliaParami".to_vec(),
+                Proof::Text(b"https://github.com/AmeliaParami/repo".to_vec()),
+            ),
+            Error::<Test>::InvalidProof
+        );
+
+        assert_noop!(
+            Linker::link_sociality(
+                Origin::signed(ALICE),
+                Network::Telegram,
+                profile.clone(),
+                Proof::Text([&post[..], &[b'0'; 256][..]].concat()),
+            ),
+            Error::<Test>::InvalidProof
+        );
+
+        assert_ok!(Linker::link_sociality(
+            Origin::signed(ALICE),
+            Network::Telegram,
+            profile.clone(),
+            Proof::Text(post.clone()),
+        ));
+
+        assert_eq!(
+            <ProofOf<Test>>::get(Network::Telegram, &DID_ALICE),
+            Proof::Text(post)
+        );
+
+        // the signing key is not in the DID document yet
+        assert_noop!(
+            Linker::ocw_verify_text(
+                DID_ALICE,
+                Network::Telegram,
+                "https://t.me/AmeliaParami",
+                "https://t.me/AmeliaParami/42"
+            ),
+            Error::<Test>::InvalidSignature
+        );
+
+        assert_ok!(Did::add_verification_method(
+            Origin::signed(ALICE),
+            DID_ALICE,
+            b"key-1".to_vec(),
+            PublicKey::Ed25519([
+                0xea, 0x4a, 0x6c, 0x63, 0xe2, 0x9c, 0x52, 0x0a, 0xbe, 0xf5, 0x50, 0x7b, 0x13, 0x2e,
+                0xc5, 0xf9, 0x95, 0x47, 0x76, 0xae, 0xbe, 0xbe, 0x7b, 0x92, 0x42, 0x1e, 0xea, 0x69,
+                0x14, 0x46, 0xd2, 0x2c,
+            ]),
+            vec![VerificationRelationship::Authentication],
+        ));
+
+        // the post is authored by another profile
+        assert_noop!(
+            Linker::ocw_verify_text(
+                DID_ALICE,
+                Network::Telegram,
+                "https://t.me/Victim",
+                "https://t.me/Victim/42"
+            ),
+            Error::<Test>::InvalidProof
+        );
+
+        // the proof is bound to the DID
+        assert_noop!(
+            Linker::ocw_verify_text(
+                DID_BOB,
+                Network::Telegram,
+                "https://t.me/AmeliaParami",
+                "https://t.me/AmeliaParami/42"
+            ),
+            Error::<Test>::InvalidProof
+        );
+
+        assert_noop!(
+            Linker::ocw_verify_text(
+                DID_ALICE,
+                Network::Telegram,
+                "https://t.me/AmeliaParami",
+                "https://t.me/AmeliaParami/43"
+            ),
+            Error::<Test>::InvalidProof
+        );
+
+        Linker::offchain_worker(0);
+
+        let tx = tx.write().transactions.pop().unwrap();
+
+        assert_tx!(
+            tx,
+            Call::Linker(crate::Call::submit_link {
+                did: DID_ALICE,
+                site: Network::Telegram,
+                profile: profile.clone(),
+                validated: true,
+            })
+        );
+
+        assert_ok!(Linker::submit_link(
+            Origin::none(),
+            DID_ALICE,
+            Network::Telegram,
+            profile,
+            true,
+        ));
+
+        assert_eq!(
+            <ProofOf<Test>>::get(Network::Telegram, &DID_ALICE),
+            Proof::Avatar
+        );
+    });
+}
+
+#[test]
+fn should_verify_github() {
+    const USER: &[u8] = include_bytes!("../artifacts/github_user.json");
+    const GISTS: &[u8] = include_bytes!("../artifacts/github_gists.json");
+
+    let (offchain, state) = testing::TestOffchainExt::new();
+
+    {
+        let mut state = state.write();
+        // the bio has no proof, falls back to gists
+        expect_get(
+            &mut state,
+            "https://api.github.com/users/AmeliaParami",
+            USER,
+        );
+        expect_get(
+            &mut state,
+            "https://api.github.com/users/AmeliaParami/gists",
+            GISTS,
+        );
+    }
+
+    let mut t = new_test_ext();
+    t.register_extension(OffchainWorkerExt::new(offchain));
+
+    t.execute_with(|| {
+        assert_ok!(Linker::ocw_verify_github(
+            DID_ALICE,
+            "https://github.com/AmeliaParami"
+        ));
+    });
+}
+
+#[test]
+fn should_verify_reddit() {
+    const ABOUT: &[u8] = include_bytes!("../artifacts/reddit_about.json");
+
+    let (offchain, state) = testing::TestOffchainExt::new();
+
+    {
+        let mut state = state.write();
+        expect_get(
+            &mut state,
+            "https://www.reddit.com/user/AmeliaParami/about.json",
+            ABOUT,
+        );
+        expect_get(
+            &mut state,
+            "https://www.reddit.com/user/AmeliaParami/about.json",
+            ABOUT,
+        );
+    }
+
+    let mut t = new_test_ext();
+    t.register_extension(OffchainWorkerExt::new(offchain));
+
+    t.execute_with(|| {
+        assert_ok!(Linker::ocw_verify_reddit(
+            DID_ALICE,
+            "https://www.reddit.com/user/AmeliaParami/"
+        ));
+
+        assert_noop!(
+            Linker::ocw_verify_reddit(DID_BOB, "https://www.reddit.com/user/AmeliaParami"),
+            Error::<Test>::InvalidSignature
+        );
+    });
+}
+
+#[test]
+fn should_verify_hackernews() {
+    const USER: &[u8] = include_bytes!("../artifacts/hackernews_user.json");
+
+    let (offchain, state) = testing::TestOffchainExt::new();
+
+    {
+        let mut state = state.write();
+        expect_get(
+            &mut state,
+            "https://hacker-news.firebaseio.com/v0/user/AmeliaParami.json",
+            USER,
+        );
+    }
+
+    let mut t = new_test_ext();
+    t.register_extension(OffchainWorkerExt::new(offchain));
+
+    t.execute_with(|| {
+        assert_ok!(Linker::ocw_verify_hackernews(
+            DID_ALICE,
+            "https://news.ycombinator.com/user?id=AmeliaParami"
+        ));
+    });
+}
+
+#[test]
+fn should_verify_mastodon() {
+    const HTM: &[u8] = include_bytes!("../artifacts/mastodon.html");
+
+    let (offchain, state) = testing::TestOffchainExt::new();
+
+    {
+        let mut state = state.write();
+        expect_get(&mut state, "https://mastodon.social/@AmeliaParami", HTM);
+    }
+
+    let mut t = new_test_ext();
+    t.register_extension(OffchainWorkerExt::new(offchain));
+
+    t.execute_with(|| {
+        assert_ok!(Linker::ocw_verify_mastodon(
+            DID_ALICE,
+            "https://mastodon.social/@AmeliaParami"
+        ));
+
+        assert_noop!(
+            Linker::ocw_verify_mastodon(DID_ALICE, "https://parami.io/AmeliaParami"),
+            Error::<Test>::UnsupportedSite
+        );
+    });
+}
+
 #[test]
 fn should_link_crypto() {
     new_test_ext().execute_with(|| {
@@ -290,13 +739,185 @@ fn should_link_crypto() {
             Network::Unknown,
             address.clone(),
             signature,
+            SignatureScheme::Plain,
         ));
 
         assert!(<Linked<Test>>::get(Network::Unknown, &address));
 
+        assert_eq!(Linker::links(&DID_ALICE, Network::Unknown), vec![address]);
+    });
+}
+
+#[test]
+fn should_link_crypto_with_typed_data() {
+    new_test_ext().execute_with(|| {
+        let address = vec![
+            0xeb, 0x01, 0x4f, 0x8c, 0x8b, 0x41, 0x8d, 0xb6, 0xb4, 0x57, 0x74, 0xc3, 0x26, 0xa0,
+            0xe6, 0x4c, 0x78, 0x91, 0x4d, 0xc0,
+        ];
+
+        // EIP-712 authorization to link DID_ALICE, expiring at 100
+        let signature = [
+            0x05, 0x26, 0x06, 0x3c, 0xf4, 0xbe, 0x2f, 0x56, 0x6d, 0xb8, 0xba, 0x03, 0x22, 0x8e,
+            0x1f, 0x34, 0xaf, 0xa6, 0x9b, 0xd4, 0xbe, 0xa1, 0x84, 0xd7, 0xea, 0x2c, 0xa7, 0x7b,
+            0xea, 0x18, 0xac, 0x3a, 0x79, 0xf7, 0x6d, 0x36, 0x15, 0xfc, 0x6f, 0x64, 0x79, 0x4f,
+            0x51, 0xad, 0xc4, 0x73, 0xd6, 0x97, 0xff, 0xac, 0xd0, 0x25, 0x59, 0x13, 0x85, 0x75,
+            0x5d, 0x30, 0xd6, 0x26, 0x62, 0x6d, 0x88, 0x97, 0x1c,
+        ];
+
+        let scheme = SignatureScheme::Eip712 { expiry: 100 };
+
+        System::set_block_number(101);
+
+        assert_noop!(
+            Linker::link_crypto(
+                Origin::signed(ALICE),
+                Network::Ethereum,
+                address.clone(),
+                signature,
+                scheme,
+            ),
+            Error::<Test>::Expired
+        );
+
+        System::set_block_number(100);
+
+        assert_noop!(
+            Linker::link_crypto(
+                Origin::signed(ALICE),
+                Network::Ethereum,
+                address.clone(),
+                signature,
+                SignatureScheme::Plain,
+            ),
+            Error::<Test>::UnexpectedAddress
+        );
+
+        assert_ok!(Linker::link_crypto(
+            Origin::signed(ALICE),
+            Network::Ethereum,
+            address.clone(),
+            signature,
+            scheme,
+        ));
+
+        assert_eq!(Linker::links(&DID_ALICE, Network::Ethereum), vec![address]);
+    });
+}
+
+#[test]
+fn should_link_near_named_account() {
+    new_test_ext().execute_with(|| {
+        let key = b"ed25519:Fn5Qkm7Xy4yYNu8bLwDXurBpeiRmngNtPKEXkv6o7CWp".to_vec();
+        let address = [&b"amelia.near:"[..], &key].concat();
+
+        // signed by the key of `Link: did:ad3:hwtGPq42GojPtyx5ngtSRSpJfjN`
+        let signature = [
+            0x00, 0xbb, 0x9a, 0x27, 0x17, 0x6d, 0xad, 0x94, 0x8a, 0xef, 0xef, 0x72, 0xf7, 0x38,
+            0x19, 0x6b, 0x55, 0xde, 0x52, 0x6f, 0x44, 0xdd, 0xb4, 0xe5, 0xf1, 0xec, 0x91, 0xfe,
+            0x3f, 0x78, 0x1e, 0x0a, 0xba, 0x8e, 0x0e, 0x08, 0x77, 0x9d, 0x8d, 0x2b, 0x44, 0xbb,
+            0x9f, 0x17, 0xea, 0xc8, 0x43, 0xbf, 0xdc, 0xa0, 0x09, 0xef, 0x92, 0xb0, 0x87, 0x7b,
+            0x24, 0xf6, 0x5b, 0x50, 0x1f, 0xf7, 0x65, 0x98, 0x0c,
+        ];
+
+        // the key can not be claimed without the signature
+        assert_noop!(
+            Linker::link_sociality(
+                Origin::signed(ALICE),
+                Network::Near,
+                b"amelia.near".to_vec(),
+                Proof::NearAccessKey(key.clone()),
+            ),
+            Error::<Test>::InvalidProof
+        );
+
+        assert_ok!(Linker::link_crypto(
+            Origin::signed(ALICE),
+            Network::Near,
+            address,
+            signature,
+            SignatureScheme::Plain,
+        ));
+
+        // pending until the key is confirmed as an access key of the account
+        let maybe_pending = <PendingOf<Test>>::get(Network::Near, &DID_ALICE);
+        assert_ne!(maybe_pending, None);
+        assert_eq!(maybe_pending.unwrap().task, b"amelia.near".to_vec());
+
         assert_eq!(
-            <LinksOf<Test>>::get(&DID_ALICE, Network::Unknown),
-            Some(address)
+            <ProofOf<Test>>::get(Network::Near, &DID_ALICE),
+            Proof::NearAccessKey(key)
+        );
+
+        assert!(!<Linked<Test>>::get(Network::Near, b"amelia.near".to_vec()));
+
+        assert_ok!(Linker::submit_link(
+            Origin::none(),
+            DID_ALICE,
+            Network::Near,
+            b"amelia.near".to_vec(),
+            true,
+        ));
+
+        assert!(<Linked<Test>>::get(Network::Near, b"amelia.near".to_vec()));
+        assert_eq!(<PendingOf<Test>>::get(Network::Near, &DID_ALICE), None);
+    });
+}
+
+#[test]
+fn should_verify_near() {
+    const KEY: &[u8] = include_bytes!("../artifacts/near_access_key.json");
+
+    let key = "ed25519:Fn5Qkm7Xy4yYNu8bLwDXurBpeiRmngNtPKEXkv6o7CWp";
+
+    let request = |account: &str| {
+        [
+            r#"{"jsonrpc":"2.0","id":"parami","method":"query","params":{"#,
+            r#""request_type":"view_access_key","finality":"final","account_id":""#,
+            account,
+            r#"","public_key":""#,
+            key,
+            r#""}}"#,
+        ]
+        .concat()
+        .into_bytes()
+    };
+
+    let (offchain, state) = testing::TestOffchainExt::new();
+
+    {
+        let mut state = state.write();
+        state.expect_request(testing::PendingRequest {
+            method: "POST".into(),
+            uri: "https://rpc.mainnet.near.org".into(),
+            headers: vec![("User-Agent".into(), USER_AGENT.into())],
+            body: request("amelia.near"),
+            response: Some(KEY.to_vec()),
+            sent: true,
+            ..Default::default()
+        });
+        state.expect_request(testing::PendingRequest {
+            method: "POST".into(),
+            uri: "https://rpc.mainnet.near.org".into(),
+            headers: vec![("User-Agent".into(), USER_AGENT.into())],
+            body: request("bob.near"),
+            response: Some(
+                br#"{"jsonrpc":"2.0","result":{"block_hash":"FxKPoyc7A5BEKWHqE1JmuTpKCbzSNJLPNjP5C3Gdubbf","block_height":71851262,"error":"access key ed25519:Fn5Qkm7Xy4yYNu8bLwDXurBpeiRmngNtPKEXkv6o7CWp does not exist while viewing","logs":[]},"id":"parami"}"#.to_vec(),
+            ),
+            sent: true,
+            ..Default::default()
+        });
+    }
+
+    let mut t = new_test_ext();
+    t.register_extension(OffchainWorkerExt::new(offchain));
+
+    t.execute_with(|| {
+        assert_ok!(Linker::ocw_verify_near("amelia.near", key));
+
+        assert_noop!(
+            Linker::ocw_verify_near("bob.near", key),
+            Error::<Test>::InvalidSignature
         );
     });
 }
@@ -366,6 +987,173 @@ fn should_force_unlink() {
             DID_ALICE,
             Network::Polkadot,
         ));
+
+        assert_eq!(<LinksOf<Test>>::get(&DID_ALICE, Network::Polkadot), None);
+        assert!(!<Linked<Test>>::get(Network::Polkadot, &POLKA.to_vec()));
+    })
+}
+
+#[test]
+fn should_not_reach_milestone_when_self_proven() {
+    new_test_ext().execute_with(|| {
+        let milestones = || {
+            System::events()
+                .into_iter()
+                .filter(|record| {
+                    matches!(
+                        record.event,
+                        Event::Did(parami_did::Event::MilestoneReached(..))
+                    )
+                })
+                .count()
+        };
+
+        System::set_block_number(1);
+
+        assert_ok!(Linker::insert_link(
+            DID_ALICE,
+            Network::Unknown,
+            b"an address".to_vec(),
+            DID_ALICE,
+        ));
+
+        assert_eq!(milestones(), 0);
+
+        assert_ok!(Linker::insert_link(
+            DID_ALICE,
+            Network::Telegram,
+            b"https://t.me/AmeliaParami".to_vec(),
+            DID_BOB,
+        ));
+
+        assert_eq!(milestones(), 1);
+    })
+}
+
+#[test]
+fn should_link_multiple() {
+    new_test_ext().execute_with(|| {
+        let profiles: Vec<Vec<u8>> = (0..8)
+            .map(|i| format!("https://t.me/AmeliaParami{}", i).into_bytes())
+            .collect();
+
+        for profile in &profiles {
+            assert_ok!(Linker::insert_link(
+                DID_ALICE,
+                Network::Telegram,
+                profile.clone(),
+                DID_ALICE,
+            ));
+        }
+
+        assert_eq!(Linker::links(&DID_ALICE, Network::Telegram), profiles);
+
+        assert_noop!(
+            Linker::insert_link(DID_ALICE, Network::Telegram, profiles[0].clone(), DID_ALICE,),
+            Error::<Test>::Exists
+        );
+
+        let profile = b"https://t.me/AmeliaParami8".to_vec();
+
+        assert_noop!(
+            Linker::insert_link(DID_ALICE, Network::Telegram, profile.clone(), DID_ALICE),
+            Error::<Test>::TooManyLinks
+        );
+
+        assert_noop!(
+            Linker::insert_pending(DID_ALICE, Network::Telegram, profile, Proof::Avatar),
+            Error::<Test>::TooManyLinks
+        );
+    })
+}
+
+#[test]
+fn should_unlink() {
+    new_test_ext().execute_with(|| {
+        let profile1 = b"https://t.me/AmeliaParami".to_vec();
+        let profile2 = b"https://t.me/AmeliaParami2".to_vec();
+
+        assert_ok!(Linker::insert_link(
+            DID_ALICE,
+            Network::Telegram,
+            profile1.clone(),
+            DID_ALICE,
+        ));
+        assert_ok!(Linker::insert_link(
+            DID_ALICE,
+            Network::Telegram,
+            profile2.clone(),
+            DID_ALICE,
+        ));
+
+        assert_ok!(Linker::unlink(
+            Origin::signed(ALICE),
+            Network::Telegram,
+            profile1.clone(),
+        ));
+
+        assert!(!<Linked<Test>>::get(Network::Telegram, &profile1));
+        assert_eq!(
+            Linker::links(&DID_ALICE, Network::Telegram),
+            vec![profile2.clone()]
+        );
+
+        assert_noop!(
+            Linker::unlink(Origin::signed(ALICE), Network::Telegram, profile1),
+            Error::<Test>::NotExists
+        );
+
+        assert_noop!(
+            Linker::unlink(Origin::signed(BOB), Network::Telegram, profile2.clone()),
+            Error::<Test>::NotExists
+        );
+
+        assert_ok!(Linker::unlink(
+            Origin::signed(ALICE),
+            Network::Telegram,
+            profile2.clone(),
+        ));
+
+        assert!(!<Linked<Test>>::get(Network::Telegram, &profile2));
+        assert_eq!(<LinksOf<Test>>::get(&DID_ALICE, Network::Telegram), None);
+    })
+}
+
+#[test]
+fn should_set_label() {
+    new_test_ext().execute_with(|| {
+        let label = b"Cold Wallet".to_vec();
+
+        assert_ok!(Linker::set_label(
+            Origin::signed(ALICE),
+            Network::Polkadot,
+            POLKA.to_vec(),
+            label.clone(),
+        ));
+
+        let links = <LinksOf<Test>>::get(&DID_ALICE, Network::Polkadot).unwrap();
+        assert_eq!(links[0].profile, POLKA.to_vec());
+        assert_eq!(links[0].label.to_vec(), label);
+
+        assert_noop!(
+            Linker::set_label(
+                Origin::signed(ALICE),
+                Network::Polkadot,
+                POLKA.to_vec(),
+                vec![0u8; 33],
+            ),
+            Error::<Test>::TooLong
+        );
+
+        assert_noop!(
+            Linker::set_label(
+                Origin::signed(BOB),
+                Network::Polkadot,
+                POLKA.to_vec(),
+                label,
+            ),
+            Error::<Test>::NotExists
+        );
     })
 }
 
@@ -383,10 +1171,7 @@ fn should_bind_by_linker() {
         ));
 
         assert_eq!(Linked::<Test>::get(Network::Telegram, &profile), true);
-        assert_eq!(
-            LinksOf::<Test>::get(DID_BOB, Network::Telegram).unwrap(),
-            profile
-        );
+        assert_eq!(Linker::links(&DID_BOB, Network::Telegram), vec![profile]);
     })
 }
 
@@ -406,3 +1191,37 @@ fn fail_to_bind_if_origin_not_linker_account() {
         );
     })
 }
+
+#[test]
+fn should_unlink_when_did_revoked() {
+    new_test_ext().execute_with(|| {
+        let profile = b"https://t.me/AmeliaParami".to_vec();
+
+        assert_ok!(Linker::insert_link(
+            DID_ALICE,
+            Network::Telegram,
+            profile.clone(),
+            DID_ALICE,
+        ));
+        assert_ok!(Linker::insert_pending(
+            DID_ALICE,
+            Network::Twitter,
+            b"https://twitter.com/AmeliaParami".to_vec(),
+            Proof::Text(b"https://twitter.com/AmeliaParami/status/1".to_vec()),
+        ));
+        assert_ok!(Linker::deposit(Origin::signed(ALICE), 10));
+
+        assert_ok!(Did::revoke(Origin::signed(ALICE)));
+
+        assert_eq!(<LinksOf<Test>>::iter_prefix(&DID_ALICE).count(), 0);
+        assert!(!<Linked<Test>>::get(Network::Telegram, &profile));
+        assert!(!<Linked<Test>>::get(Network::Polkadot, &POLKA.to_vec()));
+        assert_eq!(<PendingOf<Test>>::get(Network::Twitter, &DID_ALICE), None);
+        assert_eq!(
+            <ProofOf<Test>>::get(Network::Twitter, &DID_ALICE),
+            Proof::Avatar
+        );
+        assert_eq!(<Registrar<Test>>::get(&DID_ALICE), None);
+        assert_eq!(Balances::reserved_balance(ALICE), 0);
+    });
+}
@@ -5,6 +5,41 @@ use serde::{Deserialize, Serialize};
 use sp_runtime::RuntimeDebug;
 use sp_std::prelude::*;
 
+/// How a sociality account proves it is controlled by a DID
+#[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum Proof {
+    /// The DID is encoded in the avatar
+    Avatar,
+    /// The text proof is posted at the URL, which is under the profile
+    Text(Vec<u8>),
+    /// The public key of a NEAR named account, e.g. `ed25519:{base58}`, which signed the link,
+    /// to be confirmed as a full access key of the account, set by `link_crypto` only
+    NearAccessKey(Vec<u8>),
+}
+
+impl Default for Proof {
+    fn default() -> Self {
+        Self::Avatar
+    }
+}
+
+/// A linked account, labelled by the owner
+#[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct Link<L> {
+    pub profile: Vec<u8>,
+    pub label: L,
+}
+
+impl<L: Default> Link<L> {
+    pub fn new(profile: Vec<u8>) -> Self {
+        Self {
+            profile,
+            label: Default::default(),
+        }
+    }
+}
+
 #[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct RawImage {
@@ -20,6 +20,9 @@
 //! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
 //! DATE: 2022-09-07, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
 //! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
+//!
+//! The weights of `force_unlink`, `unlink` and `set_label` are estimated from the benchmarked
+//! `link_crypto`, with all the links of a network, until this file is regenerated.
 
 // Executed Command:
 // ./target/release/parami
@@ -50,6 +53,8 @@ pub trait WeightInfo {
     fn force_trust() -> Weight;
     fn force_block() -> Weight;
     fn force_unlink() -> Weight;
+    fn unlink() -> Weight;
+    fn set_label() -> Weight;
     fn submit_link(n: u32, ) -> Weight;
     fn submit_score(n: u32, ) -> Weight;
 }
@@ -103,13 +108,28 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().writes(3 as Weight))
     }
     // Storage: Linker LinksOf (r:1 w:1)
-    // Storage: Linker Linked (r:0 w:1)
+    // Storage: Linker Linked (r:0 w:8)
     fn force_unlink() -> Weight {
-        (13_000_000 as Weight)
+        (20_000_000 as Weight)
             .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(9 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Linker LinksOf (r:1 w:1)
+    // Storage: Linker Linked (r:0 w:1)
+    fn unlink() -> Weight {
+        (20_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
             .saturating_add(T::DbWeight::get().writes(2 as Weight))
     }
     // Storage: Did DidOf (r:1 w:0)
+    // Storage: Linker LinksOf (r:1 w:1)
+    fn set_label() -> Weight {
+        (20_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
     // Storage: Linker Registrar (r:1 w:0)
     // Storage: Linker LinksOf (r:1 w:1)
     // Storage: Linker Linked (r:1 w:1)
@@ -181,13 +201,28 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().writes(3 as Weight))
     }
     // Storage: Linker LinksOf (r:1 w:1)
-    // Storage: Linker Linked (r:0 w:1)
+    // Storage: Linker Linked (r:0 w:8)
     fn force_unlink() -> Weight {
-        (13_000_000 as Weight)
+        (20_000_000 as Weight)
             .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(9 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Linker LinksOf (r:1 w:1)
+    // Storage: Linker Linked (r:0 w:1)
+    fn unlink() -> Weight {
+        (20_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
             .saturating_add(RocksDbWeight::get().writes(2 as Weight))
     }
     // Storage: Did DidOf (r:1 w:0)
+    // Storage: Linker LinksOf (r:1 w:1)
+    fn set_label() -> Weight {
+        (20_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
     // Storage: Linker Registrar (r:1 w:0)
     // Storage: Linker LinksOf (r:1 w:1)
     // Storage: Linker Linked (r:1 w:1)
@@ -1,9 +1,10 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use codec::Codec;
-use parami_primitives::BalanceWrapper;
+use parami_primitives::{BalanceWrapper, ImportFailure, Network};
 use sp_runtime::traits::{MaybeDisplay, MaybeFromStr};
 use sp_runtime::DispatchError;
+use sp_std::vec::Vec;
 
 pub type ApiResult<T> = Result<T, DispatchError>;
 
@@ -16,5 +17,20 @@ sp_api::decl_runtime_apis! {
     {
         // calculate claim_info for given nft&did pair, result format is <(total_tokens, unlocked_tokens, claimable_tokens)>
         fn get_claim_info(nft_id: NftId, claimer: DecentralizedId) -> ApiResult<(BalanceWrapper<Balance>, BalanceWrapper<Balance>, BalanceWrapper<Balance>)>;
+
+        // list nfts owned by the did
+        fn get_nfts_of(did: DecentralizedId) -> ApiResult<Vec<NftId>>;
+
+        // list imports of the did waiting for validation, result format is <(network, namespace, token)>
+        fn get_pending_imports(did: DecentralizedId) -> ApiResult<Vec<(Network, Vec<u8>, Vec<u8>)>>;
+
+        // list failed imports of the did, result format is <(network, namespace, token, reason)>
+        fn get_failed_imports(did: DecentralizedId) -> ApiResult<Vec<(Network, Vec<u8>, Vec<u8>, ImportFailure)>>;
+
+        // progress of the buyback program of the nft, result format is <(spent, bought, remaining_funds)>
+        fn get_buyback_progress(nft_id: NftId) -> ApiResult<(BalanceWrapper<Balance>, BalanceWrapper<Balance>, BalanceWrapper<Balance>)>;
+
+        // dry-run launch of the nft, result format is <(price, remained_tokens)>, price is the currency needed for one fragment
+        fn dryly_launch(nft_id: NftId, minting_tokens: BalanceWrapper<Balance>, offered_tokens: BalanceWrapper<Balance>, currency: BalanceWrapper<Balance>, tokens: BalanceWrapper<Balance>) -> ApiResult<(BalanceWrapper<Balance>, BalanceWrapper<Balance>)>;
     }
 }
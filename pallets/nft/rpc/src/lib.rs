@@ -5,7 +5,7 @@ use jsonrpsee::{
     types::error::{CallError, ErrorObject, INTERNAL_ERROR_CODE},
 };
 pub use parami_nft_rpc_runtime_api::{ApiResult, NftRuntimeApi};
-use parami_primitives::BalanceWrapper;
+use parami_primitives::{BalanceWrapper, ImportFailure, Network};
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
 use sp_runtime::{
@@ -30,6 +30,45 @@ where
         BalanceWrapper<Balance>,
         BalanceWrapper<Balance>,
     )>;
+
+    #[method(name = "nft_getNftsOf")]
+    fn get_nfts_of(&self, did: DecentralizedId, at: Option<BlockHash>) -> RpcResult<Vec<NftId>>;
+
+    #[method(name = "nft_getPendingImports")]
+    fn get_pending_imports(
+        &self,
+        did: DecentralizedId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<(Network, Vec<u8>, Vec<u8>)>>;
+
+    #[method(name = "nft_getFailedImports")]
+    fn get_failed_imports(
+        &self,
+        did: DecentralizedId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<(Network, Vec<u8>, Vec<u8>, ImportFailure)>>;
+
+    #[method(name = "nft_getBuybackProgress")]
+    fn get_buyback_progress(
+        &self,
+        nft_id: NftId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<(
+        BalanceWrapper<Balance>,
+        BalanceWrapper<Balance>,
+        BalanceWrapper<Balance>,
+    )>;
+
+    #[method(name = "nft_drylyLaunch")]
+    fn dryly_launch(
+        &self,
+        nft_id: NftId,
+        minting_tokens: BalanceWrapper<Balance>,
+        offered_tokens: BalanceWrapper<Balance>,
+        currency: BalanceWrapper<Balance>,
+        tokens: BalanceWrapper<Balance>,
+        at: Option<BlockHash>,
+    ) -> RpcResult<(BalanceWrapper<Balance>, BalanceWrapper<Balance>)>;
 }
 
 pub struct NftRpcHandler<C, Block, NftId, DecentralizedId, Balance> {
@@ -87,4 +126,141 @@ where
             )))
         })
     }
+
+    fn get_nfts_of(
+        &self,
+        did: DecentralizedId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<NftId>> {
+        let api = self.client.runtime_api();
+        let at: BlockId<Block> = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let res = api.get_nfts_of(&at, did).map_err(|e| {
+            Error::Call(CallError::Custom(ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                "Unable to get nfts of did.",
+                Some(format!("{:?}", e)),
+            )))
+        })?;
+
+        res.map_err(|e| {
+            Error::Call(CallError::Custom(ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                "Unable to get nfts of did.",
+                Some(format!("{:?}", e)),
+            )))
+        })
+    }
+
+    fn get_pending_imports(
+        &self,
+        did: DecentralizedId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<(Network, Vec<u8>, Vec<u8>)>> {
+        let api = self.client.runtime_api();
+        let at: BlockId<Block> = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let res = api.get_pending_imports(&at, did).map_err(|e| {
+            Error::Call(CallError::Custom(ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                "Unable to get pending imports.",
+                Some(format!("{:?}", e)),
+            )))
+        })?;
+
+        res.map_err(|e| {
+            Error::Call(CallError::Custom(ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                "Unable to get pending imports.",
+                Some(format!("{:?}", e)),
+            )))
+        })
+    }
+
+    fn get_failed_imports(
+        &self,
+        did: DecentralizedId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<(Network, Vec<u8>, Vec<u8>, ImportFailure)>> {
+        let api = self.client.runtime_api();
+        let at: BlockId<Block> = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let res = api.get_failed_imports(&at, did).map_err(|e| {
+            Error::Call(CallError::Custom(ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                "Unable to get failed imports.",
+                Some(format!("{:?}", e)),
+            )))
+        })?;
+
+        res.map_err(|e| {
+            Error::Call(CallError::Custom(ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                "Unable to get failed imports.",
+                Some(format!("{:?}", e)),
+            )))
+        })
+    }
+
+    fn get_buyback_progress(
+        &self,
+        nft_id: NftId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<(
+        BalanceWrapper<Balance>,
+        BalanceWrapper<Balance>,
+        BalanceWrapper<Balance>,
+    )> {
+        let api = self.client.runtime_api();
+        let at: BlockId<Block> = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let res = api.get_buyback_progress(&at, nft_id).map_err(|e| {
+            Error::Call(CallError::Custom(ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                "Unable to get buyback progress.",
+                Some(format!("{:?}", e)),
+            )))
+        })?;
+
+        res.map_err(|e| {
+            Error::Call(CallError::Custom(ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                "Unable to get buyback progress.",
+                Some(format!("{:?}", e)),
+            )))
+        })
+    }
+
+    fn dryly_launch(
+        &self,
+        nft_id: NftId,
+        minting_tokens: BalanceWrapper<Balance>,
+        offered_tokens: BalanceWrapper<Balance>,
+        currency: BalanceWrapper<Balance>,
+        tokens: BalanceWrapper<Balance>,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<(BalanceWrapper<Balance>, BalanceWrapper<Balance>)> {
+        let api = self.client.runtime_api();
+        let at: BlockId<Block> = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let res = api
+            .dryly_launch(
+                &at,
+                nft_id,
+                minting_tokens,
+                offered_tokens,
+                currency,
+                tokens,
+            )
+            .map_err(|e| {
+                Error::Call(CallError::Custom(ErrorObject::owned(
+                    INTERNAL_ERROR_CODE,
+                    "Unable to dry-run launch.",
+                    Some(format!("{:?}", e)),
+                )))
+            })?;
+
+        res.map_err(|e| {
+            Error::Call(CallError::Custom(ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                "Unable to dry-run launch.",
+                Some(format!("{:?}", e)),
+            )))
+        })
+    }
 }
@@ -4,11 +4,12 @@ use super::*;
 use crate::Pallet as Nft;
 use codec::Encode;
 use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
-use frame_support::traits::tokens::fungibles::Inspect;
+use frame_support::traits::{tokens::fungibles::Inspect, Hooks};
 use frame_system::RawOrigin;
 use parami_did::Pallet as Did;
 use parami_linker::Pallet as Linker;
 use parami_primitives::constants::DOLLARS;
+use sp_core::{H256, U256};
 use sp_io::hashing::keccak_256;
 use sp_runtime::traits::{Bounded, Saturating, Zero};
 
@@ -63,6 +64,108 @@ where
     (public_address_last_20_bytes, sig)
 }
 
+/// A branch node with all its children, the one at `nibble` is `child`
+fn branch_node(nibble: u8, child: [u8; 32]) -> Vec<u8> {
+    let mut stream = rlp::RlpStream::new_list(17);
+    for i in 0..16 {
+        if i == nibble {
+            stream.append(&child.to_vec());
+        } else {
+            stream.append(&vec![0xffu8; 32]);
+        }
+    }
+    stream.append_empty_data();
+    stream.out().to_vec()
+}
+
+/// A leaf node of the remaining `nibbles` of the path
+fn leaf_node(nibbles: &[u8], value: Vec<u8>) -> Vec<u8> {
+    let mut encoded = Vec::new();
+    if nibbles.len() % 2 == 1 {
+        encoded.push(0x30 | nibbles[0]);
+    } else {
+        encoded.push(0x20);
+    }
+    let even = &nibbles[nibbles.len() % 2..];
+    for pair in even.chunks(2) {
+        encoded.push(pair[0] << 4 | pair[1]);
+    }
+
+    let mut stream = rlp::RlpStream::new_list(2);
+    stream.append(&encoded);
+    stream.append(&value);
+    stream.out().to_vec()
+}
+
+/// A Merkle-Patricia proof of `value` at `key` through `depth` branch nodes,
+/// returns the root and the nodes
+fn trie_proof(key: &[u8], value: Vec<u8>, depth: usize) -> (H256, Vec<Vec<u8>>) {
+    let path: Vec<u8> = keccak_256(key)
+        .iter()
+        .flat_map(|b| [b >> 4, b & 0x0f])
+        .collect();
+
+    let mut node = leaf_node(&path[depth..], value);
+    let mut nodes = vec![node.clone()];
+    for i in (0..depth).rev() {
+        node = branch_node(path[i], keccak_256(&node));
+        nodes.push(node.clone());
+    }
+    nodes.reverse();
+
+    (H256(keccak_256(&node)), nodes)
+}
+
+/// An Ethereum block header with the state root, returns the header and its hash
+fn block_header(number: u64, state_root: H256) -> (Vec<u8>, H256) {
+    let mut stream = rlp::RlpStream::new_list(15);
+    for i in 0..15 {
+        match i {
+            3 => stream.append(&state_root.as_bytes().to_vec()),
+            8 => stream.append(&number),
+            _ => stream.append_empty_data(),
+        };
+    }
+    let header = stream.out().to_vec();
+    let hash = H256(keccak_256(&header));
+
+    (header, hash)
+}
+
+fn dollars<T: Config>(amount: u128) -> BalanceOf<T> {
+    (amount * DOLLARS)
+        .try_into()
+        .map_err(|_| "balance conversion")
+        .unwrap()
+}
+
+/// Registers a DID for `owner`, kicks and launches its NFT with a swap pool
+fn launched_nft<T: Config>(owner: &T::AccountId) -> NftOf<T> {
+    T::Currency::make_free_balance_be(owner, BalanceOf::<T>::max_value());
+
+    Did::<T>::register(RawOrigin::Signed(owner.clone()).into(), None).unwrap();
+
+    Nft::<T>::kick(RawOrigin::Signed(owner.clone()).into()).unwrap();
+
+    let did = Did::<T>::did_of(owner).unwrap();
+    let nft = <Preferred<T>>::get(&did).unwrap();
+
+    Nft::<T>::launch(
+        RawOrigin::Signed(owner.clone()).into(),
+        nft,
+        b"Test Token".to_vec(),
+        b"XTT".to_vec(),
+        dollars::<T>(1_000_000),
+        None,
+        dollars::<T>(1_000),
+        dollars::<T>(500_000),
+        false,
+    )
+    .unwrap();
+
+    nft
+}
+
 benchmarks! {
     where_clause {
         where
@@ -81,11 +184,72 @@ benchmarks! {
 
         let (eth_address, sig) = gen_signature::<T>(&did);
 
-    }: _(RawOrigin::Signed(caller.clone()), Network::Ethereum, vec![1u8; 20], vec![1u8; 32], eth_address.to_vec(), sig)
+    }: _(RawOrigin::Signed(caller.clone()), Network::Ethereum, vec![1u8; 20], vec![1u8; 32], eth_address.to_vec(), sig, None, SignatureScheme::Plain)
     verify {
         assert_ne!(<Porting<T>>::get((Network::Ethereum, &vec![1u8; 20], &vec![1u8; 32])), None);
     }
 
+    cancel_port {
+        let caller: T::AccountId = whitelisted_caller();
+
+        Did::<T>::register(RawOrigin::Signed(caller.clone()).into(), None)?;
+        let did = Did::<T>::did_of(&caller).unwrap();
+        let (eth_address, sig) = gen_signature::<T>(&did);
+        Nft::<T>::port(RawOrigin::Signed(caller.clone()).into(), Network::Ethereum, vec![1u8; 20], vec![1u8; 32], eth_address.to_vec(), sig, None, SignatureScheme::Plain)?;
+    }: _(RawOrigin::Signed(caller), Network::Ethereum, vec![1u8; 20], vec![1u8; 32])
+    verify {
+        assert_eq!(<Porting<T>>::get((Network::Ethereum, &vec![1u8; 20], &vec![1u8; 32])), None);
+    }
+
+    port_with_proof {
+        let n in 2 .. MAX_PROOF_NODES;
+
+        let caller: T::AccountId = whitelisted_caller();
+
+        Did::<T>::register(RawOrigin::Signed(caller.clone()).into(), None)?;
+        let did = Did::<T>::did_of(&caller).unwrap();
+        let (eth_address, sig) = gen_signature::<T>(&did);
+
+        let namespace = vec![1u8; 20];
+        let token = vec![1u8; 32];
+        let index = 2u32;
+
+        let slot = proof::owner_slot(U256::from(token.as_slice()), index.into());
+        let value = rlp::encode(&eth_address.to_vec()).to_vec();
+        let (storage_root, storage_proof) = trie_proof(slot.as_bytes(), value, (n - n / 2 - 1) as usize);
+
+        let mut account = rlp::RlpStream::new_list(4);
+        account.append(&0u64);
+        account.append(&0u64);
+        account.append(&storage_root.as_bytes().to_vec());
+        account.append(&vec![0u8; 32]);
+        let (state_root, account_proof) = trie_proof(&namespace, account.out().to_vec(), (n / 2 - 1) as usize);
+
+        let (header, hash) = block_header(15_000_000, state_root);
+
+        <AnchoredHeaders<T>>::insert(15_000_000, hash);
+        <OwnersSlot<T>>::insert(&namespace, index);
+    }: _(RawOrigin::Signed(caller), namespace.clone(), token.clone(), eth_address.to_vec(), sig, header, account_proof, storage_proof, SignatureScheme::Plain)
+    verify {
+        assert_ne!(<Ported<T>>::get((Network::Ethereum, &namespace, &token)), None);
+    }
+
+    anchor_header {
+        for number in 0..MAX_ANCHORED_HEADERS as u64 {
+            Nft::<T>::anchor_header(T::AnchorOrigin::successful_origin(), number, H256::zero())?;
+        }
+    }: _<T::Origin>(T::AnchorOrigin::successful_origin(), MAX_ANCHORED_HEADERS as u64, H256::zero())
+    verify {
+        assert_eq!(<AnchoredHeaders<T>>::get(0), None);
+        assert_eq!(<AnchoredHeaders<T>>::get(MAX_ANCHORED_HEADERS as u64), Some(H256::zero()));
+    }
+
+    set_owners_slot {
+    }: _(RawOrigin::Root, vec![1u8; 20], 2)
+    verify {
+        assert_eq!(<OwnersSlot<T>>::get(&vec![1u8; 20]), Some(2));
+    }
+
     kick {
         let caller: T::AccountId = whitelisted_caller();
 
@@ -182,14 +346,171 @@ benchmarks! {
         assert!(T::Assets::balance(nft, &caller) > Zero::zero());
     }
 
+    transfer_nft {
+        let caller: T::AccountId = whitelisted_caller();
+        let receiver: T::AccountId = account("receiver", 1, 1);
+
+        Did::<T>::register(RawOrigin::Signed(caller.clone()).into(), None)?;
+        Did::<T>::register(RawOrigin::Signed(receiver.clone()).into(), None)?;
+
+        Nft::<T>::kick(RawOrigin::Signed(caller.clone()).into())?;
+
+        let did = Did::<T>::did_of(&caller).unwrap();
+        let to = Did::<T>::did_of(&receiver).unwrap();
+        let nft = <Preferred<T>>::get(&did).unwrap();
+    }: _(RawOrigin::Signed(caller), nft, to)
+    verify {
+        assert_eq!(<Metadata<T>>::get(nft).unwrap().owner, to);
+        assert_eq!(<Preferred<T>>::get(&to), Some(nft));
+    }
+
+    set_preferred {
+        let caller: T::AccountId = whitelisted_caller();
+
+        Did::<T>::register(RawOrigin::Signed(caller.clone()).into(), None)?;
+
+        Nft::<T>::kick(RawOrigin::Signed(caller.clone()).into())?;
+        Nft::<T>::kick(RawOrigin::Signed(caller.clone()).into())?;
+
+        let did = Did::<T>::did_of(&caller).unwrap();
+        let nft = <OwnedNfts<T>>::iter_key_prefix(&did).find(|nft| <Preferred<T>>::get(&did) != Some(*nft)).unwrap();
+    }: _(RawOrigin::Signed(caller), nft)
+    verify {
+        assert_eq!(<Preferred<T>>::get(&did), Some(nft));
+    }
+
+    set_display {
+        let n in 0 .. 256 + 1024;
+
+        let caller: T::AccountId = whitelisted_caller();
+
+        Did::<T>::register(RawOrigin::Signed(caller.clone()).into(), None)?;
+
+        Nft::<T>::kick(RawOrigin::Signed(caller.clone()).into())?;
+
+        let did = Did::<T>::did_of(&caller).unwrap();
+        let nft = <Preferred<T>>::get(&did).unwrap();
+
+        let image = vec![b'x'; n.min(256) as usize];
+        let description = vec![b'x'; n.saturating_sub(256) as usize];
+    }: _(RawOrigin::Signed(caller), nft, vec![], image, description)
+    verify {
+        assert!(<Display<T>>::contains_key(nft));
+    }
+
+    set_validate_endpoints {
+        let n in 1 .. 8;
+        let l in 0 .. 8 * 128;
+
+        let endpoints = vec![vec![b'x'; (l / n).min(128) as usize]; n as usize];
+    }: _(RawOrigin::Root, Network::Ethereum, endpoints, n)
+    verify {
+        assert_eq!(<ValidateQuorum<T>>::get(Network::Ethereum), Some(n));
+    }
+
+    launch {
+        let n in 1 .. 1000 - 4;
+        let s in 1 .. 1000 - 4;
+
+        let name = vec![b'x'; n as usize];
+        let symbol = vec![b'x'; s as usize];
+
+        let caller: T::AccountId = whitelisted_caller();
+
+        let max = BalanceOf::<T>::max_value();
+        T::Currency::make_free_balance_be(&caller, max);
+
+        Did::<T>::register(RawOrigin::Signed(caller.clone()).into(), None)?;
+
+        Nft::<T>::kick(RawOrigin::Signed(caller.clone()).into())?;
+
+        let did = Did::<T>::did_of(&caller).unwrap();
+        let nft = <Preferred<T>>::get(&did).unwrap();
+
+    }: _(RawOrigin::Signed(caller), nft, name, symbol, dollars::<T>(1_000_000), Some((dollars::<T>(1_000), dollars::<T>(100_000))), dollars::<T>(1_000), dollars::<T>(500_000), true)
+    verify {
+        assert!(<Metadata<T>>::get(nft).unwrap().minted);
+        assert!(<IcoMetaOf<T>>::get(nft).is_some());
+    }
+
+    start_buyback {
+        let caller: T::AccountId = whitelisted_caller();
+
+        let nft = launched_nft::<T>(&caller);
+
+        let period: HeightOf<T> = 1u32.into();
+        let first = frame_system::Pallet::<T>::block_number().saturating_add(period);
+
+        // every block but the last one searched is full
+        let mut at = first;
+        for _ in 1..MAX_BUYBACK_SPILL {
+            for id in 0..MAX_BUYBACKS_PER_BLOCK {
+                <BuybackQueue<T>>::try_mutate(at, |queue| queue.try_push((1000 + id).into())).unwrap();
+            }
+            at = at.saturating_add(period);
+        }
+    }: _(RawOrigin::Signed(caller), nft, dollars::<T>(100), dollars::<T>(1), period, Perbill::one(), BuybackTarget::Burn)
+    verify {
+        assert_eq!(<Buybacks<T>>::get(nft).unwrap().next_at, at);
+        assert_eq!(<BuybackQueue<T>>::get(at).into_inner(), vec![nft]);
+    }
+
+    fund_buyback {
+        let caller: T::AccountId = whitelisted_caller();
+
+        let nft = launched_nft::<T>(&caller);
+
+        Nft::<T>::start_buyback(RawOrigin::Signed(caller.clone()).into(), nft, dollars::<T>(100), dollars::<T>(1), 1u32.into(), Perbill::one(), BuybackTarget::Burn)?;
+    }: _(RawOrigin::Signed(caller), nft, dollars::<T>(100))
+    verify {
+        let buyback = <Buybacks<T>>::get(nft).unwrap();
+        assert_eq!(T::Currency::free_balance(&buyback.pot), dollars::<T>(200));
+    }
+
+    stop_buyback {
+        let caller: T::AccountId = whitelisted_caller();
+
+        let nft = launched_nft::<T>(&caller);
+
+        Nft::<T>::start_buyback(RawOrigin::Signed(caller.clone()).into(), nft, dollars::<T>(100), dollars::<T>(1), 1u32.into(), Perbill::one(), BuybackTarget::Burn)?;
+    }: _(RawOrigin::Signed(caller), nft)
+    verify {
+        assert!(<Buybacks<T>>::get(nft).is_none());
+    }
+
+    on_initialize {
+        let n in 0 .. MAX_BUYBACKS_PER_BLOCK;
+
+        let period: HeightOf<T> = 1u32.into();
+        let at = frame_system::Pallet::<T>::block_number().saturating_add(period);
+
+        let mut nfts = vec![];
+        for i in 0..n {
+            let owner: T::AccountId = account("owner", i, 0);
+
+            let nft = launched_nft::<T>(&owner);
+
+            Nft::<T>::start_buyback(RawOrigin::Signed(owner).into(), nft, dollars::<T>(100), dollars::<T>(1), period, Perbill::one(), BuybackTarget::Burn)?;
+
+            nfts.push(nft);
+        }
+    }: {
+        Nft::<T>::on_initialize(at);
+    }
+    verify {
+        for nft in nfts {
+            assert!(!<Buybacks<T>>::get(nft).unwrap().bought.is_zero());
+        }
+    }
+
     submit_porting {
         let caller: T::AccountId = whitelisted_caller();
 
         Did::<T>::register(RawOrigin::Signed(caller.clone()).into(), None)?;
         let did = Did::<T>::did_of(&caller).unwrap();
         let (eth_address, sig) = gen_signature::<T>(&did);
-        Nft::<T>::port(RawOrigin::Signed(caller).into(), Network::Ethereum, vec![1u8; 20], vec![1u8; 32], eth_address.to_vec(), sig)?;
-    }: _(RawOrigin::None, did, Network::Ethereum, vec![1u8; 20], vec![1u8; 32], true)
+        Nft::<T>::port(RawOrigin::Signed(caller).into(), Network::Ethereum, vec![1u8; 20], vec![1u8; 32], eth_address.to_vec(), sig, None, SignatureScheme::Plain)?;
+    }: _(RawOrigin::None, did, Network::Ethereum, vec![1u8; 20], vec![1u8; 32], None, vec![])
     verify {
         assert_eq!(<Porting<T>>::get((Network::Ethereum, &vec![1u8; 20], &vec![1u8; 32])), None);
         assert_ne!(<Ported<T>>::get((Network::Ethereum, &vec![1u8; 20], &vec![1u8; 32])), None);
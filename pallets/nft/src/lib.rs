@@ -2,6 +2,7 @@
 
 pub use ocw::eth_abi;
 pub use pallet::*;
+pub use types::{BuybackTarget, TokenStandard};
 
 #[rustfmt::skip]
 pub mod weights;
@@ -17,6 +18,7 @@ mod benchmarking;
 
 pub mod migrations;
 mod ocw;
+mod proof;
 mod types;
 
 use frame_support::{
@@ -30,23 +32,32 @@ use frame_support::{
             },
             nonfungibles::{Create as NftCreate, Mutate as NftMutate},
         },
-        Currency, EnsureOrigin,
+        ConstU32, Currency, EnsureOrigin,
         ExistenceRequirement::{self},
         Get, StorageVersion,
     },
-    PalletId,
+    transactional,
+    weights::Weight,
+    BoundedVec, PalletId,
 };
 use frame_system::offchain::SendTransactionTypes;
 use parami_assetmanager::AssetIdManager;
 use parami_did::EnsureDid;
+use parami_primitives::{
+    signature::{recover_address_with, Action, SignatureScheme},
+    ImportFailure,
+};
 use parami_traits::{
     types::{Network, Task},
-    Links, Nfts, Swaps,
+    BridgeAssets, BridgeNfts, Links, Nfts, OnDidRevoked, Swaps,
 };
-use sp_core::U512;
+use sp_core::{H160, H256, U256, U512};
 use sp_runtime::{
-    traits::{AccountIdConversion, AtLeast32BitUnsigned, Bounded, One, Saturating, Zero},
-    DispatchError, RuntimeDebug,
+    traits::{
+        AccountIdConversion, AtLeast32BitUnsigned, Bounded, CheckedSub, One, SaturatedConversion,
+        Saturating, Zero,
+    },
+    DispatchError, Perbill, RuntimeDebug,
 };
 use sp_std::{
     convert::{TryFrom, TryInto},
@@ -60,6 +71,9 @@ type AccountOf<T> = <T as frame_system::Config>::AccountId;
 type AssetOf<T> = <T as Config>::AssetId;
 type BalanceOf<T> = <<T as parami_did::Config>::Currency as Currency<AccountOf<T>>>::Balance;
 type DidOf<T> = <T as parami_did::Config>::DecentralizedId;
+type EndpointOf = BoundedVec<u8, ConstU32<128>>;
+type EndpointsOf = BoundedVec<EndpointOf, ConstU32<8>>;
+type DisplayOf = types::Display;
 type ExternalOf<T> = types::External<DidOf<T>>;
 type HeightOf<T> = <T as frame_system::Config>::BlockNumber;
 type MetaOf<T> = types::Metadata<DidOf<T>, AccountOf<T>, NftOf<T>, AssetOf<T>>;
@@ -67,8 +81,22 @@ type NftOf<T> = <T as Config>::AssetId;
 type TaskOf<T> = Task<ImportTask<DidOf<T>>, HeightOf<T>>;
 type IcoMeta<T> = types::IcoMeta<BalanceOf<T>, AccountOf<T>>;
 type InfluenceMiningMetaOf<T> = types::InfluenceMiningMeta<BalanceOf<T>, AccountOf<T>>;
-
-const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
+type BuybackOf<T> = types::Buyback<BalanceOf<T>, AccountOf<T>, HeightOf<T>>;
+
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(6);
+
+/// The maximum number of buyback programs executed in a block
+pub const MAX_BUYBACKS_PER_BLOCK: u32 = 16;
+/// The number of blocks searched for room when a buyback is scheduled into a full block
+pub const MAX_BUYBACK_SPILL: u32 = 16;
+/// The maximum number of anchored Ethereum block headers, the oldest is pruned beyond
+pub const MAX_ANCHORED_HEADERS: u32 = 256;
+/// The maximum number of nodes of the account and storage proofs of `port_with_proof`
+pub const MAX_PROOF_NODES: u32 = 32;
+/// The maximum length of a proof node, i.e. a branch node with all its children
+pub const MAX_PROOF_NODE_LENGTH: usize = 532;
+/// The maximum length of an RLP encoded Ethereum block header
+pub const MAX_HEADER_LENGTH: usize = 1024;
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -99,12 +127,18 @@ pub mod pallet {
 
         type AssetIdManager: AssetIdManager<Self, AssetId = AssetOf<Self>>;
 
+        /// The origin which may anchor Ethereum block headers, governance or bridge relayers
+        type AnchorOrigin: EnsureOrigin<Self::Origin>;
+
         /// The assets trait to create, mint, and transfer fragments (fungible token)
         type Assets: FungCreate<AccountOf<Self>, AssetId = AssetOf<Self>>
             + FungMetaMutate<AccountOf<Self>, AssetId = AssetOf<Self>>
             + FungMutate<AccountOf<Self>, AssetId = AssetOf<Self>, Balance = BalanceOf<Self>>
             + FungTransfer<AccountOf<Self>, AssetId = AssetOf<Self>, Balance = BalanceOf<Self>>;
 
+        /// The bridge to register fragments as bridgeable resources
+        type Bridge: BridgeAssets<AssetOf<Self>>;
+
         /// The ICO baseline of donation for currency
         #[pallet::constant]
         type DefaultInitialMintingDeposit: Get<BalanceOf<Self>>;
@@ -123,6 +157,9 @@ pub mod pallet {
         #[pallet::constant]
         type UnsignedPriority: Get<TransactionPriority>;
 
+        /// The origin which represents the holders of a NFT's fractions
+        type HoldersOrigin: EnsureOrigin<Self::Origin, Success = NftOf<Self>>;
+
         /// The links trait
         type Links: Links<DidOf<Self>>;
 
@@ -212,10 +249,47 @@ pub mod pallet {
         NftOf<T>,
     >;
 
+    /// Failed imports of a DID, kept until the NFT is ported again
+    #[pallet::storage]
+    pub(super) type Failed<T: Config> = StorageNMap<
+        _,
+        (
+            NMapKey<Identity, T::DecentralizedId>,
+            NMapKey<Twox64Concat, Network>,
+            NMapKey<Blake2_128Concat, Vec<u8>>, // Namespace
+            NMapKey<Blake2_128Concat, Vec<u8>>, // Token
+        ),
+        ImportFailure,
+    >;
+
     /// Imported NFTs
     #[pallet::storage]
     pub(super) type External<T: Config> = StorageMap<_, Twox64Concat, NftOf<T>, ExternalOf<T>>;
 
+    /// Ownership transfers of ported NFTs, waiting for the recipient to re-verify the external NFT
+    #[pallet::storage]
+    pub(super) type PendingTransfer<T: Config> =
+        StorageMap<_, Twox64Concat, NftOf<T>, T::DecentralizedId>;
+
+    /// NFTs locked while they are bridged to another chain
+    #[pallet::storage]
+    pub(super) type Bridged<T: Config> = StorageMap<_, Twox64Concat, NftOf<T>, ()>;
+
+    /// Buyback program of a NFT
+    #[pallet::storage]
+    #[pallet::getter(fn buyback)]
+    pub(super) type Buybacks<T: Config> = StorageMap<_, Twox64Concat, NftOf<T>, BuybackOf<T>>;
+
+    /// Buyback programs to execute at a block
+    #[pallet::storage]
+    pub(super) type BuybackQueue<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        HeightOf<T>,
+        BoundedVec<NftOf<T>, ConstU32<MAX_BUYBACKS_PER_BLOCK>>,
+        ValueQuery,
+    >;
+
     /// Metadata
     #[pallet::storage]
     #[pallet::getter(fn meta)]
@@ -226,6 +300,16 @@ pub mod pallet {
     #[pallet::getter(fn preferred)]
     pub(super) type Preferred<T: Config> = StorageMap<_, Identity, T::DecentralizedId, NftOf<T>>;
 
+    /// NFTs owned by a DID
+    #[pallet::storage]
+    pub(super) type OwnedNfts<T: Config> =
+        StorageDoubleMap<_, Identity, T::DecentralizedId, Twox64Concat, NftOf<T>, ()>;
+
+    /// Display metadata of an NFT, set by the owner
+    #[pallet::storage]
+    #[pallet::getter(fn display)]
+    pub(super) type Display<T: Config> = StorageMap<_, Twox64Concat, NftOf<T>, DisplayOf>;
+
     /// Block Number Claim Started At
     #[pallet::storage]
     pub(super) type ClaimStartAt<T: Config> = StorageMap<_, Twox64Concat, NftOf<T>, HeightOf<T>>;
@@ -246,9 +330,31 @@ pub mod pallet {
     #[pallet::storage]
     pub(super) type NextClassId<T: Config> = StorageValue<_, NftOf<T>, ValueQuery, DefaultId<T>>;
 
+    /// RPC endpoints to validate the ownership of imported NFTs
     #[pallet::storage]
-    pub(super) type ValidateEndpoint<T: Config> =
-        StorageMap<_, Twox64Concat, Network, BoundedVec<u8, ConstU32<128>>>;
+    pub(super) type ValidateEndpoint<T: Config> = StorageMap<_, Twox64Concat, Network, EndpointsOf>;
+
+    /// Number of endpoints which must agree on the ownership, defaults to all of them
+    #[pallet::storage]
+    pub(super) type ValidateQuorum<T: Config> = StorageMap<_, Twox64Concat, Network, u32>;
+
+    /// Times an endpoint disagreed with the quorum
+    #[pallet::storage]
+    pub(super) type Disagreements<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, Network, Blake2_128Concat, EndpointOf, u32, ValueQuery>;
+
+    /// Ethereum block hashes anchored by governance or bridge relayers
+    #[pallet::storage]
+    pub(super) type AnchoredHeaders<T: Config> = StorageMap<_, Twox64Concat, u64, H256>;
+
+    /// Numbers of the anchored Ethereum blocks, in the order they were anchored
+    #[pallet::storage]
+    pub(super) type AnchoredNumbers<T: Config> =
+        StorageValue<_, BoundedVec<u64, ConstU32<MAX_ANCHORED_HEADERS>>, ValueQuery>;
+
+    /// Storage index of the `_owners` mapping of ERC-721 contracts
+    #[pallet::storage]
+    pub(super) type OwnersSlot<T: Config> = StorageMap<_, Blake2_128Concat, Vec<u8>, u32>;
 
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
@@ -268,15 +374,55 @@ pub mod pallet {
             Vec<u8>,
             BalanceOf<T>,
         ),
-        /// Import NFT Failed \[did, network, namespace, token_id\]
-        ImportFailed(T::DecentralizedId, Network, Vec<u8>, Vec<u8>),
+        /// NFT fragments Launched \[kol, instance, currency, tokens\]
+        Launched(T::DecentralizedId, NftOf<T>, BalanceOf<T>, BalanceOf<T>),
+        /// Import NFT Failed \[did, network, namespace, token_id, reason\]
+        ImportFailed(T::DecentralizedId, Network, Vec<u8>, Vec<u8>, ImportFailure),
+        /// Import NFT Cancelled \[did, network, namespace, token_id\]
+        ImportCancelled(T::DecentralizedId, Network, Vec<u8>, Vec<u8>),
+        /// Preferred NFT Changed \[did, instance\]
+        PreferredChanged(T::DecentralizedId, NftOf<T>),
+        /// NFT display metadata Updated \[instance\]
+        DisplayUpdated(NftOf<T>),
+        /// NFT ownership Transferred \[instance, from, to\]
+        Transferred(NftOf<T>, T::DecentralizedId, T::DecentralizedId),
+        /// NFT ownership Transfer waiting for re-verification \[instance, from, to\]
+        TransferPending(NftOf<T>, T::DecentralizedId, T::DecentralizedId),
+        /// NFT Bridged out to another chain \[instance, did\]
+        BridgedOut(NftOf<T>, T::DecentralizedId),
+        /// NFT Bridged back from another chain \[instance, did\]
+        BridgedIn(NftOf<T>, T::DecentralizedId),
+        /// Endpoints disagreed with the quorum \[network, namespace, token_id, endpoints\]
+        EndpointsDisagreed(Network, Vec<u8>, Vec<u8>, Vec<Vec<u8>>),
+        /// Ethereum block header Anchored \[number, hash\]
+        HeaderAnchored(u64, H256),
+        /// Ethereum block header Pruned to make room for a new one \[number\]
+        HeaderPruned(u64),
 
         // NFT Influencemining Activity Started \[nftId, budget_in_tokens\]
         InfluenceMiningActivityStarted(NftOf<T>, BalanceOf<T>),
+        /// Influencemining funds Released by holders \[nftId, to, value\]
+        InfluenceMiningFundsReleased(NftOf<T>, AccountOf<T>, BalanceOf<T>),
+        /// Buyback program Started \[nftId, funds\]
+        BuybackStarted(NftOf<T>, BalanceOf<T>),
+        /// Buyback program Funded \[nftId, funds\]
+        BuybackFunded(NftOf<T>, BalanceOf<T>),
+        /// Fragments Bought back \[nftId, currency, tokens\]
+        BuybackExecuted(NftOf<T>, BalanceOf<T>, BalanceOf<T>),
+        /// Buyback skipped for this period \[nftId\]
+        BuybackSkipped(NftOf<T>),
+        /// Buyback program Stopped by the owner \[nftId, refunded\]
+        BuybackStopped(NftOf<T>, BalanceOf<T>),
+        /// Buyback program Finished, all funds spent \[nftId\]
+        BuybackFinished(NftOf<T>),
     }
 
     #[pallet::hooks]
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+            Self::begin_block(n)
+        }
+
         fn offchain_worker(block_number: BlockNumberFor<T>) {
             match Self::ocw_begin_block(block_number) {
                 Ok(_) => {}
@@ -302,11 +448,27 @@ pub mod pallet {
         NotTokenOwner,
         InvalidSignature,
         InsufficientToken,
+        TooManyEndpoints,
+        InvalidQuorum,
+        InvalidProof,
+        UnanchoredHeader,
+        UnknownStorageLayout,
+        Bridged,
+        NotBridged,
+        InvalidBuyback,
+        PriceImpactExceeded,
+        Expired,
+        BuybackQueueFull,
     }
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         /// Import an existing NFT for crowdfunding.
+        ///
+        /// The token standard will be detected by the off-chain worker if `standard` is `None`.
+        ///
+        /// The owner signs `Link: did:ad3:{base58}` with the `Plain` scheme,
+        /// or the EIP-712 typed data of the port action with the `Eip712` scheme.
         #[pallet::weight(<T as Config>::WeightInfo::port())]
         pub fn port(
             origin: OriginFor<T>,
@@ -315,28 +477,17 @@ pub mod pallet {
             token: Vec<u8>,
             owner_address: Vec<u8>,
             signature: parami_primitives::signature::Signature,
+            standard: Option<TokenStandard>,
+            scheme: SignatureScheme,
         ) -> DispatchResult {
             let (owner, _) = EnsureDid::<T>::ensure_origin(origin)?;
 
-            ensure!(
-                !<Porting<T>>::contains_key((network, &namespace, &token)),
-                Error::<T>::Exists
-            );
+            Self::ensure_portable(&owner, network, &namespace, &token)?;
 
-            ensure!(
-                !<Ported<T>>::contains_key((network, &namespace, &token)),
-                Error::<T>::Exists
-            );
+            let address =
+                Self::ensure_port_signature(&owner, network, owner_address, signature, scheme)?;
 
-            let msg = parami_primitives::signature::generate_message(owner.clone());
-            let address = parami_primitives::signature::recover_address(
-                network,
-                owner_address.clone(),
-                signature,
-                msg,
-            )
-            .map_err(|_e| Error::<T>::InvalidSignature)?;
-            ensure!(address == owner_address, Error::<T>::InvalidSignature);
+            <Failed<T>>::remove((&owner, network, &namespace, &token));
 
             let created = <frame_system::Pallet<T>>::block_number();
             let lifetime = T::PendingLifetime::get();
@@ -351,6 +502,7 @@ pub mod pallet {
                         namespace,
                         token,
                         owner_address: address,
+                        standard,
                     },
                     deadline,
                     created,
@@ -360,6 +512,98 @@ pub mod pallet {
             Ok(())
         }
 
+        /// Cancel a pending import.
+        #[pallet::weight(<T as Config>::WeightInfo::cancel_port())]
+        pub fn cancel_port(
+            origin: OriginFor<T>,
+            network: Network,
+            namespace: Vec<u8>,
+            token: Vec<u8>,
+        ) -> DispatchResult {
+            let (did, _) = EnsureDid::<T>::ensure_origin(origin)?;
+
+            let task =
+                <Porting<T>>::get((network, &namespace, &token)).ok_or(Error::<T>::NotExists)?;
+
+            ensure!(task.task.owner == did, Error::<T>::NotTokenOwner);
+
+            <Porting<T>>::remove((network, &namespace, &token));
+
+            Self::deposit_event(Event::ImportCancelled(did, network, namespace, token));
+
+            Ok(())
+        }
+
+        /// Import an existing ERC-721 NFT with a storage proof of its owner,
+        /// checked against an anchored Ethereum block header.
+        ///
+        /// The owner signs as in `port`.
+        #[pallet::weight(<T as Config>::WeightInfo::port_with_proof(
+            (account_proof.len() + storage_proof.len()) as u32
+        ))]
+        pub fn port_with_proof(
+            origin: OriginFor<T>,
+            namespace: Vec<u8>,
+            token: Vec<u8>,
+            owner_address: Vec<u8>,
+            signature: parami_primitives::signature::Signature,
+            header: Vec<u8>,
+            account_proof: Vec<Vec<u8>>,
+            storage_proof: Vec<Vec<u8>>,
+            scheme: SignatureScheme,
+        ) -> DispatchResult {
+            let (owner, _) = EnsureDid::<T>::ensure_origin(origin)?;
+
+            let network = Network::Ethereum;
+
+            Self::ensure_portable(&owner, network, &namespace, &token)?;
+
+            ensure!(namespace.len() == 20, Error::<T>::BadMetadata);
+            ensure!(token.len() <= 32, Error::<T>::BadMetadata);
+            ensure!(owner_address.len() == 20, Error::<T>::InvalidSignature);
+
+            ensure!(
+                header.len() <= MAX_HEADER_LENGTH
+                    && account_proof.len() + storage_proof.len() <= MAX_PROOF_NODES as usize
+                    && account_proof
+                        .iter()
+                        .chain(storage_proof.iter())
+                        .all(|node| node.len() <= MAX_PROOF_NODE_LENGTH),
+                Error::<T>::InvalidProof
+            );
+
+            let address =
+                Self::ensure_port_signature(&owner, network, owner_address, signature, scheme)?;
+
+            let header = proof::decode_header(&header).ok_or(Error::<T>::InvalidProof)?;
+            ensure!(
+                <AnchoredHeaders<T>>::get(header.number) == Some(header.hash),
+                Error::<T>::UnanchoredHeader
+            );
+
+            let index = <OwnersSlot<T>>::get(&namespace).ok_or(Error::<T>::UnknownStorageLayout)?;
+
+            let storage_root = proof::verify_account(
+                header.state_root,
+                &H160::from_slice(&namespace),
+                &account_proof,
+            )
+            .ok_or(Error::<T>::InvalidProof)?;
+
+            let slot = proof::owner_slot(U256::from(token.as_slice()), index.into());
+            let value = proof::verify_storage(storage_root, &slot, &storage_proof)
+                .ok_or(Error::<T>::InvalidProof)?;
+
+            ensure!(
+                value == U256::from(address.as_slice()),
+                Error::<T>::NotTokenOwner
+            );
+
+            Self::complete_port(owner, network, namespace, token)?;
+
+            Ok(())
+        }
+
         /// Create a new NFT for crowdfunding.
         #[pallet::weight(<T as Config>::WeightInfo::kick())]
         pub fn kick(origin: OriginFor<T>) -> DispatchResult {
@@ -370,6 +614,88 @@ pub mod pallet {
             Ok(())
         }
 
+        /// Transfer the ownership of an NFT to another DID.
+        ///
+        /// The transfer of a ported NFT completes once the recipient
+        /// re-verifies the ownership of the external NFT through `port`.
+        #[pallet::weight(<T as Config>::WeightInfo::transfer_nft())]
+        pub fn transfer_nft(origin: OriginFor<T>, nft: NftOf<T>, to: DidOf<T>) -> DispatchResult {
+            let (did, _) = EnsureDid::<T>::ensure_origin(origin)?;
+
+            let meta = <Metadata<T>>::get(nft).ok_or(Error::<T>::NotExists)?;
+            ensure!(meta.owner == did, Error::<T>::NotTokenOwner);
+            ensure!(to != did, Error::<T>::YourSelf);
+            Self::ensure_not_bridged(nft)?;
+            ensure!(
+                parami_did::Pallet::<T>::lookup_did(to).is_some(),
+                Error::<T>::NotExists
+            );
+
+            if <External<T>>::contains_key(nft) {
+                <PendingTransfer<T>>::insert(nft, to);
+
+                Self::deposit_event(Event::TransferPending(nft, did, to));
+            } else {
+                Self::transfer_ownership(nft, to)?;
+            }
+
+            Ok(())
+        }
+
+        /// Select the NFT representing the DID in ads and clock-in.
+        #[pallet::weight(<T as Config>::WeightInfo::set_preferred())]
+        pub fn set_preferred(origin: OriginFor<T>, nft: NftOf<T>) -> DispatchResult {
+            let (did, _) = EnsureDid::<T>::ensure_origin(origin)?;
+
+            let meta = <Metadata<T>>::get(nft).ok_or(Error::<T>::NotExists)?;
+            ensure!(meta.owner == did, Error::<T>::NotTokenOwner);
+            Self::ensure_not_bridged(nft)?;
+
+            <Preferred<T>>::insert(&did, nft);
+
+            Self::deposit_event(Event::PreferredChanged(did, nft));
+
+            Ok(())
+        }
+
+        /// Set the display metadata of an NFT.
+        #[pallet::weight(<T as Config>::WeightInfo::set_display(
+            (name.len() + image.len() + description.len()) as u32
+        ))]
+        pub fn set_display(
+            origin: OriginFor<T>,
+            nft: NftOf<T>,
+            name: Vec<u8>,
+            image: Vec<u8>,
+            description: Vec<u8>,
+        ) -> DispatchResult {
+            let (did, _) = EnsureDid::<T>::ensure_origin(origin)?;
+
+            let meta = <Metadata<T>>::get(nft).ok_or(Error::<T>::NotExists)?;
+            ensure!(meta.owner == did, Error::<T>::NotTokenOwner);
+            Self::ensure_not_bridged(nft)?;
+
+            ensure!(
+                name.len() <= T::StringLimit::get() as usize,
+                Error::<T>::BadMetadata
+            );
+            ensure!(image.len() <= 256, Error::<T>::BadMetadata);
+            ensure!(description.len() <= 1024, Error::<T>::BadMetadata);
+
+            <Display<T>>::insert(
+                nft,
+                types::Display {
+                    name,
+                    image,
+                    description,
+                },
+            );
+
+            Self::deposit_event(Event::DisplayUpdated(nft));
+
+            Ok(())
+        }
+
         /// Claim the fragments.
         /// ClaimInfo calculation Rules: ref to comment on [`get_claim_info_inner`](fn@get_claim_info_inner)
         #[pallet::weight(<T as Config>::WeightInfo::claim())]
@@ -457,7 +783,8 @@ pub mod pallet {
             network: Network,
             namespace: Vec<u8>,
             token: Vec<u8>,
-            validated: bool,
+            failure: Option<ImportFailure>,
+            disagreed: Vec<u32>,
         ) -> DispatchResultWithPostInfo {
             ensure_none(origin)?;
 
@@ -467,29 +794,30 @@ pub mod pallet {
 
             let task = task.unwrap();
 
-            if validated {
-                let id = Self::create(task.task.owner)?;
-
-                <Ported<T>>::insert((network, namespace.clone(), token.clone()), id);
+            match failure {
+                None => {
+                    Self::complete_port(
+                        task.task.owner,
+                        network,
+                        namespace.clone(),
+                        token.clone(),
+                    )?;
+                }
+                Some(reason) => {
+                    <Failed<T>>::insert((&task.task.owner, network, &namespace, &token), reason);
 
-                <External<T>>::insert(
-                    id,
-                    types::External {
+                    Self::deposit_event(Event::ImportFailed(
+                        did,
                         network,
-                        namespace: namespace.clone(),
-                        token: token.clone(),
-                        owner: task.task.owner,
-                    },
-                );
-            } else {
-                Self::deposit_event(Event::ImportFailed(
-                    did,
-                    network,
-                    namespace.clone(),
-                    token.clone(),
-                ));
+                        namespace.clone(),
+                        token.clone(),
+                        reason,
+                    ));
+                }
             }
 
+            Self::record_disagreements(network, &namespace, &token, disagreed);
+
             <Porting<T>>::remove((network, namespace, token));
             Ok(().into())
         }
@@ -509,21 +837,51 @@ pub mod pallet {
             ensure!(did == meta.owner, Error::<T>::NotTokenOwner);
             ensure!(!meta.minted, Error::<T>::Minted);
 
-            let limit = T::StringLimit::get() as usize - 4;
-            ensure!(
-                0 < name.len() && name.len() <= limit,
-                Error::<T>::BadMetadata
-            );
-            ensure!(
-                0 < symbol.len() && symbol.len() <= limit,
-                Error::<T>::BadMetadata
-            );
+            Self::ensure_token_metadata(&name, &symbol)?;
 
-            let is_valid_char =
-                |c: &u8| c.is_ascii_whitespace() || c.is_ascii_alphanumeric() || *c == b'_';
+            Self::create_nft_and_mint_power(
+                nft_id,
+                meta,
+                minting_tokens,
+                name,
+                symbol,
+                &account,
+                did,
+            )?;
+
+            Ok(().into())
+        }
+
+        /// Launch fragments of a NFT in one go:
+        /// mint fragments, optionally offer a part of them in an ICO,
+        /// create the swap pair and seed it with `currency` and `tokens`.
+        ///
+        /// * `ico` - optional (expected_currency, offered_tokens) of the ICO
+        /// * `enable_staking` - whether to reward liquidity providers of the swap pair
+        #[pallet::weight(<T as Config>::WeightInfo::launch(name.len() as u32, symbol.len() as u32))]
+        #[transactional]
+        pub fn launch(
+            origin: OriginFor<T>,
+            nft_id: NftOf<T>,
+            name: Vec<u8>,
+            symbol: Vec<u8>,
+            minting_tokens: BalanceOf<T>,
+            ico: Option<(BalanceOf<T>, BalanceOf<T>)>,
+            currency: BalanceOf<T>,
+            tokens: BalanceOf<T>,
+            enable_staking: bool,
+        ) -> DispatchResult {
+            let (did, account) = EnsureDid::<T>::ensure_origin(origin)?;
+            let meta = Metadata::<T>::get(nft_id).ok_or(Error::<T>::NotExists)?;
 
-            ensure!(name.iter().all(is_valid_char), Error::<T>::BadMetadata);
-            ensure!(symbol.iter().all(is_valid_char), Error::<T>::BadMetadata);
+            ensure!(did == meta.owner, Error::<T>::NotTokenOwner);
+
+            Self::ensure_token_metadata(&name, &symbol)?;
+
+            let offered_tokens = ico.map(|(_, offered)| offered).unwrap_or_default();
+            Self::launch_dry(nft_id, minting_tokens, offered_tokens, currency, tokens)?;
+
+            let asset_id = meta.token_asset_id;
 
             Self::create_nft_and_mint_power(
                 nft_id,
@@ -535,7 +893,32 @@ pub mod pallet {
                 did,
             )?;
 
-            Ok(().into())
+            if let Some((expected_currency, offered_tokens)) = ico {
+                Self::start_initial_coin_offering(
+                    nft_id,
+                    expected_currency,
+                    offered_tokens,
+                    asset_id,
+                    &account,
+                )?;
+            }
+
+            // the pool can be created by anyone ahead of the launch,
+            // it holds no liquidity as the tokens are just minted
+            if T::Swaps::new(asset_id).is_err() {
+                ensure!(
+                    T::Swaps::total_liquidity(asset_id).is_zero(),
+                    Error::<T>::Exists
+                );
+            }
+            if enable_staking {
+                T::Swaps::enable_staking(asset_id)?;
+            }
+            T::Swaps::mint(&account, asset_id, currency, currency, tokens, true)?;
+
+            Self::deposit_event(Event::Launched(did, nft_id, currency, tokens));
+
+            Ok(())
         }
 
         #[pallet::weight(<T as Config>::WeightInfo::submit_porting())]
@@ -549,6 +932,7 @@ pub mod pallet {
             let meta = Metadata::<T>::get(nft_id).ok_or(Error::<T>::NotExists)?;
             ensure!(meta.minted, Error::<T>::NotExists);
             ensure!(did == meta.owner, Error::<T>::NotTokenOwner);
+            Self::ensure_not_bridged(nft_id)?;
             ensure!(IcoMetaOf::<T>::get(nft_id).is_none(), Error::<T>::Deadline);
 
             let balance = T::Assets::balance(meta.token_asset_id, &account);
@@ -571,6 +955,7 @@ pub mod pallet {
             let (did, account) = EnsureDid::<T>::ensure_origin(origin)?;
             let meta = Metadata::<T>::get(nft_id).ok_or(Error::<T>::NotExists)?;
             ensure!(did == meta.owner, Error::<T>::NotTokenOwner);
+            Self::ensure_not_bridged(nft_id)?;
             ensure!(meta.minted, Error::<T>::NotExists);
             let ico_meta = IcoMetaOf::<T>::get(nft_id).ok_or(Error::<T>::NotExists)?;
             let pot_tokens = T::Assets::balance(meta.token_asset_id, &ico_meta.pot);
@@ -607,6 +992,7 @@ pub mod pallet {
             let (did, account) = EnsureDid::<T>::ensure_origin(origin)?;
             let meta = Metadata::<T>::get(nft_id).ok_or(Error::<T>::NotExists)?;
             ensure!(did == meta.owner, Error::<T>::NotTokenOwner);
+            Self::ensure_not_bridged(nft_id)?;
             ensure!(meta.minted, Error::<T>::NotExists);
 
             let balance = T::Assets::balance(meta.token_asset_id, &account);
@@ -623,17 +1009,215 @@ pub mod pallet {
             Ok(().into())
         }
 
-        #[pallet::weight(<T as Config>::WeightInfo::submit_porting())]
-        pub fn set_validate_endpoint(
+        /// Start a buyback program funded with currency,
+        /// `amount` of currency is spent on fragments every `period` blocks.
+        #[pallet::weight(<T as Config>::WeightInfo::start_buyback())]
+        #[transactional]
+        pub fn start_buyback(
+            origin: OriginFor<T>,
+            nft_id: NftOf<T>,
+            funds: BalanceOf<T>,
+            amount: BalanceOf<T>,
+            period: HeightOf<T>,
+            max_price_impact: Perbill,
+            target: BuybackTarget,
+        ) -> DispatchResult {
+            let (did, account) = EnsureDid::<T>::ensure_origin(origin)?;
+            let meta = Metadata::<T>::get(nft_id).ok_or(Error::<T>::NotExists)?;
+            ensure!(did == meta.owner, Error::<T>::NotTokenOwner);
+            Self::ensure_not_bridged(nft_id)?;
+            ensure!(meta.minted, Error::<T>::NotExists);
+
+            ensure!(!<Buybacks<T>>::contains_key(nft_id), Error::<T>::Exists);
+            ensure!(
+                !amount.is_zero() && !period.is_zero(),
+                Error::<T>::InvalidBuyback
+            );
+            if target == BuybackTarget::InfluenceMining {
+                ensure!(
+                    <InflueceMiningMetaStore<T>>::contains_key(nft_id),
+                    Error::<T>::NotExists
+                );
+            }
+
+            let pot = Self::generate_buyback_pot(&nft_id);
+            T::Currency::transfer(&account, &pot, funds, ExistenceRequirement::KeepAlive)?;
+
+            let next_at = <frame_system::Pallet<T>>::block_number().saturating_add(period);
+            let next_at = Self::schedule_buyback(nft_id, next_at)?;
+
+            <Buybacks<T>>::insert(
+                nft_id,
+                BuybackOf::<T> {
+                    pot,
+                    amount,
+                    period,
+                    max_price_impact,
+                    target,
+                    next_at,
+                    spent: Zero::zero(),
+                    bought: Zero::zero(),
+                },
+            );
+
+            Self::deposit_event(Event::BuybackStarted(nft_id, funds));
+
+            Ok(())
+        }
+
+        /// Add currency to the buyback program of a NFT
+        #[pallet::weight(<T as Config>::WeightInfo::fund_buyback())]
+        pub fn fund_buyback(
+            origin: OriginFor<T>,
+            nft_id: NftOf<T>,
+            funds: BalanceOf<T>,
+        ) -> DispatchResult {
+            let (_, account) = EnsureDid::<T>::ensure_origin(origin)?;
+            let buyback = <Buybacks<T>>::get(nft_id).ok_or(Error::<T>::NotExists)?;
+
+            T::Currency::transfer(
+                &account,
+                &buyback.pot,
+                funds,
+                ExistenceRequirement::KeepAlive,
+            )?;
+
+            Self::deposit_event(Event::BuybackFunded(nft_id, funds));
+
+            Ok(())
+        }
+
+        /// Stop the buyback program of a NFT, the remaining currency is refunded to the owner
+        #[pallet::weight(<T as Config>::WeightInfo::stop_buyback())]
+        pub fn stop_buyback(origin: OriginFor<T>, nft_id: NftOf<T>) -> DispatchResult {
+            let (did, account) = EnsureDid::<T>::ensure_origin(origin)?;
+            let meta = Metadata::<T>::get(nft_id).ok_or(Error::<T>::NotExists)?;
+            ensure!(did == meta.owner, Error::<T>::NotTokenOwner);
+            Self::ensure_not_bridged(nft_id)?;
+
+            let buyback = <Buybacks<T>>::take(nft_id).ok_or(Error::<T>::NotExists)?;
+
+            let refunded = T::Currency::free_balance(&buyback.pot);
+            T::Currency::transfer(
+                &buyback.pot,
+                &account,
+                refunded,
+                ExistenceRequirement::AllowDeath,
+            )?;
+
+            Self::deposit_event(Event::BuybackStopped(nft_id, refunded));
+
+            Ok(())
+        }
+
+        /// Release fragments from the influencemining pot,
+        /// called by the holders of the NFT through governance.
+        #[pallet::weight(<T as Config>::WeightInfo::claim())]
+        pub fn release_influence_mining_funds(
+            origin: OriginFor<T>,
+            to: AccountOf<T>,
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            let nft_id = T::HoldersOrigin::ensure_origin(origin)?;
+
+            let meta = Metadata::<T>::get(nft_id).ok_or(Error::<T>::NotExists)?;
+            let mut im_meta =
+                <InflueceMiningMetaStore<T>>::get(nft_id).ok_or(Error::<T>::NotExists)?;
+
+            let balance = T::Assets::balance(meta.token_asset_id, &im_meta.pot);
+            ensure!(amount <= balance, Error::<T>::InsufficientToken);
+
+            T::Assets::transfer(meta.token_asset_id, &im_meta.pot, &to, amount, false)?;
+
+            im_meta.budget_in_tokens = im_meta.budget_in_tokens.saturating_sub(amount);
+            <InflueceMiningMetaStore<T>>::insert(nft_id, im_meta);
+
+            Self::deposit_event(Event::<T>::InfluenceMiningFundsReleased(nft_id, to, amount));
+
+            Ok(())
+        }
+
+        /// Replace the validate endpoints of a network,
+        /// `quorum` of them must agree before an import is accepted.
+        #[pallet::weight(<T as Config>::WeightInfo::set_validate_endpoints(
+            endpoints.len() as u32,
+            endpoints.iter().map(|endpoint| endpoint.len()).sum::<usize>() as u32
+        ))]
+        pub fn set_validate_endpoints(
             origin: OriginFor<T>,
             network: Network,
-            endpoint: Vec<u8>,
+            endpoints: Vec<Vec<u8>>,
+            quorum: u32,
         ) -> DispatchResult {
             ensure_root(origin)?;
-            let endpoint: BoundedVec<u8, ConstU32<128>> = endpoint
-                .try_into()
+
+            ensure!(
+                0 < quorum && quorum as usize <= endpoints.len(),
+                Error::<T>::InvalidQuorum
+            );
+
+            let endpoints = endpoints
+                .into_iter()
+                .map(|endpoint| endpoint.try_into())
+                .collect::<Result<Vec<EndpointOf>, _>>()
                 .map_err(|_| "Endpoint exceeds maximum length")?;
-            <ValidateEndpoint<T>>::insert(network, endpoint);
+            let endpoints: EndpointsOf = endpoints
+                .try_into()
+                .map_err(|_| Error::<T>::TooManyEndpoints)?;
+
+            // only the replaced endpoints have disagreements
+            <Disagreements<T>>::remove_prefix(network, Some(EndpointsOf::bound() as u32));
+            <ValidateEndpoint<T>>::insert(network, endpoints);
+            <ValidateQuorum<T>>::insert(network, quorum);
+
+            Ok(())
+        }
+
+        /// Anchor an Ethereum block hash, proofs against it are accepted by `port_with_proof`.
+        ///
+        /// A number can be anchored only once, the oldest header is pruned
+        /// once `MAX_ANCHORED_HEADERS` are anchored.
+        #[pallet::weight(<T as Config>::WeightInfo::anchor_header())]
+        pub fn anchor_header(origin: OriginFor<T>, number: u64, hash: H256) -> DispatchResult {
+            T::AnchorOrigin::ensure_origin(origin)?;
+
+            ensure!(
+                !<AnchoredHeaders<T>>::contains_key(number),
+                Error::<T>::Exists
+            );
+
+            <AnchoredNumbers<T>>::mutate(|numbers| {
+                if numbers.len() >= MAX_ANCHORED_HEADERS as usize {
+                    let pruned = numbers.remove(0);
+
+                    <AnchoredHeaders<T>>::remove(pruned);
+
+                    Self::deposit_event(Event::HeaderPruned(pruned));
+                }
+
+                let _ = numbers.try_push(number);
+            });
+
+            <AnchoredHeaders<T>>::insert(number, hash);
+
+            Self::deposit_event(Event::HeaderAnchored(number, hash));
+
+            Ok(())
+        }
+
+        /// Set the storage index of the `_owners` mapping of an ERC-721 contract.
+        #[pallet::weight(<T as Config>::WeightInfo::set_owners_slot())]
+        pub fn set_owners_slot(
+            origin: OriginFor<T>,
+            namespace: Vec<u8>,
+            index: u32,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+
+            ensure!(namespace.len() == 20, Error::<T>::BadMetadata);
+
+            <OwnersSlot<T>>::insert(namespace, index);
+
             Ok(())
         }
 
@@ -787,6 +1371,7 @@ pub mod pallet {
                 );
 
                 <Preferred<T>>::insert(owner, id);
+                <OwnedNfts<T>>::insert(owner, id, ());
 
                 if minted {
                     // MARK: pallet_uniques does not support genesis
@@ -816,8 +1401,13 @@ pub mod pallet {
             }
 
             for (network, endpoint) in &self.validate_endpoints {
-                let endpoint: BoundedVec<u8, ConstU32<128>> = endpoint.clone().try_into().unwrap();
-                <ValidateEndpoint<T>>::insert(network, endpoint);
+                let endpoint: EndpointOf = endpoint.clone().try_into().unwrap();
+                <ValidateEndpoint<T>>::mutate(network, |endpoints| {
+                    endpoints
+                        .get_or_insert_with(Default::default)
+                        .try_push(endpoint)
+                        .unwrap();
+                });
             }
         }
     }
@@ -868,6 +1458,8 @@ impl<T: Config> Pallet<T> {
             },
         );
 
+        <OwnedNfts<T>>::insert(&owner, id, ());
+
         if !<Preferred<T>>::contains_key(&owner) {
             <Preferred<T>>::insert(&owner, id);
         }
@@ -877,6 +1469,248 @@ impl<T: Config> Pallet<T> {
         Ok(id)
     }
 
+    fn ensure_portable(
+        owner: &DidOf<T>,
+        network: Network,
+        namespace: &Vec<u8>,
+        token: &Vec<u8>,
+    ) -> DispatchResult {
+        ensure!(
+            !<Porting<T>>::contains_key((network, namespace, token)),
+            Error::<T>::Exists
+        );
+
+        // a ported NFT can only be ported again by the recipient of a pending transfer
+        if let Some(id) = <Ported<T>>::get((network, namespace, token)) {
+            ensure!(
+                <PendingTransfer<T>>::get(id).as_ref() == Some(owner),
+                Error::<T>::Exists
+            );
+        }
+
+        Ok(())
+    }
+
+    fn complete_port(
+        owner: DidOf<T>,
+        network: Network,
+        namespace: Vec<u8>,
+        token: Vec<u8>,
+    ) -> DispatchResult {
+        <Failed<T>>::remove((&owner, network, &namespace, &token));
+
+        match <Ported<T>>::get((network, &namespace, &token)) {
+            Some(id) => {
+                // the transfer may have been redirected in the meantime
+                if <PendingTransfer<T>>::get(id) == Some(owner) {
+                    Self::transfer_ownership(id, owner)?;
+                }
+            }
+            None => {
+                Self::import(owner, network, namespace, token)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Move the ownership of an NFT, the `pallet_uniques` item stays in the NFT pot,
+    /// which is derived from the NFT ID and follows the owner.
+    fn transfer_ownership(nft: NftOf<T>, to: DidOf<T>) -> DispatchResult {
+        let mut meta = <Metadata<T>>::get(nft).ok_or(Error::<T>::NotExists)?;
+        let from = meta.owner;
+
+        meta.owner = to;
+        <Metadata<T>>::insert(nft, meta);
+
+        <External<T>>::mutate(nft, |maybe| {
+            if let Some(external) = maybe {
+                external.owner = to;
+            }
+        });
+
+        <PendingTransfer<T>>::remove(nft);
+
+        <OwnedNfts<T>>::remove(&from, nft);
+        <OwnedNfts<T>>::insert(&to, nft, ());
+
+        if <Preferred<T>>::get(&from) == Some(nft) {
+            match <OwnedNfts<T>>::iter_key_prefix(&from).next() {
+                Some(next) => <Preferred<T>>::insert(&from, next),
+                None => <Preferred<T>>::remove(&from),
+            }
+        }
+
+        if !<Preferred<T>>::contains_key(&to) {
+            <Preferred<T>>::insert(&to, nft);
+        }
+
+        Self::deposit_event(Event::Transferred(nft, from, to));
+
+        Ok(())
+    }
+
+    fn import(
+        owner: DidOf<T>,
+        network: Network,
+        namespace: Vec<u8>,
+        token: Vec<u8>,
+    ) -> Result<NftOf<T>, DispatchError> {
+        let id = Self::create(owner)?;
+
+        <Ported<T>>::insert((network, namespace.clone(), token.clone()), id);
+
+        <External<T>>::insert(
+            id,
+            types::External {
+                network,
+                namespace,
+                token,
+                owner,
+            },
+        );
+
+        Ok(id)
+    }
+
+    pub fn nfts_of(did: DidOf<T>) -> Vec<NftOf<T>> {
+        <OwnedNfts<T>>::iter_key_prefix(&did).collect()
+    }
+
+    pub fn pending_imports(did: DidOf<T>) -> Vec<(Network, Vec<u8>, Vec<u8>)> {
+        <Porting<T>>::iter_values()
+            .filter(|task| task.task.owner == did)
+            .map(|task| (task.task.network, task.task.namespace, task.task.token))
+            .collect()
+    }
+
+    pub fn failed_imports(did: DidOf<T>) -> Vec<(Network, Vec<u8>, Vec<u8>, ImportFailure)> {
+        <Failed<T>>::iter_prefix((did,))
+            .map(|((network, namespace, token), reason)| (network, namespace, token, reason))
+            .collect()
+    }
+
+    fn record_disagreements(
+        network: Network,
+        namespace: &Vec<u8>,
+        token: &Vec<u8>,
+        disagreed: Vec<u32>,
+    ) {
+        let endpoints = <ValidateEndpoint<T>>::get(network).unwrap_or_default();
+
+        let disagreed: Vec<Vec<u8>> = disagreed
+            .into_iter()
+            .filter_map(|index| endpoints.get(index as usize))
+            .map(|endpoint| {
+                <Disagreements<T>>::mutate(network, endpoint, |count| count.saturating_inc());
+                endpoint.to_vec()
+            })
+            .collect();
+
+        if !disagreed.is_empty() {
+            Self::deposit_event(Event::EndpointsDisagreed(
+                network,
+                namespace.clone(),
+                token.clone(),
+                disagreed,
+            ));
+        }
+    }
+
+    /// Ensure the owner address authorized the DID to port, returns the address
+    fn ensure_port_signature(
+        owner: &DidOf<T>,
+        network: Network,
+        owner_address: Vec<u8>,
+        signature: parami_primitives::signature::Signature,
+        scheme: SignatureScheme,
+    ) -> Result<Vec<u8>, DispatchError> {
+        if let SignatureScheme::Eip712 { expiry } = scheme {
+            let height = <frame_system::Pallet<T>>::block_number();
+            ensure!(
+                height.saturated_into::<u64>() <= expiry,
+                Error::<T>::Expired
+            );
+        }
+
+        let genesis = <frame_system::Pallet<T>>::block_hash(HeightOf::<T>::zero());
+
+        let address = recover_address_with(
+            scheme,
+            network,
+            owner_address.clone(),
+            signature,
+            genesis.as_ref(),
+            owner,
+            Action::Port,
+        )
+        .map_err(|_e| Error::<T>::InvalidSignature)?;
+        ensure!(address == owner_address, Error::<T>::InvalidSignature);
+
+        Ok(address)
+    }
+
+    fn ensure_token_metadata(name: &[u8], symbol: &[u8]) -> DispatchResult {
+        let limit = T::StringLimit::get() as usize - 4;
+        ensure!(
+            0 < name.len() && name.len() <= limit,
+            Error::<T>::BadMetadata
+        );
+        ensure!(
+            0 < symbol.len() && symbol.len() <= limit,
+            Error::<T>::BadMetadata
+        );
+
+        let is_valid_char =
+            |c: &u8| c.is_ascii_whitespace() || c.is_ascii_alphanumeric() || *c == b'_';
+
+        ensure!(name.iter().all(is_valid_char), Error::<T>::BadMetadata);
+        ensure!(symbol.iter().all(is_valid_char), Error::<T>::BadMetadata);
+
+        Ok(())
+    }
+
+    /// Ensure the NFT is not bridged out,
+    /// the owner cannot manage it until it is bridged back
+    pub fn ensure_not_bridged(nft_id: NftOf<T>) -> DispatchResult {
+        ensure!(!<Bridged<T>>::contains_key(nft_id), Error::<T>::Bridged);
+
+        Ok(())
+    }
+
+    /// Dry-run of launch, returns (price, remained)
+    ///
+    /// * `price` - currency needed for one fragment (10^18 units) when the swap pair is seeded
+    /// * `remained` - fragments left to the KOL after the ICO and the swap pair are funded
+    pub fn launch_dry(
+        nft_id: NftOf<T>,
+        minting_tokens: BalanceOf<T>,
+        offered_tokens: BalanceOf<T>,
+        currency: BalanceOf<T>,
+        tokens: BalanceOf<T>,
+    ) -> Result<(BalanceOf<T>, BalanceOf<T>), DispatchError> {
+        let meta = Metadata::<T>::get(nft_id).ok_or(Error::<T>::NotExists)?;
+        ensure!(!meta.minted, Error::<T>::Minted);
+
+        ensure!(
+            !currency.is_zero() && !tokens.is_zero(),
+            Error::<T>::InsufficientToken
+        );
+
+        let remained = minting_tokens
+            .checked_sub(&offered_tokens)
+            .and_then(|remained| remained.checked_sub(&tokens))
+            .ok_or(Error::<T>::InsufficientToken)?;
+
+        let unit: U512 = Self::try_into(10u128.pow(18))?;
+        let currency: U512 = Self::try_into(currency)?;
+        let tokens: U512 = Self::try_into(tokens)?;
+
+        let price = Self::try_into(currency * unit / tokens)?;
+
+        Ok((price, remained))
+    }
+
     fn try_into<S, D>(value: S) -> Result<D, DispatchError>
     where
         S: TryInto<u128>,
@@ -953,6 +1787,50 @@ impl<T: Config> Nfts<AccountOf<T>> for Pallet<T> {
         <Metadata<T>>::get(nft_id).map(|meta| meta.pot)
     }
 }
+
+impl<T: Config> BridgeNfts<AccountOf<T>> for Pallet<T> {
+    type NftId = NftOf<T>;
+
+    fn lock(who: &AccountOf<T>, nft_id: Self::NftId) -> DispatchResult {
+        let did = parami_did::Pallet::<T>::lookup_did_by_account_id(who.clone())
+            .ok_or(Error::<T>::NotExists)?;
+
+        let meta = <Metadata<T>>::get(nft_id).ok_or(Error::<T>::NotExists)?;
+        ensure!(meta.owner == did, Error::<T>::NotTokenOwner);
+        ensure!(meta.minted, Error::<T>::NotExists);
+        ensure!(!<Bridged<T>>::contains_key(nft_id), Error::<T>::Bridged);
+
+        <Bridged<T>>::insert(nft_id, ());
+        <PendingTransfer<T>>::remove(nft_id);
+
+        Self::deposit_event(Event::BridgedOut(nft_id, did));
+
+        Ok(())
+    }
+
+    fn unlock(to: &AccountOf<T>, nft_id: Self::NftId) -> DispatchResult {
+        ensure!(<Bridged<T>>::contains_key(nft_id), Error::<T>::NotBridged);
+
+        // the recipient on this chain may be a fresh account,
+        // it gets a DID without referrer to own the NFT
+        let did = match parami_did::Pallet::<T>::lookup_did_by_account_id(to.clone()) {
+            Some(did) => did,
+            None => parami_did::Pallet::<T>::create(to.clone(), None)?,
+        };
+
+        let meta = <Metadata<T>>::get(nft_id).ok_or(Error::<T>::NotExists)?;
+        if meta.owner != did {
+            Self::transfer_ownership(nft_id, did)?;
+        }
+
+        <Bridged<T>>::remove(nft_id);
+
+        Self::deposit_event(Event::BridgedIn(nft_id, did));
+
+        Ok(())
+    }
+}
+
 use parami_traits::transferable::Transferable;
 
 impl<T: Config> Transferable<AccountOf<T>> for Pallet<T> {
@@ -966,6 +1844,18 @@ impl<T: Config> Transferable<AccountOf<T>> for Pallet<T> {
     }
 }
 
+impl<T: Config> OnDidRevoked<DidOf<T>, AccountOf<T>> for Pallet<T> {
+    fn on_did_revoked(did: &DidOf<T>, _account: &AccountOf<T>) -> Weight {
+        <Preferred<T>>::remove(did);
+
+        Self::max_weight()
+    }
+
+    fn max_weight() -> Weight {
+        T::DbWeight::get().writes(1)
+    }
+}
+
 impl<T: Config> Pallet<T> {
     fn mint_tokens(
         amount: BalanceOf<T>,
@@ -998,6 +1888,8 @@ impl<T: Config> Pallet<T> {
         // mint funds
         Self::mint_tokens(minting_tokens, &meta, &owner_account, &name, &symbol)?;
 
+        T::Bridge::register(meta.token_asset_id)?;
+
         // update metadata
         <Metadata<T>>::insert(
             nft_id,
@@ -1071,7 +1963,8 @@ impl<T: Config> Pallet<T> {
             Error::<T>::InsufficientBalance
         );
 
-        let owner_account = parami_did::Pallet::<T>::lookup_did(meta.owner).unwrap();
+        let owner_account =
+            parami_did::Pallet::<T>::lookup_did(meta.owner).ok_or(Error::<T>::NotExists)?;
 
         T::Currency::transfer(
             &dst_account,
@@ -1205,6 +2098,149 @@ impl<T: Config> Pallet<T> {
         return T::PalletId::get().into_sub_account_truncating(&pot_seed);
     }
 
+    fn begin_block(now: HeightOf<T>) -> Weight {
+        let queue = <BuybackQueue<T>>::take(now);
+
+        let weight = <T as Config>::WeightInfo::on_initialize(queue.len() as u32);
+
+        for nft_id in queue {
+            if let Err(e) = Self::execute_buyback(nft_id, now) {
+                sp_runtime::print(e);
+
+                if let Some(mut buyback) = <Buybacks<T>>::get(nft_id) {
+                    // when no block has room, the program stays stalled until the owner stops it
+                    match Self::schedule_buyback(nft_id, now.saturating_add(buyback.period)) {
+                        Ok(next_at) => {
+                            buyback.next_at = next_at;
+                            <Buybacks<T>>::insert(nft_id, buyback);
+                        }
+                        Err(e) => sp_runtime::print(e),
+                    }
+
+                    Self::deposit_event(Event::BuybackSkipped(nft_id));
+                }
+            }
+        }
+
+        weight
+    }
+
+    #[transactional]
+    fn execute_buyback(nft_id: NftOf<T>, now: HeightOf<T>) -> DispatchResult {
+        let mut buyback = match <Buybacks<T>>::get(nft_id) {
+            // stopped, or restarted with another schedule
+            Some(buyback) if buyback.next_at == now => buyback,
+            _ => return Ok(()),
+        };
+
+        let meta = Metadata::<T>::get(nft_id).ok_or(Error::<T>::NotExists)?;
+        let asset_id = meta.token_asset_id;
+
+        // keep the pot alive to receive fragments
+        let funds =
+            T::Currency::free_balance(&buyback.pot).saturating_sub(T::Currency::minimum_balance());
+        let currency = buyback.amount.min(funds);
+        if currency.is_zero() {
+            <Buybacks<T>>::remove(nft_id);
+
+            Self::deposit_event(Event::BuybackFinished(nft_id));
+
+            return Ok(());
+        }
+
+        let pool = T::Swaps::get_pool_account(asset_id);
+        let total_quote = T::Currency::free_balance(&pool);
+        let total_token = T::Assets::balance(asset_id, &pool);
+
+        let tokens = T::Swaps::quote_in_dry(asset_id, currency)?;
+        ensure!(
+            !tokens.is_zero() && tokens < total_token,
+            Error::<T>::PriceImpactExceeded
+        );
+
+        // price after / price before = (quote + currency) * token / ((token - tokens) * quote)
+        let unit: U512 = Self::try_into(Perbill::ACCURACY)?;
+        let max_price_impact: U512 = Self::try_into(buyback.max_price_impact.deconstruct())?;
+        let currency_u512: U512 = Self::try_into(currency)?;
+        let tokens_u512: U512 = Self::try_into(tokens)?;
+        let total_quote: U512 = Self::try_into(total_quote)?;
+        let total_token: U512 = Self::try_into(total_token)?;
+
+        ensure!(
+            (total_quote + currency_u512) * total_token * unit
+                <= (total_token - tokens_u512) * total_quote * (unit + max_price_impact),
+            Error::<T>::PriceImpactExceeded
+        );
+
+        let tokens = T::Swaps::quote_in(buyback.pot.clone(), asset_id, currency, tokens, true)?;
+
+        match buyback.target {
+            BuybackTarget::Burn => {
+                T::Assets::burn_from(asset_id, &buyback.pot, tokens)?;
+            }
+            BuybackTarget::InfluenceMining => {
+                let mut im_meta =
+                    <InflueceMiningMetaStore<T>>::get(nft_id).ok_or(Error::<T>::NotExists)?;
+
+                T::Assets::transfer(asset_id, &buyback.pot, &im_meta.pot, tokens, false)?;
+
+                im_meta.budget_in_tokens.saturating_accrue(tokens);
+                <InflueceMiningMetaStore<T>>::insert(nft_id, im_meta);
+            }
+        }
+
+        buyback.spent.saturating_accrue(currency);
+        buyback.bought.saturating_accrue(tokens);
+
+        Self::deposit_event(Event::BuybackExecuted(nft_id, currency, tokens));
+
+        if currency == funds {
+            <Buybacks<T>>::remove(nft_id);
+
+            Self::deposit_event(Event::BuybackFinished(nft_id));
+        } else {
+            buyback.next_at = Self::schedule_buyback(nft_id, now.saturating_add(buyback.period))?;
+
+            <Buybacks<T>>::insert(nft_id, buyback);
+        }
+
+        Ok(())
+    }
+
+    /// Queue a buyback program at the first block from `at` with room, returns the block
+    fn schedule_buyback(nft_id: NftOf<T>, at: HeightOf<T>) -> Result<HeightOf<T>, DispatchError> {
+        let mut at = at;
+
+        for _ in 0..MAX_BUYBACK_SPILL {
+            if <BuybackQueue<T>>::try_mutate(at, |queue| queue.try_push(nft_id)).is_ok() {
+                return Ok(at);
+            }
+
+            at = at.saturating_add(One::one());
+        }
+
+        Err(Error::<T>::BuybackQueueFull.into())
+    }
+
+    /// Progress of the buyback program of a NFT, returns (spent, bought, remaining funds)
+    pub fn buyback_progress(
+        nft_id: NftOf<T>,
+    ) -> Result<(BalanceOf<T>, BalanceOf<T>, BalanceOf<T>), DispatchError> {
+        let buyback = <Buybacks<T>>::get(nft_id).ok_or(Error::<T>::NotExists)?;
+
+        let remaining =
+            T::Currency::free_balance(&buyback.pot).saturating_sub(T::Currency::minimum_balance());
+
+        Ok((buyback.spent, buyback.bought, remaining))
+    }
+
+    pub fn generate_buyback_pot(nft_id: &NftOf<T>) -> AccountOf<T> {
+        use sp_core::Encode;
+        let mut pot_seed = b"bbk".to_vec();
+        pot_seed.append(&mut nft_id.encode());
+        return T::PalletId::get().into_sub_account_truncating(&pot_seed);
+    }
+
     pub fn generate_influence_mining_pot(nft_id: &NftOf<T>) -> AccountOf<T> {
         use sp_core::Encode;
         let mut pot_seed = b"ifm".to_vec();
@@ -1 +1,114 @@
+pub mod v4 {
+    use codec::{Decode, Encode};
+    use frame_support::traits::{Get, OnRuntimeUpgrade};
+    use frame_support::weights::Weight;
+    use scale_info::TypeInfo;
+    use sp_runtime::RuntimeDebug;
+    use sp_std::prelude::*;
 
+    use crate::{types, Config, DidOf, HeightOf, Pallet, Porting, StorageVersion, TaskOf};
+    use parami_traits::types::{Network, Task};
+
+    mod old {
+        use super::*;
+
+        #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, Default, TypeInfo)]
+        pub struct ImportTask<Did> {
+            pub owner: Did,
+            pub network: Network,
+            pub namespace: Vec<u8>,
+            pub token: Vec<u8>,
+            pub owner_address: Vec<u8>,
+        }
+
+        pub type TaskOf<T> = Task<ImportTask<DidOf<T>>, HeightOf<T>>;
+    }
+
+    pub struct ImportTaskWithStandard<T>(sp_std::marker::PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for ImportTaskWithStandard<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let version = StorageVersion::get::<Pallet<T>>();
+            if version > 3 {
+                return 0;
+            }
+
+            let mut count = 0u64;
+            Porting::<T>::translate_values::<old::TaskOf<T>, _>(|task| {
+                count += 1;
+                Some(TaskOf::<T> {
+                    task: types::ImportTask {
+                        owner: task.task.owner,
+                        network: task.task.network,
+                        namespace: task.task.namespace,
+                        token: task.task.token,
+                        owner_address: task.task.owner_address,
+                        standard: None,
+                    },
+                    deadline: task.deadline,
+                    created: task.created,
+                })
+            });
+
+            StorageVersion::new(4).put::<Pallet<T>>();
+            T::DbWeight::get().reads_writes(count + 1, count + 1)
+        }
+    }
+}
+
+pub mod v5 {
+    use frame_support::traits::{ConstU32, Get, OnRuntimeUpgrade};
+    use frame_support::weights::Weight;
+    use frame_support::BoundedVec;
+
+    use crate::{Config, EndpointsOf, Pallet, StorageVersion, ValidateEndpoint};
+
+    pub struct MultipleValidateEndpoints<T>(sp_std::marker::PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MultipleValidateEndpoints<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let version = StorageVersion::get::<Pallet<T>>();
+            if version > 4 {
+                return 0;
+            }
+
+            let mut count = 0u64;
+            ValidateEndpoint::<T>::translate_values::<BoundedVec<u8, ConstU32<128>>, _>(
+                |endpoint| {
+                    count += 1;
+                    EndpointsOf::try_from(sp_std::vec![endpoint]).ok()
+                },
+            );
+
+            StorageVersion::new(5).put::<Pallet<T>>();
+            T::DbWeight::get().reads_writes(count + 1, count + 1)
+        }
+    }
+}
+
+pub mod v6 {
+    use frame_support::traits::{Get, OnRuntimeUpgrade};
+    use frame_support::weights::Weight;
+
+    use crate::{Config, Metadata, OwnedNfts, Pallet, StorageVersion};
+
+    pub struct OwnedNftsIndex<T>(sp_std::marker::PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for OwnedNftsIndex<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let version = StorageVersion::get::<Pallet<T>>();
+            if version > 5 {
+                return 0;
+            }
+
+            let mut count = 0u64;
+            for (id, meta) in <Metadata<T>>::iter() {
+                count += 1;
+                <OwnedNfts<T>>::insert(meta.owner, id, ());
+            }
+
+            StorageVersion::new(6).put::<Pallet<T>>();
+            T::DbWeight::get().reads_writes(count + 1, count + 1)
+        }
+    }
+}
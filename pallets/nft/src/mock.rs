@@ -183,13 +183,27 @@ impl pallet_uniques::Config for Test {
     type Locker = ();
 }
 
+parameter_types! {
+    pub const DidPalletId: PalletId = PalletId(*b"prm/did ");
+}
+
 impl parami_did::Config for Test {
     type Event = Event;
     type Currency = Balances;
     type DecentralizedId = H160;
     type Hashing = Keccak256;
+    type HandleFee = ();
+    type HandleFeeDestination = ();
+    type HandlePeriod = ();
+    type RecoveryLifetime = ();
+    type Links = ();
+    type MetadataDepositBase = MetadataDepositBase;
+    type MetadataDepositPerByte = MetadataDepositPerByte;
+    type PalletId = DidPalletId;
     type WeightInfo = ();
     type Transferables = (Nft,);
+    type OnDidRevoked = Nft;
+    type OnReferralMilestone = ();
 }
 
 impl parami_ocw::Config for Test {}
@@ -269,6 +283,9 @@ impl parami_nft::Config for Test {
     type Swaps = Swap;
     type WeightInfo = ();
     type UnsignedPriority = ();
+    type AnchorOrigin = EnsureRoot<Self::AccountId>;
+    type Bridge = ();
+    type HoldersOrigin = frame_system::EnsureNever<AssetId>;
     type AssetIdManager = AssetManager;
     type NftId = u32;
 }
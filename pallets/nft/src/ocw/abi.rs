@@ -20,13 +20,27 @@ pub trait EthAbi {
             unimplemented!()
         }
     }
+
+    fn decode_output(types: &[types::ParamType], data: &[u8]) -> Option<Vec<types::Token>> {
+        #[cfg(feature = "std")]
+        {
+            let types: Vec<ethabi::ParamType> = types.iter().map(|i| i.into()).collect();
+            let tokens = ethabi::decode(types.as_slice(), data).ok()?;
+            tokens.into_iter().map(|i| i.try_into().ok()).collect()
+        }
+
+        #[cfg(not(feature = "std"))]
+        {
+            unimplemented!()
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    use sp_core::U256;
+    use sp_core::{H160, U256};
 
     #[test]
     fn test_encode_decode() {
@@ -45,4 +59,27 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_decode_output() {
+        let mut data = vec![0u8; 12];
+        data.extend_from_slice(&[0xdb; 20]);
+        data.extend_from_slice(&[0u8; 31]);
+        data.push(0x01);
+
+        let decoded =
+            eth_abi::decode_output(&[types::ParamType::Address, types::ParamType::Bool], &data);
+
+        assert_eq!(
+            decoded,
+            Some(vec![
+                types::Token::Address(H160([0xdb; 20])),
+                types::Token::Bool(true)
+            ])
+        );
+
+        let decoded = eth_abi::decode_output(&[types::ParamType::Uint(256)], &[0x01, 0x02]);
+
+        assert_eq!(decoded, None);
+    }
 }
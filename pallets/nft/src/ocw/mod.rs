@@ -3,18 +3,34 @@ pub use abi::eth_abi;
 mod abi;
 mod types;
 
-use crate::{Call, Config, Error, Pallet, Porting, ValidateEndpoint};
+use crate::{
+    types::TokenStandard, Call, Config, EndpointOf, Error, Pallet, Porting, ValidateEndpoint,
+    ValidateQuorum,
+};
+use codec::Encode;
 use ethabi::ethereum_types::U256;
 use frame_support::dispatch::DispatchError;
 use frame_support::dispatch::DispatchResult;
+use frame_support::ensure;
 use frame_system::offchain::{SendTransactionTypes, SubmitTransaction};
 use log;
 use parami_ocw::JsonValue;
 use parami_ocw::{submit_unsigned, Pallet as Ocw};
+use parami_primitives::ImportFailure;
+use parami_traits::types::Network;
 use scale_info::prelude::string::String;
+use sp_core::H160;
+use sp_runtime::{offchain::storage::StorageValueRef, traits::Saturating};
 use sp_std::prelude::Vec;
 use sp_std::str;
 
+/// ERC-165 interface id of ERC-1155
+const ERC1155_INTERFACE_ID: [u8; 4] = [0xd9, 0xb6, 0x7a, 0x26];
+
+/// Attempts on unavailable endpoints before an import fails,
+/// the n-th retry waits 2^n blocks
+const MAX_RETRIES: u32 = 5;
+
 impl<T: Config + SendTransactionTypes<Call<T>>> Pallet<T> {
     pub fn ocw_begin_block(block_number: T::BlockNumber) -> DispatchResult {
         use parami_traits::types::Network::*;
@@ -22,92 +38,206 @@ impl<T: Config + SendTransactionTypes<Call<T>>> Pallet<T> {
         for network in [Ethereum] {
             let porting = <Porting<T>>::iter_prefix_values((network,));
 
-            let endpoint = <ValidateEndpoint<T>>::get(network);
-            if endpoint.is_none() {
+            let endpoints = <ValidateEndpoint<T>>::get(network).unwrap_or_default();
+            if endpoints.is_empty() {
                 log::error!("network {:?} endpoint not found, skip import", network);
                 continue;
             }
-            let endpoint = endpoint.unwrap().into_inner();
-            let endpoint = str::from_utf8(&endpoint);
-            if endpoint.is_err() {
-                log::error!(
-                    "Convert endpoint to str failed, err = {:?}",
-                    endpoint.unwrap_err(),
-                );
-                continue;
-            }
 
-            let endpoint = endpoint.unwrap();
+            let quorum = <ValidateQuorum<T>>::get(network).unwrap_or(endpoints.len() as u32);
 
             for task in porting {
-                if task.deadline <= block_number {
+                let key = Self::ocw_retry_key(network, &task.task.namespace, &task.task.token);
+                let mut retry = StorageValueRef::persistent(&key);
+
+                let failure = if task.deadline <= block_number {
                     // call to remove
-                    Self::ocw_submit_porting(
-                        task.task.owner,
-                        task.task.network,
-                        task.task.namespace,
-                        task.task.token,
-                        false,
-                    );
+                    ImportFailure::Timeout
+                } else {
+                    let (attempts, next_attempt) = retry
+                        .get::<(u32, T::BlockNumber)>()
+                        .ok()
+                        .flatten()
+                        .unwrap_or_default();
 
-                    continue;
-                }
+                    if block_number < next_attempt {
+                        continue;
+                    }
 
-                let result = match task.task.network {
-                    Ethereum => Self::ocw_validate_etherum_token_owner(
-                        endpoint,
-                        &task.task.namespace,
-                        &task.task.token,
-                        &task.task.owner_address,
-                    ),
-                    _ => {
+                    let result = match task.task.network {
+                        Ethereum => {
+                            Self::ocw_validate_with_quorum(&endpoints, quorum, |endpoint| {
+                                Self::ocw_validate_etherum_token(
+                                    endpoint,
+                                    &task.task.namespace,
+                                    &task.task.token,
+                                    &task.task.owner_address,
+                                    task.task.standard,
+                                )
+                            })
+                        }
                         // drop unsupported sites
-                        Self::ocw_submit_porting(
-                            task.task.owner,
-                            task.task.network,
-                            task.task.namespace,
-                            task.task.token,
-                            false,
-                        );
+                        _ => Err(ImportFailure::BadContract),
+                    };
 
-                        continue;
+                    match result {
+                        Ok(disagreed) => {
+                            Self::ocw_submit_porting(
+                                task.task.owner,
+                                task.task.network,
+                                task.task.namespace,
+                                task.task.token,
+                                None,
+                                disagreed,
+                            );
+
+                            retry.clear();
+
+                            continue;
+                        }
+                        Err(ImportFailure::RpcError) if attempts + 1 < MAX_RETRIES => {
+                            let backoff: T::BlockNumber = (1u32 << attempts).into();
+                            retry.set(&(attempts + 1, block_number.saturating_add(backoff)));
+
+                            continue;
+                        }
+                        Err(failure) => failure,
                     }
                 };
 
-                if let Ok(()) = result {
-                    Self::ocw_submit_porting(
-                        task.task.owner,
-                        task.task.network,
-                        task.task.namespace,
-                        task.task.token,
-                        true,
-                    );
-                }
+                Self::ocw_submit_porting(
+                    task.task.owner,
+                    task.task.network,
+                    task.task.namespace,
+                    task.task.token,
+                    Some(failure),
+                    Vec::new(),
+                );
+
+                retry.clear();
             }
         }
 
         Ok(())
     }
 
+    /// Validate against every endpoint, succeeds when at least `quorum` of them confirmed.
+    /// Returns the indices of the endpoints which denied the ownership.
+    pub(super) fn ocw_validate_with_quorum<F>(
+        endpoints: &[EndpointOf],
+        quorum: u32,
+        validate: F,
+    ) -> Result<Vec<u32>, ImportFailure>
+    where
+        F: Fn(&str) -> DispatchResult,
+    {
+        let not_owner: DispatchError = Error::<T>::NotTokenOwner.into();
+        let bad_contract: DispatchError = Error::<T>::OcwParseError.into();
+
+        let mut confirmed = 0u32;
+        let mut disagreed = Vec::new();
+        let mut invalid = 0u32;
+        let mut unavailable = 0u32;
+
+        for (index, endpoint) in endpoints.iter().enumerate() {
+            let endpoint = match str::from_utf8(endpoint) {
+                Ok(endpoint) => endpoint,
+                Err(e) => {
+                    log::error!("Convert endpoint to str failed, err = {:?}", e);
+                    continue;
+                }
+            };
+
+            match validate(endpoint) {
+                Ok(()) => confirmed += 1,
+                Err(e) if e == not_owner => disagreed.push(index as u32),
+                Err(e) if e == bad_contract => {
+                    log::warn!("endpoint {} returned invalid data, err = {:?}", endpoint, e);
+                    invalid += 1;
+                }
+                Err(e) => {
+                    log::warn!("endpoint {} unavailable, err = {:?}", endpoint, e);
+                    unavailable += 1;
+                }
+            }
+        }
+
+        if confirmed >= quorum {
+            Ok(disagreed)
+        } else if confirmed + unavailable >= quorum {
+            // the quorum may still be reached once the endpoints recover
+            Err(ImportFailure::RpcError)
+        } else if disagreed.len() as u32 >= invalid {
+            Err(ImportFailure::NotOwner)
+        } else {
+            Err(ImportFailure::BadContract)
+        }
+    }
+
+    fn ocw_retry_key(network: Network, namespace: &[u8], token: &[u8]) -> Vec<u8> {
+        let mut key = b"parami_nft::porting_retry".to_vec();
+        key.append(&mut (network, namespace, token).encode());
+        key
+    }
+
     pub(self) fn ocw_submit_porting(
         did: T::DecentralizedId,
-        network: parami_traits::types::Network,
+        network: Network,
         namespace: Vec<u8>,
         token: Vec<u8>,
-        validated: bool,
+        failure: Option<ImportFailure>,
+        disagreed: Vec<u32>,
     ) {
         let call = Call::submit_porting {
             did,
             network,
             namespace,
             token,
-            validated,
+            failure,
+            disagreed,
         };
 
         let _ = submit_unsigned!(call);
     }
 
     pub(super) fn construct_request_body(namespace: &[u8], token: &[u8]) -> String {
+        let encoded = eth_abi::encode_input(
+            "ownerOf".as_bytes(),
+            &[types::ParamType::Uint(256)],
+            &[types::Token::Uint(U256::from(token))],
+        );
+        Self::construct_eth_call_body(namespace, &encoded)
+    }
+
+    pub(super) fn construct_balance_of_request_body(
+        namespace: &[u8],
+        token: &[u8],
+        owner_address: &[u8],
+    ) -> String {
+        let encoded = eth_abi::encode_input(
+            "balanceOf".as_bytes(),
+            &[types::ParamType::Address, types::ParamType::Uint(256)],
+            &[
+                types::Token::Address(H160::from_slice(owner_address)),
+                types::Token::Uint(U256::from(token)),
+            ],
+        );
+        Self::construct_eth_call_body(namespace, &encoded)
+    }
+
+    pub(super) fn construct_supports_interface_request_body(
+        namespace: &[u8],
+        interface_id: &[u8],
+    ) -> String {
+        let encoded = eth_abi::encode_input(
+            "supportsInterface".as_bytes(),
+            &[types::ParamType::FixedBytes(4)],
+            &[types::Token::FixedBytes(interface_id.to_vec())],
+        );
+        Self::construct_eth_call_body(namespace, &encoded)
+    }
+
+    fn construct_eth_call_body(namespace: &[u8], encoded: &[u8]) -> String {
         let body = r#"{
     "jsonrpc": "2.0",
     "id": 1,
@@ -121,42 +251,110 @@ impl<T: Config + SendTransactionTypes<Call<T>>> Pallet<T> {
         "latest"
     ]
 }"#;
-        let encoded = eth_abi::encode_input(
-            "ownerOf".as_bytes(),
-            &[types::ParamType::Uint(256)],
-            &[types::Token::Uint(U256::from(token))],
-        );
         let body = body
-            .replace("<data>", &hex::encode(&encoded))
-            .replace("<contract>", &hex::encode(&namespace));
+            .replace("<data>", &hex::encode(encoded))
+            .replace("<contract>", &hex::encode(namespace));
         return body;
     }
 
+    pub(super) fn ocw_validate_etherum_token(
+        rpc: &str,
+        namespace: &[u8],
+        token: &[u8],
+        owner_address: &[u8],
+        standard: Option<TokenStandard>,
+    ) -> DispatchResult {
+        let standard = match standard {
+            Some(standard) => standard,
+            None => Self::ocw_detect_etherum_token_standard(rpc, namespace)?,
+        };
+
+        match standard {
+            TokenStandard::Erc721 => {
+                Self::ocw_validate_etherum_token_owner(rpc, namespace, token, owner_address)
+            }
+            TokenStandard::Erc1155 => {
+                Self::ocw_validate_etherum_token_balance(rpc, namespace, token, owner_address)
+            }
+        }
+    }
+
+    pub(super) fn ocw_detect_etherum_token_standard(
+        rpc: &str,
+        namespace: &[u8],
+    ) -> Result<TokenStandard, DispatchError> {
+        let body =
+            Self::construct_supports_interface_request_body(namespace, &ERC1155_INTERFACE_ID);
+
+        let reverted: DispatchError = Error::<T>::OcwParseError.into();
+
+        match Self::ocw_eth_call(rpc, body, &[types::ParamType::Bool]) {
+            Ok(supported) => match supported.as_slice() {
+                [types::Token::Bool(true)] => Ok(TokenStandard::Erc1155),
+                _ => Ok(TokenStandard::Erc721),
+            },
+            // contracts without ERC-165 revert, treat them as ERC-721
+            Err(e) if e == reverted => Ok(TokenStandard::Erc721),
+            // the endpoint is unavailable, retried later
+            Err(e) => Err(e),
+        }
+    }
+
     pub(super) fn ocw_validate_etherum_token_owner(
         rpc: &str,
         namespace: &[u8],
         token: &[u8],
         owner_address: &[u8],
     ) -> DispatchResult {
+        ensure!(owner_address.len() == 20, Error::<T>::NotTokenOwner);
+
         let token_owner = Self::ocw_fetch_etherum_token_owner(rpc, namespace, token)?;
-        let owner_address = U256::from(owner_address);
-        if token_owner == owner_address {
+        if token_owner == H160::from_slice(owner_address) {
             Ok(())
         } else {
             Err(Error::<T>::NotTokenOwner)?
         }
     }
 
+    pub(super) fn ocw_validate_etherum_token_balance(
+        rpc: &str,
+        namespace: &[u8],
+        token: &[u8],
+        owner_address: &[u8],
+    ) -> DispatchResult {
+        ensure!(owner_address.len() == 20, Error::<T>::NotTokenOwner);
+
+        let body = Self::construct_balance_of_request_body(namespace, token, owner_address);
+        let balance = Self::ocw_eth_call(rpc, body, &[types::ParamType::Uint(256)])?;
+        match balance.as_slice() {
+            [types::Token::Uint(balance)] if !balance.is_zero() => Ok(()),
+            [types::Token::Uint(_)] => Err(Error::<T>::NotTokenOwner)?,
+            _ => Err(Error::<T>::OcwParseError)?,
+        }
+    }
+
     pub(super) fn ocw_fetch_etherum_token_owner(
         rpc: &str,
         contract: &[u8],
         token: &[u8],
-    ) -> Result<U256, DispatchError> {
+    ) -> Result<H160, DispatchError> {
         let body = Self::construct_request_body(contract, token);
+        let owner = Self::ocw_eth_call(rpc, body, &[types::ParamType::Address])?;
+        match owner.as_slice() {
+            [types::Token::Address(owner)] => Ok(*owner),
+            _ => Err(Error::<T>::OcwParseError)?,
+        }
+    }
+
+    fn ocw_eth_call(
+        rpc: &str,
+        body: String,
+        output: &[types::ParamType],
+    ) -> Result<Vec<types::Token>, DispatchError> {
         let res = Ocw::<T>::ocw_post(rpc, body.into())?;
 
         let json = res.json();
-        match json {
+        let data = match json {
             JsonValue::Object(res) => {
                 let v = res
                     .into_iter()
@@ -164,13 +362,17 @@ impl<T: Config + SendTransactionTypes<Call<T>>> Pallet<T> {
                 match v {
                     Some((_, JsonValue::String(chars))) => {
                         let str: String = chars.into_iter().collect();
-                        Ok(U256::from_str_radix(str.as_str(), 16)
-                            .map_err(|_e| Error::<T>::OcwParseError)?)
+                        let str = str.strip_prefix("0x").unwrap_or(&str);
+                        hex::decode(str).map_err(|_e| Error::<T>::OcwParseError)?
                     }
                     _ => return Err(Error::<T>::OcwParseError)?,
                 }
             }
-            _ => return Err(Error::<T>::OcwParseError)?,
-        }
+            // not a JSON-RPC response, e.g. an error page of a gateway
+            _ => return Err(parami_ocw::Error::<T>::ResponseError)?,
+        };
+
+        let tokens = eth_abi::decode_output(output, &data).ok_or(Error::<T>::OcwParseError)?;
+        Ok(tokens)
     }
 }
@@ -1,20 +1,40 @@
 use codec::{Decode, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
-use sp_core::U256;
+use sp_core::{H160, U256};
+use sp_runtime::RuntimeDebug;
 use sp_runtime_interface::pass_by::PassByCodec;
+use sp_std::prelude::*;
 
 #[derive(PassByCodec, Encode, Decode, Eq, PartialEq, TypeInfo, MaxEncodedLen)]
 pub enum ParamType {
     /// Unsigned integer.
     Uint(u32),
+    /// Address.
+    Address,
+    /// Bytes with fixed size.
+    FixedBytes(u32),
+    /// Boolean.
+    Bool,
 }
 
-#[derive(PassByCodec, Encode, Decode, Eq, PartialEq, TypeInfo, MaxEncodedLen)]
+#[derive(PassByCodec, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
 pub enum Token {
     /// Unisnged integer.
     ///
     /// solidity name: uint
     Uint(U256),
+    /// Address.
+    ///
+    /// solidity name: address
+    Address(H160),
+    /// Vector of bytes with known size.
+    ///
+    /// solidity name eg.: bytes4
+    FixedBytes(Vec<u8>),
+    /// Boolean value.
+    ///
+    /// solidity name: bool
+    Bool(bool),
 }
 
 #[cfg(feature = "std")]
@@ -22,6 +42,24 @@ impl From<&Token> for ethabi::Token {
     fn from(token: &Token) -> ethabi::Token {
         match token {
             Token::Uint(number) => ethabi::Token::Uint(number.into()),
+            Token::Address(address) => ethabi::Token::Address(address.0.into()),
+            Token::FixedBytes(bytes) => ethabi::Token::FixedBytes(bytes.clone()),
+            Token::Bool(value) => ethabi::Token::Bool(*value),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl TryFrom<ethabi::Token> for Token {
+    type Error = ();
+
+    fn try_from(token: ethabi::Token) -> Result<Token, ()> {
+        match token {
+            ethabi::Token::Uint(number) => Ok(Token::Uint(number.into())),
+            ethabi::Token::Address(address) => Ok(Token::Address(H160::from(address.0))),
+            ethabi::Token::FixedBytes(bytes) => Ok(Token::FixedBytes(bytes)),
+            ethabi::Token::Bool(value) => Ok(Token::Bool(value)),
+            _ => Err(()),
         }
     }
 }
@@ -31,6 +69,9 @@ impl From<&ParamType> for ethabi::ParamType {
     fn from(param_type: &ParamType) -> ethabi::ParamType {
         match param_type {
             ParamType::Uint(number) => ethabi::ParamType::Uint(*number as usize),
+            ParamType::Address => ethabi::ParamType::Address,
+            ParamType::FixedBytes(size) => ethabi::ParamType::FixedBytes(*size as usize),
+            ParamType::Bool => ethabi::ParamType::Bool,
         }
     }
 }
@@ -0,0 +1,157 @@
+use rlp::Rlp;
+use sp_core::{H160, H256, U256};
+use sp_io::hashing::keccak_256;
+use sp_std::prelude::*;
+
+/// Ethereum block header fields required to verify a state proof
+pub struct Header {
+    pub hash: H256,
+    pub number: u64,
+    pub state_root: H256,
+}
+
+/// Decode an RLP encoded Ethereum block header
+pub fn decode_header(raw: &[u8]) -> Option<Header> {
+    let rlp = Rlp::new(raw);
+    if !rlp.is_list() || rlp.item_count().ok()? < 15 {
+        return None;
+    }
+
+    let state_root: Vec<u8> = rlp.val_at(3).ok()?;
+    if state_root.len() != 32 {
+        return None;
+    }
+
+    let number: u64 = rlp.val_at(8).ok()?;
+
+    Some(Header {
+        hash: H256(keccak_256(raw)),
+        number,
+        state_root: H256::from_slice(&state_root),
+    })
+}
+
+/// Verify an account proof against a state root, returns the storage root of the account
+pub fn verify_account(state_root: H256, address: &H160, proof: &[Vec<u8>]) -> Option<H256> {
+    let account = verify_proof(state_root, &keccak_256(address.as_bytes()), proof).ok()??;
+
+    let storage_root: Vec<u8> = Rlp::new(&account).val_at(2).ok()?;
+    if storage_root.len() != 32 {
+        return None;
+    }
+
+    Some(H256::from_slice(&storage_root))
+}
+
+/// Verify a storage proof against a storage root, returns the value of the slot
+pub fn verify_storage(storage_root: H256, slot: &H256, proof: &[Vec<u8>]) -> Option<U256> {
+    match verify_proof(storage_root, &keccak_256(slot.as_bytes()), proof).ok()? {
+        Some(value) => {
+            let value: Vec<u8> = Rlp::new(&value).as_val().ok()?;
+            if value.len() > 32 {
+                return None;
+            }
+
+            Some(U256::from_big_endian(&value))
+        }
+        None => Some(U256::zero()),
+    }
+}
+
+/// Storage slot of `token` in a `mapping(uint256 => address)` declared at `index`
+pub fn owner_slot(token: U256, index: U256) -> H256 {
+    let mut raw = [0u8; 64];
+    token.to_big_endian(&mut raw[..32]);
+    index.to_big_endian(&mut raw[32..]);
+
+    H256(keccak_256(&raw))
+}
+
+/// Walk a Merkle-Patricia proof from `root` along `key`,
+/// returns `Ok(None)` if the proof shows the key is absent.
+fn verify_proof(root: H256, key: &[u8], proof: &[Vec<u8>]) -> Result<Option<Vec<u8>>, ()> {
+    let path: Vec<u8> = key.iter().flat_map(|b| [b >> 4, b & 0x0f]).collect();
+
+    let mut nodes = proof.iter();
+    let mut offset = 0;
+    // a reference is either the hash of the next node, or the node itself if shorter than 32 bytes
+    let mut reference = root.as_bytes().to_vec();
+
+    loop {
+        let node = if reference.len() == 32 {
+            let node = nodes.next().ok_or(())?;
+            if keccak_256(node) != reference[..] {
+                return Err(());
+            }
+            node.clone()
+        } else {
+            reference
+        };
+
+        let node = Rlp::new(&node);
+        match node.item_count().map_err(|_| ())? {
+            17 => {
+                if offset == path.len() {
+                    let value: Vec<u8> = node.val_at(16).map_err(|_| ())?;
+                    return Ok(Some(value).filter(|value| !value.is_empty()));
+                }
+
+                let child = node.at(path[offset] as usize).map_err(|_| ())?;
+                offset += 1;
+
+                reference = child_reference(&child)?;
+                if reference.is_empty() {
+                    return Ok(None);
+                }
+            }
+            2 => {
+                let encoded: Vec<u8> = node.val_at(0).map_err(|_| ())?;
+                let (leaf, nibbles) = decode_hex_prefix(&encoded)?;
+
+                if !path[offset..].starts_with(&nibbles) {
+                    return Ok(None);
+                }
+                offset += nibbles.len();
+
+                if leaf {
+                    if offset != path.len() {
+                        return Ok(None);
+                    }
+
+                    let value: Vec<u8> = node.val_at(1).map_err(|_| ())?;
+                    return Ok(Some(value));
+                }
+
+                reference = child_reference(&node.at(1).map_err(|_| ())?)?;
+            }
+            _ => return Err(()),
+        }
+    }
+}
+
+fn child_reference(child: &Rlp) -> Result<Vec<u8>, ()> {
+    if child.is_list() {
+        Ok(child.as_raw().to_vec())
+    } else {
+        Ok(child.data().map_err(|_| ())?.to_vec())
+    }
+}
+
+fn decode_hex_prefix(encoded: &[u8]) -> Result<(bool, Vec<u8>), ()> {
+    let first = *encoded.first().ok_or(())?;
+    let flag = first >> 4;
+    if flag > 3 {
+        return Err(());
+    }
+
+    let mut nibbles = Vec::new();
+    if flag & 1 == 1 {
+        nibbles.push(first & 0x0f);
+    }
+    for byte in &encoded[1..] {
+        nibbles.push(byte >> 4);
+        nibbles.push(byte & 0x0f);
+    }
+
+    Ok((flag & 2 == 2, nibbles))
+}
@@ -1,16 +1,22 @@
 use crate::{
-    mock::*, AccountOf, ClaimStartAt, Deposits, Error, External, IcoMeta, IcoMetaOf,
-    InflueceMiningMetaStore, InfluenceMiningMetaOf, Metadata, Ported, Porting, Preferred,
+    mock::*, AccountOf, AnchoredHeaders, AnchoredNumbers, Bridged, BuybackQueue, BuybackTarget,
+    Buybacks, ClaimStartAt, Deposits, Disagreements, EndpointOf, Error, External, IcoMeta,
+    IcoMetaOf, InflueceMiningMetaStore, InfluenceMiningMetaOf, Metadata, PendingTransfer, Ported,
+    Porting, Preferred, TokenStandard, ValidateEndpoint, ValidateQuorum, MAX_ANCHORED_HEADERS,
+    MAX_BUYBACKS_PER_BLOCK, MAX_BUYBACK_SPILL, MAX_PROOF_NODES,
 };
 
 use codec::Decode;
-use frame_support::{assert_err, assert_noop, assert_ok};
-use parami_primitives::constants::DOLLARS;
-use parami_traits::{transferable::Transferable, types::Network, Swaps};
+use frame_support::{assert_err, assert_noop, assert_ok, traits::Hooks};
+use parami_primitives::{constants::DOLLARS, signature::SignatureScheme, ImportFailure};
+use parami_traits::{transferable::Transferable, types::Network, BridgeNfts, Swaps};
 use parking_lot::RwLock;
-use sp_core::offchain::{testing, OffchainWorkerExt, TransactionPoolExt};
+use sp_core::{
+    offchain::{testing, OffchainDbExt, OffchainWorkerExt, TransactionPoolExt},
+    sr25519, H256,
+};
 use sp_runtime::offchain::testing::PoolState;
-use sp_runtime::traits::AccountIdConversion;
+use sp_runtime::{traits::AccountIdConversion, DispatchError, Perbill};
 use sp_std::prelude::*;
 use std::sync::Arc;
 
@@ -30,6 +36,8 @@ fn should_import() {
             token.clone(),
             SIGNING_ETH_ADDR.into(),
             SIGNATURE,
+            None,
+            SignatureScheme::Plain,
         ));
 
         let maybe_porting = <Porting<Test>>::get((Network::Ethereum, &namespace, &token));
@@ -40,11 +48,86 @@ fn should_import() {
         assert_eq!(porting.task.network, Network::Ethereum);
         assert_eq!(porting.task.namespace, namespace);
         assert_eq!(porting.task.token, token);
+        assert_eq!(porting.task.standard, None);
         assert_eq!(porting.deadline, 5);
         assert_eq!(porting.created, 0);
     });
 }
 
+#[test]
+fn should_import_with_typed_data() {
+    new_test_ext().execute_with(|| {
+        let namespace = NAMESPACE.to_vec();
+        let token = vec![0x02];
+        let did = DID_BOB;
+
+        let _result = Linker::insert_link(did, Network::Ethereum, "something".into(), did);
+
+        let owner_address = vec![
+            0xeb, 0x01, 0x4f, 0x8c, 0x8b, 0x41, 0x8d, 0xb6, 0xb4, 0x57, 0x74, 0xc3, 0x26, 0xa0,
+            0xe6, 0x4c, 0x78, 0x91, 0x4d, 0xc0,
+        ];
+
+        // EIP-712 authorization to port for DID_BOB, expiring at 100
+        let signature = [
+            0xc2, 0xf7, 0xf1, 0x16, 0x4c, 0x7b, 0x47, 0x1d, 0x01, 0x07, 0x56, 0x99, 0xb5, 0xf7,
+            0xce, 0x7c, 0x9c, 0x85, 0x96, 0xff, 0xd6, 0x0c, 0x71, 0x0e, 0x9e, 0xb8, 0x29, 0x5d,
+            0x52, 0x05, 0x58, 0x29, 0x6e, 0x08, 0xe2, 0xfb, 0x85, 0x7e, 0xa8, 0x2a, 0x77, 0x4f,
+            0xc4, 0x41, 0xcc, 0xc9, 0xdd, 0xa8, 0x54, 0xb3, 0x62, 0xb7, 0x7a, 0xd6, 0xbb, 0x54,
+            0x58, 0x21, 0x48, 0xcc, 0x9b, 0xb3, 0xbd, 0x16, 0x1b,
+        ];
+
+        let scheme = SignatureScheme::Eip712 { expiry: 100 };
+
+        System::set_block_number(101);
+
+        assert_noop!(
+            Nft::port(
+                Origin::signed(BOB),
+                Network::Ethereum,
+                namespace.clone(),
+                token.clone(),
+                owner_address.clone(),
+                signature,
+                None,
+                scheme,
+            ),
+            Error::<Test>::Expired
+        );
+
+        System::set_block_number(100);
+
+        assert_noop!(
+            Nft::port(
+                Origin::signed(BOB),
+                Network::Ethereum,
+                namespace.clone(),
+                token.clone(),
+                owner_address.clone(),
+                signature,
+                None,
+                SignatureScheme::Plain,
+            ),
+            Error::<Test>::InvalidSignature
+        );
+
+        assert_ok!(Nft::port(
+            Origin::signed(BOB),
+            Network::Ethereum,
+            namespace.clone(),
+            token.clone(),
+            owner_address.clone(),
+            signature,
+            None,
+            scheme,
+        ));
+
+        let porting = <Porting<Test>>::get((Network::Ethereum, &namespace, &token)).unwrap();
+        assert_eq!(porting.task.owner, DID_BOB);
+        assert_eq!(porting.task.owner_address, owner_address);
+    });
+}
+
 #[test]
 fn should_fail_when_imported() {
     new_test_ext().execute_with(|| {
@@ -59,6 +142,8 @@ fn should_fail_when_imported() {
                 token.clone(),
                 SIGNING_ETH_ADDR.into(),
                 SIGNATURE,
+                None,
+                SignatureScheme::Plain,
             ),
             Error::<Test>::Exists
         );
@@ -81,6 +166,8 @@ fn should_fail_when_importing() {
             token.clone(),
             SIGNING_ETH_ADDR.into(),
             SIGNATURE,
+            None,
+            SignatureScheme::Plain,
         ));
 
         assert_noop!(
@@ -91,6 +178,8 @@ fn should_fail_when_importing() {
                 token.clone(),
                 SIGNING_ETH_ADDR.into(),
                 SIGNATURE,
+                None,
+                SignatureScheme::Plain,
             ),
             Error::<Test>::Exists
         );
@@ -285,6 +374,7 @@ fn offchain_execute(
     let (pool, pool_state) = testing::TestTransactionPoolExt::new();
 
     let mut t = new_test_ext();
+    t.register_extension(OffchainDbExt::new(offchain.clone()));
     t.register_extension(OffchainWorkerExt::new(offchain));
     t.register_extension(TransactionPoolExt::new(pool));
 
@@ -402,7 +492,9 @@ fn should_import_nft_by_ocw() {
                 namespace.clone(),
                 token.into(),
                 SIGNING_ETH_ADDR.into(),
-                SIGNATURE
+                SIGNATURE,
+                Some(TokenStandard::Erc721),
+                SignatureScheme::Plain,
             ));
 
             assert_ok!(Nft::ocw_begin_block(System::block_number()));
@@ -421,7 +513,8 @@ fn should_import_nft_by_ocw() {
                     network: Network::Ethereum,
                     namespace: contract_address.to_vec(),
                     token: token.to_vec(),
-                    validated: true
+                    failure: None,
+                    disagreed: vec![],
                 })
             );
         },
@@ -429,561 +522,2121 @@ fn should_import_nft_by_ocw() {
 }
 
 #[test]
-fn should_sumbit_porting() {
-    new_test_ext().execute_with(|| {
-        let namespace = NAMESPACE.to_vec();
-        let token = vec![0x22];
-        let did = DID_BOB;
-
-        let _result = Linker::insert_link(did, Network::Ethereum, "something".into(), did);
+fn should_success_when_validate_erc1155_token_balance() {
+    let ether_endpoint = "http://etherum.endpoint/example";
+    let contract_address = b"contractaddress";
+    let token = 546u64;
 
-        assert_ok!(Nft::port(
-            Origin::signed(BOB),
-            Network::Ethereum,
-            namespace.clone(),
-            token.clone(),
-            SIGNING_ETH_ADDR.into(),
-            SIGNATURE,
-        ));
-        assert_ok!(Nft::submit_porting(
-            frame_system::RawOrigin::None.into(),
-            DID_BOB,
-            Network::Ethereum,
-            namespace.clone(),
-            token.clone(),
-            true,
-        ));
+    let body = Nft::construct_balance_of_request_body(
+        contract_address,
+        &token.to_be_bytes(),
+        &SIGNING_ETH_ADDR,
+    );
+    let res = r#"{"jsonrpc":"2.0","id":1,"result":"0x0000000000000000000000000000000000000000000000000000000000000002"}"#;
 
-        let token: &Vec<u8> = &token.into();
-        assert!(<Porting<Test>>::get((Network::Ethereum, &namespace, token)).is_none());
+    offchain_execute(
+        vec![mock_validate_request(ether_endpoint.into(), body, res)],
+        |_| {
+            let result = Nft::ocw_validate_etherum_token(
+                ether_endpoint,
+                b"contractaddress",
+                &token.to_be_bytes(),
+                &SIGNING_ETH_ADDR,
+                Some(TokenStandard::Erc1155),
+            );
 
-        assert_eq!(
-            <Ported<Test>>::get((Network::Ethereum, &namespace, token)).expect("should be ported"),
-            // genesis config creates the first token, so we got 2 here.
-            NEXT_INSTANCE_ID,
-        );
+            assert_ok!(result);
+        },
+    );
+}
 
-        let external = <External<Test>>::get(NEXT_INSTANCE_ID).expect("external should have data");
-        assert_eq!(external.owner, did);
-        assert_eq!(external.network, Network::Ethereum);
-        assert_eq!(external.namespace, namespace);
-        assert_eq!(external.token, token.clone());
+#[test]
+fn should_fail_when_erc1155_balance_is_zero() {
+    let ether_endpoint = "http://etherum.endpoint/example";
+    let contract_address = b"contractaddress";
+    let token = 546u64;
 
-        let subaccount_id =
-            <Test as crate::Config>::PalletId::get().into_sub_account_truncating(NEXT_INSTANCE_ID);
+    let body = Nft::construct_balance_of_request_body(
+        contract_address,
+        &token.to_be_bytes(),
+        &SIGNING_ETH_ADDR,
+    );
+    let res = r#"{"jsonrpc":"2.0","id":1,"result":"0x0000000000000000000000000000000000000000000000000000000000000000"}"#;
 
-        let metadata = <Metadata<Test>>::get(NEXT_INSTANCE_ID).expect("meta should have data");
-        assert_eq!(metadata.owner, did);
-        assert_eq!(metadata.class_id, NEXT_INSTANCE_ID);
-        assert_eq!(metadata.pot, subaccount_id);
-        assert_eq!(metadata.minted, false);
-        assert_eq!(metadata.token_asset_id, NEXT_INSTANCE_ID);
+    offchain_execute(
+        vec![mock_validate_request(ether_endpoint.into(), body, res)],
+        |_| {
+            let result = Nft::ocw_validate_etherum_token(
+                ether_endpoint,
+                b"contractaddress",
+                &token.to_be_bytes(),
+                &SIGNING_ETH_ADDR,
+                Some(TokenStandard::Erc1155),
+            );
 
-        let preferred = <Preferred<Test>>::get(DID_BOB).expect("prefered should have data");
-        assert_eq!(preferred, NEXT_INSTANCE_ID);
-    });
+            assert_noop!(result, Error::<Test>::NotTokenOwner);
+        },
+    );
 }
 
 #[test]
-fn should_transfer_all_assets() {
-    new_test_ext().execute_with(|| {
-        assert_ok!(Nft::mint_nft_power(
-            Origin::signed(ALICE),
-            0,
-            b"Test Token1".to_vec(),
-            b"XTT1".to_vec(),
-            500
-        ));
-
-        assert_ok!(Nft::mint_nft_power(
-            Origin::signed(ALICE),
-            1,
-            b"Test Token2".to_vec(),
-            b"XTT2".to_vec(),
-            1000
-        ));
-
-        assert_eq!(Assets::balance(0, &ALICE), 500);
-        assert_eq!(Assets::balance(1, &ALICE), 1000);
-        assert_eq!(Assets::balance(0, &BOB), 0);
-        assert_eq!(Assets::balance(1, &BOB), 0);
+fn should_detect_token_standard() {
+    let ether_endpoint = "http://etherum.endpoint/example";
+    let contract_address = b"contractaddress";
+    let interface_id = [0xd9, 0xb6, 0x7a, 0x26];
 
-        assert_ok!(<(Nft,)>::transfer_all(&ALICE, &BOB));
+    let body = Nft::construct_supports_interface_request_body(contract_address, &interface_id);
+    let supported = r#"{"jsonrpc":"2.0","id":1,"result":"0x0000000000000000000000000000000000000000000000000000000000000001"}"#;
+    let reverted =
+        r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32000,"message":"execution reverted"}}"#;
+    let unavailable = "<html><body>502 Bad Gateway</body></html>";
 
-        assert_eq!(Assets::balance(0, &ALICE), 0);
-        assert_eq!(Assets::balance(1, &ALICE), 0);
-        assert_eq!(Assets::balance(0, &BOB), 500);
-        assert_eq!(Assets::balance(1, &BOB), 1000);
-    });
+    offchain_execute(
+        vec![
+            mock_validate_request(ether_endpoint.into(), body.clone(), supported),
+            mock_validate_request(ether_endpoint.into(), body.clone(), reverted),
+            mock_validate_request(ether_endpoint.into(), body, unavailable),
+        ],
+        |_| {
+            assert_eq!(
+                Nft::ocw_detect_etherum_token_standard(ether_endpoint, contract_address),
+                Ok(TokenStandard::Erc1155)
+            );
+            assert_eq!(
+                Nft::ocw_detect_etherum_token_standard(ether_endpoint, contract_address),
+                Ok(TokenStandard::Erc721)
+            );
+            assert!(
+                Nft::ocw_detect_etherum_token_standard(ether_endpoint, contract_address).is_err()
+            );
+        },
+    );
 }
 
 #[test]
-fn should_mint_asset() {
-    use frame_support::traits::tokens::fungibles::Inspect;
-    new_test_ext().execute_with(|| {
-        let nft = Nft::preferred(DID_ALICE).unwrap();
+fn should_import_erc1155_nft_by_ocw() {
+    let ether_endpoint = "http://etherum.endpoint/example";
+    let contract_address = b"contractaddress";
+    let token = 546u64.to_be_bytes();
+    let interface_id = [0xd9, 0xb6, 0x7a, 0x26];
 
-        assert_eq!(Assets::balance(nft, &ALICE), 0);
-        let result = Nft::mint_nft_power(
-            Origin::signed(ALICE),
-            nft,
-            b"Test Token".to_vec(),
-            b"TT".to_vec(),
-            200,
-        );
-        assert_ok!(result);
-        assert_eq!(Assets::total_issuance(nft), 200);
-        assert_eq!(Assets::balance(nft, ALICE), 200);
-    });
-}
+    let detect_body =
+        Nft::construct_supports_interface_request_body(contract_address, &interface_id);
+    let detect_res = r#"{"jsonrpc":"2.0","id":1,"result":"0x0000000000000000000000000000000000000000000000000000000000000001"}"#;
+    let balance_body =
+        Nft::construct_balance_of_request_body(contract_address, &token, &SIGNING_ETH_ADDR);
+    let balance_res = r#"{"jsonrpc":"2.0","id":1,"result":"0x0000000000000000000000000000000000000000000000000000000000000001"}"#;
 
-#[test]
-fn should_start_ico() {
-    new_test_ext().execute_with(|| {
-        let nft = Nft::preferred(DID_ALICE).unwrap();
+    offchain_execute(
+        vec![
+            mock_validate_request(ether_endpoint.into(), detect_body, detect_res),
+            mock_validate_request(ether_endpoint.into(), balance_body, balance_res),
+        ],
+        |pool_state| {
+            assert_ok!(Nft::port(
+                Origin::signed(BOB),
+                Network::Ethereum,
+                contract_address.to_vec(),
+                token.into(),
+                SIGNING_ETH_ADDR.into(),
+                SIGNATURE,
+                None,
+                SignatureScheme::Plain,
+            ));
 
-        assert_ok!(Nft::mint_nft_power(
-            Origin::signed(ALICE),
-            nft,
-            b"Test Token".to_vec(),
-            b"TT".to_vec(),
-            200,
-        ));
-        assert_eq!(Assets::balance(nft, ALICE), 200);
-        assert_ok!(Nft::start_ico(Origin::signed(ALICE), nft, 100, 50));
-        assert_eq!(Assets::balance(nft, ALICE), 150);
+            assert_ok!(Nft::ocw_begin_block(System::block_number()));
 
-        let ico_meta = IcoMetaOf::<Test>::get(nft).unwrap();
-        assert_eq!(ico_meta.done, false);
-        assert_eq!(ico_meta.expected_currency, 100);
-        assert_eq!(ico_meta.offered_tokens, 50);
-        assert_eq!(Assets::balance(nft, ico_meta.pot), 50);
-    });
+            let tx = pool_state.write().transactions.pop().unwrap();
+            let tx = Extrinsic::decode(&mut &*tx).unwrap();
+
+            assert_eq!(
+                tx.call,
+                Call::Nft(crate::Call::submit_porting {
+                    did: DID_BOB,
+                    network: Network::Ethereum,
+                    namespace: contract_address.to_vec(),
+                    token: token.to_vec(),
+                    failure: None,
+                    disagreed: vec![],
+                })
+            );
+        },
+    );
 }
 
 #[test]
-fn should_participate_ico() {
+fn should_set_validate_endpoints() {
     new_test_ext().execute_with(|| {
-        let nft = Nft::preferred(DID_ALICE).unwrap();
+        let endpoints: Vec<Vec<u8>> = vec![b"http://a".to_vec(), b"http://b".to_vec()];
 
-        let result = Nft::mint_nft_power(
-            Origin::signed(ALICE),
-            nft,
-            b"Test Token".to_vec(),
-            b"TT".to_vec(),
-            200,
+        assert_noop!(
+            Nft::set_validate_endpoints(Origin::root(), Network::Ethereum, endpoints.clone(), 0),
+            Error::<Test>::InvalidQuorum
+        );
+        assert_noop!(
+            Nft::set_validate_endpoints(Origin::root(), Network::Ethereum, endpoints.clone(), 3),
+            Error::<Test>::InvalidQuorum
+        );
+        assert_noop!(
+            Nft::set_validate_endpoints(
+                Origin::root(),
+                Network::Ethereum,
+                vec![b"http://a".to_vec(); 9],
+                1
+            ),
+            Error::<Test>::TooManyEndpoints
         );
-        assert_ok!(result);
-        assert_ok!(Nft::start_ico(Origin::signed(ALICE), nft, 100, 50));
-
-        let meta = IcoMetaOf::<Test>::get(nft).unwrap();
-
-        assert_eq!(Assets::balance(nft, meta.pot), 50);
 
-        assert_eq!(Balances::free_balance(ALICE), 3000000 * DOLLARS);
-        assert_eq!(Balances::free_balance(BOB), 3000000 * DOLLARS);
-        assert_ok!(Nft::participate_ico(Origin::signed(BOB), nft, 50));
-        assert_eq!(Balances::free_balance(BOB), 3000000 * DOLLARS - 100);
-        assert_eq!(Balances::free_balance(ALICE), 3000000 * DOLLARS + 100);
+        assert_ok!(Nft::set_validate_endpoints(
+            Origin::root(),
+            Network::Ethereum,
+            endpoints.clone(),
+            2
+        ));
 
-        assert_eq!(Assets::balance(nft, meta.pot), 50);
+        let stored = <ValidateEndpoint<Test>>::get(Network::Ethereum).unwrap();
+        assert_eq!(stored.len(), 2);
+        assert_eq!(stored[1].to_vec(), endpoints[1]);
+        assert_eq!(<ValidateQuorum<Test>>::get(Network::Ethereum), Some(2));
     });
 }
 
 #[test]
-fn should_end_ico() {
-    new_test_ext().execute_with(|| {
-        let nft = Nft::preferred(DID_ALICE).unwrap();
+fn should_import_nft_when_quorum_reached() {
+    let endpoints = [
+        "http://etherum.endpoint/a",
+        "http://etherum.endpoint/b",
+        "http://etherum.endpoint/c",
+    ];
+    let contract_address = b"contractaddress";
+    let token = 546u64.to_be_bytes();
+
+    let body = Nft::construct_request_body(contract_address, &token);
+    let owned = r#"{"jsonrpc":"2.0","id":1,"result":"0x000000000000000000000000dbd04424318d1e06b34259add64bf10a8eb45a87"}"#;
+    let not_owned = r#"{"jsonrpc":"2.0","id":1,"result":"0x000000000000000000000000eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee"}"#;
+
+    offchain_execute(
+        vec![
+            mock_validate_request(endpoints[0], body.clone(), owned),
+            mock_validate_request(endpoints[1], body.clone(), not_owned),
+            mock_validate_request(endpoints[2], body, owned),
+        ],
+        |pool_state| {
+            assert_ok!(Nft::set_validate_endpoints(
+                Origin::root(),
+                Network::Ethereum,
+                endpoints.iter().map(|e| e.as_bytes().to_vec()).collect(),
+                2
+            ));
+
+            assert_ok!(Nft::port(
+                Origin::signed(BOB),
+                Network::Ethereum,
+                contract_address.to_vec(),
+                token.into(),
+                SIGNING_ETH_ADDR.into(),
+                SIGNATURE,
+                Some(TokenStandard::Erc721),
+                SignatureScheme::Plain,
+            ));
+
+            assert_ok!(Nft::ocw_begin_block(System::block_number()));
+
+            let tx = pool_state.write().transactions.pop().unwrap();
+            let tx = Extrinsic::decode(&mut &*tx).unwrap();
+
+            let call = crate::Call::submit_porting {
+                did: DID_BOB,
+                network: Network::Ethereum,
+                namespace: contract_address.to_vec(),
+                token: token.to_vec(),
+                failure: None,
+                disagreed: vec![1],
+            };
+            assert_eq!(tx.call, Call::Nft(call));
+
+            assert_ok!(Nft::submit_porting(
+                frame_system::RawOrigin::None.into(),
+                DID_BOB,
+                Network::Ethereum,
+                contract_address.to_vec(),
+                token.to_vec(),
+                None,
+                vec![1],
+            ));
+
+            let stored = <ValidateEndpoint<Test>>::get(Network::Ethereum).unwrap();
+            assert_eq!(<Disagreements<Test>>::get(Network::Ethereum, &stored[0]), 0);
+            assert_eq!(<Disagreements<Test>>::get(Network::Ethereum, &stored[1]), 1);
+            assert_eq!(<Disagreements<Test>>::get(Network::Ethereum, &stored[2]), 0);
+        },
+    );
+}
+
+#[test]
+fn should_not_import_nft_when_quorum_not_reached() {
+    let endpoints = ["http://etherum.endpoint/a", "http://etherum.endpoint/b"];
+    let contract_address = b"contractaddress";
+    let token = 546u64.to_be_bytes();
+
+    let body = Nft::construct_request_body(contract_address, &token);
+    let owned = r#"{"jsonrpc":"2.0","id":1,"result":"0x000000000000000000000000dbd04424318d1e06b34259add64bf10a8eb45a87"}"#;
+    let not_owned = r#"{"jsonrpc":"2.0","id":1,"result":"0x000000000000000000000000eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee"}"#;
+
+    offchain_execute(
+        vec![
+            mock_validate_request(endpoints[0], body.clone(), owned),
+            mock_validate_request(endpoints[1], body, not_owned),
+        ],
+        |pool_state| {
+            assert_ok!(Nft::set_validate_endpoints(
+                Origin::root(),
+                Network::Ethereum,
+                endpoints.iter().map(|e| e.as_bytes().to_vec()).collect(),
+                2
+            ));
+
+            assert_ok!(Nft::port(
+                Origin::signed(BOB),
+                Network::Ethereum,
+                contract_address.to_vec(),
+                token.into(),
+                SIGNING_ETH_ADDR.into(),
+                SIGNATURE,
+                Some(TokenStandard::Erc721),
+                SignatureScheme::Plain,
+            ));
+
+            assert_ok!(Nft::ocw_begin_block(System::block_number()));
+
+            let tx = pool_state.write().transactions.pop().unwrap();
+            let tx = Extrinsic::decode(&mut &*tx).unwrap();
+
+            let call = crate::Call::submit_porting {
+                did: DID_BOB,
+                network: Network::Ethereum,
+                namespace: contract_address.to_vec(),
+                token: token.to_vec(),
+                failure: Some(ImportFailure::NotOwner),
+                disagreed: vec![],
+            };
+            assert_eq!(tx.call, Call::Nft(call));
+        },
+    );
+}
+
+#[test]
+fn should_classify_import_failures() {
+    new_test_ext().execute_with(|| {
+        let endpoints: Vec<EndpointOf> = vec![
+            b"http://etherum.endpoint/a".to_vec().try_into().unwrap(),
+            b"http://etherum.endpoint/b".to_vec().try_into().unwrap(),
+        ];
+
+        assert_eq!(
+            Nft::ocw_validate_with_quorum(&endpoints, 1, |_| {
+                Err(parami_ocw::Error::<Test>::HttpError.into())
+            }),
+            Err(ImportFailure::RpcError)
+        );
+        assert_eq!(
+            Nft::ocw_validate_with_quorum(&endpoints, 1, |_| {
+                Err(Error::<Test>::NotTokenOwner.into())
+            }),
+            Err(ImportFailure::NotOwner)
+        );
+        assert_eq!(
+            Nft::ocw_validate_with_quorum(&endpoints, 1, |_| {
+                Err(Error::<Test>::OcwParseError.into())
+            }),
+            Err(ImportFailure::BadContract)
+        );
+        assert_eq!(
+            Nft::ocw_validate_with_quorum(&endpoints, 1, |endpoint| {
+                if endpoint.ends_with('a') {
+                    Ok(())
+                } else {
+                    Err(Error::<Test>::NotTokenOwner.into())
+                }
+            }),
+            Ok(vec![1])
+        );
+    });
+}
+
+#[test]
+fn should_retry_import_when_endpoint_unavailable() {
+    let ether_endpoint = "http://etherum.endpoint/example";
+    let contract_address = b"contractaddress";
+    let token = 546u64.to_be_bytes();
+
+    let body = Nft::construct_request_body(contract_address, &token);
+    let unavailable = "<html><body>502 Bad Gateway</body></html>";
+    let owned = r#"{"jsonrpc":"2.0","id":1,"result":"0x000000000000000000000000dbd04424318d1e06b34259add64bf10a8eb45a87"}"#;
+
+    offchain_execute(
+        vec![
+            mock_validate_request(ether_endpoint, body.clone(), unavailable),
+            mock_validate_request(ether_endpoint, body, owned),
+        ],
+        |pool_state| {
+            assert_ok!(Nft::port(
+                Origin::signed(BOB),
+                Network::Ethereum,
+                contract_address.to_vec(),
+                token.into(),
+                SIGNING_ETH_ADDR.into(),
+                SIGNATURE,
+                Some(TokenStandard::Erc721),
+                SignatureScheme::Plain,
+            ));
+
+            let block_number = System::block_number();
+
+            assert_ok!(Nft::ocw_begin_block(block_number));
+            assert!(pool_state.read().transactions.is_empty());
+
+            // backing off, no request is sent
+            assert_ok!(Nft::ocw_begin_block(block_number));
+            assert!(pool_state.read().transactions.is_empty());
+
+            assert_ok!(Nft::ocw_begin_block(block_number + 1));
+
+            let tx = pool_state.write().transactions.pop().unwrap();
+            let tx = Extrinsic::decode(&mut &*tx).unwrap();
+
+            let call = crate::Call::submit_porting {
+                did: DID_BOB,
+                network: Network::Ethereum,
+                namespace: contract_address.to_vec(),
+                token: token.to_vec(),
+                failure: None,
+                disagreed: vec![],
+            };
+            assert_eq!(tx.call, Call::Nft(call));
+        },
+    );
+}
+
+#[test]
+fn should_fail_import_when_timeout() {
+    offchain_execute(vec![], |pool_state| {
+        let namespace = NAMESPACE.to_vec();
+        let token = vec![0x22];
+
+        assert_ok!(Nft::port(
+            Origin::signed(BOB),
+            Network::Ethereum,
+            namespace.clone(),
+            token.clone(),
+            SIGNING_ETH_ADDR.into(),
+            SIGNATURE,
+            None,
+            SignatureScheme::Plain,
+        ));
+
+        let task = <Porting<Test>>::get((Network::Ethereum, &namespace, &token)).unwrap();
+
+        assert_ok!(Nft::ocw_begin_block(task.deadline));
+
+        let tx = pool_state.write().transactions.pop().unwrap();
+        let tx = Extrinsic::decode(&mut &*tx).unwrap();
+
+        let call = crate::Call::submit_porting {
+            did: DID_BOB,
+            network: Network::Ethereum,
+            namespace,
+            token,
+            failure: Some(ImportFailure::Timeout),
+            disagreed: vec![],
+        };
+        assert_eq!(tx.call, Call::Nft(call));
+    });
+}
+
+#[test]
+fn should_record_failed_import() {
+    new_test_ext().execute_with(|| {
+        let namespace = NAMESPACE.to_vec();
+        let token = vec![0x22];
+
+        assert_ok!(Nft::port(
+            Origin::signed(BOB),
+            Network::Ethereum,
+            namespace.clone(),
+            token.clone(),
+            SIGNING_ETH_ADDR.into(),
+            SIGNATURE,
+            None,
+            SignatureScheme::Plain,
+        ));
+
+        assert_eq!(
+            Nft::pending_imports(DID_BOB),
+            vec![(Network::Ethereum, namespace.clone(), token.clone())]
+        );
+        assert_eq!(Nft::failed_imports(DID_BOB), vec![]);
+
+        assert_ok!(Nft::submit_porting(
+            frame_system::RawOrigin::None.into(),
+            DID_BOB,
+            Network::Ethereum,
+            namespace.clone(),
+            token.clone(),
+            Some(ImportFailure::NotOwner),
+            vec![],
+        ));
+
+        assert!(<Porting<Test>>::get((Network::Ethereum, &namespace, &token)).is_none());
+        assert!(<Ported<Test>>::get((Network::Ethereum, &namespace, &token)).is_none());
+
+        assert_eq!(Nft::pending_imports(DID_BOB), vec![]);
+        assert_eq!(
+            Nft::failed_imports(DID_BOB),
+            vec![(
+                Network::Ethereum,
+                namespace.clone(),
+                token.clone(),
+                ImportFailure::NotOwner
+            )]
+        );
+
+        // retry clears the failure
+        assert_ok!(Nft::port(
+            Origin::signed(BOB),
+            Network::Ethereum,
+            namespace.clone(),
+            token.clone(),
+            SIGNING_ETH_ADDR.into(),
+            SIGNATURE,
+            None,
+            SignatureScheme::Plain,
+        ));
+
+        assert_eq!(Nft::failed_imports(DID_BOB), vec![]);
+    });
+}
+
+#[test]
+fn should_cancel_port() {
+    new_test_ext().execute_with(|| {
+        let namespace = NAMESPACE.to_vec();
+        let token = vec![0x22];
+
+        assert_ok!(Nft::port(
+            Origin::signed(BOB),
+            Network::Ethereum,
+            namespace.clone(),
+            token.clone(),
+            SIGNING_ETH_ADDR.into(),
+            SIGNATURE,
+            None,
+            SignatureScheme::Plain,
+        ));
+
+        assert_noop!(
+            Nft::cancel_port(
+                Origin::signed(ALICE),
+                Network::Ethereum,
+                namespace.clone(),
+                token.clone()
+            ),
+            Error::<Test>::NotTokenOwner
+        );
+
+        assert_ok!(Nft::cancel_port(
+            Origin::signed(BOB),
+            Network::Ethereum,
+            namespace.clone(),
+            token.clone()
+        ));
+
+        assert!(<Porting<Test>>::get((Network::Ethereum, &namespace, &token)).is_none());
+
+        assert_noop!(
+            Nft::cancel_port(Origin::signed(BOB), Network::Ethereum, namespace, token),
+            Error::<Test>::NotExists
+        );
+    });
+}
+
+#[test]
+fn should_sumbit_porting() {
+    new_test_ext().execute_with(|| {
+        let namespace = NAMESPACE.to_vec();
+        let token = vec![0x22];
+        let did = DID_BOB;
+
+        let _result = Linker::insert_link(did, Network::Ethereum, "something".into(), did);
+
+        assert_ok!(Nft::port(
+            Origin::signed(BOB),
+            Network::Ethereum,
+            namespace.clone(),
+            token.clone(),
+            SIGNING_ETH_ADDR.into(),
+            SIGNATURE,
+            None,
+            SignatureScheme::Plain,
+        ));
+        assert_ok!(Nft::submit_porting(
+            frame_system::RawOrigin::None.into(),
+            DID_BOB,
+            Network::Ethereum,
+            namespace.clone(),
+            token.clone(),
+            None,
+            vec![],
+        ));
+
+        let token: &Vec<u8> = &token.into();
+        assert!(<Porting<Test>>::get((Network::Ethereum, &namespace, token)).is_none());
+
+        assert_eq!(
+            <Ported<Test>>::get((Network::Ethereum, &namespace, token)).expect("should be ported"),
+            // genesis config creates the first token, so we got 2 here.
+            NEXT_INSTANCE_ID,
+        );
+
+        let external = <External<Test>>::get(NEXT_INSTANCE_ID).expect("external should have data");
+        assert_eq!(external.owner, did);
+        assert_eq!(external.network, Network::Ethereum);
+        assert_eq!(external.namespace, namespace);
+        assert_eq!(external.token, token.clone());
+
+        let subaccount_id =
+            <Test as crate::Config>::PalletId::get().into_sub_account_truncating(NEXT_INSTANCE_ID);
+
+        let metadata = <Metadata<Test>>::get(NEXT_INSTANCE_ID).expect("meta should have data");
+        assert_eq!(metadata.owner, did);
+        assert_eq!(metadata.class_id, NEXT_INSTANCE_ID);
+        assert_eq!(metadata.pot, subaccount_id);
+        assert_eq!(metadata.minted, false);
+        assert_eq!(metadata.token_asset_id, NEXT_INSTANCE_ID);
+
+        let preferred = <Preferred<Test>>::get(DID_BOB).expect("prefered should have data");
+        assert_eq!(preferred, NEXT_INSTANCE_ID);
+    });
+}
+
+#[test]
+fn should_transfer_all_assets() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Nft::mint_nft_power(
+            Origin::signed(ALICE),
+            0,
+            b"Test Token1".to_vec(),
+            b"XTT1".to_vec(),
+            500
+        ));
+
+        assert_ok!(Nft::mint_nft_power(
+            Origin::signed(ALICE),
+            1,
+            b"Test Token2".to_vec(),
+            b"XTT2".to_vec(),
+            1000
+        ));
+
+        assert_eq!(Assets::balance(0, &ALICE), 500);
+        assert_eq!(Assets::balance(1, &ALICE), 1000);
+        assert_eq!(Assets::balance(0, &BOB), 0);
+        assert_eq!(Assets::balance(1, &BOB), 0);
+
+        assert_ok!(<(Nft,)>::transfer_all(&ALICE, &BOB));
+
+        assert_eq!(Assets::balance(0, &ALICE), 0);
+        assert_eq!(Assets::balance(1, &ALICE), 0);
+        assert_eq!(Assets::balance(0, &BOB), 500);
+        assert_eq!(Assets::balance(1, &BOB), 1000);
+    });
+}
+
+#[test]
+fn should_mint_asset() {
+    use frame_support::traits::tokens::fungibles::Inspect;
+    new_test_ext().execute_with(|| {
+        let nft = Nft::preferred(DID_ALICE).unwrap();
+
+        assert_eq!(Assets::balance(nft, &ALICE), 0);
+        let result = Nft::mint_nft_power(
+            Origin::signed(ALICE),
+            nft,
+            b"Test Token".to_vec(),
+            b"TT".to_vec(),
+            200,
+        );
+        assert_ok!(result);
+        assert_eq!(Assets::total_issuance(nft), 200);
+        assert_eq!(Assets::balance(nft, ALICE), 200);
+    });
+}
+
+#[test]
+fn should_start_ico() {
+    new_test_ext().execute_with(|| {
+        let nft = Nft::preferred(DID_ALICE).unwrap();
+
+        assert_ok!(Nft::mint_nft_power(
+            Origin::signed(ALICE),
+            nft,
+            b"Test Token".to_vec(),
+            b"TT".to_vec(),
+            200,
+        ));
+        assert_eq!(Assets::balance(nft, ALICE), 200);
+        assert_ok!(Nft::start_ico(Origin::signed(ALICE), nft, 100, 50));
+        assert_eq!(Assets::balance(nft, ALICE), 150);
+
+        let ico_meta = IcoMetaOf::<Test>::get(nft).unwrap();
+        assert_eq!(ico_meta.done, false);
+        assert_eq!(ico_meta.expected_currency, 100);
+        assert_eq!(ico_meta.offered_tokens, 50);
+        assert_eq!(Assets::balance(nft, ico_meta.pot), 50);
+    });
+}
+
+#[test]
+fn should_participate_ico() {
+    new_test_ext().execute_with(|| {
+        let nft = Nft::preferred(DID_ALICE).unwrap();
+
+        let result = Nft::mint_nft_power(
+            Origin::signed(ALICE),
+            nft,
+            b"Test Token".to_vec(),
+            b"TT".to_vec(),
+            200,
+        );
+        assert_ok!(result);
+        assert_ok!(Nft::start_ico(Origin::signed(ALICE), nft, 100, 50));
+
+        let meta = IcoMetaOf::<Test>::get(nft).unwrap();
+
+        assert_eq!(Assets::balance(nft, meta.pot), 50);
+
+        assert_eq!(Balances::free_balance(ALICE), 3000000 * DOLLARS);
+        assert_eq!(Balances::free_balance(BOB), 3000000 * DOLLARS);
+        assert_ok!(Nft::participate_ico(Origin::signed(BOB), nft, 50));
+        assert_eq!(Balances::free_balance(BOB), 3000000 * DOLLARS - 100);
+        assert_eq!(Balances::free_balance(ALICE), 3000000 * DOLLARS + 100);
+
+        assert_eq!(Assets::balance(nft, meta.pot), 50);
+    });
+}
+
+#[test]
+fn should_end_ico() {
+    new_test_ext().execute_with(|| {
+        let nft = Nft::preferred(DID_ALICE).unwrap();
+
+        let result = Nft::mint_nft_power(
+            Origin::signed(ALICE),
+            nft,
+            b"Test Token".to_vec(),
+            b"TT".to_vec(),
+            200,
+        );
+        assert_ok!(result);
+        assert_ok!(Nft::start_ico(Origin::signed(ALICE), nft, 50, 100));
+        assert_ok!(Nft::participate_ico(Origin::signed(BOB), nft, 50));
+        assert_ok!(Nft::end_ico(Origin::signed(ALICE), nft));
+
+        let ico_meta = IcoMetaOf::<Test>::get(nft).unwrap();
+        let meta = Metadata::<Test>::get(nft).unwrap();
+        let block_num = ClaimStartAt::<Test>::get(nft).unwrap();
+        assert_eq!(ico_meta.done, true);
+        assert_eq!(Assets::balance(nft, meta.pot), 50);
+        assert_eq!(block_num, 0);
+    });
+}
+
+#[test]
+fn should_generate_unique_pot_for_ico_meta() {
+    new_test_ext().execute_with(|| {
+        let nft = Nft::preferred(DID_ALICE).unwrap();
+        let meta_pot = Nft::generate_claim_pot(&nft);
+        let ico_pot_1 = Nft::generate_ico_pot(&nft);
+        let ico_pot_2 = Nft::generate_ico_pot(&(nft + 1));
+
+        assert_ne!(meta_pot, ico_pot_1);
+        assert_ne!(ico_pot_1, ico_pot_2);
+    });
+}
+
+#[test]
+fn should_calculate_price_correct() {
+    new_test_ext().execute_with(|| {
+        let required_currency = Nft::calculate_required_currency(
+            50,
+            &IcoMeta::<Test> {
+                expected_currency: 100,
+                offered_tokens: 50,
+                done: true,
+                pot: ALICE,
+            },
+        )
+        .unwrap();
+
+        assert_eq!(required_currency, 100);
+
+        let required_currency = Nft::calculate_required_currency(
+            50,
+            &IcoMeta::<Test> {
+                expected_currency: 99,
+                offered_tokens: 50,
+                done: true,
+                pot: ALICE,
+            },
+        )
+        .unwrap();
+
+        assert_eq!(required_currency, 99);
+
+        let required_currency = Nft::calculate_required_currency(
+            49,
+            &IcoMeta::<Test> {
+                expected_currency: 99,
+                offered_tokens: 50,
+                done: true,
+                pot: ALICE,
+            },
+        )
+        .unwrap();
+
+        assert_eq!(required_currency, 97);
+
+        let required_currency = Nft::calculate_required_currency(
+            1,
+            &IcoMeta::<Test> {
+                expected_currency: 99,
+                offered_tokens: 50,
+                done: true,
+                pot: ALICE,
+            },
+        )
+        .unwrap();
+
+        assert_eq!(required_currency, 1);
+
+        let required_token = Nft::calculate_required_token(
+            1,
+            &IcoMeta::<Test> {
+                expected_currency: 99,
+                offered_tokens: 50,
+                done: true,
+                pot: ALICE,
+            },
+        )
+        .unwrap();
+
+        assert_eq!(required_token, 0);
+    });
+}
+
+#[test]
+fn should_failed_if_ico_with_wrong_params() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Nft::mint_nft_power(
+                Origin::signed(ALICE),
+                10086,
+                b"TT".to_vec(),
+                b"TT".to_vec(),
+                100,
+            ),
+            Error::<Test>::NotExists,
+        );
+
+        let nft = Nft::preferred(DID_ALICE).unwrap();
+        assert_noop!(
+            Nft::mint_nft_power(
+                Origin::signed(BOB),
+                nft,
+                b"TT".to_vec(),
+                b"TT".to_vec(),
+                100,
+            ),
+            Error::<Test>::NotTokenOwner,
+        );
+
+        assert_ok!(Nft::mint_nft_power(
+            Origin::signed(ALICE),
+            nft,
+            b"TT".to_vec(),
+            b"TT".to_vec(),
+            100,
+        ));
+
+        assert_noop!(
+            Nft::mint_nft_power(
+                Origin::signed(ALICE),
+                nft,
+                b"TT".to_vec(),
+                b"TT".to_vec(),
+                100,
+            ),
+            Error::<Test>::Minted,
+        );
+    });
+}
+
+#[test]
+fn should_failed_to_participate_ico_if_with_wrong_params() {
+    new_test_ext().execute_with(|| {
+        let nft = Nft::preferred(DID_ALICE).unwrap();
+        assert_noop!(
+            Nft::participate_ico(Origin::signed(ALICE), nft, 50),
+            Error::<Test>::NotExists,
+        );
+
+        assert_ok!(Nft::mint_nft_power(
+            Origin::signed(ALICE),
+            nft,
+            b"TT".to_vec(),
+            b"TT".to_vec(),
+            100,
+        ));
+
+        assert_ok!(Nft::start_ico(Origin::signed(ALICE), nft, 50, 50));
+        assert_noop!(
+            Nft::participate_ico(Origin::signed(ALICE), nft, 150),
+            Error::<Test>::InsufficientToken
+        );
+        assert_ok!(Nft::end_ico(Origin::signed(ALICE), nft));
+
+        assert_noop!(
+            Nft::participate_ico(Origin::signed(ALICE), nft, 50),
+            Error::<Test>::Deadline,
+        );
+    });
+}
+
+#[test]
+fn should_failed_to_end_ico_if_with_wrong_params() {
+    new_test_ext().execute_with(|| {
+        let nft = Nft::preferred(DID_ALICE).unwrap();
+        assert_noop!(
+            Nft::end_ico(Origin::signed(ALICE), nft),
+            Error::<Test>::NotExists
+        );
+
+        assert_ok!(Nft::mint_nft_power(
+            Origin::signed(ALICE),
+            nft,
+            b"TT".to_vec(),
+            b"TT".to_vec(),
+            100,
+        ));
+
+        assert_noop!(
+            Nft::end_ico(Origin::signed(BOB), nft),
+            Error::<Test>::NotTokenOwner
+        );
+    });
+}
+
+#[test]
+fn should_claim_for_ico_meta() {
+    use crate::Nfts;
+    new_test_ext().execute_with(|| {
+        let nft = Nft::preferred(DID_ALICE).unwrap();
+
+        assert_ok!(Nft::mint_nft_power(
+            Origin::signed(ALICE),
+            nft,
+            b"TT".to_vec(),
+            b"TT".to_vec(),
+            100 * DOLLARS,
+        ));
+        assert_ok!(Nft::start_ico(
+            Origin::signed(ALICE),
+            nft,
+            20 * DOLLARS,
+            20 * DOLLARS
+        ));
+        assert_ok!(Nft::participate_ico(Origin::signed(BOB), nft, 10 * DOLLARS));
+
+        assert_ok!(Nft::end_ico(Origin::signed(ALICE), nft));
+
+        let (total, unlocked, claimable) = Nft::get_claim_info(nft, &DID_BOB).unwrap();
+        assert_eq!(total, 10 * DOLLARS);
+        assert_eq!(unlocked, 0);
+        assert_eq!(claimable, 0);
+
+        // InitialMintingLockupPeriod is 5
+        System::set_block_number(1);
+        let (total, unlocked, claimable) = Nft::get_claim_info(nft, &DID_BOB).unwrap();
+        assert_eq!(total, 10 * DOLLARS);
+        assert_eq!(unlocked, 2 * DOLLARS);
+        assert_eq!(claimable, 2 * DOLLARS);
+
+        assert_ok!(Nft::claim(Origin::signed(BOB), nft));
+
+        System::set_block_number(2);
+        let (total, unlocked, claimable) = Nft::get_claim_info(nft, &DID_BOB).unwrap();
+        assert_eq!(total, 10 * DOLLARS);
+        assert_eq!(unlocked, 4 * DOLLARS);
+        assert_eq!(claimable, 2 * DOLLARS);
+
+        System::set_block_number(5);
+        let (total, unlocked, claimable) = Nft::get_claim_info(nft, &DID_BOB).unwrap();
+        assert_eq!(total, 10 * DOLLARS);
+        assert_eq!(unlocked, 10 * DOLLARS);
+        assert_eq!(claimable, 8 * DOLLARS);
+
+        System::set_block_number(6);
+        let (total, unlocked, claimable) = Nft::get_claim_info(nft, &DID_BOB).unwrap();
+        assert_eq!(total, 10 * DOLLARS);
+        assert_eq!(unlocked, 10 * DOLLARS);
+        assert_eq!(claimable, 8 * DOLLARS);
+    });
+}
+
+#[test]
+fn should_start_influence_mining_activity() {
+    new_test_ext().execute_with(|| {
+        let nft = Nft::preferred(DID_ALICE).unwrap();
+
+        assert_ok!(Nft::mint_nft_power(
+            Origin::signed(ALICE),
+            nft,
+            b"TT".to_vec(),
+            b"TT".to_vec(),
+            100_000_000 * DOLLARS,
+        ));
+
+        let nft_meta = <Metadata<Test>>::get(nft).unwrap();
+
+        //before stats
+        let asset_balance_of_owner_before = Assets::balance(nft_meta.token_asset_id, ALICE);
+
+        let pot_account: AccountOf<Test> = Nft::generate_influence_mining_pot(&nft);
+
+        let asset_balance_of_pot_before = Assets::balance(nft_meta.token_asset_id, &pot_account);
+
+        //action
+        assert_ok!(Nft::start_dao_influenceming_activity(
+            Origin::signed(ALICE),
+            nft,
+            100_000 * DOLLARS
+        ));
+
+        //after stats
+        let asset_balance_after = Assets::balance(nft_meta.token_asset_id, ALICE);
+        let asset_balance_of_pot_after = Assets::balance(nft_meta.token_asset_id, &pot_account);
+
+        let meta: InfluenceMiningMetaOf<Test> = <InflueceMiningMetaStore<Test>>::get(nft).unwrap();
+        assert_eq!(meta.budget_in_tokens, 100_000 * DOLLARS);
+        assert_eq!(
+            asset_balance_after,
+            asset_balance_of_owner_before - meta.budget_in_tokens
+        );
+        assert_eq!(meta.pot, pot_account);
+        assert_eq!(
+            asset_balance_of_pot_after,
+            asset_balance_of_pot_before + meta.budget_in_tokens
+        );
+        assert_eq!(meta.pot, pot_account);
+    });
+}
+
+#[test]
+fn should_not_start_influence_mining_activity_when_already_started() {
+    new_test_ext().execute_with(|| {
+        let nft = Nft::preferred(DID_ALICE).unwrap();
+
+        assert_ok!(Nft::mint_nft_power(
+            Origin::signed(ALICE),
+            nft,
+            b"TT".to_vec(),
+            b"TT".to_vec(),
+            100_000_000 * DOLLARS,
+        ));
+
+        //action
+        assert_ok!(Nft::start_dao_influenceming_activity(
+            Origin::signed(ALICE),
+            nft,
+            100_000 * DOLLARS
+        ));
+
+        assert_err!(
+            Nft::start_dao_influenceming_activity(Origin::signed(ALICE), nft, 100_000 * DOLLARS),
+            Error::<Test>::Exists
+        );
+    });
+}
+
+#[test]
+fn should_not_start_influence_mining_activity_when_origin_from_non_owner() {
+    new_test_ext().execute_with(|| {
+        let nft = Nft::preferred(DID_ALICE).unwrap();
+
+        assert_ok!(Nft::mint_nft_power(
+            Origin::signed(ALICE),
+            nft,
+            b"TT".to_vec(),
+            b"TT".to_vec(),
+            100_000_000 * DOLLARS,
+        ));
+
+        //action
+        assert_noop!(
+            Nft::start_dao_influenceming_activity(Origin::signed(BOB), nft, 100_000 * DOLLARS),
+            Error::<Test>::NotTokenOwner
+        );
+    });
+}
+
+#[test]
+fn should_not_start_influence_mining_activity_when_balance_not_enough() {
+    new_test_ext().execute_with(|| {
+        let nft = Nft::preferred(DID_ALICE).unwrap();
+
+        assert_ok!(Nft::mint_nft_power(
+            Origin::signed(ALICE),
+            nft,
+            b"TT".to_vec(),
+            b"TT".to_vec(),
+            100 * DOLLARS,
+        ));
+
+        //action
+        assert_noop!(
+            Nft::start_dao_influenceming_activity(Origin::signed(BOB), nft, 100_000 * DOLLARS),
+            Error::<Test>::NotTokenOwner
+        );
+    });
+}
+
+#[test]
+fn should_not_start_influence_mining_activity_when_nft_not_minted() {
+    new_test_ext().execute_with(|| {
+        let nft = Nft::preferred(DID_ALICE).unwrap();
+
+        //action
+        assert_noop!(
+            Nft::start_dao_influenceming_activity(Origin::signed(ALICE), nft, 100_000 * DOLLARS),
+            Error::<Test>::NotExists
+        );
+    });
+}
+
+const ANCHORED_NUMBER: u64 = 15_000_000;
+const ANCHORED_HASH: &str = "019ffc380bc2b93b26683cb6ea324d3f79a0136fe612a94dae8df13d73cda843";
+
+// proves `_owners[546] == SIGNING_ETH_ADDR` of `NAMESPACE` at block `ANCHORED_NUMBER`
+fn proof_fixture() -> (Vec<u8>, Vec<Vec<u8>>, Vec<Vec<u8>>) {
+    let header = include_bytes!("../artifacts/header.rlp").to_vec();
+    let account_proof = rlp::decode_list(include_bytes!("../artifacts/account_proof.rlp"));
+    let storage_proof = rlp::decode_list(include_bytes!("../artifacts/storage_proof.rlp"));
+
+    (header, account_proof, storage_proof)
+}
+
+fn anchor_fixture() {
+    let hash = H256::from_slice(&hex::decode(ANCHORED_HASH).unwrap());
+
+    assert_ok!(Nft::anchor_header(Origin::root(), ANCHORED_NUMBER, hash));
+    assert_ok!(Nft::set_owners_slot(Origin::root(), NAMESPACE.to_vec(), 2));
+}
+
+#[test]
+fn should_port_with_proof() {
+    new_test_ext().execute_with(|| {
+        anchor_fixture();
+
+        let (header, account_proof, storage_proof) = proof_fixture();
+        let token = 546u16.to_be_bytes().to_vec();
+
+        assert_ok!(Nft::port_with_proof(
+            Origin::signed(BOB),
+            NAMESPACE.to_vec(),
+            token.clone(),
+            SIGNING_ETH_ADDR.into(),
+            SIGNATURE,
+            header,
+            account_proof,
+            storage_proof,
+            SignatureScheme::Plain,
+        ));
+
+        let id =
+            <Ported<Test>>::get((Network::Ethereum, NAMESPACE.to_vec(), token.clone())).unwrap();
+        assert_eq!(<Metadata<Test>>::get(id).unwrap().owner, DID_BOB);
+
+        let external = <External<Test>>::get(id).unwrap();
+        assert_eq!(external.owner, DID_BOB);
+        assert_eq!(external.namespace, NAMESPACE.to_vec());
+        assert_eq!(external.token, token);
+    });
+}
+
+#[test]
+fn should_port_with_proof_and_typed_data() {
+    new_test_ext().execute_with(|| {
+        anchor_fixture();
+
+        let (header, account_proof, storage_proof) = proof_fixture();
+        let token = 546u16.to_be_bytes().to_vec();
+
+        let owner_address = vec![
+            0xeb, 0x01, 0x4f, 0x8c, 0x8b, 0x41, 0x8d, 0xb6, 0xb4, 0x57, 0x74, 0xc3, 0x26, 0xa0,
+            0xe6, 0x4c, 0x78, 0x91, 0x4d, 0xc0,
+        ];
+
+        // EIP-712 authorization to port for DID_BOB, expiring at 100
+        let signature = [
+            0xc2, 0xf7, 0xf1, 0x16, 0x4c, 0x7b, 0x47, 0x1d, 0x01, 0x07, 0x56, 0x99, 0xb5, 0xf7,
+            0xce, 0x7c, 0x9c, 0x85, 0x96, 0xff, 0xd6, 0x0c, 0x71, 0x0e, 0x9e, 0xb8, 0x29, 0x5d,
+            0x52, 0x05, 0x58, 0x29, 0x6e, 0x08, 0xe2, 0xfb, 0x85, 0x7e, 0xa8, 0x2a, 0x77, 0x4f,
+            0xc4, 0x41, 0xcc, 0xc9, 0xdd, 0xa8, 0x54, 0xb3, 0x62, 0xb7, 0x7a, 0xd6, 0xbb, 0x54,
+            0x58, 0x21, 0x48, 0xcc, 0x9b, 0xb3, 0xbd, 0x16, 0x1b,
+        ];
+
+        let scheme = SignatureScheme::Eip712 { expiry: 100 };
+
+        System::set_block_number(101);
+
+        assert_noop!(
+            Nft::port_with_proof(
+                Origin::signed(BOB),
+                NAMESPACE.to_vec(),
+                token.clone(),
+                owner_address.clone(),
+                signature,
+                header.clone(),
+                account_proof.clone(),
+                storage_proof.clone(),
+                scheme,
+            ),
+            Error::<Test>::Expired
+        );
+
+        System::set_block_number(100);
+
+        // the personal message is no longer accepted for the typed data signature
+        assert_noop!(
+            Nft::port_with_proof(
+                Origin::signed(BOB),
+                NAMESPACE.to_vec(),
+                token.clone(),
+                owner_address.clone(),
+                signature,
+                header.clone(),
+                account_proof.clone(),
+                storage_proof.clone(),
+                SignatureScheme::Plain,
+            ),
+            Error::<Test>::InvalidSignature
+        );
+
+        // the signature is accepted, but the proof shows another owner
+        assert_noop!(
+            Nft::port_with_proof(
+                Origin::signed(BOB),
+                NAMESPACE.to_vec(),
+                token,
+                owner_address,
+                signature,
+                header,
+                account_proof,
+                storage_proof,
+                scheme,
+            ),
+            Error::<Test>::NotTokenOwner
+        );
+    });
+}
+
+#[test]
+fn should_fail_to_anchor_header_when_not_authorized() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Nft::anchor_header(Origin::signed(ALICE), ANCHORED_NUMBER, H256::zero()),
+            DispatchError::BadOrigin
+        );
+    });
+}
+
+#[test]
+fn should_not_reanchor_header() {
+    new_test_ext().execute_with(|| {
+        anchor_fixture();
+
+        assert_noop!(
+            Nft::anchor_header(Origin::root(), ANCHORED_NUMBER, H256::zero()),
+            Error::<Test>::Exists
+        );
+    });
+}
+
+#[test]
+fn should_prune_anchored_headers() {
+    new_test_ext().execute_with(|| {
+        for number in 0..MAX_ANCHORED_HEADERS as u64 {
+            assert_ok!(Nft::anchor_header(Origin::root(), number, H256::zero()));
+        }
+
+        assert_eq!(<AnchoredHeaders<Test>>::get(0), Some(H256::zero()));
+
+        let number = MAX_ANCHORED_HEADERS as u64;
+        assert_ok!(Nft::anchor_header(Origin::root(), number, H256::zero()));
+
+        assert_eq!(<AnchoredHeaders<Test>>::get(0), None);
+        assert_eq!(<AnchoredHeaders<Test>>::get(1), Some(H256::zero()));
+        assert_eq!(<AnchoredHeaders<Test>>::get(number), Some(H256::zero()));
+        assert_eq!(
+            <AnchoredNumbers<Test>>::get().len(),
+            MAX_ANCHORED_HEADERS as usize
+        );
+    });
+}
+
+#[test]
+fn should_fail_to_port_with_proof_when_header_not_anchored() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Nft::set_owners_slot(Origin::root(), NAMESPACE.to_vec(), 2));
+        assert_ok!(Nft::anchor_header(
+            Origin::root(),
+            ANCHORED_NUMBER,
+            H256::zero()
+        ));
+
+        let (header, account_proof, storage_proof) = proof_fixture();
+
+        assert_noop!(
+            Nft::port_with_proof(
+                Origin::signed(BOB),
+                NAMESPACE.to_vec(),
+                546u16.to_be_bytes().to_vec(),
+                SIGNING_ETH_ADDR.into(),
+                SIGNATURE,
+                header,
+                account_proof,
+                storage_proof,
+                SignatureScheme::Plain,
+            ),
+            Error::<Test>::UnanchoredHeader
+        );
+    });
+}
+
+#[test]
+fn should_fail_to_port_with_proof_when_layout_unknown() {
+    new_test_ext().execute_with(|| {
+        let hash = H256::from_slice(&hex::decode(ANCHORED_HASH).unwrap());
+        assert_ok!(Nft::anchor_header(Origin::root(), ANCHORED_NUMBER, hash));
+
+        let (header, account_proof, storage_proof) = proof_fixture();
+
+        assert_noop!(
+            Nft::port_with_proof(
+                Origin::signed(BOB),
+                NAMESPACE.to_vec(),
+                546u16.to_be_bytes().to_vec(),
+                SIGNING_ETH_ADDR.into(),
+                SIGNATURE,
+                header,
+                account_proof,
+                storage_proof,
+                SignatureScheme::Plain,
+            ),
+            Error::<Test>::UnknownStorageLayout
+        );
+    });
+}
+
+#[test]
+fn should_fail_to_port_with_proof_when_proof_mismatched() {
+    new_test_ext().execute_with(|| {
+        anchor_fixture();
+
+        let (header, account_proof, storage_proof) = proof_fixture();
+
+        assert_noop!(
+            Nft::port_with_proof(
+                Origin::signed(BOB),
+                NAMESPACE.to_vec(),
+                547u16.to_be_bytes().to_vec(),
+                SIGNING_ETH_ADDR.into(),
+                SIGNATURE,
+                header.clone(),
+                account_proof.clone(),
+                storage_proof.clone(),
+                SignatureScheme::Plain,
+            ),
+            Error::<Test>::InvalidProof
+        );
+
+        let mut tampered = storage_proof.clone();
+        let last = tampered.last_mut().unwrap();
+        let index = last.len() - 1;
+        last[index] ^= 0xff;
+
+        assert_noop!(
+            Nft::port_with_proof(
+                Origin::signed(BOB),
+                NAMESPACE.to_vec(),
+                546u16.to_be_bytes().to_vec(),
+                SIGNING_ETH_ADDR.into(),
+                SIGNATURE,
+                header,
+                account_proof,
+                tampered,
+                SignatureScheme::Plain,
+            ),
+            Error::<Test>::InvalidProof
+        );
+    });
+}
+
+#[test]
+fn should_fail_to_port_with_proof_when_too_long() {
+    new_test_ext().execute_with(|| {
+        anchor_fixture();
+
+        let (header, account_proof, storage_proof) = proof_fixture();
+
+        let mut padded = storage_proof.clone();
+        padded.resize(MAX_PROOF_NODES as usize, vec![]);
+
+        assert_noop!(
+            Nft::port_with_proof(
+                Origin::signed(BOB),
+                NAMESPACE.to_vec(),
+                546u16.to_be_bytes().to_vec(),
+                SIGNING_ETH_ADDR.into(),
+                SIGNATURE,
+                header,
+                account_proof,
+                padded,
+                SignatureScheme::Plain,
+            ),
+            Error::<Test>::InvalidProof
+        );
+    });
+}
 
-        let result = Nft::mint_nft_power(
-            Origin::signed(ALICE),
-            nft,
-            b"Test Token".to_vec(),
-            b"TT".to_vec(),
-            200,
+#[test]
+fn should_fail_to_port_with_proof_when_not_owner() {
+    new_test_ext().execute_with(|| {
+        anchor_fixture();
+
+        // the storage proof of token 546 also proves token 2 has no owner
+        let (header, account_proof, storage_proof) = proof_fixture();
+
+        assert_noop!(
+            Nft::port_with_proof(
+                Origin::signed(BOB),
+                NAMESPACE.to_vec(),
+                vec![0x02],
+                SIGNING_ETH_ADDR.into(),
+                SIGNATURE,
+                header,
+                account_proof,
+                storage_proof,
+                SignatureScheme::Plain,
+            ),
+            Error::<Test>::NotTokenOwner
         );
-        assert_ok!(result);
-        assert_ok!(Nft::start_ico(Origin::signed(ALICE), nft, 50, 100));
-        assert_ok!(Nft::participate_ico(Origin::signed(BOB), nft, 50));
-        assert_ok!(Nft::end_ico(Origin::signed(ALICE), nft));
+    });
+}
 
-        let ico_meta = IcoMetaOf::<Test>::get(nft).unwrap();
-        let meta = Metadata::<Test>::get(nft).unwrap();
-        let block_num = ClaimStartAt::<Test>::get(nft).unwrap();
-        assert_eq!(ico_meta.done, true);
-        assert_eq!(Assets::balance(nft, meta.pot), 50);
-        assert_eq!(block_num, 0);
+#[test]
+fn should_transfer_nft() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Nft::transfer_nft(Origin::signed(ALICE), 0, DID_BOB));
+
+        assert_eq!(<Metadata<Test>>::get(0).unwrap().owner, DID_BOB);
+        assert_eq!(<Preferred<Test>>::get(DID_BOB), Some(0));
+        assert_eq!(<PendingTransfer<Test>>::get(0), None);
+
+        assert_noop!(
+            Nft::transfer_nft(Origin::signed(ALICE), 0, DID_CHARLIE),
+            Error::<Test>::NotTokenOwner
+        );
     });
 }
 
 #[test]
-fn should_generate_unique_pot_for_ico_meta() {
+fn should_fail_to_transfer_nft_to_yourself_or_unknown() {
     new_test_ext().execute_with(|| {
-        let nft = Nft::preferred(DID_ALICE).unwrap();
-        let meta_pot = Nft::generate_claim_pot(&nft);
-        let ico_pot_1 = Nft::generate_ico_pot(&nft);
-        let ico_pot_2 = Nft::generate_ico_pot(&(nft + 1));
+        assert_noop!(
+            Nft::transfer_nft(Origin::signed(ALICE), 0, DID_ALICE),
+            Error::<Test>::YourSelf
+        );
 
-        assert_ne!(meta_pot, ico_pot_1);
-        assert_ne!(ico_pot_1, ico_pot_2);
+        assert_noop!(
+            Nft::transfer_nft(Origin::signed(ALICE), 0, sp_core::H160([0xee; 20])),
+            Error::<Test>::NotExists
+        );
     });
 }
 
 #[test]
-fn should_calculate_price_correct() {
+fn should_transfer_ported_nft_after_reverification() {
     new_test_ext().execute_with(|| {
-        let required_currency = Nft::calculate_required_currency(
-            50,
-            &IcoMeta::<Test> {
-                expected_currency: 100,
-                offered_tokens: 50,
-                done: true,
-                pot: ALICE,
-            },
-        )
-        .unwrap();
+        let namespace = NAMESPACE.to_vec();
+        let token = vec![0x01];
 
-        assert_eq!(required_currency, 100);
+        assert_ok!(Nft::transfer_nft(Origin::signed(ALICE), 1, DID_BOB));
 
-        let required_currency = Nft::calculate_required_currency(
-            50,
-            &IcoMeta::<Test> {
-                expected_currency: 99,
-                offered_tokens: 50,
-                done: true,
-                pot: ALICE,
-            },
-        )
-        .unwrap();
+        assert_eq!(<Metadata<Test>>::get(1).unwrap().owner, DID_ALICE);
+        assert_eq!(<PendingTransfer<Test>>::get(1), Some(DID_BOB));
 
-        assert_eq!(required_currency, 99);
+        // only the recipient may port it again
+        assert_noop!(
+            Nft::port(
+                Origin::signed(CHARLIE),
+                Network::Ethereum,
+                namespace.clone(),
+                token.clone(),
+                SIGNING_ETH_ADDR.into(),
+                SIGNATURE,
+                None,
+                SignatureScheme::Plain,
+            ),
+            Error::<Test>::Exists
+        );
 
-        let required_currency = Nft::calculate_required_currency(
-            49,
-            &IcoMeta::<Test> {
-                expected_currency: 99,
-                offered_tokens: 50,
-                done: true,
-                pot: ALICE,
-            },
-        )
-        .unwrap();
+        assert_ok!(Nft::port(
+            Origin::signed(BOB),
+            Network::Ethereum,
+            namespace.clone(),
+            token.clone(),
+            SIGNING_ETH_ADDR.into(),
+            SIGNATURE,
+            None,
+            SignatureScheme::Plain,
+        ));
 
-        assert_eq!(required_currency, 97);
+        assert_ok!(Nft::submit_porting(
+            frame_system::RawOrigin::None.into(),
+            DID_BOB,
+            Network::Ethereum,
+            namespace.clone(),
+            token.clone(),
+            None,
+            vec![],
+        ));
 
-        let required_currency = Nft::calculate_required_currency(
-            1,
-            &IcoMeta::<Test> {
-                expected_currency: 99,
-                offered_tokens: 50,
-                done: true,
-                pot: ALICE,
-            },
-        )
-        .unwrap();
+        assert_eq!(
+            <Ported<Test>>::get((Network::Ethereum, &namespace, &token)),
+            Some(1)
+        );
+        assert_eq!(<Metadata<Test>>::get(1).unwrap().owner, DID_BOB);
+        assert_eq!(<External<Test>>::get(1).unwrap().owner, DID_BOB);
+        assert_eq!(<PendingTransfer<Test>>::get(1), None);
+        assert_eq!(<Preferred<Test>>::get(DID_ALICE), None);
+        assert_eq!(<Preferred<Test>>::get(DID_BOB), Some(1));
+    });
+}
 
-        assert_eq!(required_currency, 1);
+#[test]
+fn should_set_preferred() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(<Preferred<Test>>::get(DID_ALICE), Some(1));
 
-        let required_token = Nft::calculate_required_token(
-            1,
-            &IcoMeta::<Test> {
-                expected_currency: 99,
-                offered_tokens: 50,
-                done: true,
-                pot: ALICE,
-            },
-        )
-        .unwrap();
+        assert_ok!(Nft::set_preferred(Origin::signed(ALICE), 0));
+        assert_eq!(<Preferred<Test>>::get(DID_ALICE), Some(0));
 
-        assert_eq!(required_token, 0);
+        assert_noop!(
+            Nft::set_preferred(Origin::signed(BOB), 1),
+            Error::<Test>::NotTokenOwner
+        );
+        assert_noop!(
+            Nft::set_preferred(Origin::signed(ALICE), 99),
+            Error::<Test>::NotExists
+        );
+    });
+}
+
+#[test]
+fn should_list_nfts_of_did() {
+    new_test_ext().execute_with(|| {
+        let mut nfts = Nft::nfts_of(DID_ALICE);
+        nfts.sort();
+        assert_eq!(nfts, vec![0, 1]);
+
+        assert_ok!(Nft::kick(Origin::signed(ALICE)));
+
+        let mut nfts = Nft::nfts_of(DID_ALICE);
+        nfts.sort();
+        assert_eq!(nfts, vec![0, 1, NEXT_INSTANCE_ID]);
+
+        assert_ok!(Nft::transfer_nft(Origin::signed(ALICE), 0, DID_BOB));
+
+        assert_eq!(Nft::nfts_of(DID_BOB), vec![0]);
+        assert!(!Nft::nfts_of(DID_ALICE).contains(&0));
+    });
+}
+
+#[test]
+fn should_pick_next_preferred_when_transferred() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Nft::set_preferred(Origin::signed(ALICE), 0));
+
+        assert_ok!(Nft::transfer_nft(Origin::signed(ALICE), 0, DID_BOB));
+
+        assert_eq!(<Preferred<Test>>::get(DID_ALICE), Some(1));
+    });
+}
+
+#[test]
+fn should_set_display() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Nft::set_display(
+            Origin::signed(ALICE),
+            0,
+            b"Alice".to_vec(),
+            b"ipfs://QmAlice".to_vec(),
+            b"Alice's first collection".to_vec(),
+        ));
+
+        let display = Nft::display(0).unwrap();
+        assert_eq!(display.name, b"Alice".to_vec());
+        assert_eq!(display.image, b"ipfs://QmAlice".to_vec());
+        assert_eq!(display.description, b"Alice's first collection".to_vec());
+
+        assert_noop!(
+            Nft::set_display(Origin::signed(BOB), 0, vec![], vec![], vec![]),
+            Error::<Test>::NotTokenOwner
+        );
+        assert_noop!(
+            Nft::set_display(Origin::signed(ALICE), 0, vec![], vec![0u8; 257], vec![]),
+            Error::<Test>::BadMetadata
+        );
+    });
+}
+
+#[test]
+fn should_bridge_nft_out_and_back() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            <Nft as BridgeNfts<_>>::lock(&ALICE, 0),
+            Error::<Test>::NotExists
+        );
+
+        assert_ok!(Nft::mint_nft_power(
+            Origin::signed(ALICE),
+            0,
+            b"Test Token".to_vec(),
+            b"TT".to_vec(),
+            500
+        ));
+
+        assert_noop!(
+            <Nft as BridgeNfts<_>>::lock(&BOB, 0),
+            Error::<Test>::NotTokenOwner
+        );
+        assert_noop!(
+            <Nft as BridgeNfts<_>>::unlock(&BOB, 0),
+            Error::<Test>::NotBridged
+        );
+
+        assert_ok!(<Nft as BridgeNfts<_>>::lock(&ALICE, 0));
+        assert!(<Bridged<Test>>::contains_key(0));
+
+        assert_noop!(
+            <Nft as BridgeNfts<_>>::lock(&ALICE, 0),
+            Error::<Test>::Bridged
+        );
+        assert_noop!(
+            Nft::transfer_nft(Origin::signed(ALICE), 0, DID_BOB),
+            Error::<Test>::Bridged
+        );
+        assert_noop!(
+            Nft::set_preferred(Origin::signed(ALICE), 0),
+            Error::<Test>::Bridged
+        );
+        assert_noop!(
+            Nft::start_ico(Origin::signed(ALICE), 0, 100, 100),
+            Error::<Test>::Bridged
+        );
+
+        assert_ok!(<Nft as BridgeNfts<_>>::unlock(&BOB, 0));
+        assert!(!<Bridged<Test>>::contains_key(0));
+        assert_eq!(<Metadata<Test>>::get(0).unwrap().owner, DID_BOB);
+    });
+}
+
+#[test]
+fn should_register_did_when_bridged_back_to_new_account() {
+    new_test_ext().execute_with(|| {
+        let dave = sr25519::Public([4; 32]);
+
+        assert_ok!(Nft::mint_nft_power(
+            Origin::signed(ALICE),
+            0,
+            b"Test Token".to_vec(),
+            b"TT".to_vec(),
+            500
+        ));
+
+        assert_ok!(<Nft as BridgeNfts<_>>::lock(&ALICE, 0));
+
+        assert_eq!(Did::did_of(dave), None);
+
+        assert_ok!(<Nft as BridgeNfts<_>>::unlock(&dave, 0));
+
+        let did = Did::did_of(dave).unwrap();
+        assert_eq!(<Metadata<Test>>::get(0).unwrap().owner, did);
+        assert_eq!(Did::referrer_of(did), None);
     });
 }
 
-#[test]
-fn should_failed_if_ico_with_wrong_params() {
-    new_test_ext().execute_with(|| {
+fn mint_nft_power_with_pool() -> u32 {
+    let nft = Nft::preferred(DID_ALICE).unwrap();
+
+    assert_ok!(Nft::mint_nft_power(
+        Origin::signed(ALICE),
+        nft,
+        b"Test Token".to_vec(),
+        b"XTT".to_vec(),
+        2_000_000 * DOLLARS
+    ));
+
+    assert_ok!(Swap::create(Origin::signed(ALICE), nft));
+    assert_ok!(Swap::add_liquidity(
+        Origin::signed(ALICE),
+        nft,
+        1000 * DOLLARS,
+        1000 * DOLLARS,
+        1_000_000 * DOLLARS,
+        1
+    ));
+
+    nft
+}
+
+#[test]
+fn should_start_buyback() {
+    new_test_ext().execute_with(|| {
+        let nft = mint_nft_power_with_pool();
+
+        assert_noop!(
+            Nft::start_buyback(
+                Origin::signed(BOB),
+                nft,
+                100 * DOLLARS,
+                10 * DOLLARS,
+                5,
+                Perbill::from_percent(5),
+                BuybackTarget::Burn
+            ),
+            Error::<Test>::NotTokenOwner
+        );
+
         assert_noop!(
-            Nft::mint_nft_power(
+            Nft::start_buyback(
                 Origin::signed(ALICE),
-                10086,
-                b"TT".to_vec(),
-                b"TT".to_vec(),
-                100,
+                nft,
+                100 * DOLLARS,
+                0,
+                5,
+                Perbill::from_percent(5),
+                BuybackTarget::Burn
             ),
-            Error::<Test>::NotExists,
+            Error::<Test>::InvalidBuyback
         );
 
-        let nft = Nft::preferred(DID_ALICE).unwrap();
         assert_noop!(
-            Nft::mint_nft_power(
-                Origin::signed(BOB),
+            Nft::start_buyback(
+                Origin::signed(ALICE),
                 nft,
-                b"TT".to_vec(),
-                b"TT".to_vec(),
-                100,
+                100 * DOLLARS,
+                10 * DOLLARS,
+                5,
+                Perbill::from_percent(5),
+                BuybackTarget::InfluenceMining
             ),
-            Error::<Test>::NotTokenOwner,
+            Error::<Test>::NotExists
         );
 
-        assert_ok!(Nft::mint_nft_power(
+        assert_ok!(Nft::start_buyback(
             Origin::signed(ALICE),
             nft,
-            b"TT".to_vec(),
-            b"TT".to_vec(),
-            100,
+            100 * DOLLARS,
+            10 * DOLLARS,
+            5,
+            Perbill::from_percent(5),
+            BuybackTarget::Burn
         ));
 
+        let buyback = <Buybacks<Test>>::get(nft).unwrap();
+        assert_eq!(buyback.pot, Nft::generate_buyback_pot(&nft));
+        assert_eq!(buyback.next_at, 5);
+        assert_eq!(Balances::free_balance(&buyback.pot), 100 * DOLLARS);
+
         assert_noop!(
-            Nft::mint_nft_power(
+            Nft::start_buyback(
                 Origin::signed(ALICE),
                 nft,
-                b"TT".to_vec(),
-                b"TT".to_vec(),
-                100,
+                100 * DOLLARS,
+                10 * DOLLARS,
+                5,
+                Perbill::from_percent(5),
+                BuybackTarget::Burn
             ),
-            Error::<Test>::Minted,
+            Error::<Test>::Exists
         );
     });
 }
 
 #[test]
-fn should_failed_to_participate_ico_if_with_wrong_params() {
+fn should_spill_buyback_into_next_block() {
     new_test_ext().execute_with(|| {
-        let nft = Nft::preferred(DID_ALICE).unwrap();
+        let nft = mint_nft_power_with_pool();
+
+        for at in 5..5 + MAX_BUYBACK_SPILL as u64 {
+            for id in 0..MAX_BUYBACKS_PER_BLOCK {
+                assert_ok!(<BuybackQueue<Test>>::try_mutate(at, |queue| queue.try_push(1000 + id)));
+            }
+        }
+
         assert_noop!(
-            Nft::participate_ico(Origin::signed(ALICE), nft, 50),
-            Error::<Test>::NotExists,
+            Nft::start_buyback(
+                Origin::signed(ALICE),
+                nft,
+                100 * DOLLARS,
+                10 * DOLLARS,
+                5,
+                Perbill::from_percent(5),
+                BuybackTarget::Burn
+            ),
+            Error::<Test>::BuybackQueueFull
         );
 
-        assert_ok!(Nft::mint_nft_power(
+        <BuybackQueue<Test>>::remove(7);
+
+        assert_ok!(Nft::start_buyback(
             Origin::signed(ALICE),
             nft,
-            b"TT".to_vec(),
-            b"TT".to_vec(),
-            100,
+            100 * DOLLARS,
+            10 * DOLLARS,
+            5,
+            Perbill::from_percent(5),
+            BuybackTarget::Burn
         ));
 
-        assert_ok!(Nft::start_ico(Origin::signed(ALICE), nft, 50, 50));
-        assert_noop!(
-            Nft::participate_ico(Origin::signed(ALICE), nft, 150),
-            Error::<Test>::InsufficientToken
-        );
-        assert_ok!(Nft::end_ico(Origin::signed(ALICE), nft));
-
-        assert_noop!(
-            Nft::participate_ico(Origin::signed(ALICE), nft, 50),
-            Error::<Test>::Deadline,
-        );
+        assert_eq!(<Buybacks<Test>>::get(nft).unwrap().next_at, 7);
+        assert_eq!(<BuybackQueue<Test>>::get(7).into_inner(), vec![nft]);
     });
 }
 
 #[test]
-fn should_failed_to_end_ico_if_with_wrong_params() {
+fn should_execute_buyback_and_burn() {
     new_test_ext().execute_with(|| {
-        let nft = Nft::preferred(DID_ALICE).unwrap();
-        assert_noop!(
-            Nft::end_ico(Origin::signed(ALICE), nft),
-            Error::<Test>::NotExists
-        );
+        let nft = mint_nft_power_with_pool();
 
-        assert_ok!(Nft::mint_nft_power(
+        assert_ok!(Nft::start_buyback(
             Origin::signed(ALICE),
             nft,
-            b"TT".to_vec(),
-            b"TT".to_vec(),
-            100,
+            25 * DOLLARS,
+            10 * DOLLARS,
+            5,
+            Perbill::from_percent(5),
+            BuybackTarget::Burn
         ));
 
-        assert_noop!(
-            Nft::end_ico(Origin::signed(BOB), nft),
-            Error::<Test>::NotTokenOwner
-        );
+        let issuance = Assets::total_issuance(nft);
+
+        Nft::on_initialize(4);
+        assert_eq!(Nft::buyback_progress(nft), Ok((0, 0, 25 * DOLLARS - 1)));
+
+        System::set_block_number(5);
+        Nft::on_initialize(5);
+
+        let (spent, bought, remaining) = Nft::buyback_progress(nft).unwrap();
+        assert_eq!(spent, 10 * DOLLARS);
+        assert!(bought > 0);
+        assert_eq!(remaining, 15 * DOLLARS - 1);
+        assert_eq!(Assets::total_issuance(nft), issuance - bought);
+        assert_eq!(<Buybacks<Test>>::get(nft).unwrap().next_at, 10);
+
+        Nft::on_initialize(10);
+        Nft::on_initialize(15);
+
+        assert!(!<Buybacks<Test>>::contains_key(nft));
+
+        let pot = Nft::generate_buyback_pot(&nft);
+        assert_eq!(Balances::free_balance(&pot), 1);
+        assert_eq!(Assets::balance(nft, &pot), 0);
     });
 }
 
 #[test]
-fn should_claim_for_ico_meta() {
-    use crate::Nfts;
+fn should_execute_buyback_for_influence_mining() {
     new_test_ext().execute_with(|| {
-        let nft = Nft::preferred(DID_ALICE).unwrap();
+        let nft = mint_nft_power_with_pool();
 
-        assert_ok!(Nft::mint_nft_power(
+        assert_ok!(Nft::start_dao_influenceming_activity(
             Origin::signed(ALICE),
             nft,
-            b"TT".to_vec(),
-            b"TT".to_vec(),
-            100 * DOLLARS,
+            100_000 * DOLLARS
         ));
-        assert_ok!(Nft::start_ico(
+
+        assert_ok!(Nft::start_buyback(
             Origin::signed(ALICE),
             nft,
-            20 * DOLLARS,
-            20 * DOLLARS
+            100 * DOLLARS,
+            10 * DOLLARS,
+            5,
+            Perbill::from_percent(5),
+            BuybackTarget::InfluenceMining
         ));
-        assert_ok!(Nft::participate_ico(Origin::signed(BOB), nft, 10 * DOLLARS));
-
-        assert_ok!(Nft::end_ico(Origin::signed(ALICE), nft));
-
-        let (total, unlocked, claimable) = Nft::get_claim_info(nft, &DID_BOB).unwrap();
-        assert_eq!(total, 10 * DOLLARS);
-        assert_eq!(unlocked, 0);
-        assert_eq!(claimable, 0);
-
-        // InitialMintingLockupPeriod is 5
-        System::set_block_number(1);
-        let (total, unlocked, claimable) = Nft::get_claim_info(nft, &DID_BOB).unwrap();
-        assert_eq!(total, 10 * DOLLARS);
-        assert_eq!(unlocked, 2 * DOLLARS);
-        assert_eq!(claimable, 2 * DOLLARS);
 
-        assert_ok!(Nft::claim(Origin::signed(BOB), nft));
+        let issuance = Assets::total_issuance(nft);
 
-        System::set_block_number(2);
-        let (total, unlocked, claimable) = Nft::get_claim_info(nft, &DID_BOB).unwrap();
-        assert_eq!(total, 10 * DOLLARS);
-        assert_eq!(unlocked, 4 * DOLLARS);
-        assert_eq!(claimable, 2 * DOLLARS);
+        Nft::on_initialize(5);
 
-        System::set_block_number(5);
-        let (total, unlocked, claimable) = Nft::get_claim_info(nft, &DID_BOB).unwrap();
-        assert_eq!(total, 10 * DOLLARS);
-        assert_eq!(unlocked, 10 * DOLLARS);
-        assert_eq!(claimable, 8 * DOLLARS);
+        let (_, bought, _) = Nft::buyback_progress(nft).unwrap();
+        assert!(bought > 0);
+        assert_eq!(Assets::total_issuance(nft), issuance);
 
-        System::set_block_number(6);
-        let (total, unlocked, claimable) = Nft::get_claim_info(nft, &DID_BOB).unwrap();
-        assert_eq!(total, 10 * DOLLARS);
-        assert_eq!(unlocked, 10 * DOLLARS);
-        assert_eq!(claimable, 8 * DOLLARS);
+        let meta = <InflueceMiningMetaStore<Test>>::get(nft).unwrap();
+        assert_eq!(meta.budget_in_tokens, 100_000 * DOLLARS + bought);
+        assert_eq!(Assets::balance(nft, &meta.pot), 100_000 * DOLLARS + bought);
     });
 }
 
 #[test]
-fn should_start_influence_mining_activity() {
+fn should_skip_buyback_when_price_impact_exceeded() {
     new_test_ext().execute_with(|| {
-        let nft = Nft::preferred(DID_ALICE).unwrap();
+        let nft = mint_nft_power_with_pool();
 
-        assert_ok!(Nft::mint_nft_power(
+        assert_ok!(Nft::start_buyback(
             Origin::signed(ALICE),
             nft,
-            b"TT".to_vec(),
-            b"TT".to_vec(),
-            100_000_000 * DOLLARS,
+            100 * DOLLARS,
+            10 * DOLLARS,
+            5,
+            Perbill::from_percent(1),
+            BuybackTarget::Burn
         ));
 
-        let nft_meta = <Metadata<Test>>::get(nft).unwrap();
+        let issuance = Assets::total_issuance(nft);
 
-        //before stats
-        let asset_balance_of_owner_before = Assets::balance(nft_meta.token_asset_id, ALICE);
+        Nft::on_initialize(5);
 
-        let pot_account: AccountOf<Test> = Nft::generate_influence_mining_pot(&nft);
+        assert_eq!(Nft::buyback_progress(nft), Ok((0, 0, 100 * DOLLARS - 1)));
+        assert_eq!(Assets::total_issuance(nft), issuance);
+        assert_eq!(<Buybacks<Test>>::get(nft).unwrap().next_at, 10);
+    });
+}
 
-        let asset_balance_of_pot_before = Assets::balance(nft_meta.token_asset_id, &pot_account);
+#[test]
+fn should_stop_buyback() {
+    new_test_ext().execute_with(|| {
+        let nft = mint_nft_power_with_pool();
 
-        //action
-        assert_ok!(Nft::start_dao_influenceming_activity(
+        assert_ok!(Nft::start_buyback(
             Origin::signed(ALICE),
             nft,
-            100_000 * DOLLARS
+            100 * DOLLARS,
+            10 * DOLLARS,
+            5,
+            Perbill::from_percent(5),
+            BuybackTarget::Burn
         ));
 
-        //after stats
-        let asset_balance_after = Assets::balance(nft_meta.token_asset_id, ALICE);
-        let asset_balance_of_pot_after = Assets::balance(nft_meta.token_asset_id, &pot_account);
+        let balance = Balances::free_balance(ALICE);
 
-        let meta: InfluenceMiningMetaOf<Test> = <InflueceMiningMetaStore<Test>>::get(nft).unwrap();
-        assert_eq!(meta.budget_in_tokens, 100_000 * DOLLARS);
-        assert_eq!(
-            asset_balance_after,
-            asset_balance_of_owner_before - meta.budget_in_tokens
-        );
-        assert_eq!(meta.pot, pot_account);
-        assert_eq!(
-            asset_balance_of_pot_after,
-            asset_balance_of_pot_before + meta.budget_in_tokens
+        assert_noop!(
+            Nft::stop_buyback(Origin::signed(BOB), nft),
+            Error::<Test>::NotTokenOwner
         );
-        assert_eq!(meta.pot, pot_account);
+
+        assert_ok!(Nft::stop_buyback(Origin::signed(ALICE), nft));
+
+        assert!(!<Buybacks<Test>>::contains_key(nft));
+        assert_eq!(Balances::free_balance(ALICE), balance + 100 * DOLLARS);
+
+        Nft::on_initialize(5);
+
+        assert_err!(Nft::buyback_progress(nft), Error::<Test>::NotExists);
     });
 }
 
 #[test]
-fn should_not_start_influence_mining_activity_when_already_started() {
+fn should_launch() {
     new_test_ext().execute_with(|| {
         let nft = Nft::preferred(DID_ALICE).unwrap();
 
-        assert_ok!(Nft::mint_nft_power(
+        assert_eq!(
+            Nft::launch_dry(
+                nft,
+                1_000_000 * DOLLARS,
+                100_000 * DOLLARS,
+                1_000 * DOLLARS,
+                500_000 * DOLLARS
+            ),
+            Ok((DOLLARS / 500, 400_000 * DOLLARS))
+        );
+
+        assert_ok!(Nft::launch(
             Origin::signed(ALICE),
             nft,
-            b"TT".to_vec(),
-            b"TT".to_vec(),
-            100_000_000 * DOLLARS,
+            b"Test Token".to_vec(),
+            b"XTT".to_vec(),
+            1_000_000 * DOLLARS,
+            Some((1_000 * DOLLARS, 100_000 * DOLLARS)),
+            1_000 * DOLLARS,
+            500_000 * DOLLARS,
+            true
         ));
 
-        //action
-        assert_ok!(Nft::start_dao_influenceming_activity(
-            Origin::signed(ALICE),
-            nft,
+        let meta = <Metadata<Test>>::get(nft).unwrap();
+        assert!(meta.minted);
+
+        let ico_meta = <IcoMetaOf<Test>>::get(nft).unwrap();
+        assert_eq!(ico_meta.offered_tokens, 100_000 * DOLLARS);
+        assert_eq!(
+            Assets::balance(meta.token_asset_id, &ico_meta.pot),
             100_000 * DOLLARS
-        ));
+        );
 
-        assert_err!(
-            Nft::start_dao_influenceming_activity(Origin::signed(ALICE), nft, 100_000 * DOLLARS),
-            Error::<Test>::Exists
+        let pool = Swap::get_pool_account(meta.token_asset_id);
+        assert_eq!(Balances::free_balance(&pool), 1_000 * DOLLARS);
+        assert_eq!(
+            Assets::balance(meta.token_asset_id, &pool),
+            500_000 * DOLLARS
+        );
+
+        assert_eq!(
+            Assets::balance(meta.token_asset_id, &ALICE),
+            400_000 * DOLLARS
+        );
+
+        assert_noop!(
+            Nft::launch_dry(
+                nft,
+                1_000_000 * DOLLARS,
+                0,
+                1_000 * DOLLARS,
+                500_000 * DOLLARS
+            ),
+            Error::<Test>::Minted
         );
     });
 }
 
 #[test]
-fn should_not_start_influence_mining_activity_when_origin_from_non_owner() {
+fn should_launch_with_existing_pool() {
     new_test_ext().execute_with(|| {
         let nft = Nft::preferred(DID_ALICE).unwrap();
+        let meta = <Metadata<Test>>::get(nft).unwrap();
 
-        assert_ok!(Nft::mint_nft_power(
+        assert_ok!(Swap::create(Origin::signed(BOB), meta.token_asset_id));
+
+        assert_ok!(Nft::launch(
             Origin::signed(ALICE),
             nft,
-            b"TT".to_vec(),
-            b"TT".to_vec(),
-            100_000_000 * DOLLARS,
+            b"Test Token".to_vec(),
+            b"XTT".to_vec(),
+            1_000_000 * DOLLARS,
+            None,
+            1_000 * DOLLARS,
+            500_000 * DOLLARS,
+            true
         ));
 
-        //action
-        assert_noop!(
-            Nft::start_dao_influenceming_activity(Origin::signed(BOB), nft, 100_000 * DOLLARS),
-            Error::<Test>::NotTokenOwner
+        let pool = Swap::get_pool_account(meta.token_asset_id);
+        assert_eq!(Balances::free_balance(&pool), 1_000 * DOLLARS);
+        assert_eq!(
+            Assets::balance(meta.token_asset_id, &pool),
+            500_000 * DOLLARS
         );
     });
 }
 
 #[test]
-fn should_not_start_influence_mining_activity_when_balance_not_enough() {
+fn should_fail_to_launch_with_wrong_params() {
     new_test_ext().execute_with(|| {
         let nft = Nft::preferred(DID_ALICE).unwrap();
 
-        assert_ok!(Nft::mint_nft_power(
-            Origin::signed(ALICE),
-            nft,
-            b"TT".to_vec(),
-            b"TT".to_vec(),
-            100 * DOLLARS,
-        ));
-
-        //action
         assert_noop!(
-            Nft::start_dao_influenceming_activity(Origin::signed(BOB), nft, 100_000 * DOLLARS),
+            Nft::launch(
+                Origin::signed(BOB),
+                nft,
+                b"Test Token".to_vec(),
+                b"XTT".to_vec(),
+                1_000_000 * DOLLARS,
+                None,
+                1_000 * DOLLARS,
+                500_000 * DOLLARS,
+                false
+            ),
             Error::<Test>::NotTokenOwner
         );
+
+        assert_noop!(
+            Nft::launch(
+                Origin::signed(ALICE),
+                nft,
+                b"Test-Token".to_vec(),
+                b"XTT".to_vec(),
+                1_000_000 * DOLLARS,
+                None,
+                1_000 * DOLLARS,
+                500_000 * DOLLARS,
+                false
+            ),
+            Error::<Test>::BadMetadata
+        );
+
+        assert_noop!(
+            Nft::launch(
+                Origin::signed(ALICE),
+                nft,
+                b"Test Token".to_vec(),
+                b"XTT".to_vec(),
+                1_000_000 * DOLLARS,
+                Some((1_000 * DOLLARS, 600_000 * DOLLARS)),
+                1_000 * DOLLARS,
+                500_000 * DOLLARS,
+                false
+            ),
+            Error::<Test>::InsufficientToken
+        );
     });
 }
 
 #[test]
-fn should_not_start_influence_mining_activity_when_nft_not_minted() {
+fn should_not_leave_half_launched_nft() {
     new_test_ext().execute_with(|| {
         let nft = Nft::preferred(DID_ALICE).unwrap();
 
-        //action
+        assert_ok!(Swap::create(Origin::signed(BOB), nft));
+
         assert_noop!(
-            Nft::start_dao_influenceming_activity(Origin::signed(ALICE), nft, 100_000 * DOLLARS),
-            Error::<Test>::NotExists
+            Nft::launch(
+                Origin::signed(ALICE),
+                nft,
+                b"Test Token".to_vec(),
+                b"XTT".to_vec(),
+                1_000_000 * DOLLARS,
+                Some((1_000 * DOLLARS, 100_000 * DOLLARS)),
+                1_000 * DOLLARS,
+                500_000 * DOLLARS,
+                false
+            ),
+            parami_swap::Error::<Test>::Exists
         );
+
+        assert!(!<Metadata<Test>>::get(nft).unwrap().minted);
+    });
+}
+
+#[test]
+fn should_remove_preferred_when_did_revoked() {
+    new_test_ext().execute_with(|| {
+        assert!(<Preferred<Test>>::get(DID_ALICE).is_some());
+
+        assert_ok!(Did::revoke(Origin::signed(ALICE)));
+
+        assert_eq!(<Preferred<Test>>::get(DID_ALICE), None);
     });
 }
@@ -12,6 +12,14 @@ pub struct External<Did> {
     pub token: Vec<u8>,
 }
 
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, Default, TypeInfo)]
+pub struct Display {
+    pub name: Vec<u8>,
+    /// URI of the image
+    pub image: Vec<u8>,
+    pub description: Vec<u8>,
+}
+
 #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, Default, TypeInfo, MaxEncodedLen)]
 pub struct Metadata<Did, AccountId, NftClassId, AssetId> {
     pub owner: Did,
@@ -21,6 +29,14 @@ pub struct Metadata<Did, AccountId, NftClassId, AssetId> {
     pub token_asset_id: AssetId,
 }
 
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum TokenStandard {
+    /// Non-fungible token, ownership verified through `ownerOf(uint256)`
+    Erc721,
+    /// Multi token, ownership verified through `balanceOf(address,uint256) > 0`
+    Erc1155,
+}
+
 #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, Default, TypeInfo)]
 pub struct ImportTask<Did> {
     pub owner: Did,
@@ -28,6 +44,8 @@ pub struct ImportTask<Did> {
     pub namespace: Vec<u8>,
     pub token: Vec<u8>,
     pub owner_address: Vec<u8>,
+    /// Token standard of the contract, detected by the off-chain worker if not given
+    pub standard: Option<TokenStandard>,
 }
 
 #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, Default, TypeInfo)]
@@ -44,3 +62,27 @@ pub struct InfluenceMiningMeta<Balance, Account> {
     pub pot: Account,
     pub done: bool,
 }
+
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum BuybackTarget {
+    /// Burn the fragments bought back
+    Burn,
+    /// Add the fragments bought back to the influencemining budget
+    InfluenceMining,
+}
+
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct Buyback<Balance, Account, Height> {
+    pub pot: Account,
+    /// Currency spent per period
+    pub amount: Balance,
+    pub period: Height,
+    /// Maximum increase of the price caused by a single buy
+    pub max_price_impact: Perbill,
+    pub target: BuybackTarget,
+    pub next_at: Height,
+    /// Currency spent so far
+    pub spent: Balance,
+    /// Fragments bought back so far
+    pub bought: Balance,
+}
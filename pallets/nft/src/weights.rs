@@ -20,6 +20,9 @@
 //! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
 //! DATE: 2022-08-26, STEPS: `2`, REPEAT: 50, LOW RANGE: `[]`, HIGH RANGE: `[]`
 //! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
+//!
+//! The weights following `submit_porting` are estimated from the benchmarked weights
+//! with similar storage access until this file is regenerated.
 
 // Executed Command:
 // ./target/release/parami
@@ -50,6 +53,19 @@ pub trait WeightInfo {
     fn mint(n: u32, s: u32, ) -> Weight;
     fn claim() -> Weight;
     fn submit_porting() -> Weight;
+    fn transfer_nft() -> Weight;
+    fn set_preferred() -> Weight;
+    fn set_display(n: u32, ) -> Weight;
+    fn cancel_port() -> Weight;
+    fn set_validate_endpoints(n: u32, l: u32, ) -> Weight;
+    fn port_with_proof(n: u32, ) -> Weight;
+    fn anchor_header() -> Weight;
+    fn set_owners_slot() -> Weight;
+    fn launch(n: u32, s: u32, ) -> Weight;
+    fn start_buyback() -> Weight;
+    fn fund_buyback() -> Weight;
+    fn stop_buyback() -> Weight;
+    fn on_initialize(n: u32, ) -> Weight;
 }
 
 /// Weights for parami_nft using the Substrate node and recommended hardware.
@@ -133,6 +149,161 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(4 as Weight))
             .saturating_add(T::DbWeight::get().writes(6 as Weight))
     }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Nft Metadata (r:1 w:1)
+    // Storage: Nft Bridged (r:1 w:0)
+    // Storage: Did Metadata (r:1 w:0)
+    // Storage: Nft External (r:1 w:1)
+    // Storage: Nft OwnedNfts (r:1 w:2)
+    // Storage: Nft Preferred (r:2 w:2)
+    // Storage: Nft PendingTransfer (r:0 w:1)
+    fn transfer_nft() -> Weight {
+        (36_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(8 as Weight))
+            .saturating_add(T::DbWeight::get().writes(7 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Nft Metadata (r:1 w:0)
+    // Storage: Nft Bridged (r:1 w:0)
+    // Storage: Nft Preferred (r:0 w:1)
+    fn set_preferred() -> Weight {
+        (18_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Nft Metadata (r:1 w:0)
+    // Storage: Nft Bridged (r:1 w:0)
+    // Storage: Nft Display (r:0 w:1)
+    fn set_display(n: u32, ) -> Weight {
+        (19_000_000 as Weight)
+            // Standard Error: 0
+            .saturating_add((2_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Nft Porting (r:1 w:1)
+    fn cancel_port() -> Weight {
+        (20_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Nft Disagreements (r:0 w:8)
+    // Storage: Nft ValidateEndpoint (r:0 w:1)
+    // Storage: Nft ValidateQuorum (r:0 w:1)
+    fn set_validate_endpoints(n: u32, l: u32, ) -> Weight {
+        (12_000_000 as Weight)
+            // Standard Error: 0
+            .saturating_add((400_000 as Weight).saturating_mul(n as Weight))
+            // Standard Error: 0
+            .saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+            .saturating_add(T::DbWeight::get().writes(10 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Nft Porting (r:1 w:0)
+    // Storage: Nft Ported (r:1 w:1)
+    // Storage: Nft AnchoredHeaders (r:1 w:0)
+    // Storage: Nft OwnersSlot (r:1 w:0)
+    // Storage: Nft Failed (r:0 w:1)
+    // Storage: AssetManager NextAssetId (r:1 w:1)
+    // Storage: Nft Metadata (r:1 w:1)
+    // Storage: Nft OwnedNfts (r:0 w:1)
+    // Storage: Nft Preferred (r:1 w:1)
+    // Storage: Nft External (r:0 w:1)
+    fn port_with_proof(n: u32, ) -> Weight {
+        (92_000_000 as Weight)
+            // Standard Error: 0
+            .saturating_add((4_500_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(8 as Weight))
+            .saturating_add(T::DbWeight::get().writes(7 as Weight))
+    }
+    // Storage: Nft AnchoredHeaders (r:1 w:2)
+    // Storage: Nft AnchoredNumbers (r:1 w:1)
+    fn anchor_header() -> Weight {
+        (22_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    // Storage: Nft OwnersSlot (r:0 w:1)
+    fn set_owners_slot() -> Weight {
+        (9_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Nft Metadata (r:1 w:1)
+    // Storage: System Account (r:3 w:3)
+    // Storage: Uniques Class (r:1 w:1)
+    // Storage: Uniques Asset (r:1 w:1)
+    // Storage: Uniques CollectionMaxSupply (r:1 w:0)
+    // Storage: Assets Asset (r:1 w:1)
+    // Storage: Assets Metadata (r:1 w:1)
+    // Storage: Assets Account (r:3 w:3)
+    // Storage: XAssets ResourceMap (r:1 w:1)
+    // Storage: XAssets ResourceId2Asset (r:0 w:1)
+    // Storage: ChainBridge Resources (r:0 w:1)
+    // Storage: Nft IcoMetaOf (r:0 w:1)
+    // Storage: Swap Metadata (r:1 w:1)
+    // Storage: Swap NextTokenId (r:1 w:1)
+    // Storage: Swap Provider (r:1 w:1)
+    // Storage: Swap Liquidity (r:1 w:1)
+    // Storage: Swap Account (r:1 w:1)
+    // Storage: Uniques ClassAccount (r:0 w:1)
+    // Storage: Uniques Account (r:0 w:1)
+    fn launch(n: u32, s: u32, ) -> Weight {
+        (298_000_000 as Weight)
+            // Standard Error: 0
+            .saturating_add((7_000 as Weight).saturating_mul(n as Weight))
+            // Standard Error: 0
+            .saturating_add((4_000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(T::DbWeight::get().reads(19 as Weight))
+            .saturating_add(T::DbWeight::get().writes(22 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Nft Metadata (r:1 w:0)
+    // Storage: Nft Bridged (r:1 w:0)
+    // Storage: Nft Buybacks (r:1 w:1)
+    // Storage: System Account (r:2 w:2)
+    // Storage: Nft BuybackQueue (r:16 w:1)
+    fn start_buyback() -> Weight {
+        (68_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(22 as Weight))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Nft Buybacks (r:1 w:0)
+    // Storage: System Account (r:2 w:2)
+    fn fund_buyback() -> Weight {
+        (44_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Nft Metadata (r:1 w:0)
+    // Storage: Nft Bridged (r:1 w:0)
+    // Storage: Nft Buybacks (r:1 w:1)
+    // Storage: System Account (r:2 w:2)
+    fn stop_buyback() -> Weight {
+        (52_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    // Storage: Nft BuybackQueue (r:17 w:2)
+    // Storage: Nft Buybacks (r:1 w:1)
+    // Storage: Nft Metadata (r:1 w:0)
+    // Storage: System Account (r:3 w:3)
+    // Storage: Swap Metadata (r:1 w:1)
+    // Storage: Assets Asset (r:1 w:1)
+    // Storage: Assets Account (r:2 w:2)
+    fn on_initialize(n: u32, ) -> Weight {
+        (4_000_000 as Weight)
+            // Standard Error: 0
+            .saturating_add((96_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().reads((25 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes((9 as Weight).saturating_mul(n as Weight)))
+    }
 }
 
 // For backwards compatibility and tests
@@ -215,4 +386,159 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(4 as Weight))
             .saturating_add(RocksDbWeight::get().writes(6 as Weight))
     }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Nft Metadata (r:1 w:1)
+    // Storage: Nft Bridged (r:1 w:0)
+    // Storage: Did Metadata (r:1 w:0)
+    // Storage: Nft External (r:1 w:1)
+    // Storage: Nft OwnedNfts (r:1 w:2)
+    // Storage: Nft Preferred (r:2 w:2)
+    // Storage: Nft PendingTransfer (r:0 w:1)
+    fn transfer_nft() -> Weight {
+        (36_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(8 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(7 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Nft Metadata (r:1 w:0)
+    // Storage: Nft Bridged (r:1 w:0)
+    // Storage: Nft Preferred (r:0 w:1)
+    fn set_preferred() -> Weight {
+        (18_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Nft Metadata (r:1 w:0)
+    // Storage: Nft Bridged (r:1 w:0)
+    // Storage: Nft Display (r:0 w:1)
+    fn set_display(n: u32, ) -> Weight {
+        (19_000_000 as Weight)
+            // Standard Error: 0
+            .saturating_add((2_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Nft Porting (r:1 w:1)
+    fn cancel_port() -> Weight {
+        (20_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Nft Disagreements (r:0 w:8)
+    // Storage: Nft ValidateEndpoint (r:0 w:1)
+    // Storage: Nft ValidateQuorum (r:0 w:1)
+    fn set_validate_endpoints(n: u32, l: u32, ) -> Weight {
+        (12_000_000 as Weight)
+            // Standard Error: 0
+            .saturating_add((400_000 as Weight).saturating_mul(n as Weight))
+            // Standard Error: 0
+            .saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+            .saturating_add(RocksDbWeight::get().writes(10 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Nft Porting (r:1 w:0)
+    // Storage: Nft Ported (r:1 w:1)
+    // Storage: Nft AnchoredHeaders (r:1 w:0)
+    // Storage: Nft OwnersSlot (r:1 w:0)
+    // Storage: Nft Failed (r:0 w:1)
+    // Storage: AssetManager NextAssetId (r:1 w:1)
+    // Storage: Nft Metadata (r:1 w:1)
+    // Storage: Nft OwnedNfts (r:0 w:1)
+    // Storage: Nft Preferred (r:1 w:1)
+    // Storage: Nft External (r:0 w:1)
+    fn port_with_proof(n: u32, ) -> Weight {
+        (92_000_000 as Weight)
+            // Standard Error: 0
+            .saturating_add((4_500_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(8 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(7 as Weight))
+    }
+    // Storage: Nft AnchoredHeaders (r:1 w:2)
+    // Storage: Nft AnchoredNumbers (r:1 w:1)
+    fn anchor_header() -> Weight {
+        (22_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    // Storage: Nft OwnersSlot (r:0 w:1)
+    fn set_owners_slot() -> Weight {
+        (9_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Nft Metadata (r:1 w:1)
+    // Storage: System Account (r:3 w:3)
+    // Storage: Uniques Class (r:1 w:1)
+    // Storage: Uniques Asset (r:1 w:1)
+    // Storage: Uniques CollectionMaxSupply (r:1 w:0)
+    // Storage: Assets Asset (r:1 w:1)
+    // Storage: Assets Metadata (r:1 w:1)
+    // Storage: Assets Account (r:3 w:3)
+    // Storage: XAssets ResourceMap (r:1 w:1)
+    // Storage: XAssets ResourceId2Asset (r:0 w:1)
+    // Storage: ChainBridge Resources (r:0 w:1)
+    // Storage: Nft IcoMetaOf (r:0 w:1)
+    // Storage: Swap Metadata (r:1 w:1)
+    // Storage: Swap NextTokenId (r:1 w:1)
+    // Storage: Swap Provider (r:1 w:1)
+    // Storage: Swap Liquidity (r:1 w:1)
+    // Storage: Swap Account (r:1 w:1)
+    // Storage: Uniques ClassAccount (r:0 w:1)
+    // Storage: Uniques Account (r:0 w:1)
+    fn launch(n: u32, s: u32, ) -> Weight {
+        (298_000_000 as Weight)
+            // Standard Error: 0
+            .saturating_add((7_000 as Weight).saturating_mul(n as Weight))
+            // Standard Error: 0
+            .saturating_add((4_000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(RocksDbWeight::get().reads(19 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(22 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Nft Metadata (r:1 w:0)
+    // Storage: Nft Bridged (r:1 w:0)
+    // Storage: Nft Buybacks (r:1 w:1)
+    // Storage: System Account (r:2 w:2)
+    // Storage: Nft BuybackQueue (r:16 w:1)
+    fn start_buyback() -> Weight {
+        (68_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(22 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Nft Buybacks (r:1 w:0)
+    // Storage: System Account (r:2 w:2)
+    fn fund_buyback() -> Weight {
+        (44_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Nft Metadata (r:1 w:0)
+    // Storage: Nft Bridged (r:1 w:0)
+    // Storage: Nft Buybacks (r:1 w:1)
+    // Storage: System Account (r:2 w:2)
+    fn stop_buyback() -> Weight {
+        (52_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    // Storage: Nft BuybackQueue (r:17 w:2)
+    // Storage: Nft Buybacks (r:1 w:1)
+    // Storage: Nft Metadata (r:1 w:0)
+    // Storage: System Account (r:3 w:3)
+    // Storage: Swap Metadata (r:1 w:1)
+    // Storage: Assets Asset (r:1 w:1)
+    // Storage: Assets Account (r:2 w:2)
+    fn on_initialize(n: u32, ) -> Weight {
+        (4_000_000 as Weight)
+            // Standard Error: 0
+            .saturating_add((96_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((25 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((9 as Weight).saturating_mul(n as Weight)))
+    }
 }
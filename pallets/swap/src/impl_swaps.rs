@@ -63,6 +63,20 @@ impl<T: Config> Swaps<AccountOf<T>> for Pallet<T> {
         Ok(())
     }
 
+    fn enable_staking(token_id: Self::AssetId) -> DispatchResult<()> {
+        let mut meta = <Metadata<T>>::get(token_id).ok_or(Error::<T>::NotExists)?;
+        ensure!(!meta.enable_staking, Error::<T>::Exists);
+        ensure!(meta.liquidity.is_zero(), Error::<T>::Exists);
+
+        meta.enable_staking = true;
+
+        T::Stakes::start(token_id, T::StakingRewardAmount::get())?;
+
+        <Metadata<T>>::insert(token_id, &meta);
+
+        Ok(())
+    }
+
     fn get_pool_account(token_id: Self::AssetId) -> AccountOf<T> {
         T::PalletId::get().into_sub_account_truncating(token_id)
     }
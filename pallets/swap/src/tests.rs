@@ -33,6 +33,22 @@ fn should_not_create_when_exists() {
     });
 }
 
+#[test]
+fn should_enable_staking() {
+    new_test_ext().execute_with(|| {
+        let token = 1;
+
+        assert_noop!(Swap::enable_staking(token), Error::<Test>::NotExists);
+
+        assert_ok!(Swap::create(Origin::signed(ALICE), token));
+
+        assert_ok!(Swap::enable_staking(token));
+        assert!(<Metadata<Test>>::get(&token).unwrap().enable_staking);
+
+        assert_noop!(Swap::enable_staking(token), Error::<Test>::Exists);
+    });
+}
+
 #[test]
 fn should_add_liquidity() {
     new_test_ext().execute_with(|| {
@@ -22,12 +22,13 @@ use frame_support::{
     dispatch::DispatchResult,
     ensure,
     storage::PrefixIterator,
-    traits::{Currency, ExistenceRequirement::KeepAlive, StorageVersion, WithdrawReasons},
+    traits::{Currency, ExistenceRequirement::KeepAlive, Get, StorageVersion, WithdrawReasons},
+    weights::Weight,
     Blake2_256, StorageHasher,
 };
 #[cfg(not(feature = "std"))]
 use num_traits::Float;
-use parami_traits::{Tag, Tags};
+use parami_traits::{OnDidRevoked, Tag, Tags};
 use scale_info::TypeInfo;
 use sp_runtime::traits::{Hash, MaybeSerializeDeserialize, Member};
 use sp_std::collections::btree_map::BTreeMap;
@@ -44,6 +45,10 @@ type MetaOf<T> = types::Metadata<Tag, <T as Config>::DecentralizedId, HeightOf<T
 
 const STORAGE_VERSION: StorageVersion = StorageVersion::new(6);
 
+/// The maximum number of scores of each kind removed when a DID is revoked,
+/// DIDs are never reused, the scores left are unreachable
+const MAX_REVOKED_SCORES: u32 = 256;
+
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
@@ -419,3 +424,25 @@ impl<T: Config> Tags<TagHash, AdOf<T>, T::DecentralizedId> for Pallet<T> {
         return map;
     }
 }
+
+impl<T: Config> OnDidRevoked<T::DecentralizedId, AccountOf<T>> for Pallet<T> {
+    fn on_did_revoked(did: &T::DecentralizedId, _account: &AccountOf<T>) -> Weight {
+        use sp_io::KillStorageResult::*;
+
+        let mut removed = 0;
+        for result in [
+            <PersonasOf<T>>::remove_prefix(did, Some(MAX_REVOKED_SCORES)),
+            <InfluencesOf<T>>::remove_prefix(did, Some(MAX_REVOKED_SCORES)),
+        ] {
+            match result {
+                AllRemoved(count) | SomeRemaining(count) => removed += count,
+            }
+        }
+
+        T::DbWeight::get().writes(removed as Weight)
+    }
+
+    fn max_weight() -> Weight {
+        T::DbWeight::get().writes(2 * MAX_REVOKED_SCORES as Weight)
+    }
+}
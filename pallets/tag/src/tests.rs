@@ -168,3 +168,21 @@ fn tags_trait() {
         assert_eq!(Tag::get_influence(&did, &tag1), 4);
     });
 }
+
+#[test]
+fn should_clear_personas_when_did_revoked() {
+    new_test_ext().execute_with(|| {
+        let alice = sr25519::Public([1; 32]);
+        let did = DID::from_slice(&[0xff; 20]);
+
+        let tag = vec![0u8, 1u8, 2u8, 3u8, 4u8, 5u8];
+
+        assert_ok!(Tag::influence(&did, &tag, 5));
+        assert_ok!(Tag::impact(&did, &tag, 3));
+
+        assert_ok!(Did::revoke(Origin::signed(alice)));
+
+        assert_eq!(Tag::personas_of(&did), BTreeMap::new());
+        assert_eq!(Tag::influences_of(&did), BTreeMap::new());
+    });
+}
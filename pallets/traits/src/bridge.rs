@@ -0,0 +1,37 @@
+use frame_support::Parameter;
+use sp_runtime::{traits::AtLeast32BitUnsigned, DispatchResult};
+
+/// Fungible assets which can be registered as bridge resources
+pub trait BridgeAssets<AssetId> {
+    fn register(asset_id: AssetId) -> DispatchResult;
+}
+
+impl<AssetId> BridgeAssets<AssetId> for () {
+    fn register(_asset_id: AssetId) -> DispatchResult {
+        Ok(())
+    }
+}
+
+/// NFTs which are locked while they are bridged to another chain
+pub trait BridgeNfts<AccountId> {
+    type NftId: Parameter + AtLeast32BitUnsigned + Copy;
+
+    /// Lock an NFT owned by `who` before it is bridged out
+    fn lock(who: &AccountId, nft_id: Self::NftId) -> DispatchResult;
+
+    /// Unlock a bridged NFT to `to` once it is bridged back,
+    /// a DID is registered for `to` if it has none, as only DIDs can own NFTs
+    fn unlock(to: &AccountId, nft_id: Self::NftId) -> DispatchResult;
+}
+
+impl<AccountId> BridgeNfts<AccountId> for () {
+    type NftId = u32;
+
+    fn lock(_who: &AccountId, _nft_id: Self::NftId) -> DispatchResult {
+        Ok(())
+    }
+
+    fn unlock(_to: &AccountId, _nft_id: Self::NftId) -> DispatchResult {
+        Ok(())
+    }
+}
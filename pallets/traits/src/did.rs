@@ -0,0 +1,25 @@
+use frame_support::weights::Weight;
+use impl_trait_for_tuples::impl_for_tuples;
+
+pub trait OnDidRevoked<DecentralizedId, AccountId> {
+    /// Clean up the states of a revoked DID, returns the weight consumed
+    fn on_did_revoked(did: &DecentralizedId, account: &AccountId) -> Weight;
+
+    /// The maximum weight `on_did_revoked` may consume
+    fn max_weight() -> Weight;
+}
+
+#[impl_for_tuples(10)]
+impl<DecentralizedId, AccountId> OnDidRevoked<DecentralizedId, AccountId> for Tuple {
+    fn on_did_revoked(did: &DecentralizedId, account: &AccountId) -> Weight {
+        let mut weight: Weight = 0;
+        for_tuples!( #( weight = weight.saturating_add(Tuple::on_did_revoked(did, account)); )* );
+        weight
+    }
+
+    fn max_weight() -> Weight {
+        let mut weight: Weight = 0;
+        for_tuples!( #( weight = weight.saturating_add(Tuple::max_weight()); )* );
+        weight
+    }
+}
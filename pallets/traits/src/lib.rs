@@ -1,5 +1,11 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+mod bridge;
+pub use bridge::{BridgeAssets, BridgeNfts};
+
+mod did;
+pub use did::OnDidRevoked;
+
 mod links;
 pub use links::Links;
 
@@ -71,6 +71,13 @@ pub trait Swaps<AccountId> {
     /// wether the swap pair was created or not
     fn new(token_id: Self::AssetId) -> DispatchResult;
 
+    /// Enable staking rewards for liquidity providers of a swap pair
+    ///
+    /// # Arguments
+    ///
+    /// * `token_id` - The Asset ID
+    fn enable_staking(token_id: Self::AssetId) -> DispatchResult;
+
     /// Get pot account ID for a given pair
     ///
     /// # Arguments
@@ -15,7 +15,7 @@ pub mod migrations;
 
 // #[cfg(feature = "runtime-benchmarks")]
 // mod benchmarking;
-use codec::MaxEncodedLen;
+use codec::{Encode, MaxEncodedLen};
 use frame_support::{
     dispatch::{DispatchResult, DispatchResultWithPostInfo},
     ensure,
@@ -32,7 +32,9 @@ use frame_support::{
 };
 use frame_system::ensure_signed;
 use parami_chainbridge::{ChainId, ResourceId};
+use parami_traits::{BridgeAssets, BridgeNfts};
 use sp_core::U256;
+use sp_io::hashing::blake2_128;
 use sp_runtime::traits::{AccountIdConversion, One, SaturatedConversion};
 use sp_std::prelude::*;
 
@@ -43,6 +45,7 @@ type BalanceOf<T> =
 
 type AccountOf<T> = <T as frame_system::Config>::AccountId;
 type AssetOf<T> = <T as Config>::AssetId;
+type NftOf<T> = <<T as Config>::Nfts as BridgeNfts<AccountOf<T>>>::NftId;
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -87,6 +90,12 @@ pub mod pallet {
 
         type NativeTokenResourceId: Get<ResourceId>;
 
+        /// The NFTs which can be bridged out
+        type Nfts: BridgeNfts<AccountOf<Self>>;
+
+        /// Resource ID of the NFTs
+        type NftResourceId: Get<ResourceId>;
+
         /// Weight information for extrinsics in this pallet
         type WeightInfo: WeightInfo;
 
@@ -273,6 +282,41 @@ pub mod pallet {
             Ok(().into())
         }
 
+        /// Lock an NFT and bridge it out to a whitelisted chain.
+        #[pallet::weight(<T as Config>::WeightInfo::transfer_token())]
+        pub fn transfer_nft(
+            origin: OriginFor<T>,
+            nft_id: NftOf<T>,
+            recipient: Vec<u8>,
+            dest_id: ChainId,
+        ) -> DispatchResultWithPostInfo {
+            let source = ensure_signed(origin)?;
+
+            ensure!(
+                <parami_chainbridge::Pallet<T>>::chain_whitelisted(dest_id),
+                Error::<T>::InvalidTransfer
+            );
+            ensure!(
+                EnableCrossBridgeTransfer::<T>::try_get().unwrap_or(false),
+                Error::<T>::TransferNotEnabled
+            );
+
+            T::Nfts::lock(&source, nft_id)?;
+
+            let mut token_id = [0u8; 32];
+            U256::from(nft_id.saturated_into::<u128>()).to_big_endian(&mut token_id);
+
+            <parami_chainbridge::Pallet<T>>::transfer_nonfungible(
+                dest_id,
+                T::NftResourceId::get(),
+                token_id.to_vec(),
+                recipient,
+                Vec::new(),
+            )?;
+
+            Ok(().into())
+        }
+
         #[pallet::weight(<T as Config>::WeightInfo::force_set_resource())]
         pub fn force_set_resource(
             origin: OriginFor<T>,
@@ -345,6 +389,33 @@ pub mod pallet {
             return Ok(().into());
         }
 
+        #[pallet::weight(<T as Config>::WeightInfo::transfer())]
+        pub fn handle_transfer_nonfungibles(
+            origin: OriginFor<T>,
+            to: <T as frame_system::Config>::AccountId,
+            token_id: U256,
+            _metadata: Vec<u8>,
+            resource_id: ResourceId,
+        ) -> DispatchResultWithPostInfo {
+            let _bridge = T::BridgeOrigin::ensure_origin(origin)?;
+            ensure!(
+                resource_id == T::NftResourceId::get(),
+                <Error<T>>::NotExists
+            );
+            ensure!(
+                token_id <= U256::from(u128::MAX),
+                <Error<T>>::InvalidTransfer
+            );
+
+            let nft_id: NftOf<T> = token_id
+                .low_u128()
+                .try_into()
+                .map_err(|_| <Error<T>>::InvalidTransfer)?;
+            T::Nfts::unlock(&to, nft_id)?;
+
+            return Ok(().into());
+        }
+
         #[pallet::weight(<T as Config>::WeightInfo::remark())]
         pub fn remark(
             origin: OriginFor<T>,
@@ -379,6 +450,26 @@ impl<T: Config> Pallet<T> {
     }
 }
 
+impl<T: Config> BridgeAssets<AssetOf<T>> for Pallet<T> {
+    fn register(asset_id: AssetOf<T>) -> DispatchResult {
+        if <ResourceMap<T>>::contains_key(asset_id) {
+            return Ok(());
+        }
+
+        let chain_id = <T as parami_chainbridge::Config>::ChainId::get();
+        let resource_id =
+            parami_chainbridge::derive_resource_id(chain_id, &blake2_128(&asset_id.encode()));
+
+        <ResourceMap<T>>::insert(asset_id, resource_id);
+        <ResourceId2Asset<T>>::insert(resource_id, asset_id);
+
+        parami_chainbridge::Pallet::<T>::register_resource(
+            resource_id,
+            "XAssets.handle_transfer_fungibles".into(),
+        )
+    }
+}
+
 impl<T: Config> parami_traits::transferable::Transferable<AccountOf<T>> for pallet::Pallet<T> {
     fn transfer_all(src: &AccountOf<T>, dest: &AccountOf<T>) -> DispatchResult {
         for (_, asset_id) in <ResourceId2Asset<T>>::iter() {
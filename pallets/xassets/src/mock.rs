@@ -93,6 +93,7 @@ frame_support::construct_runtime!(
 parameter_types! {
     pub const TestUserId: u64 = 1;
     pub NativeTokenId: parami_chainbridge::ResourceId = parami_chainbridge::derive_resource_id(0, &blake2_128(b"AD3"));
+    pub NftId: parami_chainbridge::ResourceId = parami_chainbridge::derive_resource_id(233, &blake2_128(b"nft"));
 }
 
 impl SortedMembers<u64> for TestUserId {
@@ -198,6 +199,8 @@ impl parami_xassets::Config for MockRuntime {
     type Currency = Balances;
     type HashResourceId = HashId;
     type NativeTokenResourceId = NativeTokenId;
+    type Nfts = ();
+    type NftResourceId = NftId;
     type WeightInfo = MockWeightInfo;
     type Assets = Assets;
     type AssetId = u32;
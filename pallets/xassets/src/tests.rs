@@ -770,3 +770,127 @@ fn should_transfer_all_assets() {
             assert_eq!(mock::Assets::balance(asset_id, &dest_account), 100);
         });
 }
+
+#[test]
+fn should_transfer_nft() {
+    TestExternalitiesBuilder::default()
+        .build()
+        .execute_with(|| {
+            let dest_chain = 0;
+            let recipient = vec![99];
+            let resource_id = NftId::get();
+
+            assert_noop!(
+                mock::XAssets::transfer_nft(Origin::signed(1), 7, recipient.clone(), dest_chain),
+                Error::<MockRuntime>::InvalidTransfer
+            );
+
+            assert_ok!(mock::ChainBridge::whitelist_chain(
+                Origin::root(),
+                dest_chain.clone()
+            ));
+
+            assert_noop!(
+                mock::XAssets::transfer_nft(Origin::signed(1), 7, recipient.clone(), dest_chain),
+                Error::<MockRuntime>::TransferNotEnabled
+            );
+
+            assert_ok!(mock::XAssets::set_enable_cross_bridge_transfer(
+                Origin::root(),
+                true
+            ));
+
+            assert_ok!(mock::XAssets::transfer_nft(
+                Origin::signed(1),
+                7,
+                recipient.clone(),
+                dest_chain,
+            ));
+
+            let mut token_id = [0u8; 32];
+            token_id[31] = 7;
+
+            expect_event(parami_chainbridge::Event::NonFungibleTransfer(
+                dest_chain,
+                1,
+                resource_id,
+                token_id.to_vec(),
+                recipient,
+                vec![],
+            ));
+        });
+}
+
+#[test]
+fn should_handle_transfer_nonfungibles() {
+    TestExternalitiesBuilder::default()
+        .build()
+        .execute_with(|| {
+            let resource_id = NftId::get();
+
+            assert_ok!(mock::XAssets::handle_transfer_nonfungibles(
+                Origin::signed(mock::ChainBridge::account_id()),
+                10086,
+                7.into(),
+                vec![],
+                resource_id,
+            ));
+
+            assert_noop!(
+                mock::XAssets::handle_transfer_nonfungibles(
+                    Origin::signed(RELAYER_A),
+                    10086,
+                    7.into(),
+                    vec![],
+                    resource_id,
+                ),
+                DispatchError::BadOrigin
+            );
+
+            assert_noop!(
+                mock::XAssets::handle_transfer_nonfungibles(
+                    Origin::signed(mock::ChainBridge::account_id()),
+                    10086,
+                    7.into(),
+                    vec![],
+                    NativeTokenResourceId::get(),
+                ),
+                Error::<MockRuntime>::NotExists
+            );
+
+            assert_noop!(
+                mock::XAssets::handle_transfer_nonfungibles(
+                    Origin::signed(mock::ChainBridge::account_id()),
+                    10086,
+                    U256::from(u64::MAX) + 1,
+                    vec![],
+                    resource_id,
+                ),
+                Error::<MockRuntime>::InvalidTransfer
+            );
+        });
+}
+
+#[test]
+fn should_register_bridge_asset() {
+    TestExternalitiesBuilder::default()
+        .build()
+        .execute_with(|| {
+            let asset_id: u32 = 42;
+
+            assert_ok!(<XAssets as BridgeAssets<u32>>::register(asset_id));
+
+            let resource_id = ResourceMap::<MockRuntime>::get(asset_id).unwrap();
+            assert_eq!(
+                ResourceId2Asset::<MockRuntime>::get(resource_id),
+                Some(asset_id)
+            );
+            assert_eq!(
+                mock::ChainBridge::get_resources(resource_id).unwrap(),
+                Into::<Vec<u8>>::into("XAssets.handle_transfer_fungibles")
+            );
+
+            assert_ok!(<XAssets as BridgeAssets<u32>>::register(asset_id));
+            assert_eq!(ResourceMap::<MockRuntime>::get(asset_id), Some(resource_id));
+        });
+}
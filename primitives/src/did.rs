@@ -0,0 +1,115 @@
+use base58::ToBase58;
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+
+/// Public key of a verification method
+#[derive(Clone, Copy, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum PublicKey {
+    Sr25519([u8; 32]),
+    Ed25519([u8; 32]),
+    /// compressed secp256k1 public key
+    Secp256k1([u8; 33]),
+}
+
+impl PublicKey {
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            PublicKey::Sr25519(key) => key,
+            PublicKey::Ed25519(key) => key,
+            PublicKey::Secp256k1(key) => key,
+        }
+    }
+
+    /// Verify a signature of `message` with the key,
+    /// a secp256k1 signature is the recoverable one of the blake2-256 hash of `message`
+    pub fn verify(&self, signature: &[u8], message: &[u8]) -> bool {
+        use sp_core::{ed25519, sr25519};
+        use sp_io::{crypto, hashing::blake2_256};
+
+        match self {
+            PublicKey::Sr25519(key) => match <[u8; 64]>::try_from(signature) {
+                Ok(signature) => crypto::sr25519_verify(
+                    &sr25519::Signature::from_raw(signature),
+                    message,
+                    &sr25519::Public::from_raw(*key),
+                ),
+                Err(_) => false,
+            },
+            PublicKey::Ed25519(key) => match <[u8; 64]>::try_from(signature) {
+                Ok(signature) => crypto::ed25519_verify(
+                    &ed25519::Signature::from_raw(signature),
+                    message,
+                    &ed25519::Public::from_raw(*key),
+                ),
+                Err(_) => false,
+            },
+            PublicKey::Secp256k1(key) => match <[u8; 65]>::try_from(signature) {
+                Ok(signature) => {
+                    crypto::secp256k1_ecdsa_recover_compressed(&signature, &blake2_256(message))
+                        .map_or(false, |recovered| recovered == *key)
+                }
+                Err(_) => false,
+            },
+        }
+    }
+}
+
+/// Verification relationships defined in W3C DID Core
+#[derive(
+    Clone,
+    Copy,
+    Decode,
+    Encode,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub enum VerificationRelationship {
+    Authentication,
+    AssertionMethod,
+    KeyAgreement,
+    CapabilityInvocation,
+    CapabilityDelegation,
+}
+
+#[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct VerificationMethod {
+    /// fragment of the method id, e.g. `key-1`
+    pub id: Vec<u8>,
+    pub public_key: PublicKey,
+    pub purposes: Vec<VerificationRelationship>,
+}
+
+#[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct Service {
+    /// fragment of the service id, e.g. `linked-domain`
+    pub id: Vec<u8>,
+    pub service_type: Vec<u8>,
+    pub endpoint: Vec<u8>,
+}
+
+/// Resolved DID document
+#[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct DidDocument<DecentralizedId> {
+    pub id: DecentralizedId,
+    pub controllers: Vec<DecentralizedId>,
+    pub verification_methods: Vec<VerificationMethod>,
+    pub services: Vec<Service>,
+    pub deactivated: bool,
+}
+
+/// Format a DID as `did:ad3:{base58}`
+pub fn to_did_uri<T: AsRef<[u8]>>(did: T) -> Vec<u8> {
+    let mut bytes = b"did:ad3:".to_vec();
+
+    let did = did.as_ref().to_base58();
+    bytes.extend_from_slice(did.as_bytes());
+
+    bytes
+}
@@ -40,6 +40,8 @@ pub enum Network {
     Tron = 0x17,
     /// NEAR
     Near = 0x18,
+    /// ATOM
+    Cosmos = 0x19,
 
     // social networks id start from 0x80
     /// Discord
@@ -65,3 +67,16 @@ impl Default for Network {
         Network::Unknown
     }
 }
+
+#[derive(Clone, Copy, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum ImportFailure {
+    /// The endpoints were unavailable or returned errors
+    RpcError,
+    /// The ownership was denied by the endpoints
+    NotOwner,
+    /// The contract or token does not respond as a supported standard
+    BadContract,
+    /// The import was not validated before its deadline
+    Timeout,
+}
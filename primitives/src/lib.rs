@@ -3,6 +3,7 @@
 pub use enums::*;
 
 pub mod constants;
+pub mod did;
 mod enums;
 pub mod names;
 pub mod signature;
@@ -10,5 +10,7 @@ pub const SWAP: &[u8; 8] = b"prm/swap";
 pub const ASSET: &[u8; 8] = b"xassets ";
 pub const STAKE: &[u8; 8] = b"prm/stak";
 pub const CLOCKIN: &[u8; 8] = b"prm/clok";
+pub const GOVERNANCE: &[u8; 8] = b"prm/govn";
+pub const CREDENTIAL: &[u8; 8] = b"prm/cred";
 
 pub const CHAIN_BRIDGE: &[u8; 8] = b"chnbrdge";
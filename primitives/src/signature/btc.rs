@@ -1,6 +1,6 @@
 use sp_runtime_interface::runtime_interface;
 
-pub use hashing::{checksum, ripemd160, sha256d};
+pub use hashing::{checksum, ripemd160, ripemd160_raw, sha256d};
 
 #[runtime_interface]
 pub trait Hashing {
@@ -35,4 +35,72 @@ pub trait Hashing {
             unimplemented!()
         }
     }
+
+    /// RIPEMD160 of the bytes, without SHA256 as in `ripemd160`
+    fn ripemd160_raw(bytes: &[u8]) -> [u8; 20] {
+        #[cfg(feature = "std")]
+        {
+            use ripemd160::{Digest, Ripemd160};
+
+            let mut hasher_ripemd = Ripemd160::new();
+            hasher_ripemd.update(bytes);
+            let mut ret = [0; 20];
+            ret.copy_from_slice(&hasher_ripemd.finalize()[..]);
+            ret
+        }
+
+        #[cfg(not(feature = "std"))]
+        {
+            unimplemented!()
+        }
+    }
+}
+
+/// BIP-340 tagged hash
+pub fn tagged_hash(tag: &[u8], msg: &[u8]) -> [u8; 32] {
+    let tag = sp_io::hashing::sha2_256(tag);
+
+    sp_io::hashing::sha2_256(&[&tag[..], &tag[..], msg].concat())
+}
+
+/// Verify a BIP-340 Schnorr signature
+///
+/// There is no host function for Schnorr, so `s⋅G - e⋅P` is computed with ECDSA recovery,
+/// which returns `r⁻¹⋅(s'⋅R - z⋅G)` where `R` is the point with x-coordinate `r`.
+/// Recovering with `r = px`, `s' = -e⋅px` and `z = -s⋅px` gives `s⋅G - e⋅P`.
+pub fn verify_schnorr(public: &[u8; 32], signature: &[u8; 64], msg: &[u8; 32]) -> bool {
+    use libsecp256k1::curve::Scalar;
+
+    let mut r = [0u8; 32];
+    r.copy_from_slice(&signature[..32]);
+    let mut s_bytes = [0u8; 32];
+    s_bytes.copy_from_slice(&signature[32..]);
+
+    let mut px = Scalar::from_int(0);
+    let mut s = Scalar::from_int(0);
+    let mut e = Scalar::from_int(0);
+
+    // keys not below the curve order cannot be used as `r` of ECDSA
+    if bool::from(px.set_b32(public)) || bool::from(s.set_b32(&s_bytes)) {
+        return false;
+    }
+
+    let challenge = tagged_hash(
+        b"BIP0340/challenge",
+        &[&r[..], &public[..], &msg[..]].concat(),
+    );
+    let _ = e.set_b32(&challenge);
+
+    let mut sig = [0u8; 65];
+    sig[..32].copy_from_slice(public);
+    sig[32..64].copy_from_slice(&(-(e * px)).b32());
+    // the even point lifted from the x-only public key
+    sig[64] = 0;
+
+    let z = (-(s * px)).b32();
+
+    match sp_io::crypto::secp256k1_ecdsa_recover(&sig, &z) {
+        Ok(point) => point[..32] == r && point[63] & 1 == 0,
+        Err(_) => false,
+    }
 }
@@ -1,7 +1,9 @@
-use crate::signature::witness::WitnessProgram;
+use crate::signature::witness::{Bech32, ToBase32, WitnessProgram};
 use crate::Network;
 use base58::ToBase58;
-use codec::Encode;
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
 use sp_std::vec::Vec;
 
 mod btc;
@@ -15,6 +17,39 @@ pub use btc::hashing;
 
 pub type Signature = [u8; 65];
 
+/// SS58 address prefix of Kusama
+const KUSAMA_SS58_PREFIX: u8 = 2;
+
+/// EIP-712 type of the domain, salted with the genesis hash of the chain
+const EIP712_DOMAIN_TYPE: &[u8] = b"EIP712Domain(string name,string version,bytes32 salt)";
+/// EIP-712 type of the authorization
+const EIP712_AUTHORIZATION_TYPE: &[u8] = b"Authorization(string did,string action,uint256 expiry)";
+
+/// Scheme of the message signed by an account
+#[derive(Clone, Copy, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum SignatureScheme {
+    /// The message of `generate_message`, signed as a personal message
+    Plain,
+    /// The EIP-712 typed data of `generate_typed_data`, valid until the height of `expiry`
+    Eip712 { expiry: u64 },
+}
+
+/// Action authorized by a signature
+#[derive(Clone, Copy, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum Action {
+    Link,
+    Port,
+}
+
+impl Action {
+    pub fn as_bytes(&self) -> &'static [u8] {
+        match self {
+            Action::Link => b"link",
+            Action::Port => b"port",
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum Error {
     UnsupportedNetwork,
@@ -34,7 +69,11 @@ pub fn recover_address(
         Unknown => Ok(address),
         Binance => recover_address_eth(address, signature, bytes),
         Bitcoin => recover_address_btc(address, signature, bytes),
+        Cosmos => recover_address_atom(address, signature, bytes),
+        Eosio => recover_address_eos(address, signature, bytes),
         Ethereum => recover_address_eth(address, signature, bytes),
+        Kusama => recover_address_ksm(address, signature, bytes),
+        Near => recover_address_near(address, signature, bytes),
         Polkadot => recover_address_dot(address, signature, bytes),
         Solana => recover_address_sol(address, signature, bytes),
         Tron => recover_address_trx(address, signature, bytes),
@@ -42,25 +81,172 @@ pub fn recover_address(
     }
 }
 
+/// Recover the address of a signature of the scheme
+///
+/// The expiry of `SignatureScheme::Eip712` is left to the caller.
+pub fn recover_address_with<T: AsRef<[u8]>>(
+    scheme: SignatureScheme,
+    crypto: Network,
+    address: Vec<u8>,
+    signature: Signature,
+    genesis: &[u8],
+    did: T,
+    action: Action,
+) -> Result<Vec<u8>, Error> {
+    use Network::*;
+
+    match scheme {
+        SignatureScheme::Plain => {
+            recover_address(crypto, address, signature, generate_message(did))
+        }
+        SignatureScheme::Eip712 { expiry } => match crypto {
+            Binance | Ethereum => {
+                let hash = generate_typed_data(genesis, did, action, expiry);
+
+                recover_hash_eth(&signature, &hash)
+            }
+            _ => Err(Error::UnsupportedNetwork)?,
+        },
+    }
+}
+
 pub fn generate_message<T: AsRef<[u8]>>(did: T) -> Vec<u8> {
     let mut bytes = b"Link: ".to_vec();
 
-    let did = did.as_ref();
-    let did = did.to_base58();
-    let mut did = did.as_bytes().to_vec();
+    let mut did = crate::did::to_did_uri(did);
 
-    let mut prefix = b"did:ad3:".to_vec();
-
-    bytes.append(&mut prefix);
     bytes.append(&mut did);
     bytes
 }
 
+/// Generate the EIP-712 hash of the authorization of an action,
+/// in the domain of `Parami` version `1`, salted with the genesis hash
+pub fn generate_typed_data<T: AsRef<[u8]>>(
+    genesis: &[u8],
+    did: T,
+    action: Action,
+    expiry: u64,
+) -> [u8; 32] {
+    use sp_io::hashing::keccak_256;
+
+    let mut salt = [0u8; 32];
+    let len = genesis.len().min(32);
+    salt[..len].copy_from_slice(&genesis[..len]);
+
+    let domain = keccak_256(
+        &[
+            &keccak_256(EIP712_DOMAIN_TYPE)[..],
+            &keccak_256(b"Parami"),
+            &keccak_256(b"1"),
+            &salt,
+        ]
+        .concat(),
+    );
+
+    let mut uint256 = [0u8; 32];
+    uint256[24..].copy_from_slice(&expiry.to_be_bytes());
+
+    let message = keccak_256(
+        &[
+            &keccak_256(EIP712_AUTHORIZATION_TYPE)[..],
+            &keccak_256(&crate::did::to_did_uri(did)),
+            &keccak_256(action.as_bytes()),
+            &uint256,
+        ]
+        .concat(),
+    );
+
+    keccak_256(&[&b"\x19\x01"[..], &domain, &message].concat())
+}
+
+/// Generate the message to sign for proving a sociality account,
+/// in the format of `Link: did:ad3:{base58} {base58 nonce}`
+pub fn generate_text_proof<T: AsRef<[u8]>, N: AsRef<[u8]>>(did: T, nonce: N) -> Vec<u8> {
+    let mut bytes = generate_message(did);
+
+    bytes.push(b' ');
+    bytes.extend_from_slice(nonce.as_ref().to_base58().as_bytes());
+
+    bytes
+}
+
+/// Parse the signature of a text proof posted in `text`,
+/// in the format of `Link: did:ad3:{base58} {base58 signature}`
+pub fn parse_text_proof<T: AsRef<[u8]>>(text: &[u8], did: T) -> Option<Vec<u8>> {
+    use base58::FromBase58;
+
+    let mut prefix = generate_message(did);
+    prefix.push(b' ');
+
+    let start = text
+        .windows(prefix.len())
+        .position(|window| window == &prefix[..])?
+        + prefix.len();
+
+    let signature: Vec<u8> = text[start..]
+        .iter()
+        .copied()
+        .take_while(u8::is_ascii_alphanumeric)
+        .collect();
+
+    let signature = sp_std::str::from_utf8(&signature).ok()?;
+
+    signature.from_base58().ok()
+}
+
+fn recover_address_atom(
+    raw: Vec<u8>,
+    signature: Signature,
+    bytes: Vec<u8>,
+) -> Result<Vec<u8>, Error> {
+    use sp_std::str;
+
+    let address = str::from_utf8(&raw).map_err(|_| Error::InvalidAddress)?;
+    let (hrp, _) = address.rsplit_once('1').ok_or(Error::InvalidAddress)?;
+
+    // ADR-036 sign doc, as signed by `signArbitrary` of Keplr
+    let data = [
+        &b"{\"account_number\":\"0\",\"chain_id\":\"\",\"fee\":{\"amount\":[],\"gas\":\"0\"},\"memo\":\"\",\"msgs\":[{\"type\":\"sign/MsgSignData\",\"value\":{\"data\":\""[..],
+        &to_base64(&bytes)[..],
+        &b"\",\"signer\":\""[..],
+        &raw[..],
+        &b"\"}}],\"sequence\":\"0\"}"[..],
+    ]
+    .concat();
+    let hash = sp_io::hashing::sha2_256(&data);
+
+    // cosmos signatures come without the recovery id
+    for v in 0..2 {
+        let mut sig: Signature = [0u8; 65];
+        sig[..64].copy_from_slice(&signature[..64]);
+        sig[64] = v;
+
+        let pk = match sp_io::crypto::secp256k1_ecdsa_recover_compressed(&sig, &hash) {
+            Ok(pk) => pk,
+            Err(_) => continue,
+        };
+
+        let recovered = Bech32::encode(&btc::ripemd160(&pk).to_base32()[..], hrp.into())
+            .map_err(|_| Error::InvalidAddress)?;
+
+        if recovered == raw {
+            return Ok(recovered);
+        }
+    }
+
+    Err(Error::InvalidSignature)?
+}
+
 fn recover_address_btc(
     address: Vec<u8>,
     signature: Signature,
     mut bytes: Vec<u8>,
 ) -> Result<Vec<u8>, Error> {
+    // Taproot has no public key recovery, it is verified with BIP-322
+    if address.starts_with(b"bc1p") || address.starts_with(b"tb1p") {
+        return recover_address_btc_taproot(address, signature, bytes);
+    }
+
     let mut length = (bytes.len() as u8).encode();
     let mut data = b"\x18Bitcoin Signed Message:\n".encode();
     data.append(&mut length);
@@ -68,44 +254,126 @@ fn recover_address_btc(
     let hash = btc::sha256d(&data);
 
     let mut sig: Signature = [0u8; 65];
-    sig[64] = (signature[0] - 27) & 3;
+    sig[64] = signature[0].wrapping_sub(27) & 3;
     sig[..64].copy_from_slice(&signature[1..65]);
 
     let pk = sp_io::crypto::secp256k1_ecdsa_recover_compressed(&sig, &hash)
         .map_err(|_| Error::InvalidSignature)?;
 
-    let recovered = if address[0] == b'1' {
-        let mut result = [0u8; 25];
-
-        result[0] = 0;
-        result[1..21].copy_from_slice(&btc::ripemd160(&pk));
-        let cs = btc::checksum(&result[0..21]);
-        result[21..25].copy_from_slice(&cs);
-
-        result.to_base58().as_bytes().to_vec()
-    } else if address[0] == b'b' && address[1] == b'c' {
-        // Native P2WPKH is a scriptPubKey of 22 bytes.
-        // It starts with a OP_0, followed by a canonical push of the keyhash (i.e. 0x0014{20-byte keyhash})
-        // keyhash is RIPEMD160(SHA256) of a compressed public key
-        // https://bitcoincore.org/en/segwit_wallet_dev/
-
-        let pk_hash = btc::ripemd160(&pk);
-        let mut pk = [0u8; 22];
-        pk[0] = 0;
-        pk[1] = 20;
-        pk[2..].copy_from_slice(&pk_hash);
-        let wp =
-            WitnessProgram::from_scriptpubkey(&pk.to_vec()).map_err(|_| Error::InvalidAddress)?;
-
-        wp.to_address(b"bc".to_vec())
-            .map_err(|_| Error::InvalidAddress)?
-    } else {
-        Err(Error::InvalidAddress)?
+    let recovered = match address.first() {
+        // P2PKH, mainnet and testnet
+        Some(b'1') => to_base58check(0x00, &btc::ripemd160(&pk)),
+        Some(b'm') | Some(b'n') => to_base58check(0x6f, &btc::ripemd160(&pk)),
+
+        // P2SH-P2WPKH, the redeem script is a P2WPKH scriptPubKey
+        Some(b'3') | Some(b'2') => {
+            let mut redeem = [0u8; 22];
+            redeem[0] = 0;
+            redeem[1] = 20;
+            redeem[2..].copy_from_slice(&btc::ripemd160(&pk));
+
+            let version = if address[0] == b'3' { 0x05 } else { 0xc4 };
+
+            to_base58check(version, &btc::ripemd160(&redeem))
+        }
+
+        _ if address.starts_with(b"bc1q") || address.starts_with(b"tb1q") => {
+            // Native P2WPKH is a scriptPubKey of 22 bytes.
+            // It starts with a OP_0, followed by a canonical push of the keyhash (i.e. 0x0014{20-byte keyhash})
+            // keyhash is RIPEMD160(SHA256) of a compressed public key
+            // https://bitcoincore.org/en/segwit_wallet_dev/
+
+            let pk_hash = btc::ripemd160(&pk);
+            let mut pk = [0u8; 22];
+            pk[0] = 0;
+            pk[1] = 20;
+            pk[2..].copy_from_slice(&pk_hash);
+            let wp = WitnessProgram::from_scriptpubkey(&pk.to_vec())
+                .map_err(|_| Error::InvalidAddress)?;
+
+            wp.to_address(address[..2].to_vec())
+                .map_err(|_| Error::InvalidAddress)?
+        }
+
+        _ => Err(Error::InvalidAddress)?,
     };
 
     Ok(recovered)
 }
 
+/// Verify a BIP-322 simple signature of a P2TR address signed with the key path
+///
+/// The signature is the 64 bytes Schnorr signature followed by the sighash type,
+/// which is `0x00` for `SIGHASH_DEFAULT`.
+fn recover_address_btc_taproot(
+    address: Vec<u8>,
+    signature: Signature,
+    bytes: Vec<u8>,
+) -> Result<Vec<u8>, Error> {
+    let (_, wp) = WitnessProgram::from_address(&address).map_err(|_| Error::InvalidAddress)?;
+    if wp.version != 1 || wp.program.len() != 32 {
+        Err(Error::InvalidAddress)?
+    }
+
+    let sighash_type = signature[64];
+    if sighash_type != 0x00 && sighash_type != 0x01 {
+        Err(Error::InvalidSignature)?
+    }
+
+    let mut output_key = [0u8; 32];
+    output_key.copy_from_slice(&wp.program);
+
+    // OP_1 OP_PUSHBYTES_32 {output key}
+    let script_pubkey = [&[0x51, 0x20][..], &output_key[..]].concat();
+
+    let message_hash = btc::tagged_hash(b"BIP0322-signed-message", &bytes);
+
+    // the virtual transaction to spend, without witness
+    let to_spend = [
+        &0u32.to_le_bytes()[..],
+        &[0x01],
+        &[0u8; 32],
+        &0xffffffffu32.to_le_bytes(),
+        &[0x22, 0x00, 0x20],
+        &message_hash,
+        &0u32.to_le_bytes(),
+        &[0x01],
+        &0u64.to_le_bytes(),
+        &[script_pubkey.len() as u8],
+        &script_pubkey,
+        &0u32.to_le_bytes(),
+    ]
+    .concat();
+    let txid = btc::sha256d(&to_spend);
+
+    // BIP-341 signature message of the virtual transaction to sign,
+    // which has a single input spending `to_spend`, and a single OP_RETURN output
+    let sha256 = sp_io::hashing::sha2_256;
+    let sig_msg = [
+        &[0x00, sighash_type][..],
+        &0u32.to_le_bytes(),
+        &0u32.to_le_bytes(),
+        &sha256(&[&txid[..], &0u32.to_le_bytes()].concat()),
+        &sha256(&0u64.to_le_bytes()),
+        &sha256(&[&[script_pubkey.len() as u8][..], &script_pubkey].concat()),
+        &sha256(&0u32.to_le_bytes()),
+        &sha256(&[&0u64.to_le_bytes()[..], &[0x01, 0x6a]].concat()),
+        &[0x00],
+        &0u32.to_le_bytes(),
+    ]
+    .concat();
+    let sighash = btc::tagged_hash(b"TapSighash", &sig_msg);
+
+    let mut sig = [0u8; 64];
+    sig.copy_from_slice(&signature[..64]);
+
+    if btc::verify_schnorr(&output_key, &sig, &sighash) {
+        Ok(address)
+    } else {
+        Err(Error::InvalidSignature)?
+    }
+}
+
 fn recover_address_dot(
     raw: Vec<u8>,
     signature: Signature,
@@ -131,6 +399,41 @@ fn recover_address_dot(
     }
 }
 
+fn recover_address_eos(
+    raw: Vec<u8>,
+    signature: Signature,
+    bytes: Vec<u8>,
+) -> Result<Vec<u8>, Error> {
+    let hash = sp_io::hashing::sha2_256(&bytes);
+
+    // K1 signatures start with 27 + 4 (compressed) + recovery id
+    let mut sig: Signature = [0u8; 65];
+    sig[64] = signature[0].wrapping_sub(27) & 3;
+    sig[..64].copy_from_slice(&signature[1..65]);
+
+    let pk = sp_io::crypto::secp256k1_ecdsa_recover_compressed(&sig, &hash)
+        .map_err(|_| Error::InvalidSignature)?;
+
+    let mut result = [0u8; 37];
+    result[..33].copy_from_slice(&pk);
+
+    let recovered = if raw.starts_with(b"PUB_K1_") {
+        let cs = btc::ripemd160_raw(&[&pk[..], &b"K1"[..]].concat());
+        result[33..].copy_from_slice(&cs[..4]);
+
+        [&b"PUB_K1_"[..], result.to_base58().as_bytes()].concat()
+    } else if raw.starts_with(b"EOS") {
+        let cs = btc::ripemd160_raw(&pk);
+        result[33..].copy_from_slice(&cs[..4]);
+
+        [&b"EOS"[..], result.to_base58().as_bytes()].concat()
+    } else {
+        Err(Error::InvalidAddress)?
+    };
+
+    Ok(recovered)
+}
+
 fn recover_address_eth(
     _address: Vec<u8>,
     signature: Signature,
@@ -142,13 +445,107 @@ fn recover_address_eth(
     data.append(&mut bytes);
     let hash = sp_io::hashing::keccak_256(&data);
 
-    let pubkey = sp_io::crypto::secp256k1_ecdsa_recover(&signature, &hash)
+    recover_hash_eth(&signature, &hash)
+}
+
+fn recover_hash_eth(signature: &Signature, hash: &[u8; 32]) -> Result<Vec<u8>, Error> {
+    let pubkey = sp_io::crypto::secp256k1_ecdsa_recover(signature, hash)
         .map_err(|_| Error::InvalidSignature)?;
     let pk = sp_io::hashing::keccak_256(&pubkey);
 
     Ok(pk[12..32].to_vec())
 }
 
+fn recover_address_ksm(
+    raw: Vec<u8>,
+    signature: Signature,
+    bytes: Vec<u8>,
+) -> Result<Vec<u8>, Error> {
+    use base58::FromBase58;
+    use sp_std::str;
+
+    let address = str::from_utf8(&raw).map_err(|_| Error::InvalidAddress)?;
+    let address = address.from_base58().map_err(|_| Error::InvalidAddress)?;
+
+    // prefix, public key and checksum
+    if address.len() != 35 || address[0] != KUSAMA_SS58_PREFIX {
+        Err(Error::InvalidAddress)?
+    }
+
+    recover_address_dot(raw, signature, bytes)
+}
+
+fn recover_address_near(
+    raw: Vec<u8>,
+    signature: Signature,
+    bytes: Vec<u8>,
+) -> Result<Vec<u8>, Error> {
+    use sp_core::ed25519;
+
+    // implicit accounts are the hex of the public key,
+    // named accounts (e.g. alice.near) carry the public key of an access key,
+    // which is left to the caller to confirm
+    let public = if let Some((_, key)) = parse_near_named_account(&raw) {
+        near_public_key(key).ok_or(Error::InvalidAddress)?
+    } else {
+        if raw.len() != 64 || !raw.iter().all(|c| matches!(c, b'0'..=b'9' | b'a'..=b'f')) {
+            Err(Error::InvalidAddress)?
+        }
+
+        let mut nonce = [0u8; 32];
+        for (i, pair) in raw.chunks(2).enumerate() {
+            let pair = sp_std::str::from_utf8(pair).map_err(|_| Error::InvalidAddress)?;
+            nonce[i] = u8::from_str_radix(pair, 16).map_err(|_| Error::InvalidAddress)?;
+        }
+
+        nonce
+    };
+
+    let address = ed25519::Public::from_raw(public);
+    let signature = ed25519::Signature::from_slice(&signature[1..]).ok_or(Error::InvalidAddress)?;
+
+    if sp_io::crypto::ed25519_verify(&signature, &bytes, &address) {
+        Ok(raw)
+    } else {
+        Err(Error::InvalidSignature)?
+    }
+}
+
+/// Parse a NEAR named account in the format of `{account_id}:ed25519:{base58}`,
+/// returns the account ID and the public key, e.g. `alice.near` and `ed25519:{base58}`
+pub fn parse_near_named_account(address: &[u8]) -> Option<(&[u8], &[u8])> {
+    let pos = address.iter().position(|c| *c == b':')?;
+    let (account, key) = (&address[..pos], &address[pos + 1..]);
+
+    let is_separator = |c: &u8| matches!(c, b'-' | b'_' | b'.');
+
+    // lowercase alphanumerics, separated by a single `-`, `_` or `.`
+    let valid = (2..=64).contains(&account.len())
+        && account
+            .iter()
+            .all(|c| matches!(c, b'a'..=b'z' | b'0'..=b'9') || is_separator(c))
+        && !is_separator(&account[0])
+        && !is_separator(&account[account.len() - 1])
+        && !account
+            .windows(2)
+            .any(|w| is_separator(&w[0]) && is_separator(&w[1]));
+
+    if !valid || near_public_key(key).is_none() {
+        return None;
+    }
+
+    Some((account, key))
+}
+
+fn near_public_key(key: &[u8]) -> Option<[u8; 32]> {
+    use base58::FromBase58;
+
+    let key = key.strip_prefix(b"ed25519:")?;
+    let key = sp_std::str::from_utf8(key).ok()?.from_base58().ok()?;
+
+    <[u8; 32]>::try_from(&key[..]).ok()
+}
+
 fn recover_address_sol(
     raw: Vec<u8>,
     signature: Signature,
@@ -199,20 +596,55 @@ fn recover_address_trx(
     Ok(pk)
 }
 
-pub fn usize_to_u8_array(length: usize) -> Vec<u8> {
-    if length > 100 {
-        return b"0".to_vec();
+fn to_base58check(version: u8, hash: &[u8; 20]) -> Vec<u8> {
+    let mut result = [0u8; 25];
+
+    result[0] = version;
+    result[1..21].copy_from_slice(hash);
+    let cs = btc::checksum(&result[0..21]);
+    result[21..25].copy_from_slice(&cs);
+
+    result.to_base58().as_bytes().to_vec()
+}
+
+fn to_base64(bytes: &[u8]) -> Vec<u8> {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut result = Vec::with_capacity((bytes.len() + 2) / 3 * 4);
+
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or_default(),
+            chunk.get(2).copied().unwrap_or_default(),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                result.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize]);
+            } else {
+                result.push(b'=');
+            }
+        }
     }
 
-    let digits = b"0123456789".encode();
-    let tens = length / 10;
-    let ones = length % 10;
+    result
+}
 
+pub fn usize_to_u8_array(length: usize) -> Vec<u8> {
+    let mut length = length;
     let mut vec_res: Vec<u8> = Vec::new();
-    if tens != 0 {
-        vec_res.push(digits[tens]);
+
+    loop {
+        vec_res.push(b'0' + (length % 10) as u8);
+        length /= 10;
+
+        if length == 0 {
+            break;
+        }
     }
-    vec_res.push(digits[ones]);
 
+    vec_res.reverse();
     vec_res
 }
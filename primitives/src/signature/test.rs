@@ -15,6 +15,38 @@ fn should_generate_message() {
     assert_eq!(generate_message(&DID_ALICE), MESSAGE.to_vec());
 }
 
+#[test]
+fn should_generate_length() {
+    assert_eq!(usize_to_u8_array(0), b"0".to_vec());
+    assert_eq!(usize_to_u8_array(42), b"42".to_vec());
+    assert_eq!(usize_to_u8_array(100), b"100".to_vec());
+    assert_eq!(usize_to_u8_array(1234), b"1234".to_vec());
+}
+
+#[test]
+fn should_recover_atom() {
+    // PK: ae2159358090b617afa98e406e45de4bc6488a346a2b53de7cbcce167b7efc9b
+    let address = b"cosmos13aun4mzlmh79te7l6c24tpkcf22tre9wn3yjq8".to_vec();
+
+    // ADR-036 signature, without the recovery id
+    // 87853b6d5dfbbbc0ea0c8bc42603ad5a3728edc53ef4f29960956c7d60c63d5f17039207b399b2764df488ff3e7862062804bd27eea7abb914167ee384e9e24e
+    let signature = [
+        0x87, 0x85, 0x3b, 0x6d, 0x5d, 0xfb, 0xbb, 0xc0, 0xea, 0x0c, 0x8b, 0xc4, 0x26, 0x03, 0xad,
+        0x5a, 0x37, 0x28, 0xed, 0xc5, 0x3e, 0xf4, 0xf2, 0x99, 0x60, 0x95, 0x6c, 0x7d, 0x60, 0xc6,
+        0x3d, 0x5f, 0x17, 0x03, 0x92, 0x07, 0xb3, 0x99, 0xb2, 0x76, 0x4d, 0xf4, 0x88, 0xff, 0x3e,
+        0x78, 0x62, 0x06, 0x28, 0x04, 0xbd, 0x27, 0xee, 0xa7, 0xab, 0xb9, 0x14, 0x16, 0x7e, 0xe3,
+        0x84, 0xe9, 0xe2, 0x4e, 0x00,
+    ];
+
+    let mut sig = [0u8; 65];
+    sig.copy_from_slice(&signature);
+
+    assert_eq!(
+        recover_address(Network::Cosmos, address.clone(), sig, MESSAGE.to_vec()).unwrap(),
+        address
+    );
+}
+
 #[test]
 fn should_recover_btc() {
     // PK: 5KYZdUEo39z3FPrtuX2QbbwGnNP5zTd7yyr2SC1j299sBCnWjss
@@ -61,6 +93,144 @@ fn should_recover_btc_segwit() {
     );
 }
 
+#[test]
+fn should_recover_btc_nested_segwit() {
+    // PK: p2wpkh-p2sh:Kzbv1fJbGs24LpWjdPNgvtBEdkVF9w1urLiqbfrvTt2YGqQS6SSC
+    let address = b"3KNRikS8d7ezSKovyXLUVtgRirecCRDD2D".to_vec();
+
+    // 2355990807caaf1dd843715ffcfb5a8d77667fd1a34285aed9eb86dace0c9bca6e0cc4748bca9fe4cf3019dc3619d92026a531f698e34148b2cdbb43dce5ab5163
+    let signature = [
+        0x23, 0x55, 0x99, 0x08, 0x07, 0xca, 0xaf, 0x1d, 0xd8, 0x43, 0x71, 0x5f, 0xfc, 0xfb, 0x5a,
+        0x8d, 0x77, 0x66, 0x7f, 0xd1, 0xa3, 0x42, 0x85, 0xae, 0xd9, 0xeb, 0x86, 0xda, 0xce, 0x0c,
+        0x9b, 0xca, 0x6e, 0x0c, 0xc4, 0x74, 0x8b, 0xca, 0x9f, 0xe4, 0xcf, 0x30, 0x19, 0xdc, 0x36,
+        0x19, 0xd9, 0x20, 0x26, 0xa5, 0x31, 0xf6, 0x98, 0xe3, 0x41, 0x48, 0xb2, 0xcd, 0xbb, 0x43,
+        0xdc, 0xe5, 0xab, 0x51, 0x63,
+    ];
+
+    let mut sig = [0u8; 65];
+    sig.copy_from_slice(&signature);
+
+    assert_eq!(
+        recover_address(Network::Bitcoin, address.clone(), sig, MESSAGE.to_vec()).unwrap(),
+        address
+    );
+}
+
+#[test]
+fn should_recover_btc_taproot() {
+    // BIP-322 test vector
+    // PK: L3VFeEujGtevx9w18HD1fhRbCH67Az2dpCymeRE1SoPK6XQtaN2k
+    let address = b"bc1ppv609nr0vr25u07u95waq5lucwfm6tde4nydujnu8npg4q75mr5sxq8lt3".to_vec();
+
+    // ddebd3eb25012ffa82937d9f25f9644e047bb2f472ab6c5089bbb53588ada2884cb5bcc53911f32d8dcf9548733b694d120db6a4e485194559e8d8fe668d269f01
+    let signature = [
+        0xdd, 0xeb, 0xd3, 0xeb, 0x25, 0x01, 0x2f, 0xfa, 0x82, 0x93, 0x7d, 0x9f, 0x25, 0xf9, 0x64,
+        0x4e, 0x04, 0x7b, 0xb2, 0xf4, 0x72, 0xab, 0x6c, 0x50, 0x89, 0xbb, 0xb5, 0x35, 0x88, 0xad,
+        0xa2, 0x88, 0x4c, 0xb5, 0xbc, 0xc5, 0x39, 0x11, 0xf3, 0x2d, 0x8d, 0xcf, 0x95, 0x48, 0x73,
+        0x3b, 0x69, 0x4d, 0x12, 0x0d, 0xb6, 0xa4, 0xe4, 0x85, 0x19, 0x45, 0x59, 0xe8, 0xd8, 0xfe,
+        0x66, 0x8d, 0x26, 0x9f, 0x01,
+    ];
+
+    let mut sig = [0u8; 65];
+    sig.copy_from_slice(&signature);
+
+    assert_eq!(
+        recover_address(
+            Network::Bitcoin,
+            address.clone(),
+            sig,
+            b"Hello World".to_vec()
+        )
+        .unwrap(),
+        address
+    );
+
+    assert!(matches!(
+        recover_address(Network::Bitcoin, address, sig, MESSAGE.to_vec()),
+        Err(Error::InvalidSignature)
+    ));
+}
+
+#[test]
+fn should_recover_btc_testnet() {
+    // PK: Kzbv1fJbGs24LpWjdPNgvtBEdkVF9w1urLiqbfrvTt2YGqQS6SSC
+    let address = b"n289468rnQf2cb5f5GTwB2fV2qTjcu9ZZV".to_vec();
+
+    // 1f55990807caaf1dd843715ffcfb5a8d77667fd1a34285aed9eb86dace0c9bca6e0cc4748bca9fe4cf3019dc3619d92026a531f698e34148b2cdbb43dce5ab5163
+    let signature = [
+        0x1f, 0x55, 0x99, 0x08, 0x07, 0xca, 0xaf, 0x1d, 0xd8, 0x43, 0x71, 0x5f, 0xfc, 0xfb, 0x5a,
+        0x8d, 0x77, 0x66, 0x7f, 0xd1, 0xa3, 0x42, 0x85, 0xae, 0xd9, 0xeb, 0x86, 0xda, 0xce, 0x0c,
+        0x9b, 0xca, 0x6e, 0x0c, 0xc4, 0x74, 0x8b, 0xca, 0x9f, 0xe4, 0xcf, 0x30, 0x19, 0xdc, 0x36,
+        0x19, 0xd9, 0x20, 0x26, 0xa5, 0x31, 0xf6, 0x98, 0xe3, 0x41, 0x48, 0xb2, 0xcd, 0xbb, 0x43,
+        0xdc, 0xe5, 0xab, 0x51, 0x63,
+    ];
+
+    let mut sig = [0u8; 65];
+    sig.copy_from_slice(&signature);
+
+    assert_eq!(
+        recover_address(Network::Bitcoin, address.clone(), sig, MESSAGE.to_vec()).unwrap(),
+        address
+    );
+
+    let address = b"2NAvdnVNAEaALe7SUeexM7qfgwCrmvBKoxK".to_vec();
+
+    // 2355990807caaf1dd843715ffcfb5a8d77667fd1a34285aed9eb86dace0c9bca6e0cc4748bca9fe4cf3019dc3619d92026a531f698e34148b2cdbb43dce5ab5163
+    let signature = [
+        0x23, 0x55, 0x99, 0x08, 0x07, 0xca, 0xaf, 0x1d, 0xd8, 0x43, 0x71, 0x5f, 0xfc, 0xfb, 0x5a,
+        0x8d, 0x77, 0x66, 0x7f, 0xd1, 0xa3, 0x42, 0x85, 0xae, 0xd9, 0xeb, 0x86, 0xda, 0xce, 0x0c,
+        0x9b, 0xca, 0x6e, 0x0c, 0xc4, 0x74, 0x8b, 0xca, 0x9f, 0xe4, 0xcf, 0x30, 0x19, 0xdc, 0x36,
+        0x19, 0xd9, 0x20, 0x26, 0xa5, 0x31, 0xf6, 0x98, 0xe3, 0x41, 0x48, 0xb2, 0xcd, 0xbb, 0x43,
+        0xdc, 0xe5, 0xab, 0x51, 0x63,
+    ];
+
+    let mut sig = [0u8; 65];
+    sig.copy_from_slice(&signature);
+
+    assert_eq!(
+        recover_address(Network::Bitcoin, address.clone(), sig, MESSAGE.to_vec()).unwrap(),
+        address
+    );
+
+    let address = b"tb1qug9quswyl8pxalrfudfr9p34mmjvj2f6pqp659".to_vec();
+
+    // 2755990807caaf1dd843715ffcfb5a8d77667fd1a34285aed9eb86dace0c9bca6e0cc4748bca9fe4cf3019dc3619d92026a531f698e34148b2cdbb43dce5ab5163
+    let signature = [
+        0x27, 0x55, 0x99, 0x08, 0x07, 0xca, 0xaf, 0x1d, 0xd8, 0x43, 0x71, 0x5f, 0xfc, 0xfb, 0x5a,
+        0x8d, 0x77, 0x66, 0x7f, 0xd1, 0xa3, 0x42, 0x85, 0xae, 0xd9, 0xeb, 0x86, 0xda, 0xce, 0x0c,
+        0x9b, 0xca, 0x6e, 0x0c, 0xc4, 0x74, 0x8b, 0xca, 0x9f, 0xe4, 0xcf, 0x30, 0x19, 0xdc, 0x36,
+        0x19, 0xd9, 0x20, 0x26, 0xa5, 0x31, 0xf6, 0x98, 0xe3, 0x41, 0x48, 0xb2, 0xcd, 0xbb, 0x43,
+        0xdc, 0xe5, 0xab, 0x51, 0x63,
+    ];
+
+    let mut sig = [0u8; 65];
+    sig.copy_from_slice(&signature);
+
+    assert_eq!(
+        recover_address(Network::Bitcoin, address.clone(), sig, MESSAGE.to_vec()).unwrap(),
+        address
+    );
+
+    let address = b"tb1pwnmzakeh2ts7fp932ve89emqy26txr0jk0ltykcdznz3pe66nv2s4ehv9k".to_vec();
+
+    // 758f05c3abd051587dbdddeb34a365e8d061f87a773d41e5b0476d16195531d7c0d0f0f8d5d0841d10a06fd5ee31e9c34574eb2f61d1371a3e480ffd335f223200
+    let signature = [
+        0x75, 0x8f, 0x05, 0xc3, 0xab, 0xd0, 0x51, 0x58, 0x7d, 0xbd, 0xdd, 0xeb, 0x34, 0xa3, 0x65,
+        0xe8, 0xd0, 0x61, 0xf8, 0x7a, 0x77, 0x3d, 0x41, 0xe5, 0xb0, 0x47, 0x6d, 0x16, 0x19, 0x55,
+        0x31, 0xd7, 0xc0, 0xd0, 0xf0, 0xf8, 0xd5, 0xd0, 0x84, 0x1d, 0x10, 0xa0, 0x6f, 0xd5, 0xee,
+        0x31, 0xe9, 0xc3, 0x45, 0x74, 0xeb, 0x2f, 0x61, 0xd1, 0x37, 0x1a, 0x3e, 0x48, 0x0f, 0xfd,
+        0x33, 0x5f, 0x22, 0x32, 0x00,
+    ];
+
+    let mut sig = [0u8; 65];
+    sig.copy_from_slice(&signature);
+
+    assert_eq!(
+        recover_address(Network::Bitcoin, address.clone(), sig, MESSAGE.to_vec()).unwrap(),
+        address
+    );
+}
+
 #[test]
 fn should_recover_dot() {
     // URI: //Alice
@@ -84,6 +254,35 @@ fn should_recover_dot() {
     );
 }
 
+#[test]
+fn should_recover_eos() {
+    // PK: 0ea82fe932055196992c73ffd17cf5cc2ca9ef499aa5a4dc9b0631be2b7c1dba
+    let legacy = b"EOS6Bs31fx8iWKT6ejyCszpFf21cLu42VogfV5ugrnwsqh6Vs3U7X".to_vec();
+    let k1 = b"PUB_K1_6Bs31fx8iWKT6ejyCszpFf21cLu42VogfV5ugrnwsqh6Tvnb7i".to_vec();
+
+    // 20dcfea7592ac8cf2ed8094939b3007beefea7b722aa03d71eeb1a2e00bc9ecc30381eb8ae0e6576de2cde7028d0f0f7eae206c69bf0c629c09e781e87ae341248
+    let signature = [
+        0x20, 0xdc, 0xfe, 0xa7, 0x59, 0x2a, 0xc8, 0xcf, 0x2e, 0xd8, 0x09, 0x49, 0x39, 0xb3, 0x00,
+        0x7b, 0xee, 0xfe, 0xa7, 0xb7, 0x22, 0xaa, 0x03, 0xd7, 0x1e, 0xeb, 0x1a, 0x2e, 0x00, 0xbc,
+        0x9e, 0xcc, 0x30, 0x38, 0x1e, 0xb8, 0xae, 0x0e, 0x65, 0x76, 0xde, 0x2c, 0xde, 0x70, 0x28,
+        0xd0, 0xf0, 0xf7, 0xea, 0xe2, 0x06, 0xc6, 0x9b, 0xf0, 0xc6, 0x29, 0xc0, 0x9e, 0x78, 0x1e,
+        0x87, 0xae, 0x34, 0x12, 0x48,
+    ];
+
+    let mut sig = [0u8; 65];
+    sig.copy_from_slice(&signature);
+
+    assert_eq!(
+        recover_address(Network::Eosio, legacy.clone(), sig, MESSAGE.to_vec()).unwrap(),
+        legacy
+    );
+
+    assert_eq!(
+        recover_address(Network::Eosio, k1.clone(), sig, MESSAGE.to_vec()).unwrap(),
+        k1
+    );
+}
+
 #[test]
 fn should_recover_eth() {
     // PK: be6383dad004f233317e46ddb46ad31b16064d14447a95cc1d8c8d4bc61c3728
@@ -111,6 +310,199 @@ fn should_recover_eth() {
     );
 }
 
+#[test]
+fn should_recover_eth_long_message() {
+    // PK: be6383dad004f233317e46ddb46ad31b16064d14447a95cc1d8c8d4bc61c3728
+    // eb014f8c8b418db6b45774c326a0e64c78914dc0
+    let address = vec![
+        0xeb, 0x01, 0x4f, 0x8c, 0x8b, 0x41, 0x8d, 0xb6, 0xb4, 0x57, 0x74, 0xc3, 0x26, 0xa0, 0xe6,
+        0x4c, 0x78, 0x91, 0x4d, 0xc0,
+    ];
+
+    // 123 bytes, the length prefix takes three digits
+    let message = MESSAGE.repeat(3);
+
+    // bf9c4e245cb466634d1775731e84dde824a9df662d83fc2e7f547cafff8ea60b32c1b6d28d7aa74eee85940ca16f4728055c7bfc1fc86ff34973fca68b26d13f1b
+    let signature = [
+        0xbf, 0x9c, 0x4e, 0x24, 0x5c, 0xb4, 0x66, 0x63, 0x4d, 0x17, 0x75, 0x73, 0x1e, 0x84, 0xdd,
+        0xe8, 0x24, 0xa9, 0xdf, 0x66, 0x2d, 0x83, 0xfc, 0x2e, 0x7f, 0x54, 0x7c, 0xaf, 0xff, 0x8e,
+        0xa6, 0x0b, 0x32, 0xc1, 0xb6, 0xd2, 0x8d, 0x7a, 0xa7, 0x4e, 0xee, 0x85, 0x94, 0x0c, 0xa1,
+        0x6f, 0x47, 0x28, 0x05, 0x5c, 0x7b, 0xfc, 0x1f, 0xc8, 0x6f, 0xf3, 0x49, 0x73, 0xfc, 0xa6,
+        0x8b, 0x26, 0xd1, 0x3f, 0x1b,
+    ];
+
+    assert_eq!(
+        recover_address(Network::Ethereum, address.clone(), signature, message).unwrap(),
+        address
+    );
+}
+
+#[test]
+fn should_recover_eth_typed_data() {
+    pub const DID_ALICE: H160 = H160([
+        0x32, 0xac, 0x79, 0x9d, //
+        0x35, 0xde, 0x72, 0xa2, //
+        0xae, 0x57, 0xa4, 0x6c, //
+        0xa9, 0x75, 0x31, 0x9f, //
+        0xbb, 0xb1, 0x25, 0xa9,
+    ]);
+
+    // PK: be6383dad004f233317e46ddb46ad31b16064d14447a95cc1d8c8d4bc61c3728
+    // eb014f8c8b418db6b45774c326a0e64c78914dc0
+    let address = vec![
+        0xeb, 0x01, 0x4f, 0x8c, 0x8b, 0x41, 0x8d, 0xb6, 0xb4, 0x57, 0x74, 0xc3, 0x26, 0xa0, 0xe6,
+        0x4c, 0x78, 0x91, 0x4d, 0xc0,
+    ];
+
+    let genesis = [69u8; 32];
+
+    // Authorization { did: "did:ad3:hwtGPq42GojPtyx5ngtSRSpJfjN", action: "link", expiry: 100 }
+    let signature = [
+        0x05, 0x26, 0x06, 0x3c, 0xf4, 0xbe, 0x2f, 0x56, 0x6d, 0xb8, 0xba, 0x03, 0x22, 0x8e, 0x1f,
+        0x34, 0xaf, 0xa6, 0x9b, 0xd4, 0xbe, 0xa1, 0x84, 0xd7, 0xea, 0x2c, 0xa7, 0x7b, 0xea, 0x18,
+        0xac, 0x3a, 0x79, 0xf7, 0x6d, 0x36, 0x15, 0xfc, 0x6f, 0x64, 0x79, 0x4f, 0x51, 0xad, 0xc4,
+        0x73, 0xd6, 0x97, 0xff, 0xac, 0xd0, 0x25, 0x59, 0x13, 0x85, 0x75, 0x5d, 0x30, 0xd6, 0x26,
+        0x62, 0x6d, 0x88, 0x97, 0x1c,
+    ];
+
+    let scheme = SignatureScheme::Eip712 { expiry: 100 };
+
+    assert_eq!(
+        recover_address_with(
+            scheme,
+            Network::Ethereum,
+            address.clone(),
+            signature,
+            &genesis,
+            &DID_ALICE,
+            Action::Link,
+        )
+        .unwrap(),
+        address
+    );
+
+    assert_ne!(
+        recover_address_with(
+            scheme,
+            Network::Ethereum,
+            address.clone(),
+            signature,
+            &genesis,
+            &DID_ALICE,
+            Action::Port,
+        )
+        .unwrap(),
+        address
+    );
+
+    assert!(matches!(
+        recover_address_with(
+            scheme,
+            Network::Polkadot,
+            address.clone(),
+            signature,
+            &genesis,
+            &DID_ALICE,
+            Action::Link,
+        ),
+        Err(Error::UnsupportedNetwork)
+    ));
+}
+
+#[test]
+fn should_recover_ksm() {
+    // URI: //Alice
+    let address = b"HNZata7iMYWmk5RvZRTiAsSDhV8366zq2YGb3tLH5Upf74F".to_vec();
+
+    // 00b019009d196eb10f3d7f46309b591d21950fa617ced4f0b01b908b472bf0566610691636fde1088383b2b8134e5aee1bf48b2f4b46056709f8f0d81f79ebe58b
+    let signature = [
+        0x00, 0xb0, 0x19, 0x00, 0x9d, 0x19, 0x6e, 0xb1, 0x0f, 0x3d, 0x7f, 0x46, 0x30, 0x9b, 0x59,
+        0x1d, 0x21, 0x95, 0x0f, 0xa6, 0x17, 0xce, 0xd4, 0xf0, 0xb0, 0x1b, 0x90, 0x8b, 0x47, 0x2b,
+        0xf0, 0x56, 0x66, 0x10, 0x69, 0x16, 0x36, 0xfd, 0xe1, 0x08, 0x83, 0x83, 0xb2, 0xb8, 0x13,
+        0x4e, 0x5a, 0xee, 0x1b, 0xf4, 0x8b, 0x2f, 0x4b, 0x46, 0x05, 0x67, 0x09, 0xf8, 0xf0, 0xd8,
+        0x1f, 0x79, 0xeb, 0xe5, 0x8b,
+    ];
+
+    let mut sig = [0u8; 65];
+    sig.copy_from_slice(&signature);
+
+    assert_eq!(
+        recover_address(Network::Kusama, address.clone(), sig, MESSAGE.to_vec()).unwrap(),
+        address
+    );
+
+    // generic substrate prefix
+    let address = b"5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY".to_vec();
+
+    assert!(matches!(
+        recover_address(Network::Kusama, address, sig, MESSAGE.to_vec()),
+        Err(Error::InvalidAddress)
+    ));
+}
+
+#[test]
+fn should_recover_near() {
+    // seed: 9b60553c9c171f895ed53d453ce1ec39c700bea876ffcde362b406c3e42b3d50
+    let address = b"db8fbd41a593b33b07efddb4c31033b2214c8b8737b0858f858c2bb8f90abffd".to_vec();
+
+    // 00bb9a27176dad948aefef72f738196b55de526f44ddb4e5f1ec91fe3f781e0aba8e0e08779d8d2b44bb9f17eac843bfdca009ef92b0877b24f65b501ff765980c
+    let signature = [
+        0x00, 0xbb, 0x9a, 0x27, 0x17, 0x6d, 0xad, 0x94, 0x8a, 0xef, 0xef, 0x72, 0xf7, 0x38, 0x19,
+        0x6b, 0x55, 0xde, 0x52, 0x6f, 0x44, 0xdd, 0xb4, 0xe5, 0xf1, 0xec, 0x91, 0xfe, 0x3f, 0x78,
+        0x1e, 0x0a, 0xba, 0x8e, 0x0e, 0x08, 0x77, 0x9d, 0x8d, 0x2b, 0x44, 0xbb, 0x9f, 0x17, 0xea,
+        0xc8, 0x43, 0xbf, 0xdc, 0xa0, 0x09, 0xef, 0x92, 0xb0, 0x87, 0x7b, 0x24, 0xf6, 0x5b, 0x50,
+        0x1f, 0xf7, 0x65, 0x98, 0x0c,
+    ];
+
+    let mut sig = [0u8; 65];
+    sig.copy_from_slice(&signature);
+
+    assert_eq!(
+        recover_address(Network::Near, address.clone(), sig, MESSAGE.to_vec()).unwrap(),
+        address
+    );
+
+    // named accounts carry the public key of an access key
+    let named = b"amelia.near:ed25519:Fn5Qkm7Xy4yYNu8bLwDXurBpeiRmngNtPKEXkv6o7CWp".to_vec();
+
+    assert_eq!(
+        recover_address(Network::Near, named.clone(), sig, MESSAGE.to_vec()).unwrap(),
+        named
+    );
+
+    assert_eq!(
+        parse_near_named_account(&named),
+        Some((
+            &b"amelia.near"[..],
+            &b"ed25519:Fn5Qkm7Xy4yYNu8bLwDXurBpeiRmngNtPKEXkv6o7CWp"[..]
+        ))
+    );
+
+    assert!(matches!(
+        recover_address(
+            Network::Near,
+            b"amelia.near".to_vec(),
+            sig,
+            MESSAGE.to_vec()
+        ),
+        Err(Error::InvalidAddress)
+    ));
+
+    for id in [&b"Amelia.near"[..], b"amelia..near", b"-amelia.near", b"a"] {
+        let named = [id, b":ed25519:Fn5Qkm7Xy4yYNu8bLwDXurBpeiRmngNtPKEXkv6o7CWp"].concat();
+
+        assert_eq!(parse_near_named_account(&named), None);
+    }
+
+    // signed by another key
+    let other = b"amelia.near:ed25519:11111111111111111111111111111111".to_vec();
+
+    assert!(matches!(
+        recover_address(Network::Near, other, sig, MESSAGE.to_vec()),
+        Err(Error::InvalidSignature)
+    ));
+}
+
 #[test]
 fn should_recover_sol() {
     // PK: 4c696e6b3a206469643a6164333a6877744750713432476f6a50747978356e6774535253704a666a4e
@@ -156,3 +548,36 @@ fn should_recover_trx() {
         address
     );
 }
+
+#[test]
+fn should_parse_text_proof() {
+    use crate::did::PublicKey;
+
+    pub const DID_ALICE: H160 = H160([
+        0x32, 0xac, 0x79, 0x9d, //
+        0x35, 0xde, 0x72, 0xa2, //
+        0xae, 0x57, 0xa4, 0x6c, //
+        0xa9, 0x75, 0x31, 0x9f, //
+        0xbb, 0xb1, 0x25, 0xa9,
+    ]);
+
+    // ed25519 seed: [7u8; 32]
+    let key = PublicKey::Ed25519([
+        0xea, 0x4a, 0x6c, 0x63, 0xe2, 0x9c, 0x52, 0x0a, 0xbe, 0xf5, 0x50, 0x7b, 0x13, 0x2e, 0xc5,
+        0xf9, 0x95, 0x47, 0x76, 0xae, 0xbe, 0xbe, 0x7b, 0x92, 0x42, 0x1e, 0xea, 0x69, 0x14, 0x46,
+        0xd2, 0x2c,
+    ]);
+
+    let message =
+        b"Link: did:ad3:hwtGPq42GojPtyx5ngtSRSpJfjN 5QT96d212bT7DKHFNbrdFKePSRB5ZcdPxkkseFgoC1cH";
+
+    let text = b"Hello! Link: did:ad3:hwtGPq42GojPtyx5ngtSRSpJfjN 3J5LdUviga74KTUygpWwwFqFwjtp6ueTBfESYf7m5VJYvwzJHkQu2mPf7KAF84cEnjf4Y7dfdNNhLvgnhqmPfT3S #parami";
+
+    let signature = parse_text_proof(text, &DID_ALICE).unwrap();
+    assert_eq!(signature.len(), 64);
+
+    assert!(key.verify(&signature, message));
+    assert!(!key.verify(&signature, b"Link: did:ad3:hwtGPq42GojPtyx5ngtSRSpJfjN"));
+
+    assert_eq!(parse_text_proof(text, &H160::zero()), None);
+}
@@ -36,10 +36,65 @@ impl WitnessProgram {
         let p5 = self.program.to_base32();
         // let p5 = convert_bits(self.program.to_vec(), 8, 5, true)?;
         data.extend_from_slice(&p5);
-        let b32 = data.encode(hrp)?;
+        // BIP-350, version 1 and above use bech32m
+        let b32 = if self.version == 0 {
+            data.encode(hrp)?
+        } else {
+            data.encode_m(hrp)?
+        };
         Ok(b32)
     }
 
+    /// Converts a SegWit Address to a Witness Program, returning the human-readable part as well
+    pub fn from_address(address: &[u8]) -> Result<(Vec<u8>, Self), &'static str> {
+        let pos = address
+            .iter()
+            .rposition(|c| *c == SEP)
+            .ok_or("MissingSeparator")?;
+        if pos < 1 || pos + 8 > address.len() {
+            return Err("InvalidLength");
+        }
+
+        let hrp = address[..pos].to_ascii_lowercase();
+        let mut data: Vec<u8> = Vec::new();
+        for c in address[pos + 1..].iter() {
+            let c = c.to_ascii_lowercase();
+            let p = ALPHABET.iter().position(|a| *a == c).ok_or("InvalidChar")?;
+            data.push(p as u8);
+        }
+
+        let version = data[0];
+        let constant = if version == 0 {
+            BECH32_CONST
+        } else {
+            BECH32M_CONST
+        };
+
+        let mut values = hrp_expand(&hrp);
+        values.extend_from_slice(&data);
+        if polymod(values) != constant {
+            return Err("InvalidChecksum");
+        }
+
+        // Convert 5-bit program into 8-bit, dropping the padding
+        let mut program: Vec<u8> = Vec::new();
+        let mut acc: u32 = 0;
+        let mut bits: u32 = 0;
+        for v in data[1..data.len() - 6].iter() {
+            acc = (acc << 5) | *v as u32;
+            bits += 5;
+            if bits >= 8 {
+                bits -= 8;
+                program.push((acc >> bits) as u8);
+            }
+        }
+        if bits >= 5 || acc & ((1 << bits) - 1) != 0 {
+            return Err("InvalidPadding");
+        }
+
+        Ok((hrp, WitnessProgram { version, program }))
+    }
+
     /// Extracts a WitnessProgram out of a provided script public key
     pub fn from_scriptpubkey(pubkey: &[u8]) -> Result<Self, &'static str> {
         // We need a version byte and a program length byte, with a program at
@@ -68,28 +123,42 @@ impl WitnessProgram {
 const SEP: u8 = b'1';
 const ALPHABET: &'static [u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
 
+const BECH32_CONST: u32 = 1;
+const BECH32M_CONST: u32 = 0x2bc830a3;
+
 pub trait Bech32 {
     fn encode(&self, hrp: Vec<u8>) -> Result<Vec<u8>, &'static str>;
+
+    /// Encode with the checksum of bech32m (BIP-350)
+    fn encode_m(&self, hrp: Vec<u8>) -> Result<Vec<u8>, &'static str>;
 }
 
 impl Bech32 for [u8] {
     fn encode(&self, hrp: Vec<u8>) -> Result<Vec<u8>, &'static str> {
-        if hrp.len() < 1 {
-            return Err("invalidData");
-        }
+        encode_with(self, hrp, BECH32_CONST)
+    }
 
-        let mut combined: Vec<u8> = self.clone().to_vec();
-        combined.extend_from_slice(&create_checksum(&hrp, &self.to_vec()));
-        let mut encoded = hrp;
-        encoded.push(SEP);
-        for p in combined {
-            if p >= 32 {
-                return Err("invalidData");
-            }
-            encoded.push(ALPHABET[p as usize]);
+    fn encode_m(&self, hrp: Vec<u8>) -> Result<Vec<u8>, &'static str> {
+        encode_with(self, hrp, BECH32M_CONST)
+    }
+}
+
+fn encode_with(data: &[u8], hrp: Vec<u8>, constant: u32) -> Result<Vec<u8>, &'static str> {
+    if hrp.len() < 1 {
+        return Err("invalidData");
+    }
+
+    let mut combined: Vec<u8> = data.to_vec();
+    combined.extend_from_slice(&create_checksum(&hrp, &data.to_vec(), constant));
+    let mut encoded = hrp;
+    encoded.push(SEP);
+    for p in combined {
+        if p >= 32 {
+            return Err("invalidData");
         }
-        Ok(encoded)
+        encoded.push(ALPHABET[p as usize]);
     }
+    Ok(encoded)
 }
 
 const GEN: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
@@ -106,12 +175,12 @@ fn hrp_expand(hrp: &Vec<u8>) -> Vec<u8> {
     v
 }
 
-fn create_checksum(hrp: &Vec<u8>, data: &Vec<u8>) -> Vec<u8> {
+fn create_checksum(hrp: &Vec<u8>, data: &Vec<u8>, constant: u32) -> Vec<u8> {
     let mut values: Vec<u8> = hrp_expand(hrp);
     values.extend_from_slice(data);
     // Pad with 6 zeros
     values.extend_from_slice(&[0u8; 6]);
-    let plm: u32 = polymod(values) ^ 1;
+    let plm: u32 = polymod(values) ^ constant;
     let mut checksum: Vec<u8> = Vec::new();
     for p in 0..6 {
         checksum.push(((plm >> 5 * (5 - p)) & 0x1f) as u8);
@@ -236,7 +305,7 @@ mod tests {
                 ],
             ),
             (
-                "bc1pw508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7k7grplx",
+                "bc1pw508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7kt5nd6y",
                 vec![
                     0x51, 0x28, 0x75, 0x1e, 0x76, 0xe8, 0x19, 0x91, 0x96, 0xd4, 0x54, 0x94, 0x1c,
                     0x45, 0xd1, 0xb3, 0xa3, 0x23, 0xf1, 0x43, 0x3b, 0xd6, 0x75, 0x1e, 0x76, 0xe8,
@@ -244,9 +313,9 @@ mod tests {
                     0x43, 0x3b, 0xd6,
                 ],
             ),
-            ("BC1SW50QA3JX3S", vec![0x60, 0x02, 0x75, 0x1e]),
+            ("BC1SW50QGDZ25J", vec![0x60, 0x02, 0x75, 0x1e]),
             (
-                "bc1zw508d6qejxtdg4y5r3zarvaryvg6kdaj",
+                "bc1zw508d6qejxtdg4y5r3zarvaryvaxxpcs",
                 vec![
                     0x52, 0x10, 0x75, 0x1e, 0x76, 0xe8, 0x19, 0x91, 0x96, 0xd4, 0x54, 0x94, 0x1c,
                     0x45, 0xd1, 0xb3, 0xa3, 0x23,
@@ -262,7 +331,7 @@ mod tests {
             assert!(spk_result.is_ok());
             let prog = spk_result.unwrap();
 
-            let enc_result = prog.to_address(hrp);
+            let enc_result = prog.to_address(hrp.clone());
             assert!(enc_result.is_ok());
 
             let enc_address = enc_result.unwrap();
@@ -270,6 +339,11 @@ mod tests {
                 address.to_lowercase(),
                 from_utf8(&enc_address).unwrap().to_lowercase()
             );
+
+            let (dec_hrp, dec_prog) = WitnessProgram::from_address(address.as_bytes()).unwrap();
+            assert_eq!(dec_hrp, hrp);
+            assert_eq!(dec_prog.version, prog.version);
+            assert_eq!(dec_prog.program, prog.program);
         }
     }
 }
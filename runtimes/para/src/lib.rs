@@ -126,6 +126,15 @@ pub type Executive = frame_executive::Executive<
     frame_system::ChainContext<Runtime>,
     Runtime,
     AllPalletsWithSystem,
+    (
+        parami_ad::migrations::v5::BidWithCurrencyOrAsset<Runtime>,
+        parami_ad::migrations::v6::SlotsIndex<Runtime>,
+        parami_nft::migrations::v4::ImportTaskWithStandard<Runtime>,
+        parami_nft::migrations::v5::MultipleValidateEndpoints<Runtime>,
+        parami_nft::migrations::v6::OwnedNftsIndex<Runtime>,
+        parami_did::migrations::v3::InviteesIndex<Runtime>,
+        parami_linker::migrations::v3::MultipleLinks<Runtime>,
+    ),
 >;
 
 /// Era type as expected by this runtime.
@@ -1511,6 +1520,42 @@ impl parami_assetmanager::Config for Runtime {
     type AssetId = AssetId;
 }
 
+parameter_types! {
+    pub const GovernanceMaxProposals: u32 = 8;
+    pub const GovernancePalletId: PalletId = PalletId(*names::GOVERNANCE);
+    pub const GovernanceProposalDeposit: Balance = 10 * DOLLARS;
+    pub const GovernanceQuorum: Perbill = Perbill::from_percent(20);
+    pub const GovernanceVotingPeriod: BlockNumber = 7 * DAYS;
+}
+
+impl parami_governance::Config for Runtime {
+    type Event = Event;
+    type Origin = Origin;
+    type Proposal = Call;
+    type MaxProposals = GovernanceMaxProposals;
+    type ProposalDeposit = GovernanceProposalDeposit;
+    type PalletId = GovernancePalletId;
+    type Quorum = GovernanceQuorum;
+    type VotingPeriod = GovernanceVotingPeriod;
+    type WeightInfo = parami_governance::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+    pub const CredentialDeposit: Balance = deposit(1, 128);
+    pub const CredentialPalletId: PalletId = PalletId(*names::CREDENTIAL);
+    pub const CredentialSchemaDepositBase: Balance = deposit(1, 0);
+    pub const CredentialSchemaDepositPerByte: Balance = deposit(0, 1);
+}
+
+impl parami_credential::Config for Runtime {
+    type Event = Event;
+    type CredentialDeposit = CredentialDeposit;
+    type SchemaDepositBase = CredentialSchemaDepositBase;
+    type SchemaDepositPerByte = CredentialSchemaDepositPerByte;
+    type PalletId = CredentialPalletId;
+    type WeightInfo = parami_credential::weights::SubstrateWeight<Runtime>;
+}
+
 parameter_types! {
     // &blake2_128(b"hash")
     // 0x000000000000000000000000000000f44be64d2de895454c3467021928e55ee9
@@ -1521,6 +1566,9 @@ parameter_types! {
     // 0x000000000000000000000000000000a56889c89dddcbb363cbd6a8d11de9e100
     pub NativeTokenId: parami_chainbridge::ResourceId = parami_chainbridge::derive_resource_id(0, &blake2_128(b"AD3"));
 
+    // &blake2_128(b"nft")
+    pub NftResourceId: parami_chainbridge::ResourceId = parami_chainbridge::derive_resource_id(233, &blake2_128(b"nft"));
+
     pub const XAssetPalletId: PalletId = PalletId(*names::ASSET);
 }
 
@@ -1532,6 +1580,8 @@ impl parami_xassets::Config for Runtime {
     type Currency = Balances;
     type HashResourceId = HashId;
     type NativeTokenResourceId = NativeTokenId;
+    type Nfts = Nft;
+    type NftResourceId = NftResourceId;
     type WeightInfo = parami_xassets::weights::SubstrateWeight<Runtime>;
     type Assets = Assets;
     type StringLimit = StringLimit;
@@ -1539,13 +1589,39 @@ impl parami_xassets::Config for Runtime {
     type PalletId = XAssetPalletId;
 }
 
+parameter_types! {
+    pub const DidPalletId: PalletId = PalletId(*names::DID);
+    pub const DidMetadataDepositBase: Balance = deposit(1, 0);
+    pub const DidMetadataDepositPerByte: Balance = deposit(0, 1);
+    pub const DidHandleFee: Balance = 10 * DOLLARS;
+    pub const DidHandlePeriod: BlockNumber = 365 * DAYS;
+    pub const DidRecoveryLifetime: BlockNumber = 30 * DAYS;
+    pub const DidReferralBonus: Balance = 1 * DOLLARS;
+    pub const DidReferralMinAge: BlockNumber = 7 * DAYS;
+}
+
 impl parami_did::Config for Runtime {
     type Event = Event;
     type Currency = Balances;
     type DecentralizedId = DecentralizedId;
     type Hashing = Keccak256;
+    type HandleFee = DidHandleFee;
+    type HandleFeeDestination = Treasury;
+    type HandlePeriod = DidHandlePeriod;
+    type RecoveryLifetime = DidRecoveryLifetime;
+    type Links = Linker;
+    type MetadataDepositBase = DidMetadataDepositBase;
+    type MetadataDepositPerByte = DidMetadataDepositPerByte;
+    type PalletId = DidPalletId;
     type WeightInfo = parami_did::weights::SubstrateWeight<Runtime>;
-    type Transferables = (Nft,);
+    type Transferables = (Nft, Advertiser, Linker, Credential);
+    type OnDidRevoked = (Linker, Tag, Advertiser, Ad, Nft, Credential);
+    type OnReferralMilestone = parami_did::PayReferralBonus<
+        Runtime,
+        TreasuryPalletId,
+        DidReferralBonus,
+        DidReferralMinAge,
+    >;
 }
 
 parameter_types! {
@@ -1578,9 +1654,13 @@ parameter_types! {
 
 impl parami_nft::Config for Runtime {
     type Event = Event;
+    type AnchorOrigin =
+        EnsureOneOf<EnsureRootOrHalfCouncil, parami_chainbridge::EnsureBridge<Runtime>>;
     type AssetId = AssetId;
     type Assets = Assets;
     type AssetIdManager = AssetManager;
+    type Bridge = XAssets;
+    type HoldersOrigin = parami_governance::EnsureNftHolders<AssetId>;
     type DefaultInitialMintingDeposit = DefaultInitialMintingDeposit;
     type InitialMintingLockupPeriod = InitialMintingLockupPeriod;
     type InitialMintingValueBase = InitialMintingValueBase;
@@ -1724,6 +1804,8 @@ construct_runtime!(
         Tag: parami_tag::{Pallet, Call, Storage, Config<T>, Event<T>} = 109,
         AssetManager: parami_assetmanager::{Pallet, Storage, Config<T>} = 110,
         Stake: parami_stake::{Pallet, Storage, Event<T>} = 111,
+        Governance: parami_governance::{Pallet, Call, Storage, Origin<T>, Event<T>} = 113,
+        Credential: parami_credential::{Pallet, Call, Storage, Event<T>} = 114,
     }
 );
 
@@ -1937,10 +2019,77 @@ impl_runtime_apis! {
         }
     }
 
+    impl parami_credential_rpc_runtime_api::CredentialRuntimeApi<Block, Hash, DecentralizedId> for Runtime {
+        fn verify(credential: Hash, issuer: DecentralizedId, subject: DecentralizedId, schema: Hash) -> Result<(), DispatchError> {
+            Credential::verify(credential, issuer, subject, schema)
+        }
+    }
+
+    impl parami_did_rpc_runtime_api::DidRuntimeApi<Block, DecentralizedId, AccountId> for Runtime {
+        fn resolve(did: DecentralizedId) -> Result<parami_primitives::did::DidDocument<DecentralizedId>, DispatchError> {
+            Did::resolve(did)
+        }
+
+        fn did_preview(account: AccountId) -> Result<DecentralizedId, DispatchError> {
+            Did::preview(&account).map(|(did, _)| did)
+        }
+
+        fn get_metadata(did: DecentralizedId, key: Vec<u8>) -> Option<Vec<u8>> {
+            Did::metadata(&did, &key)
+        }
+
+        fn metadata_storage_key(did: DecentralizedId, key: Vec<u8>) -> Option<Vec<u8>> {
+            Did::metadata_storage_key(&did, &key)
+        }
+
+        fn lookup_handle(handle: Vec<u8>) -> Option<DecentralizedId> {
+            Did::lookup_handle(&handle)
+        }
+
+        fn reverse_lookup_handle(did: DecentralizedId) -> Option<Vec<u8>> {
+            Did::reverse_lookup_handle(&did)
+        }
+
+        fn invitees(did: DecentralizedId, start: u32, limit: u32) -> Vec<DecentralizedId> {
+            Did::invitees(&did, start, limit)
+        }
+
+        fn referral_counts(did: DecentralizedId) -> Vec<u32> {
+            Did::referral_counts_of(&did).to_vec()
+        }
+    }
+
     impl parami_nft_rpc_runtime_api::NftRuntimeApi<Block, NftId, DecentralizedId, Balance> for Runtime {
         fn get_claim_info(nft_id: NftId, claimer: DecentralizedId) -> Result<(BalanceWrapper<Balance>, BalanceWrapper<Balance>, BalanceWrapper<Balance>), DispatchError> {
             Nft::get_claim_info(nft_id, &claimer).map(|(total, claimed, claimable)| (total.into(), claimed.into(), claimable.into()))
         }
+
+        fn get_nfts_of(did: DecentralizedId) -> Result<Vec<NftId>, DispatchError> {
+            Ok(Nft::nfts_of(did))
+        }
+
+        fn get_pending_imports(did: DecentralizedId) -> Result<Vec<(parami_primitives::Network, Vec<u8>, Vec<u8>)>, DispatchError> {
+            Ok(Nft::pending_imports(did))
+        }
+
+        fn get_failed_imports(did: DecentralizedId) -> Result<Vec<(parami_primitives::Network, Vec<u8>, Vec<u8>, parami_primitives::ImportFailure)>, DispatchError> {
+            Ok(Nft::failed_imports(did))
+        }
+
+        fn get_buyback_progress(nft_id: NftId) -> Result<(BalanceWrapper<Balance>, BalanceWrapper<Balance>, BalanceWrapper<Balance>), DispatchError> {
+            Nft::buyback_progress(nft_id).map(|(spent, bought, remaining)| (spent.into(), bought.into(), remaining.into()))
+        }
+
+        fn dryly_launch(
+            nft_id: NftId,
+            minting_tokens: BalanceWrapper<Balance>,
+            offered_tokens: BalanceWrapper<Balance>,
+            currency: BalanceWrapper<Balance>,
+            tokens: BalanceWrapper<Balance>,
+        ) -> Result<(BalanceWrapper<Balance>, BalanceWrapper<Balance>), DispatchError> {
+            Nft::launch_dry(nft_id, minting_tokens.into(), offered_tokens.into(), currency.into(), tokens.into())
+                .map(|(price, remained)| (price.into(), remained.into()))
+        }
     }
 
     impl parami_swap_rpc_runtime_api::SwapRuntimeApi<Block, AssetId, Balance> for Runtime {
@@ -2034,7 +2183,9 @@ impl_runtime_apis! {
 
             list_benchmark!(list, extra, parami_ad, Ad);
             list_benchmark!(list, extra, parami_advertiser, Advertiser);
+            list_benchmark!(list, extra, parami_credential, Credential);
             list_benchmark!(list, extra, parami_did, Did);
+            list_benchmark!(list, extra, parami_governance, Governance);
             list_benchmark!(list, extra, parami_linker, Linker);
             list_benchmark!(list, extra, parami_nft, Nft);
             list_benchmark!(list, extra, parami_swap, Swap);
@@ -2080,7 +2231,9 @@ impl_runtime_apis! {
 
             add_benchmark!(params, batches, parami_ad, Ad);
             add_benchmark!(params, batches, parami_advertiser, Advertiser);
+            add_benchmark!(params, batches, parami_credential, Credential);
             add_benchmark!(params, batches, parami_did, Did);
+            add_benchmark!(params, batches, parami_governance, Governance);
             add_benchmark!(params, batches, parami_linker, Linker);
             add_benchmark!(params, batches, parami_nft, Nft);
             add_benchmark!(params, batches, parami_swap, Swap);
@@ -0,0 +1,113 @@
+import { ApiPromise, Keyring, WsProvider } from '@polkadot/api';
+import Spinnies from 'spinnies';
+
+import { submitUntilFinalized } from './utils';
+
+// Replays historic `did.setMetadata` extrinsics, which were written to the
+// offchain index only, into the on-chain `did.metadataOf` storage.
+(async () => {
+  const spinnies = new Spinnies();
+
+  const provider = new WsProvider(process.env.DEPLOY_TARGET_RPC);
+  const keyring = new Keyring({ type: 'sr25519' });
+
+  const chain = await ApiPromise.create({ provider });
+
+  const keypair = keyring.addFromMnemonic(
+    process.env.DEPLOY_MNEMONIC || '//Alice'
+  );
+
+  const from = Number(process.env.MIGRATE_FROM_BLOCK || 0);
+  const to = Number(
+    process.env.MIGRATE_TO_BLOCK ||
+      (await chain.rpc.chain.getHeader()).number.toNumber()
+  );
+  const batchSize = Number(process.env.MIGRATE_BATCH_SIZE || 100);
+
+  // later writes of the same key override earlier ones
+  const entries = new Map<string, { did: string; key: string; value: string }>();
+
+  spinnies.add('scan', { text: `Scanning blocks ${from}..${to}...` });
+  for (let height = from; height <= to; height++) {
+    const hash = await chain.rpc.chain.getBlockHash(height);
+    const block = await chain.rpc.chain.getBlock(hash);
+
+    const extrinsics = block.block.extrinsics
+      .map((extrinsic, index) => ({ extrinsic, index }))
+      .filter(
+        ({ extrinsic }) =>
+          extrinsic.method.section === 'did' &&
+          extrinsic.method.method === 'setMetadata'
+      );
+
+    if (extrinsics.length === 0) {
+      continue;
+    }
+
+    const at = await chain.at(hash);
+    const events = (await at.query.system.events()) as any;
+
+    for (const { extrinsic, index } of extrinsics) {
+      const succeeded = events.some(
+        ({ phase, event }) =>
+          phase.isApplyExtrinsic &&
+          phase.asApplyExtrinsic.eq(index) &&
+          chain.events.system.ExtrinsicSuccess.is(event)
+      );
+      if (!succeeded) {
+        continue;
+      }
+
+      const did = (await at.query.did.didOf(extrinsic.signer.toString())) as any;
+      if (did.isNone) {
+        continue;
+      }
+
+      const [key, value] = extrinsic.method.args;
+      const entry = {
+        did: did.unwrap().toHex(),
+        key: key.toHex(),
+        value: value.toHex(),
+      };
+
+      entries.set(`${entry.did}/${entry.key}`, entry);
+    }
+
+    spinnies.update('scan', {
+      text: `Scanning blocks ${height}/${to}, ${entries.size} entries found`,
+    });
+  }
+  spinnies.succeed('scan', { text: ` ${entries.size} entries found` });
+
+  spinnies.add('filter', { text: 'Skipping entries already on chain...' });
+  const calls = [];
+  for (const { did, key, value } of entries.values()) {
+    const existing = (await chain.query.did.metadataOf(did, key)) as any;
+    if (existing.isSome) {
+      continue;
+    }
+
+    calls.push(chain.tx.did.forceSetMetadata(did, key, value));
+  }
+  spinnies.succeed('filter', { text: ` ${calls.length} entries to replay` });
+
+  for (let i = 0; i < calls.length; i += batchSize) {
+    const batch = calls.slice(i, i + batchSize);
+
+    spinnies.add(`batch-${i}`, {
+      text: `Replaying ${i + batch.length}/${calls.length}...`,
+    });
+
+    const { block } = await submitUntilFinalized(
+      chain,
+      chain.tx.sudo.sudo(chain.tx.utility.batchAll(batch)),
+      keypair
+    );
+
+    spinnies.succeed(`batch-${i}`, {
+      text: ` ${i + batch.length}/${calls.length} replayed in ${block}`,
+    });
+  }
+
+  await chain.disconnect();
+})();